@@ -0,0 +1,104 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+tokio::task_local! {
+    static CURRENT: AfterCommit;
+}
+
+type Hook = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Side effects held back until the surrounding transaction commits
+///
+/// Use cases hand cache invalidation and event publishing to `defer`. With a
+/// queue in `scope` they wait for `run`, which the transaction owner calls
+/// after a successful commit, and are dropped by `discard` after a rollback.
+/// Outside a scope they run at once.
+#[derive(Clone, Default)]
+pub struct AfterCommit {
+    hooks: Arc<Mutex<Vec<Hook>>>,
+}
+
+impl AfterCommit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `future` with this queue collecting deferred effects
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        CURRENT.scope(self.clone(), future).await
+    }
+
+    /// Queue `effect` on the queue in scope, or run it now without one
+    pub async fn defer<F>(effect: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        match CURRENT.try_with(Clone::clone) {
+            Ok(queue) => queue.lock().push(Box::pin(effect)),
+            Err(_) => effect.await,
+        }
+    }
+
+    /// Run the queued effects in the order they were deferred
+    pub async fn run(&self) {
+        let hooks = std::mem::take(&mut *self.lock());
+        for hook in hooks {
+            hook.await;
+        }
+    }
+
+    /// Drop the queued effects without running them
+    pub fn discard(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Hook>> {
+        self.hooks.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn bump(counter: &Arc<AtomicUsize>) -> impl Future<Output = ()> + Send + 'static {
+        let counter = counter.clone();
+        async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_effects_wait_for_run_inside_a_scope() {
+        let ran = Arc::new(AtomicUsize::new(0));
+        let queue = AfterCommit::new();
+
+        queue.scope(AfterCommit::defer(bump(&ran))).await;
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+
+        queue.run().await;
+        assert_eq!(ran.load(Ordering::SeqCst), 1);
+        queue.run().await;
+        assert_eq!(ran.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_discarded_effects_never_run() {
+        let ran = Arc::new(AtomicUsize::new(0));
+        let queue = AfterCommit::new();
+
+        queue.scope(AfterCommit::defer(bump(&ran))).await;
+        queue.discard();
+        queue.run().await;
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_effects_run_at_once_outside_a_scope() {
+        let ran = Arc::new(AtomicUsize::new(0));
+        AfterCommit::defer(bump(&ran)).await;
+        assert_eq!(ran.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,83 @@
+use domain::UserStatus;
+use serde::{Deserialize, Serialize};
+use shared::UserId;
+
+/// Authorization role carried in access tokens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    User,
+    Admin,
+}
+
+/// The party actually acting when a token is used on someone else's behalf
+/// (RFC 8693 `act` claim)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Actor {
+    pub sub: UserId,
+}
+
+/// JWT claims issued and accepted by the service
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claims {
+    /// The user the token acts as
+    pub sub: UserId,
+    pub role: Role,
+    pub iss: String,
+    pub iat: i64,
+    pub exp: i64,
+    /// Present only on impersonation tokens: the admin behind the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<Actor>,
+    /// Session epoch of `sub` at issue time; a later epoch revokes the token
+    #[serde(default)]
+    pub epoch: i64,
+}
+
+/// Identity of an authenticated request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthContext {
+    /// The user the request acts as
+    pub user_id: UserId,
+    pub role: Role,
+    /// The real actor when the request is impersonated
+    pub actor_id: Option<UserId>,
+    /// Session epoch the token was issued under
+    pub epoch: i64,
+}
+
+impl AuthContext {
+    pub fn is_admin(&self) -> bool {
+        self.role == Role::Admin
+    }
+
+    pub fn is_impersonated(&self) -> bool {
+        self.actor_id.is_some()
+    }
+
+    /// Whether the caller may change `user_id`: their own account, or any
+    /// account for admins
+    pub fn may_modify(&self, user_id: UserId) -> bool {
+        self.user_id == user_id || self.is_admin()
+    }
+
+    /// Statuses a caller sees in user listings: admins see every status,
+    /// anonymous and regular callers only active users
+    pub fn visible_statuses(caller: Option<&Self>) -> Option<Vec<UserStatus>> {
+        match caller {
+            Some(caller) if caller.is_admin() => None,
+            _ => Some(vec![UserStatus::Active]),
+        }
+    }
+}
+
+impl From<&Claims> for AuthContext {
+    fn from(claims: &Claims) -> Self {
+        Self {
+            user_id: claims.sub,
+            role: claims.role,
+            actor_id: claims.act.map(|actor| actor.sub),
+            epoch: claims.epoch,
+        }
+    }
+}
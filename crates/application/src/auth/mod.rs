@@ -0,0 +1,5 @@
+pub mod claims;
+pub mod redirect;
+
+pub use claims::{Actor, AuthContext, Claims, Role};
+pub use redirect::check_redirect_uri;
@@ -0,0 +1,93 @@
+use shared::config::OAuthProviderConfig;
+use shared::{AppError, AppResult};
+
+/// Check a callback `redirect_uri` against the provider's allowlist
+///
+/// Entries of `redirect_uris` must match verbatim. Prefix entries are
+/// compared on the parsed URI: scheme, host and port must be identical and
+/// the path must equal the prefix's or continue it at a `/`. Comparing hosts
+/// rather than strings keeps look-alikes such as `app.example.com.evil.test`
+/// from passing a `https://app.example.com` prefix.
+pub fn check_redirect_uri(provider: &OAuthProviderConfig, redirect_uri: &str) -> AppResult<()> {
+    if provider.redirect_uris.iter().any(|uri| uri == redirect_uri) {
+        return Ok(());
+    }
+
+    let rejected = || AppError::ValidationError("redirect_uri is not allowed".to_string());
+    let candidate = url::Url::parse(redirect_uri).map_err(|_| rejected())?;
+    let allowed = provider
+        .redirect_uri_prefixes
+        .iter()
+        .filter_map(|prefix| url::Url::parse(prefix).ok())
+        .any(|prefix| is_under(&candidate, &prefix));
+    if allowed { Ok(()) } else { Err(rejected()) }
+}
+
+fn is_under(candidate: &url::Url, prefix: &url::Url) -> bool {
+    if candidate.scheme() != prefix.scheme()
+        || candidate.host_str() != prefix.host_str()
+        || candidate.port_or_known_default() != prefix.port_or_known_default()
+        || !candidate.username().is_empty()
+        || candidate.password().is_some()
+    {
+        return false;
+    }
+    let base = prefix.path().trim_end_matches('/');
+    let path = candidate.path();
+    path == base
+        || path
+            .strip_prefix(base)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider() -> OAuthProviderConfig {
+        OAuthProviderConfig {
+            client_id: "client".to_string(),
+            redirect_uris: vec!["https://app.example.com/auth/callback".to_string()],
+            redirect_uri_prefixes: vec!["https://app.example.com/oauth/".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_listed_and_prefixed_uris_are_allowed() {
+        let provider = provider();
+
+        for uri in [
+            "https://app.example.com/auth/callback",
+            "https://app.example.com/oauth",
+            "https://app.example.com/oauth/github/callback?state=abc",
+            "https://app.example.com:443/oauth/done",
+        ] {
+            assert!(check_redirect_uri(&provider, uri).is_ok(), "{}", uri);
+        }
+    }
+
+    #[test]
+    fn test_look_alike_uris_are_rejected() {
+        let provider = provider();
+
+        for uri in [
+            "https://app.example.com.evil.test/auth/callback",
+            "https://app.example.com.evil.test/oauth/callback",
+            "https://app.example.com@evil.test/oauth/callback",
+            "https://evil.test/?https://app.example.com/oauth/",
+            "http://app.example.com/oauth/callback",
+            "https://app.example.com:8443/oauth/callback",
+            "https://app.example.com/oauthx/callback",
+            "https://app.example.com/oauth/../admin",
+            "https://app.example.com/auth/callback/extra",
+            "not a uri",
+        ] {
+            let result = check_redirect_uri(&provider, uri);
+            assert!(
+                matches!(result, Err(AppError::ValidationError(_))),
+                "{}",
+                uri
+            );
+        }
+    }
+}
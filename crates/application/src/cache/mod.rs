@@ -0,0 +1,5 @@
+pub mod query_cache;
+pub mod single_flight;
+
+pub use query_cache::QueryCache;
+pub use single_flight::SingleFlight;
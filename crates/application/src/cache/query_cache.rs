@@ -0,0 +1,136 @@
+use serde::{Serialize, de::DeserializeOwned};
+use shared::config::CachePolicy;
+use shared::{AppError, AppResult, CacheError};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::ports::CacheStore;
+
+/// Caches query results keyed by a signature of (method + params)
+///
+/// Every key embeds the current namespace version, so bumping the version
+/// with `invalidate()` makes all previously cached results unreachable at once.
+/// Cache failures never fail the query: they are logged and the loader runs.
+/// Results are kept for the policy's TTL for their method, else `ttl`.
+#[derive(Clone)]
+pub struct QueryCache {
+    store: Arc<dyn CacheStore>,
+    namespace: String,
+    ttl: Duration,
+    policy: CachePolicy,
+}
+
+impl QueryCache {
+    pub fn new(store: Arc<dyn CacheStore>, namespace: impl Into<String>, ttl: Duration) -> Self {
+        Self {
+            store,
+            namespace: namespace.into(),
+            ttl,
+            policy: CachePolicy(HashMap::new()),
+        }
+    }
+
+    /// Keep each method's results for its TTL in `policy`
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// How long results of `method` are cached
+    pub fn ttl_for(&self, method: &str) -> Duration {
+        self.policy
+            .ttl_seconds(method)
+            .map_or(self.ttl, Duration::from_secs)
+    }
+
+    fn version_key(&self) -> String {
+        format!("{}:ns", self.namespace)
+    }
+
+    /// Current namespace version (0 when never bumped)
+    pub async fn version(&self) -> AppResult<i64> {
+        let Some(raw) = self.store.get(&self.version_key()).await? else {
+            return Ok(0);
+        };
+        std::str::from_utf8(&raw)
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .ok_or_else(|| {
+                AppError::CacheError(CacheError::Command(
+                    "Invalid cache namespace version".to_string(),
+                ))
+            })
+    }
+
+    /// Build the cache key for a query signature under the current version
+    pub async fn key_for<P: Serialize>(&self, method: &str, params: &P) -> AppResult<String> {
+        let version = self.version().await?;
+        let encoded = serde_json::to_vec(params).map_err(|e| {
+            AppError::InternalError(format!("Failed to encode cache params: {}", e))
+        })?;
+        Ok(format!(
+            "{}:v{}:{}:{:016x}",
+            self.namespace,
+            version,
+            method,
+            fnv1a(&encoded)
+        ))
+    }
+
+    /// Return the cached result for the query signature, or run `load`
+    /// and cache its result.
+    pub async fn get_or_load<T, P, F, Fut>(&self, method: &str, params: &P, load: F) -> AppResult<T>
+    where
+        T: Serialize + DeserializeOwned,
+        P: Serialize,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = AppResult<T>>,
+    {
+        let key = match self.key_for(method, params).await {
+            Ok(key) => key,
+            Err(e) => {
+                tracing::warn!("Query cache unavailable, bypassing: {}", e);
+                return load().await;
+            }
+        };
+
+        match self.store.get(&key).await {
+            Ok(Some(bytes)) => match serde_json::from_slice(&bytes) {
+                Ok(value) => return Ok(value),
+                Err(e) => tracing::warn!("Discarding undecodable cache entry {}: {}", key, e),
+            },
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Query cache read failed for {}: {}", key, e),
+        }
+
+        let value = load().await?;
+
+        match serde_json::to_vec(&value) {
+            Ok(bytes) => {
+                if let Err(e) = self.store.set(&key, &bytes, self.ttl_for(method)).await {
+                    tracing::warn!("Query cache write failed for {}: {}", key, e);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to encode cache entry {}: {}", key, e),
+        }
+
+        Ok(value)
+    }
+
+    /// Invalidate every cached query in this namespace by bumping its version
+    pub async fn invalidate(&self) -> AppResult<()> {
+        self.store.incr(&self.version_key()).await?;
+        Ok(())
+    }
+}
+
+/// FNV-1a 64-bit hash; stable across processes unlike `DefaultHasher`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    bytes
+        .iter()
+        .fold(OFFSET, |hash, b| (hash ^ u64::from(*b)).wrapping_mul(PRIME))
+}
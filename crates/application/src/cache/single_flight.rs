@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+/// Collapses concurrent loads of the same key into one in-flight call
+///
+/// The first caller for a key runs the loader; callers arriving while it is
+/// still running await the same result instead of starting their own. Once
+/// the load finishes the key is forgotten, so this deduplicates concurrent
+/// work only and never serves stale values (that is the cache's job).
+pub struct SingleFlight<T> {
+    calls: Mutex<HashMap<String, Arc<OnceCell<T>>>>,
+}
+
+impl<T: Clone> SingleFlight<T> {
+    pub fn new() -> Self {
+        Self {
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Run `load` for `key`, or join the call already in flight for it
+    pub async fn run<F, Fut>(&self, key: &str, load: F) -> T
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let call = self
+            .calls
+            .lock()
+            .unwrap()
+            .entry(key.to_string())
+            .or_default()
+            .clone();
+
+        // If the leader is cancelled, one of the waiters runs its own loader
+        let value = call.get_or_init(load).await.clone();
+
+        let mut calls = self.calls.lock().unwrap();
+        if calls
+            .get(key)
+            .is_some_and(|current| Arc::ptr_eq(current, &call))
+        {
+            calls.remove(key);
+        }
+        value
+    }
+}
+
+impl<T: Clone> Default for SingleFlight<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_sequential_calls_each_run_the_loader() {
+        let flight = SingleFlight::new();
+        let calls = AtomicUsize::new(0);
+
+        for _ in 0..2 {
+            let value = flight
+                .run("key", || async { calls.fetch_add(1, Ordering::SeqCst) })
+                .await;
+            assert_eq!(value, calls.load(Ordering::SeqCst) - 1);
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(flight.calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_distinct_keys_do_not_share_calls() {
+        let flight = SingleFlight::new();
+        let (a, b) = tokio::join!(
+            flight.run("a", || async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                "a"
+            }),
+            flight.run("b", || async { "b" }),
+        );
+        assert_eq!((a, b), ("a", "b"));
+    }
+}
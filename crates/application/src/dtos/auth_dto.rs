@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use shared::UserId;
+use validator::Validate;
+
+/// Request DTO for changing the caller's own password
+///
+/// Deliberately not `Debug`, so neither password can reach the logs.
+#[derive(Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct ChangePasswordRequest {
+    #[validate(length(min = 1, message = "Current password is required"))]
+    pub current_password: String,
+    pub new_password: String,
+}
+
+/// Response DTO for an issued access token
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    /// Seconds until the token expires
+    pub expires_in: u64,
+    /// Admin behind the token when it was issued for impersonation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor_id: Option<UserId>,
+}
+
+impl TokenResponse {
+    pub fn bearer(access_token: String, expires_in: u64, actor_id: Option<UserId>) -> Self {
+        Self {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in,
+            actor_id,
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use shared::{FieldError, UserId};
+
+use crate::dtos::{CreateUserRequest, PublicUserResponse, UserResponse};
+
+/// One row of a bulk create, tagged with its position in the input
+#[derive(Debug)]
+pub struct BulkCreateRow {
+    /// 1-based row number reported back to the caller
+    pub row: usize,
+    pub request: CreateUserRequest,
+}
+
+/// Outcome of a single bulk create row
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkRowStatus {
+    Created,
+    Failed,
+}
+
+/// Per-row result of a bulk create
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkRowResult {
+    pub row: usize,
+    pub status: BulkRowStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<UserId>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+}
+
+impl BulkRowResult {
+    pub fn created(row: usize, user_id: UserId) -> Self {
+        Self {
+            row,
+            status: BulkRowStatus::Created,
+            user_id: Some(user_id),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn failed(row: usize, errors: Vec<FieldError>) -> Self {
+        Self {
+            row,
+            status: BulkRowStatus::Failed,
+            user_id: None,
+            errors,
+        }
+    }
+}
+
+/// Report of a bulk create: one result per input row, ordered by row
+///
+/// For a dry run, `created` rows are the ones that would have been created.
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkCreateReport {
+    pub dry_run: bool,
+    pub total: usize,
+    pub created: usize,
+    pub failed: usize,
+    pub rows: Vec<BulkRowResult>,
+}
+
+impl BulkCreateReport {
+    pub fn from_rows(dry_run: bool, mut rows: Vec<BulkRowResult>) -> Self {
+        rows.sort_by_key(|r| r.row);
+        let created = rows
+            .iter()
+            .filter(|r| r.status == BulkRowStatus::Created)
+            .count();
+        Self {
+            dry_run,
+            total: rows.len(),
+            created,
+            failed: rows.len() - created,
+            rows,
+        }
+    }
+
+    /// Merge rows that failed before reaching the service (e.g. unparseable input)
+    pub fn with_failures(self, failures: Vec<BulkRowResult>) -> Self {
+        let mut rows = self.rows;
+        rows.extend(failures);
+        Self::from_rows(self.dry_run, rows)
+    }
+}
+
+/// Request DTO for deleting many users at once
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchDeleteRequest {
+    pub ids: Vec<UserId>,
+}
+
+/// Request DTO for looking up many users at once
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchGetRequest {
+    pub ids: Vec<UserId>,
+}
+
+/// Users found by a batch lookup, in request order
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchGetResponse<U = UserResponse> {
+    pub users: Vec<U>,
+    /// Requested ids that did not match any user
+    pub not_found: Vec<UserId>,
+}
+
+impl BatchGetResponse {
+    /// The same lookup with each user in its public shape
+    pub fn into_public(self) -> BatchGetResponse<PublicUserResponse> {
+        BatchGetResponse {
+            users: self.users.into_iter().map(Into::into).collect(),
+            not_found: self.not_found,
+        }
+    }
+}
+
+/// Report of a batch delete
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchDeleteReport {
+    /// Distinct ids in the request
+    pub requested: usize,
+    pub deleted: usize,
+    /// Requested ids that did not match any user
+    pub not_found: Vec<UserId>,
+}
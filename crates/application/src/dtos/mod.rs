@@ -1,3 +1,14 @@
+pub mod auth_dto;
+pub mod bulk_dto;
+pub mod timestamp;
 pub mod user_dto;
 
-pub use user_dto::{CreateUserRequest, UpdateUserRequest, UserListResponse, UserResponse};
+pub use auth_dto::{ChangePasswordRequest, TokenResponse};
+pub use bulk_dto::{
+    BatchDeleteReport, BatchDeleteRequest, BatchGetRequest, BatchGetResponse, BulkCreateReport,
+    BulkCreateRow, BulkRowResult, BulkRowStatus,
+};
+pub use user_dto::{
+    CreateUserRequest, PublicUserResponse, UpdateProfileRequest, UpdateUserRequest,
+    UserListResponse, UserResponse,
+};
@@ -0,0 +1,133 @@
+//! Serde module for response timestamps in the configured wire format
+//!
+//! Use with `#[serde(with = "crate::dtos::timestamp")]`. The format is
+//! process-wide and set once at startup via [`set_format`]; deserialization
+//! accepts either format so cached payloads stay readable after a switch.
+
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use serde::de::{self, Visitor};
+use serde::{Deserializer, Serializer};
+use shared::config::TimestampFormat;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static FORMAT: AtomicU8 = AtomicU8::new(RFC3339);
+
+const RFC3339: u8 = 0;
+const UNIX_MILLIS: u8 = 1;
+
+/// Set the timestamp format used when serializing responses
+pub fn set_format(format: TimestampFormat) {
+    let value = match format {
+        TimestampFormat::Rfc3339 => RFC3339,
+        TimestampFormat::UnixMillis => UNIX_MILLIS,
+    };
+    FORMAT.store(value, Ordering::Relaxed);
+}
+
+/// Timestamp format currently used for serialization
+pub fn format() -> TimestampFormat {
+    match FORMAT.load(Ordering::Relaxed) {
+        UNIX_MILLIS => TimestampFormat::UnixMillis,
+        _ => TimestampFormat::Rfc3339,
+    }
+}
+
+pub fn serialize<S: Serializer>(value: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+    serialize_as(value, format(), serializer)
+}
+
+/// Serialize a timestamp in an explicit format
+pub fn serialize_as<S: Serializer>(
+    value: &DateTime<Utc>,
+    format: TimestampFormat,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match format {
+        // Always three fractional digits, matching the stored precision
+        TimestampFormat::Rfc3339 => {
+            serializer.serialize_str(&value.to_rfc3339_opts(SecondsFormat::Millis, false))
+        }
+        TimestampFormat::UnixMillis => serializer.serialize_i64(value.timestamp_millis()),
+    }
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+    deserializer.deserialize_any(TimestampVisitor)
+}
+
+struct TimestampVisitor;
+
+impl Visitor<'_> for TimestampVisitor {
+    type Value = DateTime<Utc>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an RFC 3339 string or Unix epoch milliseconds")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        DateTime::parse_from_rfc3339(value)
+            .map(|dt| dt.with_timezone(&Utc))
+            .map_err(E::custom)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        Utc.timestamp_millis_opt(value)
+            .single()
+            .ok_or_else(|| E::custom(format!("timestamp out of range: {}", value)))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        let value = i64::try_from(value).map_err(E::custom)?;
+        self.visit_i64(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(value: &DateTime<Utc>, format: TimestampFormat) -> serde_json::Value {
+        serialize_as(value, format, serde_json::value::Serializer).unwrap()
+    }
+
+    #[test]
+    fn test_same_timestamp_in_both_formats() {
+        let value = Utc.timestamp_millis_opt(1_700_000_000_123).unwrap();
+
+        assert_eq!(
+            render(&value, TimestampFormat::Rfc3339),
+            serde_json::json!("2023-11-14T22:13:20.123+00:00")
+        );
+        assert_eq!(
+            render(&value, TimestampFormat::UnixMillis),
+            serde_json::json!(1_700_000_000_123i64)
+        );
+    }
+
+    #[test]
+    fn test_rfc3339_renders_milliseconds_only() {
+        let whole = Utc.timestamp_millis_opt(1_700_000_000_000).unwrap();
+        let fine = Utc.timestamp_nanos(1_700_000_000_123_456_789);
+
+        assert_eq!(
+            render(&whole, TimestampFormat::Rfc3339),
+            serde_json::json!("2023-11-14T22:13:20.000+00:00")
+        );
+        assert_eq!(
+            render(&fine, TimestampFormat::Rfc3339),
+            serde_json::json!("2023-11-14T22:13:20.123+00:00")
+        );
+    }
+
+    #[test]
+    fn test_deserializes_either_format() {
+        let value = Utc.timestamp_millis_opt(1_700_000_000_123).unwrap();
+        for input in [
+            serde_json::json!("2023-11-14T22:13:20.123Z"),
+            serde_json::json!(1_700_000_000_123i64),
+        ] {
+            assert_eq!(deserialize(input).unwrap(), value);
+        }
+    }
+}
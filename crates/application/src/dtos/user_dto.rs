@@ -1,34 +1,109 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use shared::UserId;
+use validator::Validate;
 
 use domain::{User, UserStatus};
 
+/// Widths of the `users` columns: hard caps checked at the edge, before the
+/// configurable `ValidationConfig` policy the service applies
+pub const USERNAME_MAX_CHARS: u64 = 30;
+pub const EMAIL_MAX_CHARS: u64 = 255;
+pub const FULL_NAME_MAX_CHARS: u64 = 100;
+pub const AVATAR_URL_MAX_CHARS: u64 = 2048;
+
 /// Request DTO for creating a user
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
 pub struct CreateUserRequest {
+    #[validate(length(
+        min = 1,
+        max = USERNAME_MAX_CHARS,
+        message = "Username must be between 1 and 30 characters"
+    ))]
     pub username: String,
+    #[validate(
+        email(message = "Invalid email format"),
+        length(max = EMAIL_MAX_CHARS, message = "Email cannot exceed 255 characters")
+    )]
     pub email: String,
+    #[validate(length(
+        max = FULL_NAME_MAX_CHARS,
+        message = "Full name cannot exceed 100 characters"
+    ))]
     pub full_name: Option<String>,
 }
 
 /// Request DTO for updating a user
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Validate)]
+#[serde(default, deny_unknown_fields)]
 pub struct UpdateUserRequest {
+    #[validate(length(
+        min = 1,
+        max = USERNAME_MAX_CHARS,
+        message = "Username must be between 1 and 30 characters"
+    ))]
     pub username: Option<String>,
+    #[validate(
+        email(message = "Invalid email format"),
+        length(max = EMAIL_MAX_CHARS, message = "Email cannot exceed 255 characters")
+    )]
     pub email: Option<String>,
+    #[validate(length(
+        max = FULL_NAME_MAX_CHARS,
+        message = "Full name cannot exceed 100 characters"
+    ))]
+    pub full_name: Option<String>,
+    /// http(s) URL of the avatar image; an empty string removes it
+    #[validate(length(
+        max = AVATAR_URL_MAX_CHARS,
+        message = "Avatar URL cannot exceed 2048 characters"
+    ))]
+    pub avatar_url: Option<String>,
+}
+
+/// Request DTO for a user updating their own profile
+///
+/// Only self-service fields are accepted; anything else, `status` or `role`
+/// included, is rejected as an unknown field.
+#[derive(Debug, Default, Deserialize, Validate)]
+#[serde(default, deny_unknown_fields)]
+pub struct UpdateProfileRequest {
+    #[validate(length(
+        max = FULL_NAME_MAX_CHARS,
+        message = "Full name cannot exceed 100 characters"
+    ))]
     pub full_name: Option<String>,
+    /// http(s) URL of the avatar image; an empty string removes it
+    #[validate(length(
+        max = AVATAR_URL_MAX_CHARS,
+        message = "Avatar URL cannot exceed 2048 characters"
+    ))]
+    pub avatar_url: Option<String>,
+}
+
+impl From<UpdateProfileRequest> for UpdateUserRequest {
+    fn from(request: UpdateProfileRequest) -> Self {
+        Self {
+            full_name: request.full_name,
+            avatar_url: request.avatar_url,
+            ..Self::default()
+        }
+    }
 }
 
 /// Response DTO for user data
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct UserResponse {
     pub id: UserId,
     pub username: String,
     pub email: String,
     pub full_name: Option<String>,
+    pub avatar_url: Option<String>,
     pub status: UserStatus,
+    #[serde(with = "crate::dtos::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::dtos::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -39,6 +114,7 @@ impl From<User> for UserResponse {
             username: user.username().to_string(),
             email: user.email().to_string(),
             full_name: user.full_name().map(|s| s.to_string()),
+            avatar_url: user.avatar_url().map(|url| url.to_string()),
             status: user.status(),
             created_at: user.created_at(),
             updated_at: user.updated_at(),
@@ -46,11 +122,53 @@ impl From<User> for UserResponse {
     }
 }
 
+/// User data for self and public views: the admin-only moderation and
+/// audit fields of `UserResponse` (`status`, `updated_at`) are left out
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PublicUserResponse {
+    pub id: UserId,
+    pub username: String,
+    pub email: String,
+    pub full_name: Option<String>,
+    pub avatar_url: Option<String>,
+    #[serde(with = "crate::dtos::timestamp")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<UserResponse> for PublicUserResponse {
+    fn from(user: UserResponse) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            full_name: user.full_name,
+            avatar_url: user.avatar_url,
+            created_at: user.created_at,
+        }
+    }
+}
+
 /// List response with pagination info
-#[derive(Debug, Serialize)]
-pub struct UserListResponse {
-    pub users: Vec<UserResponse>,
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserListResponse<U = UserResponse> {
+    pub users: Vec<U>,
     pub total: i64,
     pub limit: i64,
     pub offset: i64,
+    /// Cursor for the following page; absent once a page comes back short
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl UserListResponse {
+    /// The same page with each user in its public shape
+    pub fn into_public(self) -> UserListResponse<PublicUserResponse> {
+        UserListResponse {
+            users: self.users.into_iter().map(Into::into).collect(),
+            total: self.total,
+            limit: self.limit,
+            offset: self.offset,
+            next_cursor: self.next_cursor,
+        }
+    }
 }
@@ -0,0 +1,3 @@
+pub mod purge_deleted_users;
+
+pub use purge_deleted_users::PurgeDeletedUsersJob;
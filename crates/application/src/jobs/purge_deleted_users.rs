@@ -0,0 +1,61 @@
+use chrono::TimeDelta;
+use shared::AppResult;
+use shared::config::RetentionConfig;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::MissedTickBehavior;
+
+use crate::services::UserService;
+
+/// Periodically hard-deletes users that have been soft-deleted for longer
+/// than the retention period
+///
+/// Runs never overlap: a tick that fires while the previous purge is still
+/// in progress is skipped.
+pub struct PurgeDeletedUsersJob {
+    service: Arc<UserService>,
+    retention: TimeDelta,
+    running: Mutex<()>,
+}
+
+impl PurgeDeletedUsersJob {
+    pub fn new(service: Arc<UserService>, retention: TimeDelta) -> Self {
+        Self {
+            service,
+            retention,
+            running: Mutex::new(()),
+        }
+    }
+
+    pub fn from_config(service: Arc<UserService>, config: &RetentionConfig) -> Self {
+        let days = i64::try_from(config.deleted_user_retention_days).unwrap_or(i64::MAX);
+        Self::new(service, TimeDelta::try_days(days).unwrap_or(TimeDelta::MAX))
+    }
+
+    /// Run one purge, or return `None` when a previous run is still going
+    pub async fn run_once(&self) -> Option<AppResult<u64>> {
+        let Ok(_running) = self.running.try_lock() else {
+            tracing::debug!("Previous purge of deleted users still running, skipping");
+            return None;
+        };
+
+        let result = self.service.purge_deleted_users(self.retention).await;
+        match &result {
+            Ok(purged) => tracing::info!(purged, "Purged soft-deleted users"),
+            Err(e) => tracing::warn!("Failed to purge soft-deleted users: {}", e),
+        }
+        Some(result)
+    }
+
+    /// Run the job every `interval`, starting now; never returns, so drop
+    /// the future to stop it
+    pub async fn run_every(&self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            ticker.tick().await;
+            self.run_once().await;
+        }
+    }
+}
@@ -1,5 +1,22 @@
+pub mod after_commit;
+pub mod auth;
+pub mod cache;
 pub mod dtos;
+pub mod jobs;
+pub mod pagination;
+pub mod ports;
 pub mod services;
 
-pub use dtos::{CreateUserRequest, UpdateUserRequest, UserListResponse, UserResponse};
-pub use services::UserService;
+pub use after_commit::AfterCommit;
+pub use auth::{AuthContext, Claims, Role};
+pub use cache::{QueryCache, SingleFlight};
+pub use dtos::{
+    BatchDeleteReport, BatchDeleteRequest, BatchGetRequest, BatchGetResponse, BulkCreateReport,
+    BulkCreateRow, BulkRowResult, BulkRowStatus, ChangePasswordRequest, CreateUserRequest,
+    PublicUserResponse, TokenResponse, UpdateProfileRequest, UpdateUserRequest, UserListResponse,
+    UserResponse,
+};
+pub use jobs::PurgeDeletedUsersJob;
+pub use pagination::Cursor;
+pub use ports::{CacheStore, EmailMessage, EmailSender, EventBus, PasswordHasher, TokenService};
+pub use services::{AuthService, UserService};
@@ -0,0 +1,113 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use shared::{AppError, AppResult, UserId};
+
+type HmacSha256 = Hmac<Sha256>;
+
+const PAYLOAD_LEN: usize = 8 + 16;
+const TAG_LEN: usize = 32;
+
+/// Keyset pagination cursor over `(created_at, id)`
+///
+/// Encoded as URL-safe base64 of the position followed by an HMAC-SHA256 tag
+/// keyed by a server secret, so clients cannot forge or alter positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub created_at: DateTime<Utc>,
+    pub id: UserId,
+}
+
+impl Cursor {
+    pub fn new(created_at: DateTime<Utc>, id: UserId) -> Self {
+        Self { created_at, id }
+    }
+
+    /// Encode the cursor into an opaque, signed token
+    pub fn encode(&self, secret: &[u8]) -> String {
+        let mut bytes = Vec::with_capacity(PAYLOAD_LEN + TAG_LEN);
+        bytes.extend_from_slice(&self.created_at.timestamp_micros().to_be_bytes());
+        bytes.extend_from_slice(self.id.as_uuid().as_bytes());
+
+        let tag = mac(secret, &bytes).finalize().into_bytes();
+        bytes.extend_from_slice(&tag);
+
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Decode a token produced by `encode`, rejecting malformed or tampered input
+    pub fn decode(token: &str, secret: &[u8]) -> AppResult<Self> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(token)
+            .map_err(|_| invalid_cursor())?;
+        if bytes.len() != PAYLOAD_LEN + TAG_LEN {
+            return Err(invalid_cursor());
+        }
+
+        let (payload, tag) = bytes.split_at(PAYLOAD_LEN);
+        mac(secret, payload)
+            .verify_slice(tag)
+            .map_err(|_| invalid_cursor())?;
+
+        let (micros, id) = payload.split_at(8);
+        let micros = i64::from_be_bytes(micros.try_into().map_err(|_| invalid_cursor())?);
+        let created_at = DateTime::from_timestamp_micros(micros).ok_or_else(invalid_cursor)?;
+        let id = Uuid::from_slice(id).map_err(|_| invalid_cursor())?;
+
+        Ok(Self::new(created_at, UserId::from_uuid(id)))
+    }
+}
+
+fn mac(secret: &[u8], payload: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(payload);
+    mac
+}
+
+fn invalid_cursor() -> AppError {
+    AppError::ValidationError("Invalid pagination cursor".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"test-cursor-secret";
+
+    fn sample() -> Cursor {
+        let created_at = DateTime::from_timestamp_micros(1_700_000_000_123_456).unwrap();
+        Cursor::new(created_at, UserId::new())
+    }
+
+    #[test]
+    fn test_cursor_round_trips() {
+        let cursor = sample();
+
+        let decoded = Cursor::decode(&cursor.encode(SECRET), SECRET).unwrap();
+
+        assert_eq!(decoded, cursor);
+    }
+
+    #[test]
+    fn test_byte_flipped_cursor_is_rejected() {
+        let token = sample().encode(SECRET);
+        let mut bytes = URL_SAFE_NO_PAD.decode(&token).unwrap();
+        bytes[3] ^= 0x01;
+        let tampered = URL_SAFE_NO_PAD.encode(bytes);
+
+        let result = Cursor::decode(&tampered, SECRET);
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_cursor_signed_with_other_secret_is_rejected() {
+        let token = sample().encode(b"another-secret");
+
+        assert!(Cursor::decode(&token, SECRET).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod cursor;
+
+pub use cursor::Cursor;
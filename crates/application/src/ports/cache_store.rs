@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use shared::AppResult;
+use std::time::Duration;
+
+/// CacheStore trait (Port)
+///
+/// Minimal key/value interface the application layer needs from a cache.
+/// Infrastructure provides the concrete adapter (e.g. Redis).
+#[async_trait]
+pub trait CacheStore: Send + Sync {
+    /// Get the raw value stored under `key`
+    async fn get(&self, key: &str) -> AppResult<Option<Vec<u8>>>;
+
+    /// Store `value` under `key`, expiring after `ttl`
+    async fn set(&self, key: &str, value: &[u8], ttl: Duration) -> AppResult<()>;
+
+    /// Atomically increment the integer stored under `key`, returning the new value
+    async fn incr(&self, key: &str) -> AppResult<i64>;
+
+    /// Remove `key`
+    async fn delete(&self, key: &str) -> AppResult<()>;
+}
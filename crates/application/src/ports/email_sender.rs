@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use shared::AppResult;
+
+/// Outgoing email message
+#[derive(Debug, Clone)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// EmailSender trait (Port)
+///
+/// Delivers transactional email. Infrastructure provides the concrete
+/// adapter (e.g. SMTP).
+#[async_trait]
+pub trait EmailSender: Send + Sync {
+    /// Deliver `message` to its recipient
+    async fn send(&self, message: EmailMessage) -> AppResult<()>;
+
+    /// Check that the mail server is reachable and accepts a session
+    async fn verify_connection(&self) -> AppResult<()>;
+}
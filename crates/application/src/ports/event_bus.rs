@@ -0,0 +1,10 @@
+use domain::UserEvent;
+
+/// EventBus trait (Port)
+///
+/// Fans user lifecycle events out to subscribers. Infrastructure provides
+/// the concrete adapter (in-process now, an external broker later).
+pub trait EventBus: Send + Sync {
+    /// Publish `event`; having no subscribers is not an error
+    fn publish(&self, event: UserEvent);
+}
@@ -0,0 +1,11 @@
+pub mod cache_store;
+pub mod email_sender;
+pub mod event_bus;
+pub mod password_hasher;
+pub mod token_service;
+
+pub use cache_store::CacheStore;
+pub use email_sender::{EmailMessage, EmailSender};
+pub use event_bus::EventBus;
+pub use password_hasher::PasswordHasher;
+pub use token_service::TokenService;
@@ -0,0 +1,16 @@
+use shared::AppResult;
+
+/// PasswordHasher trait (Port)
+///
+/// Hashes and verifies passwords. Infrastructure provides the concrete
+/// adapter (e.g. Argon2).
+pub trait PasswordHasher: Send + Sync {
+    /// Hash `password` with the current parameters
+    fn hash(&self, password: &str) -> AppResult<String>;
+
+    /// Check `password` against a stored hash
+    fn verify(&self, password: &str, hash: &str) -> AppResult<bool>;
+
+    /// Whether a stored hash uses weaker parameters than the current ones
+    fn needs_rehash(&self, hash: &str) -> bool;
+}
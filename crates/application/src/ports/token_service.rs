@@ -0,0 +1,15 @@
+use shared::AppResult;
+
+use crate::auth::Claims;
+
+/// TokenService trait (Port)
+///
+/// Signs and verifies access tokens. Infrastructure provides the concrete
+/// adapter (e.g. JWT).
+pub trait TokenService: Send + Sync {
+    /// Sign `claims` into a token string
+    fn issue(&self, claims: &Claims) -> AppResult<String>;
+
+    /// Verify a token's signature and expiry and return its claims
+    fn verify(&self, token: &str) -> AppResult<Claims>;
+}
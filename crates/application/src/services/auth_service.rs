@@ -0,0 +1,244 @@
+use chrono::Utc;
+use shared::config::{JwtConfig, PasswordHashing, PasswordPolicy};
+use shared::{AppError, AppResult, UserId};
+use std::sync::Arc;
+
+use domain::{Password, UserRepository};
+
+use crate::auth::{Actor, AuthContext, Claims, Role};
+use crate::dtos::TokenResponse;
+use crate::ports::{PasswordHasher, TokenService};
+
+/// Authentication use cases: verifying, issuing and refreshing access tokens
+pub struct AuthService {
+    user_repository: Arc<dyn UserRepository>,
+    tokens: Arc<dyn TokenService>,
+    config: JwtConfig,
+    passwords: Option<Arc<dyn PasswordHasher>>,
+    rehash_on_login: bool,
+    password_policy: PasswordPolicy,
+}
+
+impl AuthService {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        tokens: Arc<dyn TokenService>,
+        config: JwtConfig,
+    ) -> Self {
+        Self {
+            user_repository,
+            tokens,
+            config,
+            passwords: None,
+            rehash_on_login: false,
+            password_policy: PasswordPolicy::default(),
+        }
+    }
+
+    /// Verify passwords with this hasher, upgrading outdated hashes when
+    /// `rehash_on_login` is set
+    pub fn with_password_hasher(
+        mut self,
+        passwords: Arc<dyn PasswordHasher>,
+        config: &PasswordHashing,
+    ) -> Self {
+        self.passwords = Some(passwords);
+        self.rehash_on_login = config.rehash_on_login;
+        self
+    }
+
+    /// Rules new passwords must satisfy
+    pub fn with_password_policy(mut self, policy: PasswordPolicy) -> Self {
+        self.password_policy = policy;
+        self
+    }
+
+    /// Resolve the identity behind a bearer token
+    ///
+    /// Tokens issued under an older session epoch than the user's current one
+    /// are rejected; the epoch moves on whenever the password changes. Tokens
+    /// for users that no longer exist, including soft-deleted ones, are
+    /// rejected too.
+    pub async fn authenticate(&self, token: &str) -> AppResult<AuthContext> {
+        let claims = self.tokens.verify(token)?;
+        let context = AuthContext::from(&claims);
+        match self
+            .user_repository
+            .find_session_epoch(context.user_id)
+            .await?
+        {
+            Some(current) if current <= context.epoch => Ok(context),
+            _ => Err(AppError::Unauthorized(
+                "Session has been revoked".to_string(),
+            )),
+        }
+    }
+
+    /// Use Case: Check a user's password
+    ///
+    /// On success a hash stored with weaker parameters than the current ones
+    /// is transparently replaced; failing to store it does not fail the login.
+    pub async fn verify_password(&self, user_id: UserId, password: &str) -> AppResult<()> {
+        let passwords = self.passwords.as_ref().ok_or_else(|| {
+            AppError::ConfigurationError("No password hasher configured".to_string())
+        })?;
+        let invalid = || AppError::Unauthorized("Invalid credentials".to_string());
+
+        let stored = self
+            .user_repository
+            .find_password_hash(user_id)
+            .await?
+            .ok_or_else(invalid)?;
+        if !passwords.verify(password, &stored)? {
+            return Err(invalid());
+        }
+
+        if self.rehash_on_login && passwords.needs_rehash(&stored) {
+            let upgraded = passwords.hash(password)?;
+            match self
+                .user_repository
+                .set_password_hash(user_id, &upgraded)
+                .await
+            {
+                Ok(()) => tracing::info!(user_id = %user_id, "Upgraded password hash"),
+                Err(e) => {
+                    tracing::warn!(user_id = %user_id, "Failed to upgrade password hash: {}", e)
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Issue a regular access token for `user_id` under its current session epoch
+    pub async fn issue_token(&self, user_id: UserId, role: Role) -> AppResult<TokenResponse> {
+        let epoch = self
+            .user_repository
+            .find_session_epoch(user_id)
+            .await?
+            .unwrap_or_default();
+        self.sign(
+            user_id,
+            role,
+            None,
+            self.config.access_token_ttl_seconds,
+            epoch,
+        )
+    }
+
+    /// Use Case: Let a user rotate their own password
+    ///
+    /// The current password must verify and the new one satisfy the password
+    /// policy. Storing it bumps the session epoch, revoking every token issued
+    /// so far; the returned token replaces the caller's own.
+    pub async fn change_password(
+        &self,
+        caller: &AuthContext,
+        current_password: &str,
+        new_password: &str,
+    ) -> AppResult<TokenResponse> {
+        if caller.is_impersonated() {
+            return Err(AppError::Forbidden(
+                "Impersonation tokens cannot change passwords".to_string(),
+            ));
+        }
+        self.verify_password(caller.user_id, current_password)
+            .await?;
+        let new_password = Password::new(new_password, &self.password_policy)?;
+
+        let passwords = self.passwords.as_ref().ok_or_else(|| {
+            AppError::ConfigurationError("No password hasher configured".to_string())
+        })?;
+        let hash = passwords.hash(new_password.as_str())?;
+        let epoch = self
+            .user_repository
+            .change_password_hash(caller.user_id, &hash)
+            .await?;
+        tracing::info!(user_id = %caller.user_id, "Password changed, earlier sessions revoked");
+
+        self.sign(
+            caller.user_id,
+            caller.role,
+            None,
+            self.config.access_token_ttl_seconds,
+            epoch,
+        )
+    }
+
+    /// Use Case: Let an admin act as another user
+    ///
+    /// The short-lived token acts as the target with plain user rights and
+    /// records the admin in its `act` claim.
+    pub async fn impersonate(
+        &self,
+        admin: &AuthContext,
+        target: UserId,
+    ) -> AppResult<TokenResponse> {
+        if !admin.is_admin() {
+            return Err(AppError::Forbidden(
+                "Only admins can impersonate users".to_string(),
+            ));
+        }
+        if admin.is_impersonated() {
+            return Err(AppError::Forbidden(
+                "Impersonation tokens cannot start another impersonation".to_string(),
+            ));
+        }
+
+        let epoch = self
+            .user_repository
+            .find_session_epoch(target)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("User with ID {} not found", target)))?;
+
+        tracing::info!(
+            user_id = %target,
+            actor_id = %admin.user_id,
+            "Issuing impersonation token"
+        );
+        self.sign(
+            target,
+            Role::User,
+            Some(admin.user_id),
+            self.config.impersonation_ttl_seconds,
+            epoch,
+        )
+    }
+
+    /// Use Case: Exchange a still-valid token for a fresh one
+    pub fn refresh(&self, context: &AuthContext) -> AppResult<TokenResponse> {
+        if context.is_impersonated() {
+            return Err(AppError::Forbidden(
+                "Impersonation tokens cannot be refreshed".to_string(),
+            ));
+        }
+        self.sign(
+            context.user_id,
+            context.role,
+            None,
+            self.config.access_token_ttl_seconds,
+            context.epoch,
+        )
+    }
+
+    fn sign(
+        &self,
+        user_id: UserId,
+        role: Role,
+        actor_id: Option<UserId>,
+        ttl_seconds: u64,
+        epoch: i64,
+    ) -> AppResult<TokenResponse> {
+        let now = Utc::now().timestamp();
+        let claims = Claims {
+            sub: user_id,
+            role,
+            iss: self.config.issuer.clone(),
+            iat: now,
+            exp: now + ttl_seconds as i64,
+            act: actor_id.map(|sub| Actor { sub }),
+            epoch,
+        };
+        let token = self.tokens.issue(&claims)?;
+        Ok(TokenResponse::bearer(token, ttl_seconds, actor_id))
+    }
+}
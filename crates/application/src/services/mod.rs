@@ -1,3 +1,5 @@
+pub mod auth_service;
 pub mod user_service;
 
+pub use auth_service::AuthService;
 pub use user_service::UserService;
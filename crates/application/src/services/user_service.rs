@@ -1,9 +1,27 @@
-use shared::{AppError, AppResult, UserId};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Serialize, de::DeserializeOwned};
+use shared::config::{FeatureFlags, ValidationConfig};
+use shared::defaults::security::DEFAULT_CURSOR_SECRET;
+use shared::{AppError, AppResult, UserId, ValidationErrors};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use domain::{Email, User, UserRepository, Username};
+use domain::{
+    Clock, Email, SystemClock, Url, User, UserEvent, UserFilter, UserRepository, UserSort, Username,
+};
 
-use crate::dtos::{CreateUserRequest, UpdateUserRequest, UserListResponse, UserResponse};
+use crate::after_commit::AfterCommit;
+use crate::auth::AuthContext;
+use crate::cache::{QueryCache, SingleFlight};
+use crate::dtos::{
+    BatchDeleteReport, BatchGetResponse, BulkCreateReport, BulkCreateRow, BulkRowResult,
+    CreateUserRequest, UpdateUserRequest, UserListResponse, UserResponse,
+};
+use crate::pagination::Cursor;
+use crate::ports::EventBus;
+
+/// Upper bound on rows accepted by a single bulk create or batch delete
+pub const MAX_BULK_ROWS: usize = 1000;
 
 /// User service containing all user-related use cases
 ///
@@ -11,12 +29,127 @@ use crate::dtos::{CreateUserRequest, UpdateUserRequest, UserListResponse, UserRe
 /// It follows the Application Service pattern from Clean Architecture.
 pub struct UserService {
     user_repository: Arc<dyn UserRepository>,
+    query_cache: Option<QueryCache>,
+    events: Option<Arc<dyn EventBus>>,
+    validation: ValidationConfig,
+    features: FeatureFlags,
+    cursor_secret: Vec<u8>,
+    lookups: SingleFlight<AppResult<Option<User>>>,
+    clock: Arc<dyn Clock>,
 }
 
 impl UserService {
     /// Create a new UserService
     pub fn new(user_repository: Arc<dyn UserRepository>) -> Self {
-        Self { user_repository }
+        Self {
+            user_repository,
+            query_cache: None,
+            events: None,
+            validation: ValidationConfig::default(),
+            features: FeatureFlags::default(),
+            cursor_secret: DEFAULT_CURSOR_SECRET.as_bytes().to_vec(),
+            lookups: SingleFlight::new(),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Validate usernames and full names against configured length bounds
+    pub fn with_validation(mut self, validation: ValidationConfig) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Apply deployment feature flags such as `allow_user_deletion`
+    pub fn with_features(mut self, features: FeatureFlags) -> Self {
+        self.features = features;
+        self
+    }
+
+    /// Sign pagination cursors with this key
+    pub fn with_cursor_secret(mut self, secret: impl Into<Vec<u8>>) -> Self {
+        self.cursor_secret = secret.into();
+        self
+    }
+
+    /// Stamp created and updated users with this clock instead of wall time
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Cache list queries; any user mutation invalidates the cached results
+    pub fn with_query_cache(mut self, query_cache: QueryCache) -> Self {
+        self.query_cache = Some(query_cache);
+        self
+    }
+
+    /// Publish user lifecycle events to subscribers (e.g. streaming endpoints)
+    pub fn with_event_bus(mut self, events: Arc<dyn EventBus>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Publish `event` once the write that caused it has committed
+    async fn publish(&self, event: UserEvent) {
+        if let Some(events) = self.events.clone() {
+            AfterCommit::defer(async move { events.publish(event) }).await;
+        }
+    }
+
+    /// Invalidate cached queries once the mutation has committed, so no
+    /// read can re-cache the old state in between. Failures are logged only,
+    /// since the write itself already succeeded.
+    async fn invalidate_queries(&self) {
+        if let Some(cache) = self.query_cache.clone() {
+            AfterCommit::defer(async move {
+                if let Err(e) = cache.invalidate().await {
+                    tracing::warn!("Failed to invalidate user query cache: {}", e);
+                }
+            })
+            .await;
+        }
+    }
+
+    /// Serve a derived query from the versioned query cache when enabled
+    async fn cached<T, P, F, Fut>(&self, method: &str, params: &P, load: F) -> AppResult<T>
+    where
+        T: Serialize + DeserializeOwned,
+        P: Serialize,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = AppResult<T>>,
+    {
+        match &self.query_cache {
+            Some(cache) => cache.get_or_load(method, params, load).await,
+            None => load().await,
+        }
+    }
+
+    /// Reject `email` when another user owns the same mailbox under the
+    /// email policy (e.g. `a+tag@gmail.com` vs `a@gmail.com`)
+    async fn ensure_mailbox_available(
+        &self,
+        email: &Email,
+        owner: Option<UserId>,
+    ) -> AppResult<()> {
+        let policy = &self.validation.email_policy;
+        // Without a rule the canonical form is the address itself, which
+        // the exact uniqueness check already covers
+        if email.domain().and_then(|d| policy.rule_for(d)).is_none() {
+            return Ok(());
+        }
+
+        if let Some(existing) = self
+            .user_repository
+            .find_by_canonical_email(email, policy)
+            .await?
+            && Some(existing.id()) != owner
+        {
+            return Err(AppError::AlreadyExists(format!(
+                "Email '{}' already exists",
+                email
+            )));
+        }
+        Ok(())
     }
 
     /// Use Case: Create a new user
@@ -25,55 +158,216 @@ impl UserService {
     /// - Username must be unique
     /// - Email must be unique
     /// - Username and email must be valid
+    #[tracing::instrument(skip_all)]
     pub async fn create_user(&self, request: CreateUserRequest) -> AppResult<UserResponse> {
-        // Validate and create value objects
-        let username = Username::new(request.username)?;
-        let email = Email::new(request.email)?;
+        let user = self.build_user(request)?;
 
-        // Business rule: Username must be unique
-        if self.user_repository.username_exists(&username).await? {
-            return Err(AppError::AlreadyExists(format!(
-                "Username '{}' already exists",
-                username
-            )));
+        // Business rule: Username and email must be unique
+        let (username_taken, email_taken) = self
+            .user_repository
+            .username_or_email_exists(user.username(), user.email())
+            .await?;
+        match (username_taken, email_taken) {
+            (true, true) => {
+                return Err(AppError::AlreadyExists(format!(
+                    "Username '{}' and email '{}' already exist",
+                    user.username(),
+                    user.email()
+                )));
+            }
+            (true, false) => {
+                return Err(AppError::AlreadyExists(format!(
+                    "Username '{}' already exists",
+                    user.username()
+                )));
+            }
+            (false, true) => {
+                return Err(AppError::AlreadyExists(format!(
+                    "Email '{}' already exists",
+                    user.email()
+                )));
+            }
+            (false, false) => {}
         }
+        self.ensure_mailbox_available(user.email(), None).await?;
 
-        // Business rule: Email must be unique
-        if self.user_repository.email_exists(&email).await? {
-            return Err(AppError::AlreadyExists(format!(
-                "Email '{}' already exists",
-                email
+        // Persist user
+        self.user_repository.create(&user).await?;
+        self.invalidate_queries().await;
+        self.publish(UserEvent::created(&user)).await;
+
+        Ok(UserResponse::from(user))
+    }
+
+    /// Use Case: Create many users at once
+    ///
+    /// Every row is validated and checked for uniqueness (against existing
+    /// users and earlier rows of the same batch). Valid rows are inserted in a
+    /// single transaction; invalid rows are reported without aborting the batch.
+    /// With `dry_run` the same checks run and the insert transaction is rolled
+    /// back, so the report previews a real run without writing anything.
+    #[tracing::instrument(skip_all, fields(rows = rows.len(), dry_run = dry_run))]
+    pub async fn bulk_create_users(
+        &self,
+        rows: Vec<BulkCreateRow>,
+        dry_run: bool,
+    ) -> AppResult<BulkCreateReport> {
+        if rows.len() > MAX_BULK_ROWS {
+            return Err(AppError::ValidationError(format!(
+                "A bulk create accepts at most {} rows",
+                MAX_BULK_ROWS
             )));
         }
 
+        let mut results = Vec::with_capacity(rows.len());
+        let mut accepted: Vec<(usize, User)> = Vec::new();
+        let mut seen_usernames = HashSet::new();
+        let mut seen_emails = HashSet::new();
+
+        for BulkCreateRow { row, request } in rows {
+            let user = match self.build_user(request) {
+                Ok(user) => user,
+                Err(AppError::Validation(errors)) => {
+                    results.push(BulkRowResult::failed(row, errors.errors().to_vec()));
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            let (username_taken, email_taken) = self
+                .user_repository
+                .username_or_email_exists(user.username(), user.email())
+                .await?;
+            let mut errors = ValidationErrors::new();
+            if !seen_usernames.insert(user.username().as_str().to_string()) || username_taken {
+                errors.add(
+                    "username",
+                    "already_exists",
+                    format!("Username '{}' already exists", user.username()),
+                );
+            }
+            if !seen_emails.insert(user.email().as_str().to_string()) || email_taken {
+                errors.add(
+                    "email",
+                    "already_exists",
+                    format!("Email '{}' already exists", user.email()),
+                );
+            }
+
+            if errors.is_empty() {
+                accepted.push((row, user));
+            } else {
+                results.push(BulkRowResult::failed(row, errors.errors().to_vec()));
+            }
+        }
+
+        if !accepted.is_empty() {
+            let users: Vec<User> = accepted.iter().map(|(_, user)| user.clone()).collect();
+            self.user_repository.create_many(&users, dry_run).await?;
+            if !dry_run {
+                self.invalidate_queries().await;
+                for user in &users {
+                    self.publish(UserEvent::created(user)).await;
+                }
+            }
+        }
+
+        results.extend(
+            accepted
+                .into_iter()
+                .map(|(row, user)| BulkRowResult::created(row, user.id())),
+        );
+        Ok(BulkCreateReport::from_rows(dry_run, results))
+    }
+
+    /// Validate a create request into a new user entity, collecting every
+    /// field failure
+    fn build_user(&self, request: CreateUserRequest) -> AppResult<User> {
+        let mut errors = ValidationErrors::new();
+        let username = errors.capture(
+            "username",
+            Username::with_policy(request.username, &self.validation),
+        );
+        let email = errors.capture("email", Email::new(request.email));
+        if let Some(full_name) = &request.full_name {
+            errors.capture(
+                "full_name",
+                User::validate_full_name(full_name, &self.validation),
+            );
+        }
+        let (Some(username), Some(email)) = (username, email) else {
+            return Err(AppError::Validation(errors));
+        };
+        errors.into_result()?;
+
         // Create domain entity
-        let mut user = User::new(username, email);
+        let mut user = User::new(username, email, self.clock.as_ref());
 
         // Set optional fields
         if let Some(full_name) = request.full_name {
-            user.update_full_name(Some(full_name))?;
+            user.update_full_name(Some(full_name), &self.validation, self.clock.as_ref())?;
         }
 
-        // Persist user
-        self.user_repository.create(&user).await?;
-
-        Ok(UserResponse::from(user))
+        Ok(user)
     }
 
     /// Use Case: Get user by ID
+    ///
+    /// Concurrent lookups of the same id share a single repository call.
+    #[tracing::instrument(skip_all, fields(user_id = %user_id))]
     pub async fn get_user(&self, user_id: UserId) -> AppResult<UserResponse> {
         let user = self
-            .user_repository
-            .find_by_id(user_id)
+            .lookups
+            .run(&format!("user:{}", user_id), || {
+                self.user_repository.find_by_id(user_id)
+            })
             .await?
             .ok_or_else(|| AppError::NotFound(format!("User with ID {} not found", user_id)))?;
 
         Ok(UserResponse::from(user))
     }
 
+    /// Use Case: Get many users by ID in one lookup, reporting the ids that
+    /// did not match a user
+    #[tracing::instrument(skip_all, fields(count = ids.len()))]
+    pub async fn get_users(&self, ids: Vec<UserId>) -> AppResult<BatchGetResponse> {
+        if ids.len() > self.validation.max_batch_get_ids {
+            return Err(AppError::ValidationError(format!(
+                "A batch lookup accepts at most {} ids",
+                self.validation.max_batch_get_ids
+            )));
+        }
+
+        let mut seen = HashSet::new();
+        let ids: Vec<UserId> = ids.into_iter().filter(|id| seen.insert(*id)).collect();
+        let mut found: HashMap<UserId, User> = if ids.is_empty() {
+            HashMap::new()
+        } else {
+            self.user_repository
+                .find_by_ids(&ids)
+                .await?
+                .into_iter()
+                .map(|user| (user.id(), user))
+                .collect()
+        };
+
+        let mut response = BatchGetResponse {
+            users: Vec::with_capacity(found.len()),
+            not_found: Vec::new(),
+        };
+        for id in ids {
+            match found.remove(&id) {
+                Some(user) => response.users.push(UserResponse::from(user)),
+                None => response.not_found.push(id),
+            }
+        }
+        Ok(response)
+    }
+
     /// Use Case: Get user by username
+    #[tracing::instrument(skip_all)]
     pub async fn get_user_by_username(&self, username: String) -> AppResult<UserResponse> {
-        let username = Username::new(username)?;
+        let username = Username::with_policy(username, &self.validation)?;
         let user = self
             .user_repository
             .find_by_username(&username)
@@ -86,6 +380,7 @@ impl UserService {
     }
 
     /// Use Case: Update user
+    #[tracing::instrument(skip_all, fields(user_id = %user_id))]
     pub async fn update_user(
         &self,
         user_id: UserId,
@@ -98,55 +393,73 @@ impl UserService {
             .await?
             .ok_or_else(|| AppError::NotFound(format!("User with ID {} not found", user_id)))?;
 
-        // Update username if provided
-        if let Some(username_str) = request.username {
-            let new_username = Username::new(username_str)?;
-
-            // Check if username is already taken by another user
-            if let Some(existing_user) =
-                self.user_repository.find_by_username(&new_username).await?
-            {
-                if existing_user.id() != user_id {
-                    return Err(AppError::AlreadyExists(format!(
-                        "Username '{}' already exists",
-                        new_username
-                    )));
-                }
-            }
-
-            user.update_username(new_username);
-        }
+        // Validate the new username now; it is claimed once everything else
+        // is known to be valid
+        let new_username = request
+            .username
+            .map(|username| Username::with_policy(username, &self.validation))
+            .transpose()?
+            .filter(|username| username != user.username());
 
         // Update email if provided
         if let Some(email_str) = request.email {
             let new_email = Email::new(email_str)?;
 
             // Check if email is already taken by another user
-            if let Some(existing_user) = self.user_repository.find_by_email(&new_email).await? {
-                if existing_user.id() != user_id {
-                    return Err(AppError::AlreadyExists(format!(
-                        "Email '{}' already exists",
-                        new_email
-                    )));
-                }
+            if let Some(existing_user) = self.user_repository.find_by_email(&new_email).await?
+                && existing_user.id() != user_id
+            {
+                return Err(AppError::AlreadyExists(format!(
+                    "Email '{}' already exists",
+                    new_email
+                )));
             }
+            self.ensure_mailbox_available(&new_email, Some(user_id))
+                .await?;
 
-            user.update_email(new_email);
+            user.update_email(new_email, self.clock.as_ref());
         }
 
         // Update full name if provided (even if None to allow clearing)
         if request.full_name.is_some() {
-            user.update_full_name(request.full_name)?;
+            user.update_full_name(request.full_name, &self.validation, self.clock.as_ref())?;
         }
 
-        // Persist changes
-        self.user_repository.update(&user).await?;
+        // Update avatar URL if provided; an empty string removes it
+        if let Some(avatar_url) = request.avatar_url {
+            let avatar_url = match avatar_url.trim() {
+                "" => None,
+                url => Some(Url::new(url)?),
+            };
+            user.update_avatar_url(avatar_url, self.clock.as_ref());
+        }
+
+        // Persist changes. A new username is claimed in the same statement,
+        // so two users renaming to the same name at once cannot both succeed
+        // and a refused claim leaves every other field unchanged too.
+        match new_username {
+            Some(new_username) => {
+                user.update_username(new_username, self.clock.as_ref());
+                self.user_repository.update_claiming_username(&user).await?;
+            }
+            None => self.user_repository.update(&user).await?,
+        }
+        self.invalidate_queries().await;
+        self.publish(UserEvent::updated(&user)).await;
 
         Ok(UserResponse::from(user))
     }
 
     /// Use Case: Delete user
+    ///
+    /// A soft delete, undone by `restore_user` until the user is purged.
+    /// Forbidden when `allow_user_deletion` is off.
+    #[tracing::instrument(skip_all, fields(user_id = %user_id))]
     pub async fn delete_user(&self, user_id: UserId) -> AppResult<()> {
+        if !self.features.allow_user_deletion {
+            return Err(AppError::Forbidden("User deletion is disabled".to_string()));
+        }
+
         // Verify user exists
         let _user = self
             .user_repository
@@ -156,13 +469,250 @@ impl UserService {
 
         // Delete user
         self.user_repository.delete(user_id).await?;
+        self.invalidate_queries().await;
+        self.publish(UserEvent::deleted(user_id)).await;
 
         Ok(())
     }
 
+    /// Use Case: Restore a soft-deleted user
+    #[tracing::instrument(skip_all, fields(user_id = %user_id))]
+    pub async fn restore_user(&self, user_id: UserId) -> AppResult<UserResponse> {
+        let user = self.user_repository.restore(user_id).await?;
+        self.invalidate_queries().await;
+        self.publish(UserEvent::updated(&user)).await;
+
+        Ok(UserResponse::from(user))
+    }
+
+    /// Use Case: Permanently remove users soft-deleted more than `retention` ago
+    #[tracing::instrument(skip_all)]
+    pub async fn purge_deleted_users(&self, retention: TimeDelta) -> AppResult<u64> {
+        if !self.features.allow_user_deletion {
+            return Err(AppError::Forbidden(
+                "Permanent user deletion is disabled".to_string(),
+            ));
+        }
+        let cutoff = self.clock.now() - retention;
+        let purged = self.user_repository.purge_deleted(cutoff).await?;
+        if purged > 0 {
+            self.invalidate_queries().await;
+        }
+        Ok(purged)
+    }
+
+    /// Use Case: Delete many users, reporting which ids did not exist
+    ///
+    /// Soft deletes like `delete_user`, and is forbidden under the same flag.
+    #[tracing::instrument(skip_all, fields(count = ids.len()))]
+    pub async fn delete_users(&self, ids: Vec<UserId>) -> AppResult<BatchDeleteReport> {
+        if !self.features.allow_user_deletion {
+            return Err(AppError::Forbidden("User deletion is disabled".to_string()));
+        }
+        if ids.len() > MAX_BULK_ROWS {
+            return Err(AppError::ValidationError(format!(
+                "A batch delete accepts at most {} ids",
+                MAX_BULK_ROWS
+            )));
+        }
+
+        let mut seen = HashSet::new();
+        let ids: Vec<UserId> = ids.into_iter().filter(|id| seen.insert(*id)).collect();
+        if ids.is_empty() {
+            return Ok(BatchDeleteReport {
+                requested: 0,
+                deleted: 0,
+                not_found: Vec::new(),
+            });
+        }
+
+        let deleted: HashSet<UserId> = self
+            .user_repository
+            .delete_many(&ids)
+            .await?
+            .into_iter()
+            .collect();
+        if !deleted.is_empty() {
+            self.invalidate_queries().await;
+        }
+        for id in ids.iter().filter(|id| deleted.contains(id)) {
+            self.publish(UserEvent::deleted(*id)).await;
+        }
+
+        Ok(BatchDeleteReport {
+            requested: ids.len(),
+            deleted: deleted.len(),
+            not_found: ids.into_iter().filter(|id| !deleted.contains(id)).collect(),
+        })
+    }
+
     /// Use Case: List users with pagination
-    pub async fn list_users(&self, limit: i64, offset: i64) -> AppResult<UserListResponse> {
-        // Validate pagination parameters
+    ///
+    /// Suspended and inactive users are listed only for admins. `sort` is a
+    /// spec such as `status,-username`; without one the configured
+    /// `default_user_sort` applies.
+    #[tracing::instrument(skip_all, fields(limit = limit, offset = offset))]
+    pub async fn list_users(
+        &self,
+        caller: Option<&AuthContext>,
+        sort: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<UserListResponse> {
+        self.search_users(visible_to(caller), sort, limit, offset)
+            .await
+    }
+
+    /// Use Case: List users matching a filter with pagination
+    #[tracing::instrument(skip_all, fields(limit = limit, offset = offset))]
+    pub async fn search_users(
+        &self,
+        filter: UserFilter,
+        sort: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<UserListResponse> {
+        self.validate_page(limit, offset)?;
+        let sort = self.resolve_sort(sort)?;
+
+        // Fetch users and total count
+        let load = || async {
+            let users = self
+                .user_repository
+                .list(&filter, &sort, limit, offset)
+                .await?;
+            let total = self.user_repository.count(&filter).await?;
+
+            Ok(self.page(users, total, limit, offset, sort.is_default()))
+        };
+
+        self.cached("list_users", &(&filter, &sort, limit, offset), load)
+            .await
+    }
+
+    /// Parse a requested sort spec, or the configured default without one
+    fn resolve_sort(&self, spec: Option<&str>) -> AppResult<UserSort> {
+        let max_keys = self.validation.max_sort_keys;
+        match spec {
+            Some(spec) => UserSort::parse(spec, max_keys),
+            None => UserSort::parse(&self.validation.default_user_sort, max_keys).map_err(|e| {
+                AppError::ConfigurationError(format!("Invalid validation.default_user_sort: {}", e))
+            }),
+        }
+    }
+
+    /// Use Case: List the users following a `next_cursor` from an earlier page
+    ///
+    /// Keyset pagination stays fast at any depth and does not skip or repeat
+    /// users when rows are inserted between requests.
+    #[tracing::instrument(skip_all, fields(limit = limit))]
+    pub async fn list_users_after(
+        &self,
+        caller: Option<&AuthContext>,
+        cursor: &str,
+        limit: i64,
+    ) -> AppResult<UserListResponse> {
+        self.validate_page(limit, 0)?;
+        let after = Cursor::decode(cursor, &self.cursor_secret)?;
+        let filter = visible_to(caller);
+
+        let load = || async {
+            let users = self
+                .user_repository
+                .list_after(&filter, (after.created_at, after.id), limit)
+                .await?;
+            let total = self.user_repository.count(&filter).await?;
+
+            Ok(self.page(users, total, limit, 0, true))
+        };
+
+        self.cached("list_users_after", &(&filter, cursor, limit), load)
+            .await
+    }
+
+    /// Build a list page; a full page carries the cursor for the next one
+    ///
+    /// Cursors follow the newest-first order, so pages in any other order
+    /// (`cursorable` false) carry none.
+    fn page(
+        &self,
+        users: Vec<User>,
+        total: i64,
+        limit: i64,
+        offset: i64,
+        cursorable: bool,
+    ) -> UserListResponse {
+        let next_cursor = users
+            .last()
+            .filter(|_| cursorable && users.len() as i64 == limit)
+            .map(|last| Cursor::new(last.created_at(), last.id()).encode(&self.cursor_secret));
+
+        UserListResponse {
+            users: users.into_iter().map(UserResponse::from).collect(),
+            total,
+            limit,
+            offset,
+            next_cursor,
+        }
+    }
+
+    /// Use Case: List the newest users created after `since`, newest first
+    ///
+    /// Suspended and inactive users are listed only for admins.
+    #[tracing::instrument(skip_all, fields(limit = limit))]
+    pub async fn recent_users(
+        &self,
+        caller: Option<&AuthContext>,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<UserResponse>> {
+        self.validate_page(limit, 0)?;
+
+        let filter = visible_to(caller);
+        let load = || async {
+            let users = self
+                .user_repository
+                .list_created_since(&filter, since, limit)
+                .await?;
+            Ok(users.into_iter().map(UserResponse::from).collect())
+        };
+
+        self.cached("recent_users", &(&filter, since, limit), load)
+            .await
+    }
+
+    /// Use Case: List users whose email is at `domain` (e.g. one B2B account)
+    #[tracing::instrument(skip_all, fields(limit = limit, offset = offset))]
+    pub async fn users_by_domain(
+        &self,
+        domain: String,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<UserResponse>> {
+        self.validate_page(limit, offset)?;
+
+        // Validate the domain as it would appear in an address
+        let probe = Email::new(format!("user@{}", domain.trim()))
+            .map_err(|_| AppError::ValidationError(format!("Invalid email domain: {}", domain)))?;
+        let domain = probe.domain().unwrap_or_default();
+
+        let load = || async {
+            let users = self
+                .user_repository
+                .find_by_email_domain(domain, limit, offset)
+                .await?;
+            Ok(users.into_iter().map(UserResponse::from).collect())
+        };
+
+        self.cached("users_by_domain", &(domain, limit, offset), load)
+            .await
+    }
+
+    /// Validate pagination parameters
+    ///
+    /// Offsets past `max_offset` are rejected: the database would still scan
+    /// and discard every skipped row, so deep pages should use a cursor.
+    fn validate_page(&self, limit: i64, offset: i64) -> AppResult<()> {
         if !(1..=100).contains(&limit) {
             return Err(AppError::ValidationError(
                 "Limit must be between 1 and 100".to_string(),
@@ -175,39 +725,102 @@ impl UserService {
             ));
         }
 
-        // Fetch users and total count
-        let users = self.user_repository.list(limit, offset).await?;
-        let total = self.user_repository.count().await?;
+        if offset > self.validation.max_offset {
+            return Err(AppError::ValidationError(format!(
+                "Offset cannot exceed {}; use cursor pagination to read further",
+                self.validation.max_offset
+            )));
+        }
 
-        Ok(UserListResponse {
-            users: users.into_iter().map(UserResponse::from).collect(),
-            total,
-            limit,
-            offset,
-        })
+        Ok(())
+    }
+}
+
+/// The listing filter for a caller: only the statuses they may see
+fn visible_to(caller: Option<&AuthContext>) -> UserFilter {
+    UserFilter {
+        include_statuses: AuthContext::visible_statuses(caller),
+        ..UserFilter::default()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dtos::BulkRowStatus;
+    use crate::ports::CacheStore;
     use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use shared::config::{CachePolicy, EmailDomainRule, EmailPolicy};
     use std::collections::HashMap;
     use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
 
     // Mock repository for testing
     struct MockUserRepository {
         users: Mutex<HashMap<UserId, User>>,
+        deleted: Mutex<HashMap<UserId, (User, DateTime<Utc>)>>,
+        list_calls: AtomicUsize,
+        find_calls: AtomicUsize,
     }
 
     impl MockUserRepository {
+        /// Pretend a soft delete happened `age` ago
+        fn backdate_deletion(&self, id: UserId, age: TimeDelta) {
+            if let Some((_, deleted_at)) = self.deleted.lock().unwrap().get_mut(&id) {
+                *deleted_at -= age;
+            }
+        }
+
         fn new() -> Self {
             Self {
                 users: Mutex::new(HashMap::new()),
+                deleted: Mutex::new(HashMap::new()),
+                list_calls: AtomicUsize::new(0),
+                find_calls: AtomicUsize::new(0),
             }
         }
     }
 
+    // In-memory cache store for testing
+    #[derive(Default)]
+    struct MockCacheStore {
+        entries: Mutex<HashMap<String, Vec<u8>>>,
+        ttls: Mutex<HashMap<String, Duration>>,
+    }
+
+    #[async_trait]
+    impl CacheStore for MockCacheStore {
+        async fn get(&self, key: &str) -> AppResult<Option<Vec<u8>>> {
+            Ok(self.entries.lock().unwrap().get(key).cloned())
+        }
+
+        async fn set(&self, key: &str, value: &[u8], ttl: Duration) -> AppResult<()> {
+            self.entries
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), value.to_vec());
+            self.ttls.lock().unwrap().insert(key.to_string(), ttl);
+            Ok(())
+        }
+
+        async fn incr(&self, key: &str) -> AppResult<i64> {
+            let mut entries = self.entries.lock().unwrap();
+            let current: i64 = entries
+                .get(key)
+                .and_then(|v| std::str::from_utf8(v).ok()?.parse().ok())
+                .unwrap_or(0);
+            entries.insert(key.to_string(), (current + 1).to_string().into_bytes());
+            Ok(current + 1)
+        }
+
+        async fn delete(&self, key: &str) -> AppResult<()> {
+            self.entries.lock().unwrap().remove(key);
+            Ok(())
+        }
+    }
+
     #[async_trait]
     impl UserRepository for MockUserRepository {
         async fn create(&self, user: &User) -> AppResult<()> {
@@ -215,17 +828,40 @@ mod tests {
             Ok(())
         }
 
+        async fn create_many(&self, users: &[User], dry_run: bool) -> AppResult<()> {
+            if dry_run {
+                return Ok(());
+            }
+            let mut stored = self.users.lock().unwrap();
+            for user in users {
+                stored.insert(user.id(), user.clone());
+            }
+            Ok(())
+        }
+
         async fn find_by_id(&self, id: UserId) -> AppResult<Option<User>> {
+            self.find_calls.fetch_add(1, Ordering::SeqCst);
+            // Suspend once, as a real query would, so concurrent callers overlap
+            tokio::task::yield_now().await;
             Ok(self.users.lock().unwrap().get(&id).cloned())
         }
 
+        async fn find_by_ids(&self, ids: &[UserId]) -> AppResult<Vec<User>> {
+            let users = self.users.lock().unwrap();
+            Ok(ids.iter().filter_map(|id| users.get(id).cloned()).collect())
+        }
+
         async fn find_by_username(&self, username: &Username) -> AppResult<Option<User>> {
             Ok(self
                 .users
                 .lock()
                 .unwrap()
                 .values()
-                .find(|u| u.username() == username)
+                .find(|u| {
+                    u.username()
+                        .as_str()
+                        .eq_ignore_ascii_case(username.as_str())
+                })
                 .cloned())
         }
 
@@ -239,16 +875,125 @@ mod tests {
                 .cloned())
         }
 
+        async fn find_by_canonical_email(
+            &self,
+            email: &Email,
+            policy: &EmailPolicy,
+        ) -> AppResult<Option<User>> {
+            let canonical = email.canonical(policy);
+            let users = self.users.lock().unwrap();
+            Ok(users
+                .values()
+                .find(|u| u.email().canonical(policy) == canonical)
+                .cloned())
+        }
+
+        async fn find_by_email_domain(
+            &self,
+            domain: &str,
+            limit: i64,
+            offset: i64,
+        ) -> AppResult<Vec<User>> {
+            let users = self.users.lock().unwrap();
+            let mut matching: Vec<User> = users
+                .values()
+                .filter(|u| u.email().domain() == Some(domain))
+                .cloned()
+                .collect();
+            matching.sort_by_key(|u| std::cmp::Reverse((u.created_at(), *u.id().as_uuid())));
+            Ok(matching
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .collect())
+        }
+
         async fn update(&self, user: &User) -> AppResult<()> {
             self.users.lock().unwrap().insert(user.id(), user.clone());
             Ok(())
         }
 
+        async fn update_claiming_username(&self, user: &User) -> AppResult<()> {
+            let mut users = self.users.lock().unwrap();
+            if users.values().any(|u| {
+                u.id() != user.id()
+                    && u.username()
+                        .as_str()
+                        .eq_ignore_ascii_case(user.username().as_str())
+            }) {
+                return Err(AppError::AlreadyExists(format!(
+                    "Username '{}' already exists",
+                    user.username()
+                )));
+            }
+            let stored = users.get_mut(&user.id()).ok_or_else(|| {
+                AppError::NotFound(format!("User with ID {} not found", user.id()))
+            })?;
+            *stored = user.clone();
+            Ok(())
+        }
+
         async fn delete(&self, id: UserId) -> AppResult<()> {
-            self.users.lock().unwrap().remove(&id);
+            self.delete_many(&[id]).await?;
             Ok(())
         }
 
+        async fn delete_many(&self, ids: &[UserId]) -> AppResult<Vec<UserId>> {
+            let mut users = self.users.lock().unwrap();
+            let mut deleted = self.deleted.lock().unwrap();
+            Ok(ids
+                .iter()
+                .filter(|id| match users.remove(id) {
+                    Some(user) => deleted.insert(**id, (user, Utc::now())).is_none(),
+                    None => false,
+                })
+                .copied()
+                .collect())
+        }
+
+        async fn restore(&self, id: UserId) -> AppResult<User> {
+            let mut users = self.users.lock().unwrap();
+            let mut deleted = self.deleted.lock().unwrap();
+            let (user, _) = deleted
+                .get(&id)
+                .cloned()
+                .ok_or_else(|| AppError::NotFound(format!("No deleted user with ID {}", id)))?;
+            if users
+                .values()
+                .any(|u| u.username() == user.username() || u.email() == user.email())
+            {
+                return Err(AppError::AlreadyExists(
+                    "Username or email already exists".to_string(),
+                ));
+            }
+            deleted.remove(&id);
+            users.insert(id, user.clone());
+            Ok(user)
+        }
+
+        async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> AppResult<u64> {
+            let mut deleted = self.deleted.lock().unwrap();
+            let before = deleted.len();
+            deleted.retain(|_, (_, deleted_at)| *deleted_at >= deleted_before);
+            Ok((before - deleted.len()) as u64)
+        }
+
+        async fn find_password_hash(&self, _id: UserId) -> AppResult<Option<String>> {
+            Ok(None)
+        }
+
+        async fn set_password_hash(&self, _id: UserId, _password_hash: &str) -> AppResult<()> {
+            Ok(())
+        }
+
+        async fn change_password_hash(&self, _id: UserId, _password_hash: &str) -> AppResult<i64> {
+            Ok(1)
+        }
+
+        async fn find_session_epoch(&self, _id: UserId) -> AppResult<Option<i64>> {
+            Ok(None)
+        }
+
         async fn username_exists(&self, username: &Username) -> AppResult<bool> {
             Ok(self.find_by_username(username).await?.is_some())
         }
@@ -257,18 +1002,46 @@ mod tests {
             Ok(self.find_by_email(email).await?.is_some())
         }
 
-        async fn list(&self, limit: i64, offset: i64) -> AppResult<Vec<User>> {
+        async fn list(
+            &self,
+            filter: &UserFilter,
+            sort: &UserSort,
+            limit: i64,
+            offset: i64,
+        ) -> AppResult<Vec<User>> {
+            self.list_calls.fetch_add(1, Ordering::SeqCst);
             let users = self.users.lock().unwrap();
-            Ok(users
-                .values()
+            let mut matching: Vec<&User> = users.values().filter(|u| filter.matches(u)).collect();
+            matching.sort_by(|a, b| sort.compare(a, b));
+            Ok(matching
+                .into_iter()
                 .skip(offset as usize)
                 .take(limit as usize)
                 .cloned()
                 .collect())
         }
 
-        async fn count(&self) -> AppResult<i64> {
-            Ok(self.users.lock().unwrap().len() as i64)
+        async fn list_after(
+            &self,
+            _filter: &UserFilter,
+            _after: (DateTime<Utc>, UserId),
+            _limit: i64,
+        ) -> AppResult<Vec<User>> {
+            Ok(Vec::new())
+        }
+
+        async fn list_created_since(
+            &self,
+            _filter: &UserFilter,
+            _since: DateTime<Utc>,
+            _limit: i64,
+        ) -> AppResult<Vec<User>> {
+            Ok(Vec::new())
+        }
+
+        async fn count(&self, filter: &UserFilter) -> AppResult<i64> {
+            let users = self.users.lock().unwrap();
+            Ok(users.values().filter(|u| filter.matches(u)).count() as i64)
         }
     }
 
@@ -313,4 +1086,881 @@ mod tests {
         let result = service.create_user(request2).await;
         assert!(matches!(result, Err(AppError::AlreadyExists(_))));
     }
+
+    #[tokio::test]
+    async fn test_create_user_reports_all_field_errors() {
+        let repo = Arc::new(MockUserRepository::new());
+        let service = UserService::new(repo);
+
+        let request = CreateUserRequest {
+            username: "ab".to_string(),
+            email: "not-an-email".to_string(),
+            full_name: Some("x".repeat(101)),
+        };
+
+        let errors = match service.create_user(request).await {
+            Err(AppError::Validation(errors)) => errors,
+            other => panic!("expected validation errors, got {:?}", other),
+        };
+
+        let fields: Vec<&str> = errors.errors().iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["username", "email", "full_name"]);
+        assert_eq!(errors.errors()[0].code, "invalid_username");
+        assert_eq!(errors.errors()[1].code, "invalid_email");
+    }
+
+    #[tokio::test]
+    async fn test_list_users_served_from_query_cache() {
+        let repo = Arc::new(MockUserRepository::new());
+        let cache = QueryCache::new(
+            Arc::new(MockCacheStore::default()),
+            "users",
+            Duration::from_secs(30),
+        );
+        let service = UserService::new(repo.clone()).with_query_cache(cache);
+
+        let first = service.list_users(None, None, 20, 0).await.unwrap();
+        let second = service.list_users(None, None, 20, 0).await.unwrap();
+
+        assert_eq!(repo.list_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(first.total, second.total);
+
+        // Different params form a different signature
+        service.list_users(None, None, 10, 0).await.unwrap();
+        assert_eq!(repo.list_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_each_endpoint_is_cached_for_its_policy_ttl() {
+        let store = Arc::new(MockCacheStore::default());
+        let policy = CachePolicy(HashMap::from([
+            ("recent_users".to_string(), 5),
+            ("list_users".to_string(), 120),
+        ]));
+        let cache =
+            QueryCache::new(store.clone(), "users", Duration::from_secs(30)).with_policy(policy);
+        let service = UserService::new(Arc::new(MockUserRepository::new())).with_query_cache(cache);
+
+        service
+            .recent_users(None, Utc::now() - TimeDelta::hours(1), 10)
+            .await
+            .unwrap();
+        service.list_users(None, None, 20, 0).await.unwrap();
+        service
+            .users_by_domain("example.com".to_string(), 20, 0)
+            .await
+            .unwrap();
+
+        let ttl_of = |method: &str| -> Duration {
+            let ttls = store.ttls.lock().unwrap();
+            let (_, ttl) = ttls
+                .iter()
+                .find(|(key, _)| key.contains(&format!(":{}:", method)))
+                .unwrap();
+            *ttl
+        };
+        assert_eq!(ttl_of("recent_users"), Duration::from_secs(5));
+        assert_eq!(ttl_of("list_users"), Duration::from_secs(120));
+        // Endpoints without an entry keep the cache-wide TTL
+        assert_eq!(ttl_of("users_by_domain"), Duration::from_secs(30));
+    }
+
+    #[tokio::test]
+    async fn test_list_users_hides_suspended_users_from_non_admins() {
+        use crate::auth::Role;
+
+        let repo = Arc::new(MockUserRepository::new());
+        let service = UserService::new(repo.clone());
+        for name in ["visible", "suspended"] {
+            service
+                .create_user(CreateUserRequest {
+                    username: name.to_string(),
+                    email: format!("{}@example.com", name),
+                    full_name: None,
+                })
+                .await
+                .unwrap();
+        }
+        let mut user = repo
+            .find_by_username(&Username::new("suspended".to_string()).unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        user.suspend(&SystemClock);
+        repo.update(&user).await.unwrap();
+
+        let context = |role| AuthContext {
+            user_id: UserId::new(),
+            role,
+            actor_id: None,
+            epoch: 0,
+        };
+        let names = |list: UserListResponse| {
+            let mut names: Vec<String> = list.users.into_iter().map(|u| u.username).collect();
+            names.sort();
+            names
+        };
+
+        let public = service.list_users(None, None, 20, 0).await.unwrap();
+        assert_eq!(public.total, 1);
+        assert_eq!(names(public), ["visible"]);
+        let member = service
+            .list_users(Some(&context(Role::User)), None, 20, 0)
+            .await
+            .unwrap();
+        assert_eq!(names(member), ["visible"]);
+        let admin = service
+            .list_users(Some(&context(Role::Admin)), None, 20, 0)
+            .await
+            .unwrap();
+        assert_eq!(admin.total, 2);
+        assert_eq!(names(admin), ["suspended", "visible"]);
+    }
+
+    #[tokio::test]
+    async fn test_list_users_applies_multi_key_sort_and_configured_default() {
+        let repo = Arc::new(MockUserRepository::new());
+        let service = UserService::new(repo.clone());
+        for name in ["anna", "bert", "cleo"] {
+            service
+                .create_user(CreateUserRequest {
+                    username: name.to_string(),
+                    email: format!("{}@example.com", name),
+                    full_name: None,
+                })
+                .await
+                .unwrap();
+        }
+        let mut anna = repo
+            .find_by_username(&Username::new("anna".to_string()).unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        anna.deactivate(&SystemClock);
+        repo.update(&anna).await.unwrap();
+        let names = |list: UserListResponse| -> Vec<String> {
+            list.users.into_iter().map(|u| u.username).collect()
+        };
+
+        let sorted = service
+            .search_users(UserFilter::default(), Some("status,-username"), 20, 0)
+            .await
+            .unwrap();
+        assert!(sorted.next_cursor.is_none());
+        assert_eq!(names(sorted), ["cleo", "bert", "anna"]);
+
+        let service = UserService::new(repo).with_validation(ValidationConfig {
+            default_user_sort: "-status,username".to_string(),
+            ..ValidationConfig::default()
+        });
+        let by_default = service
+            .search_users(UserFilter::default(), None, 20, 0)
+            .await
+            .unwrap();
+        assert_eq!(names(by_default), ["anna", "bert", "cleo"]);
+    }
+
+    #[tokio::test]
+    async fn test_list_users_rejects_too_many_sort_keys() {
+        let service = UserService::new(Arc::new(MockUserRepository::new()));
+
+        match service
+            .list_users(None, Some("status,username,email,created_at"), 20, 0)
+            .await
+        {
+            Err(AppError::ValidationError(msg)) => {
+                assert_eq!(msg, "Sort accepts at most 3 fields")
+            }
+            other => panic!("expected validation error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_user_invalidates_query_cache() {
+        let repo = Arc::new(MockUserRepository::new());
+        let cache = QueryCache::new(
+            Arc::new(MockCacheStore::default()),
+            "users",
+            Duration::from_secs(30),
+        );
+        let service = UserService::new(repo.clone()).with_query_cache(cache);
+
+        assert_eq!(
+            service.list_users(None, None, 20, 0).await.unwrap().total,
+            0
+        );
+
+        service
+            .create_user(CreateUserRequest {
+                username: "testuser".to_string(),
+                email: "test@example.com".to_string(),
+                full_name: None,
+            })
+            .await
+            .unwrap();
+
+        let after = service.list_users(None, None, 20, 0).await.unwrap();
+        assert_eq!(after.total, 1);
+        assert_eq!(repo.list_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[derive(Default)]
+    struct RecordingEventBus {
+        published: Mutex<Vec<UserEvent>>,
+    }
+
+    impl EventBus for RecordingEventBus {
+        fn publish(&self, event: UserEvent) {
+            self.published.lock().unwrap().push(event);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_effects_of_a_write_wait_for_the_commit() {
+        let repo = Arc::new(MockUserRepository::new());
+        let cache = QueryCache::new(
+            Arc::new(MockCacheStore::default()),
+            "users",
+            Duration::from_secs(30),
+        );
+        let events = Arc::new(RecordingEventBus::default());
+        let service = UserService::new(repo.clone())
+            .with_query_cache(cache)
+            .with_event_bus(events.clone());
+        service.list_users(None, None, 20, 0).await.unwrap();
+
+        let after_commit = AfterCommit::new();
+        after_commit
+            .scope(service.bulk_create_users(
+                vec![BulkCreateRow {
+                    row: 1,
+                    request: CreateUserRequest {
+                        username: "pending".to_string(),
+                        email: "pending@example.com".to_string(),
+                        full_name: None,
+                    },
+                }],
+                false,
+            ))
+            .await
+            .unwrap();
+        assert!(events.published.lock().unwrap().is_empty());
+        assert_eq!(
+            service.list_users(None, None, 20, 0).await.unwrap().total,
+            0
+        );
+
+        after_commit.run().await;
+        assert_eq!(events.published.lock().unwrap().len(), 1);
+        assert_eq!(
+            service.list_users(None, None, 20, 0).await.unwrap().total,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_user_bumps_cache_namespace() {
+        let repo = Arc::new(MockUserRepository::new());
+        let store = Arc::new(MockCacheStore::default());
+        let cache = QueryCache::new(store.clone(), "users", Duration::from_secs(30));
+        let service = UserService::new(repo.clone()).with_query_cache(cache.clone());
+
+        service.list_users(None, None, 20, 0).await.unwrap();
+        let stale_key = cache
+            .key_for(
+                "list_users",
+                &(visible_to(None), UserSort::default(), 20i64, 0i64),
+            )
+            .await
+            .unwrap();
+        assert!(store.get(&stale_key).await.unwrap().is_some());
+        assert_eq!(cache.version().await.unwrap(), 0);
+
+        service
+            .create_user(CreateUserRequest {
+                username: "testuser".to_string(),
+                email: "test@example.com".to_string(),
+                full_name: None,
+            })
+            .await
+            .unwrap();
+
+        // The old entry is still stored but no longer addressed by any key
+        assert_eq!(cache.version().await.unwrap(), 1);
+        let fresh_key = cache
+            .key_for(
+                "list_users",
+                &(visible_to(None), UserSort::default(), 20i64, 0i64),
+            )
+            .await
+            .unwrap();
+        assert_ne!(fresh_key, stale_key);
+        assert!(store.get(&stale_key).await.unwrap().is_some());
+        assert_eq!(
+            service.list_users(None, None, 20, 0).await.unwrap().total,
+            1
+        );
+        assert_eq!(repo.list_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_create_reports_each_row() {
+        let repo = Arc::new(MockUserRepository::new());
+        let service = UserService::new(repo.clone());
+
+        let row = |row: usize, username: &str, email: &str| BulkCreateRow {
+            row,
+            request: CreateUserRequest {
+                username: username.to_string(),
+                email: email.to_string(),
+                full_name: None,
+            },
+        };
+        let report = service
+            .bulk_create_users(
+                vec![
+                    row(1, "alice", "alice@example.com"),
+                    row(2, "x", "not-an-email"),
+                    row(3, "alice", "alice2@example.com"),
+                    row(4, "bob", "bob@example.com"),
+                ],
+                false,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!((report.total, report.created, report.failed), (4, 2, 2));
+        let statuses: Vec<_> = report.rows.iter().map(|r| r.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                BulkRowStatus::Created,
+                BulkRowStatus::Failed,
+                BulkRowStatus::Failed,
+                BulkRowStatus::Created,
+            ]
+        );
+        assert_eq!(report.rows[1].errors.len(), 2);
+        assert_eq!(report.rows[2].errors[0].code, "already_exists");
+        assert_eq!(repo.users.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_create_dry_run_matches_real_run_without_writing() {
+        let repo = Arc::new(MockUserRepository::new());
+        let service = UserService::new(repo.clone());
+
+        let rows = || {
+            [
+                ("carol", "carol@example.com"),
+                ("carol", "carol2@example.com"),
+                ("dave", "bad-email"),
+            ]
+            .iter()
+            .enumerate()
+            .map(|(i, (username, email))| BulkCreateRow {
+                row: i + 1,
+                request: CreateUserRequest {
+                    username: username.to_string(),
+                    email: email.to_string(),
+                    full_name: None,
+                },
+            })
+            .collect::<Vec<_>>()
+        };
+
+        let preview = service.bulk_create_users(rows(), true).await.unwrap();
+        assert!(preview.dry_run);
+        assert!(repo.users.lock().unwrap().is_empty());
+
+        let real = service.bulk_create_users(rows(), false).await.unwrap();
+        assert!(!real.dry_run);
+        assert_eq!(repo.users.lock().unwrap().len(), 1);
+
+        let outcomes = |report: &BulkCreateReport| {
+            report
+                .rows
+                .iter()
+                .map(|r| {
+                    let codes: Vec<String> = r.errors.iter().map(|e| e.code.clone()).collect();
+                    (r.row, r.status.clone(), codes)
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(outcomes(&preview), outcomes(&real));
+    }
+
+    #[tokio::test]
+    async fn test_create_user_reports_precise_collision() {
+        let repo = Arc::new(MockUserRepository::new());
+        let service = UserService::new(repo);
+        let request = |username: &str, email: &str| CreateUserRequest {
+            username: username.to_string(),
+            email: email.to_string(),
+            full_name: None,
+        };
+
+        // Neither collides
+        service
+            .create_user(request("taken", "taken@example.com"))
+            .await
+            .unwrap();
+
+        let message = |result: AppResult<UserResponse>| match result {
+            Err(AppError::AlreadyExists(message)) => message,
+            other => panic!("expected AlreadyExists, got {:?}", other),
+        };
+
+        let username_only = message(
+            service
+                .create_user(request("taken", "free@example.com"))
+                .await,
+        );
+        assert_eq!(username_only, "Username 'taken' already exists");
+
+        let email_only = message(
+            service
+                .create_user(request("free", "taken@example.com"))
+                .await,
+        );
+        assert_eq!(email_only, "Email 'taken@example.com' already exists");
+
+        let both = message(
+            service
+                .create_user(request("taken", "taken@example.com"))
+                .await,
+        );
+        assert_eq!(
+            both,
+            "Username 'taken' and email 'taken@example.com' already exist"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_user_honours_configured_username_length() {
+        let request = || CreateUserRequest {
+            username: "u".repeat(40),
+            email: "long@example.com".to_string(),
+            full_name: None,
+        };
+
+        let default_service = UserService::new(Arc::new(MockUserRepository::new()));
+        assert!(default_service.create_user(request()).await.is_err());
+
+        let service = UserService::new(Arc::new(MockUserRepository::new())).with_validation(
+            ValidationConfig {
+                username_max_length: 50,
+                ..ValidationConfig::default()
+            },
+        );
+        let user = service.create_user(request()).await.unwrap();
+        assert_eq!(user.username.len(), 40);
+    }
+
+    #[tokio::test]
+    async fn test_delete_users_reports_missing_ids() {
+        let service = UserService::new(Arc::new(MockUserRepository::new()));
+        let mut existing = Vec::new();
+        for name in ["alice", "bob"] {
+            let user = service
+                .create_user(CreateUserRequest {
+                    username: name.to_string(),
+                    email: format!("{}@example.com", name),
+                    full_name: None,
+                })
+                .await
+                .unwrap();
+            existing.push(user.id);
+        }
+        let missing = UserId::new();
+
+        let report = service
+            .delete_users(vec![existing[0], missing, existing[1], existing[0]])
+            .await
+            .unwrap();
+
+        assert_eq!(report.requested, 3);
+        assert_eq!(report.deleted, 2);
+        assert_eq!(report.not_found, vec![missing]);
+        assert_eq!(
+            service.list_users(None, None, 20, 0).await.unwrap().total,
+            0
+        );
+    }
+
+    fn restore_request(email: &str) -> CreateUserRequest {
+        CreateUserRequest {
+            username: "restorable".to_string(),
+            email: email.to_string(),
+            full_name: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restore_user_undoes_soft_delete() {
+        let service = UserService::new(Arc::new(MockUserRepository::new()));
+        let user = service
+            .create_user(restore_request("restore@example.com"))
+            .await
+            .unwrap();
+        service.delete_user(user.id).await.unwrap();
+        assert!(service.get_user(user.id).await.is_err());
+
+        let restored = service.restore_user(user.id).await.unwrap();
+
+        assert_eq!(restored.id, user.id);
+        assert_eq!(
+            service.get_user(user.id).await.unwrap().username,
+            "restorable"
+        );
+        assert!(matches!(
+            service.restore_user(user.id).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_restore_user_conflicts_when_username_was_reused() {
+        let service = UserService::new(Arc::new(MockUserRepository::new()));
+        let user = service
+            .create_user(restore_request("first@example.com"))
+            .await
+            .unwrap();
+        service.delete_user(user.id).await.unwrap();
+        service
+            .create_user(restore_request("second@example.com"))
+            .await
+            .unwrap();
+
+        let result = service.restore_user(user.id).await;
+
+        assert!(matches!(result, Err(AppError::AlreadyExists(_))));
+        assert!(service.get_user(user.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_user_sets_and_clears_avatar_url() {
+        let service = UserService::new(Arc::new(MockUserRepository::new()));
+        let user = service
+            .create_user(restore_request("avatar@example.com"))
+            .await
+            .unwrap();
+        let avatar = |url: &str| UpdateUserRequest {
+            username: None,
+            email: None,
+            full_name: None,
+            avatar_url: Some(url.to_string()),
+        };
+
+        let updated = service
+            .update_user(user.id, avatar("https://cdn.example.com/a.png"))
+            .await
+            .unwrap();
+        assert_eq!(
+            updated.avatar_url.as_deref(),
+            Some("https://cdn.example.com/a.png")
+        );
+
+        let rejected = service
+            .update_user(user.id, avatar("javascript:alert(1)"))
+            .await;
+        assert!(matches!(rejected, Err(AppError::ValidationError(_))));
+
+        let cleared = service.update_user(user.id, avatar("")).await.unwrap();
+        assert_eq!(cleared.avatar_url, None);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_renames_to_one_username_let_exactly_one_win() {
+        let service = Arc::new(UserService::new(Arc::new(MockUserRepository::new())));
+        let mut ids = Vec::new();
+        for name in ["first", "second"] {
+            let request = CreateUserRequest {
+                username: name.to_string(),
+                email: format!("{}@example.com", name),
+                full_name: None,
+            };
+            ids.push(service.create_user(request).await.unwrap().id);
+        }
+
+        let handles: Vec<_> = ids
+            .iter()
+            .map(|&id| {
+                let service = service.clone();
+                tokio::spawn(async move {
+                    let rename = UpdateUserRequest {
+                        username: Some("contested".to_string()),
+                        ..UpdateUserRequest::default()
+                    };
+                    service.update_user(id, rename).await
+                })
+            })
+            .collect();
+        let mut results = Vec::new();
+        for handle in handles {
+            results.push(handle.await.unwrap());
+        }
+
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+        assert!(results.iter().any(|result| matches!(
+            result,
+            Err(AppError::AlreadyExists(msg)) if msg == "Username 'contested' already exists"
+        )));
+        let owner = service
+            .get_user_by_username("contested".to_string())
+            .await
+            .unwrap();
+        assert!(ids.contains(&owner.id));
+    }
+
+    #[tokio::test]
+    async fn test_refused_rename_leaves_other_fields_unchanged() {
+        let service = UserService::new(Arc::new(MockUserRepository::new()));
+        let mut ids = Vec::new();
+        for name in ["holder", "renamer"] {
+            let request = CreateUserRequest {
+                username: name.to_string(),
+                email: format!("{}@example.com", name),
+                full_name: Some("Original".to_string()),
+            };
+            ids.push(service.create_user(request).await.unwrap().id);
+        }
+
+        let update = UpdateUserRequest {
+            username: Some("Holder".to_string()),
+            full_name: Some("Changed".to_string()),
+            ..UpdateUserRequest::default()
+        };
+        assert!(matches!(
+            service.update_user(ids[1], update).await,
+            Err(AppError::AlreadyExists(_))
+        ));
+
+        let user = service.get_user(ids[1]).await.unwrap();
+        assert_eq!(user.username, "renamer");
+        assert_eq!(user.full_name.as_deref(), Some("Original"));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_get_user_shares_one_lookup() {
+        let repo = Arc::new(MockUserRepository::new());
+        let service = Arc::new(UserService::new(repo.clone()));
+        let user = service
+            .create_user(restore_request("flight@example.com"))
+            .await
+            .unwrap();
+
+        let handles: Vec<_> = (0..50)
+            .map(|_| {
+                let service = service.clone();
+                tokio::spawn(async move { service.get_user(user.id).await })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.await.unwrap().unwrap().id, user.id);
+        }
+
+        assert_eq!(repo.find_calls.load(Ordering::SeqCst), 1);
+
+        // A later lookup is not served from the finished flight
+        service.get_user(user.id).await.unwrap();
+        assert_eq!(repo.find_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_service_timestamps_follow_injected_clock() {
+        let start = chrono::DateTime::parse_from_rfc3339("2024-06-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let clock = Arc::new(domain::FixedClock::new(start));
+        let service =
+            UserService::new(Arc::new(MockUserRepository::new())).with_clock(clock.clone());
+
+        let user = service
+            .create_user(restore_request("clock@example.com"))
+            .await
+            .unwrap();
+        assert_eq!(user.created_at, start);
+        assert_eq!(user.updated_at, user.created_at);
+
+        clock.advance(chrono::TimeDelta::seconds(90));
+        let updated = service
+            .update_user(
+                user.id,
+                UpdateUserRequest {
+                    username: None,
+                    email: None,
+                    full_name: Some("Clocked".to_string()),
+                    avatar_url: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.created_at, start);
+        assert_eq!(
+            updated.updated_at - updated.created_at,
+            chrono::TimeDelta::seconds(90)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_user_rejects_plus_address_variant_under_policy() {
+        let policy = ValidationConfig {
+            email_policy: EmailPolicy {
+                rules: vec![EmailDomainRule {
+                    domains: vec!["gmail.com".to_string()],
+                    strip_plus_tag: true,
+                    ignore_dots: false,
+                }],
+            },
+            ..ValidationConfig::default()
+        };
+        let request = |username: &str, email: &str| CreateUserRequest {
+            username: username.to_string(),
+            email: email.to_string(),
+            full_name: None,
+        };
+
+        let service = UserService::new(Arc::new(MockUserRepository::new())).with_validation(policy);
+        service
+            .create_user(request("first", "a@gmail.com"))
+            .await
+            .unwrap();
+        let result = service
+            .create_user(request("second", "a+1@gmail.com"))
+            .await;
+        assert!(matches!(result, Err(AppError::AlreadyExists(_))));
+
+        let service = UserService::new(Arc::new(MockUserRepository::new()));
+        service
+            .create_user(request("first", "a@gmail.com"))
+            .await
+            .unwrap();
+        let created = service
+            .create_user(request("second", "a+1@gmail.com"))
+            .await
+            .unwrap();
+        assert_eq!(created.email, "a+1@gmail.com");
+    }
+
+    #[tokio::test]
+    async fn test_purge_removes_only_users_deleted_beyond_retention() {
+        let repo = Arc::new(MockUserRepository::new());
+        let service = Arc::new(UserService::new(repo.clone()));
+        let mut ids = Vec::new();
+        for name in ["expired", "recent"] {
+            let user = service
+                .create_user(CreateUserRequest {
+                    username: name.to_string(),
+                    email: format!("{}@example.com", name),
+                    full_name: None,
+                })
+                .await
+                .unwrap();
+            service.delete_user(user.id).await.unwrap();
+            ids.push(user.id);
+        }
+        repo.backdate_deletion(ids[0], TimeDelta::days(40));
+
+        let job = crate::jobs::PurgeDeletedUsersJob::new(service.clone(), TimeDelta::days(30));
+        assert_eq!(job.run_once().await.unwrap().unwrap(), 1);
+
+        assert!(matches!(
+            service.restore_user(ids[0]).await,
+            Err(AppError::NotFound(_))
+        ));
+        assert!(service.restore_user(ids[1]).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_user_deletion_forbids_every_delete_path() {
+        let repo = Arc::new(MockUserRepository::new());
+        let service = UserService::new(repo.clone()).with_features(FeatureFlags {
+            allow_user_deletion: false,
+        });
+        let mut ids = Vec::new();
+        for name in ["keeper", "retired"] {
+            let user = service
+                .create_user(CreateUserRequest {
+                    username: name.to_string(),
+                    email: format!("{}@example.com", name),
+                    full_name: None,
+                })
+                .await
+                .unwrap();
+            ids.push(user.id);
+        }
+
+        assert!(matches!(
+            service.delete_user(ids[0]).await,
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(matches!(
+            service.delete_users(vec![ids[0]]).await,
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(service.get_user(ids[0]).await.is_ok());
+
+        // Soft deleted before the flag was turned off: never purged, still
+        // restorable
+        repo.delete(ids[1]).await.unwrap();
+        repo.backdate_deletion(ids[1], TimeDelta::days(40));
+        assert!(matches!(
+            service.purge_deleted_users(TimeDelta::days(30)).await,
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(service.restore_user(ids[1]).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_users_by_domain_returns_only_that_domain() {
+        let service = UserService::new(Arc::new(MockUserRepository::new()));
+        for (name, email) in [
+            ("alice", "alice@acme.com"),
+            ("bob", "bob@ACME.com"),
+            ("carol", "carol@notacme.com"),
+            ("dave", "dave@acme.com.evil.io"),
+        ] {
+            service
+                .create_user(CreateUserRequest {
+                    username: name.to_string(),
+                    email: email.to_string(),
+                    full_name: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        let users = service
+            .users_by_domain("Acme.com".to_string(), 20, 0)
+            .await
+            .unwrap();
+        let mut names: Vec<String> = users.into_iter().map(|u| u.username).collect();
+        names.sort_unstable();
+        assert_eq!(names, vec!["alice", "bob"]);
+
+        assert!(matches!(
+            service
+                .users_by_domain("not a domain".to_string(), 20, 0)
+                .await,
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_users_rejects_offset_beyond_max() {
+        let service = UserService::new(Arc::new(MockUserRepository::new())).with_validation(
+            ValidationConfig {
+                max_offset: 50,
+                ..ValidationConfig::default()
+            },
+        );
+
+        assert!(service.list_users(None, None, 20, 50).await.is_ok());
+        match service.list_users(None, None, 20, 51).await {
+            Err(AppError::ValidationError(msg)) => assert!(msg.contains("cursor")),
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,75 @@
+use chrono::{DateTime, SubsecRound, TimeDelta, Utc};
+use std::sync::Mutex;
+
+/// Fractional-second digits kept in stored and serialized timestamps
+pub const TIMESTAMP_PRECISION: u16 = 3;
+
+/// Truncate `at` to [`TIMESTAMP_PRECISION`] (milliseconds)
+///
+/// Entity timestamps carry no more precision than the database column and
+/// the API render, so a value survives a write/read or serialize/parse
+/// round trip unchanged.
+pub fn truncate_timestamp(at: DateTime<Utc>) -> DateTime<Utc> {
+    at.trunc_subsecs(TIMESTAMP_PRECISION)
+}
+
+/// Source of the current time for entities and services
+///
+/// Production code uses [`SystemClock`]; tests inject a [`FixedClock`] so
+/// timestamps are deterministic. Both return millisecond precision.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Wall-clock time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        truncate_timestamp(Utc::now())
+    }
+}
+
+/// A clock that only moves when told to
+#[derive(Debug)]
+pub struct FixedClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl FixedClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Move the clock forward by `delta`
+    pub fn advance(&self, delta: TimeDelta) {
+        *self.now.lock().unwrap() += delta;
+    }
+
+    /// Jump the clock to `now`
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        truncate_timestamp(*self.now.lock().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Timelike};
+
+    #[test]
+    fn test_clocks_truncate_to_milliseconds() {
+        let at = Utc.timestamp_nanos(1_700_000_000_123_456_789);
+        assert_eq!(FixedClock::new(at).now().nanosecond(), 123_000_000);
+        assert_eq!(SystemClock.now().nanosecond() % 1_000_000, 0);
+    }
+}
@@ -1,24 +1,21 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use shared::config::ValidationConfig;
 use shared::{AppError, UserId};
 
-use crate::value_objects::{Email, Username};
+use crate::clock::{Clock, truncate_timestamp};
+use crate::value_objects::{Email, Url, Username};
 
 /// User status enumeration
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum UserStatus {
+    #[default]
     Active,
     Inactive,
     Suspended,
 }
 
-impl Default for UserStatus {
-    fn default() -> Self {
-        Self::Active
-    }
-}
-
 /// User entity representing a user in the system
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
@@ -26,20 +23,22 @@ pub struct User {
     username: Username,
     email: Email,
     full_name: Option<String>,
+    avatar_url: Option<Url>,
     status: UserStatus,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
 }
 
 impl User {
-    /// Create a new user
-    pub fn new(username: Username, email: Email) -> Self {
-        let now = Utc::now();
+    /// Create a new user stamped with the clock's current time
+    pub fn new(username: Username, email: Email, clock: &dyn Clock) -> Self {
+        let now = clock.now();
         Self {
             id: UserId::new(),
             username,
             email,
             full_name: None,
+            avatar_url: None,
             status: UserStatus::default(),
             created_at: now,
             updated_at: now,
@@ -47,6 +46,8 @@ impl User {
     }
 
     /// Reconstruct user from database (used by infrastructure layer)
+    ///
+    /// Timestamps are truncated to milliseconds like every entity timestamp.
     pub fn from_persistence(
         id: UserId,
         username: Username,
@@ -61,12 +62,19 @@ impl User {
             username,
             email,
             full_name,
+            avatar_url: None,
             status,
-            created_at,
-            updated_at,
+            created_at: truncate_timestamp(created_at),
+            updated_at: truncate_timestamp(updated_at),
         }
     }
 
+    /// Attach a persisted avatar URL (used by infrastructure layer)
+    pub fn with_avatar_url(mut self, avatar_url: Option<Url>) -> Self {
+        self.avatar_url = avatar_url;
+        self
+    }
+
     /// Get user ID
     pub fn id(&self) -> UserId {
         self.id
@@ -87,6 +95,11 @@ impl User {
         self.full_name.as_deref()
     }
 
+    /// Get avatar URL
+    pub fn avatar_url(&self) -> Option<&Url> {
+        self.avatar_url.as_ref()
+    }
+
     /// Get status
     pub fn status(&self) -> UserStatus {
         self.status
@@ -103,47 +116,65 @@ impl User {
     }
 
     /// Update username
-    pub fn update_username(&mut self, username: Username) {
+    pub fn update_username(&mut self, username: Username, clock: &dyn Clock) {
         self.username = username;
-        self.updated_at = Utc::now();
+        self.updated_at = clock.now();
     }
 
     /// Update email
-    pub fn update_email(&mut self, email: Email) {
+    pub fn update_email(&mut self, email: Email, clock: &dyn Clock) {
         self.email = email;
-        self.updated_at = Utc::now();
+        self.updated_at = clock.now();
+    }
+
+    /// Validate a full name without mutating a user
+    pub fn validate_full_name(full_name: &str, policy: &ValidationConfig) -> Result<(), AppError> {
+        if full_name.len() > policy.full_name_max_length {
+            return Err(AppError::ValidationError(format!(
+                "Full name cannot exceed {} characters",
+                policy.full_name_max_length
+            )));
+        }
+        Ok(())
     }
 
     /// Update full name
-    pub fn update_full_name(&mut self, full_name: Option<String>) -> Result<(), AppError> {
+    pub fn update_full_name(
+        &mut self,
+        full_name: Option<String>,
+        policy: &ValidationConfig,
+        clock: &dyn Clock,
+    ) -> Result<(), AppError> {
         if let Some(ref name) = full_name {
-            if name.len() > 100 {
-                return Err(AppError::ValidationError(
-                    "Full name cannot exceed 100 characters".to_string(),
-                ));
-            }
+            Self::validate_full_name(name, policy)?;
         }
         self.full_name = full_name;
-        self.updated_at = Utc::now();
+        self.updated_at = clock.now();
         Ok(())
     }
 
+    /// Update avatar URL
+    pub fn update_avatar_url(&mut self, avatar_url: Option<Url>, clock: &dyn Clock) {
+        self.avatar_url = avatar_url;
+        self.updated_at = clock.now();
+    }
+
     /// Activate user
-    pub fn activate(&mut self) {
+    pub fn activate(&mut self, clock: &dyn Clock) {
         self.status = UserStatus::Active;
-        self.updated_at = Utc::now();
+        self.updated_at = clock.now();
     }
 
     /// Deactivate user
-    pub fn deactivate(&mut self) {
+    pub fn deactivate(&mut self, clock: &dyn Clock) {
         self.status = UserStatus::Inactive;
-        self.updated_at = Utc::now();
+        self.updated_at = clock.now();
     }
 
     /// Suspend user
-    pub fn suspend(&mut self) {
+    pub fn suspend(&mut self, clock: &dyn Clock) {
         self.status = UserStatus::Suspended;
-        self.updated_at = Utc::now();
+        self.updated_at = clock.now();
     }
 
     /// Check if user is active
@@ -155,12 +186,14 @@ impl User {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::{FixedClock, SystemClock};
+    use chrono::TimeDelta;
 
     #[test]
     fn test_create_user() {
         let username = Username::new("testuser").unwrap();
         let email = Email::new("test@example.com").unwrap();
-        let user = User::new(username.clone(), email.clone());
+        let user = User::new(username.clone(), email.clone(), &SystemClock);
 
         assert_eq!(user.username(), &username);
         assert_eq!(user.email(), &email);
@@ -172,14 +205,18 @@ mod tests {
     fn test_update_user() {
         let username = Username::new("testuser").unwrap();
         let email = Email::new("test@example.com").unwrap();
-        let mut user = User::new(username, email);
+        let mut user = User::new(username, email, &SystemClock);
 
         let new_username = Username::new("newuser").unwrap();
-        user.update_username(new_username.clone());
+        user.update_username(new_username.clone(), &SystemClock);
         assert_eq!(user.username(), &new_username);
 
-        user.update_full_name(Some("Test User".to_string()))
-            .unwrap();
+        user.update_full_name(
+            Some("Test User".to_string()),
+            &ValidationConfig::default(),
+            &SystemClock,
+        )
+        .unwrap();
         assert_eq!(user.full_name(), Some("Test User"));
     }
 
@@ -187,15 +224,37 @@ mod tests {
     fn test_user_status_changes() {
         let username = Username::new("testuser").unwrap();
         let email = Email::new("test@example.com").unwrap();
-        let mut user = User::new(username, email);
+        let mut user = User::new(username, email, &SystemClock);
 
         assert!(user.is_active());
 
-        user.suspend();
+        user.suspend(&SystemClock);
         assert_eq!(user.status(), UserStatus::Suspended);
         assert!(!user.is_active());
 
-        user.activate();
+        user.activate(&SystemClock);
         assert!(user.is_active());
     }
+
+    #[test]
+    fn test_fixed_clock_controls_timestamps() {
+        let start = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let clock = FixedClock::new(start);
+        let mut user = User::new(
+            Username::new("clocked").unwrap(),
+            Email::new("clocked@example.com").unwrap(),
+            &clock,
+        );
+
+        assert_eq!(user.created_at(), start);
+        assert_eq!(user.created_at(), user.updated_at());
+
+        clock.advance(TimeDelta::minutes(5));
+        user.deactivate(&clock);
+
+        assert_eq!(user.created_at(), start);
+        assert_eq!(user.updated_at() - user.created_at(), TimeDelta::minutes(5));
+    }
 }
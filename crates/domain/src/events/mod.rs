@@ -0,0 +1,3 @@
+pub mod user_events;
+
+pub use user_events::{UserEvent, UserEventPayload};
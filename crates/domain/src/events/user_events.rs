@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use shared::UserId;
+
+use crate::clock::truncate_timestamp;
+use crate::entities::User;
+
+/// Lifecycle events emitted by the user use cases
+///
+/// Serialized as `{"type": "user.created", "data": {...}, "occurred_at": ...}`
+/// so consumers can dispatch on `type` without knowing every payload.
+#[derive(Debug, Clone, Serialize)]
+pub struct UserEvent {
+    #[serde(flatten)]
+    pub payload: UserEventPayload,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "data")]
+pub enum UserEventPayload {
+    #[serde(rename = "user.created")]
+    Created(User),
+    #[serde(rename = "user.updated")]
+    Updated(User),
+    #[serde(rename = "user.deleted")]
+    Deleted { id: UserId },
+}
+
+impl UserEvent {
+    pub fn created(user: &User) -> Self {
+        Self::now(UserEventPayload::Created(user.clone()))
+    }
+
+    pub fn updated(user: &User) -> Self {
+        Self::now(UserEventPayload::Updated(user.clone()))
+    }
+
+    pub fn deleted(id: UserId) -> Self {
+        Self::now(UserEventPayload::Deleted { id })
+    }
+
+    fn now(payload: UserEventPayload) -> Self {
+        Self {
+            payload,
+            occurred_at: truncate_timestamp(Utc::now()),
+        }
+    }
+
+    /// Stable event type name, e.g. `user.created`
+    pub fn event_type(&self) -> &'static str {
+        match self.payload {
+            UserEventPayload::Created(_) => "user.created",
+            UserEventPayload::Updated(_) => "user.updated",
+            UserEventPayload::Deleted { .. } => "user.deleted",
+        }
+    }
+
+    /// ID of the user the event is about
+    pub fn user_id(&self) -> UserId {
+        match &self.payload {
+            UserEventPayload::Created(user) | UserEventPayload::Updated(user) => user.id(),
+            UserEventPayload::Deleted { id } => *id,
+        }
+    }
+}
@@ -1,7 +1,13 @@
+pub mod clock;
 pub mod entities;
+pub mod events;
 pub mod repositories;
 pub mod value_objects;
 
+pub use clock::{Clock, FixedClock, SystemClock, truncate_timestamp};
 pub use entities::{User, UserStatus};
-pub use repositories::UserRepository;
-pub use value_objects::{Email, Username};
+pub use events::{UserEvent, UserEventPayload};
+pub use repositories::{
+    SortDirection, SortKey, UserFilter, UserRepository, UserSort, UserSortField,
+};
+pub use value_objects::{Email, Password, Slug, Url, Username};
@@ -1,3 +1,5 @@
 pub mod user_repository;
+pub mod user_sort;
 
-pub use user_repository::UserRepository;
+pub use user_repository::{UserFilter, UserRepository};
+pub use user_sort::{SortDirection, SortKey, UserSort, UserSortField};
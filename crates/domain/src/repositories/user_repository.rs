@@ -1,9 +1,46 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared::config::EmailPolicy;
 use shared::{AppResult, UserId};
 
-use crate::entities::User;
+use super::UserSort;
+use crate::entities::{User, UserStatus};
 use crate::value_objects::{Email, Username};
 
+/// Criteria for narrowing user listings; empty fields match everything
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserFilter {
+    /// Only users in this status
+    pub status: Option<UserStatus>,
+    /// Only users in one of these statuses; `None` allows every status
+    pub include_statuses: Option<Vec<UserStatus>>,
+    /// Case-insensitive substring match on username or email
+    pub search: Option<String>,
+}
+
+impl UserFilter {
+    /// Check whether a user satisfies the filter
+    pub fn matches(&self, user: &User) -> bool {
+        if let Some(status) = self.status
+            && user.status() != status
+        {
+            return false;
+        }
+        if let Some(statuses) = &self.include_statuses
+            && !statuses.contains(&user.status())
+        {
+            return false;
+        }
+        if let Some(search) = &self.search {
+            let needle = search.to_lowercase();
+            return user.username().as_str().to_lowercase().contains(&needle)
+                || user.email().as_str().to_lowercase().contains(&needle);
+        }
+        true
+    }
+}
+
 /// UserRepository trait (Port)
 ///
 /// This trait defines the interface for user persistence operations.
@@ -15,30 +52,130 @@ pub trait UserRepository: Send + Sync {
     /// Create a new user
     async fn create(&self, user: &User) -> AppResult<()>;
 
+    /// Create several users atomically: either all are persisted or none
+    ///
+    /// With `dry_run` the inserts still run (so constraint violations
+    /// surface) but the transaction is always rolled back.
+    async fn create_many(&self, users: &[User], dry_run: bool) -> AppResult<()>;
+
     /// Find user by ID
     async fn find_by_id(&self, id: UserId) -> AppResult<Option<User>>;
 
-    /// Find user by username
+    /// Find every user in `ids` in one lookup; missing ids are skipped and
+    /// the order of the result is unspecified
+    async fn find_by_ids(&self, ids: &[UserId]) -> AppResult<Vec<User>>;
+
+    /// Find user by username, ignoring case
     async fn find_by_username(&self, username: &Username) -> AppResult<Option<User>>;
 
     /// Find user by email
     async fn find_by_email(&self, email: &Email) -> AppResult<Option<User>>;
 
+    /// Find a user whose email shares `email`'s canonical form under `policy`
+    async fn find_by_canonical_email(
+        &self,
+        email: &Email,
+        policy: &EmailPolicy,
+    ) -> AppResult<Option<User>>;
+
+    /// Find users whose email is at exactly `domain`, newest first
+    async fn find_by_email_domain(
+        &self,
+        domain: &str,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<User>>;
+
     /// Update user
     async fn update(&self, user: &User) -> AppResult<()>;
 
-    /// Delete user by ID
+    /// Update a user whose username changed, checking that no other live
+    /// user holds the new name (ignoring case) in the same statement
+    ///
+    /// Every field and `updated_at` are written together or not at all. Of
+    /// two users claiming one username concurrently exactly one succeeds; the
+    /// other gets `AlreadyExists`. Fails with `NotFound` when the user does
+    /// not exist.
+    async fn update_claiming_username(&self, user: &User) -> AppResult<()>;
+
+    /// Soft-delete user by ID; the user disappears from every lookup
     async fn delete(&self, id: UserId) -> AppResult<()>;
 
-    /// Check if username exists
+    /// Soft-delete every user in `ids` in one statement, returning the ids
+    /// that actually existed and were removed
+    async fn delete_many(&self, ids: &[UserId]) -> AppResult<Vec<UserId>>;
+
+    /// Undo a soft delete, returning the restored user
+    ///
+    /// Fails with `NotFound` when the user is not soft-deleted, and with
+    /// `AlreadyExists` when its username or email has since been taken.
+    async fn restore(&self, id: UserId) -> AppResult<User>;
+
+    /// Permanently remove users soft-deleted before `deleted_before`,
+    /// returning how many rows were purged
+    async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> AppResult<u64>;
+
+    /// Get the stored password hash; `None` when the user has no password set
+    async fn find_password_hash(&self, id: UserId) -> AppResult<Option<String>>;
+
+    /// Replace the stored password hash
+    async fn set_password_hash(&self, id: UserId, password_hash: &str) -> AppResult<()>;
+
+    /// Replace the stored password hash and bump the session epoch in one
+    /// step, returning the new epoch
+    async fn change_password_hash(&self, id: UserId, password_hash: &str) -> AppResult<i64>;
+
+    /// Current session epoch; `None` when the user does not exist
+    async fn find_session_epoch(&self, id: UserId) -> AppResult<Option<i64>>;
+
+    /// Check if username exists, ignoring case
     async fn username_exists(&self, username: &Username) -> AppResult<bool>;
 
     /// Check if email exists
     async fn email_exists(&self, email: &Email) -> AppResult<bool>;
 
-    /// List all users with pagination
-    async fn list(&self, limit: i64, offset: i64) -> AppResult<Vec<User>>;
+    /// Check which of a username and an email are already taken, as
+    /// `(username_exists, email_exists)`
+    ///
+    /// The default issues two lookups; adapters should answer in one round trip.
+    async fn username_or_email_exists(
+        &self,
+        username: &Username,
+        email: &Email,
+    ) -> AppResult<(bool, bool)> {
+        Ok((
+            self.username_exists(username).await?,
+            self.email_exists(email).await?,
+        ))
+    }
+
+    /// List users matching the filter in `sort` order with pagination
+    async fn list(
+        &self,
+        filter: &UserFilter,
+        sort: &UserSort,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<User>>;
+
+    /// List users matching the filter that come after the `(created_at, id)`
+    /// keyset position `after` in `list`'s newest-first order
+    async fn list_after(
+        &self,
+        filter: &UserFilter,
+        after: (DateTime<Utc>, UserId),
+        limit: i64,
+    ) -> AppResult<Vec<User>>;
+
+    /// List up to `limit` users matching the filter created strictly after
+    /// `since`, newest first
+    async fn list_created_since(
+        &self,
+        filter: &UserFilter,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<User>>;
 
-    /// Count total users
-    async fn count(&self) -> AppResult<i64>;
+    /// Count users matching the filter
+    async fn count(&self, filter: &UserFilter) -> AppResult<i64>;
 }
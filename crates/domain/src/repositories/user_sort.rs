@@ -0,0 +1,206 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+use shared::{AppError, AppResult};
+
+use crate::entities::{User, UserStatus};
+
+/// User attribute a listing can be ordered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserSortField {
+    Username,
+    Email,
+    Status,
+    CreatedAt,
+    UpdatedAt,
+}
+
+impl UserSortField {
+    /// Name used in sort specs, matching the API field name
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Username => "username",
+            Self::Email => "email",
+            Self::Status => "status",
+            Self::CreatedAt => "created_at",
+            Self::UpdatedAt => "updated_at",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        [
+            Self::Username,
+            Self::Email,
+            Self::Status,
+            Self::CreatedAt,
+            Self::UpdatedAt,
+        ]
+        .into_iter()
+        .find(|field| field.as_str() == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SortKey {
+    pub field: UserSortField,
+    pub direction: SortDirection,
+}
+
+/// Ordered sort keys for a user listing
+///
+/// Users equal on every key are ordered newest first, then by id, so pages
+/// never overlap. The default is newest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserSort(Vec<SortKey>);
+
+impl Default for UserSort {
+    fn default() -> Self {
+        Self(vec![SortKey {
+            field: UserSortField::CreatedAt,
+            direction: SortDirection::Desc,
+        }])
+    }
+}
+
+impl UserSort {
+    /// Parse a spec such as `status,-username`: comma-separated field names,
+    /// each ascending unless prefixed with `-`
+    ///
+    /// At most `max_keys` keys are accepted and a field may appear once.
+    pub fn parse(spec: &str, max_keys: usize) -> AppResult<Self> {
+        let mut keys: Vec<SortKey> = Vec::new();
+        for part in spec.split(',').map(str::trim) {
+            let (name, direction) = match part.strip_prefix('-') {
+                Some(name) => (name, SortDirection::Desc),
+                None => (part, SortDirection::Asc),
+            };
+            let field = UserSortField::parse(name)
+                .ok_or_else(|| AppError::ValidationError(format!("Cannot sort by '{}'", part)))?;
+            if keys.iter().any(|key| key.field == field) {
+                return Err(AppError::ValidationError(format!(
+                    "Sort field '{}' is repeated",
+                    name
+                )));
+            }
+            keys.push(SortKey { field, direction });
+        }
+        if keys.len() > max_keys {
+            return Err(AppError::ValidationError(format!(
+                "Sort accepts at most {} fields",
+                max_keys
+            )));
+        }
+        Ok(Self(keys))
+    }
+
+    pub fn keys(&self) -> &[SortKey] {
+        &self.0
+    }
+
+    /// Whether this is the newest-first order that cursors page through
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Order two users as the repository would list them
+    pub fn compare(&self, a: &User, b: &User) -> Ordering {
+        self.0
+            .iter()
+            .map(|key| {
+                let ordering = match key.field {
+                    UserSortField::Username => a.username().as_str().cmp(b.username().as_str()),
+                    UserSortField::Email => a.email().as_str().cmp(b.email().as_str()),
+                    UserSortField::Status => status_rank(a.status()).cmp(&status_rank(b.status())),
+                    UserSortField::CreatedAt => a.created_at().cmp(&b.created_at()),
+                    UserSortField::UpdatedAt => a.updated_at().cmp(&b.updated_at()),
+                };
+                match key.direction {
+                    SortDirection::Asc => ordering,
+                    SortDirection::Desc => ordering.reverse(),
+                }
+            })
+            .chain([
+                b.created_at().cmp(&a.created_at()),
+                b.id().as_uuid().cmp(a.id().as_uuid()),
+            ])
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Position of a status when sorted by its stored name
+fn status_rank(status: UserStatus) -> u8 {
+    match status {
+        UserStatus::Active => 0,
+        UserStatus::Inactive => 1,
+        UserStatus::Suspended => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Email, SystemClock, Username};
+
+    fn user(name: &str) -> User {
+        User::new(
+            Username::new(name.to_string()).unwrap(),
+            Email::new(format!("{}@example.com", name)).unwrap(),
+            &SystemClock,
+        )
+    }
+
+    #[test]
+    fn test_parses_fields_in_order_with_directions() {
+        let sort = UserSort::parse("status, -username", 3).unwrap();
+
+        assert_eq!(
+            sort.keys(),
+            &[
+                SortKey {
+                    field: UserSortField::Status,
+                    direction: SortDirection::Asc,
+                },
+                SortKey {
+                    field: UserSortField::Username,
+                    direction: SortDirection::Desc,
+                },
+            ]
+        );
+        assert!(!sort.is_default());
+        assert!(UserSort::parse("-created_at", 3).unwrap().is_default());
+    }
+
+    #[test]
+    fn test_rejects_too_many_unknown_or_repeated_fields() {
+        let err = UserSort::parse("status,username,email,created_at", 3).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Validation error: Sort accepts at most 3 fields"
+        );
+        assert!(UserSort::parse("password_hash", 3).is_err());
+        assert!(UserSort::parse("status,", 3).is_err());
+        assert!(UserSort::parse("username,-username", 3).is_err());
+    }
+
+    #[test]
+    fn test_compare_uses_keys_in_order() {
+        let mut suspended = user("carol");
+        suspended.suspend(&SystemClock);
+        let mut users = [user("alice"), suspended, user("bob")];
+
+        let sort = UserSort::parse("status,-username", 3).unwrap();
+        users.sort_by(|a, b| sort.compare(a, b));
+
+        let names: Vec<&str> = users.iter().map(|u| u.username().as_str()).collect();
+        assert_eq!(names, vec!["bob", "alice", "carol"]);
+    }
+}
@@ -1,6 +1,7 @@
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use shared::AppError;
+use shared::config::EmailPolicy;
 use std::sync::OnceLock;
 
 static EMAIL_REGEX: OnceLock<Regex> = OnceLock::new();
@@ -61,6 +62,31 @@ impl Email {
     pub fn local_part(&self) -> Option<&str> {
         self.0.split('@').next()
     }
+
+    /// Mailbox identity under `policy`, for uniqueness checks only.
+    ///
+    /// Mail is still sent to `as_str()`; this form may strip a `+tag` or
+    /// dots from the local part when the policy says the domain ignores them.
+    pub fn canonical(&self, policy: &EmailPolicy) -> String {
+        let (Some(local), Some(domain)) = (self.local_part(), self.domain()) else {
+            return self.0.clone();
+        };
+        let Some(rule) = policy.rule_for(domain) else {
+            return self.0.clone();
+        };
+
+        let local = if rule.strip_plus_tag {
+            local.split('+').next().unwrap_or(local)
+        } else {
+            local
+        };
+        let local = if rule.ignore_dots {
+            local.replace('.', "")
+        } else {
+            local.to_string()
+        };
+        format!("{}@{}", local, domain)
+    }
 }
 
 impl std::fmt::Display for Email {
@@ -106,4 +132,41 @@ mod tests {
         assert_eq!(email.local_part(), Some("user"));
         assert_eq!(email.domain(), Some("example.com"));
     }
+
+    fn gmail_policy() -> EmailPolicy {
+        EmailPolicy {
+            rules: vec![shared::config::EmailDomainRule {
+                domains: vec!["gmail.com".to_string()],
+                strip_plus_tag: true,
+                ignore_dots: true,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_canonical_under_policy() {
+        let policy = gmail_policy();
+        let tagged = Email::new("a+1@gmail.com").unwrap();
+        let plain = Email::new("a@gmail.com").unwrap();
+        let dotted = Email::new("A.b+x@Gmail.com").unwrap();
+
+        assert_eq!(tagged.canonical(&policy), plain.canonical(&policy));
+        assert_eq!(dotted.canonical(&policy), "ab@gmail.com");
+        // The original address is kept for sending
+        assert_eq!(tagged.as_str(), "a+1@gmail.com");
+    }
+
+    #[test]
+    fn test_canonical_without_policy_is_verbatim() {
+        let tagged = Email::new("a+1@gmail.com").unwrap();
+        let plain = Email::new("a@gmail.com").unwrap();
+        assert_ne!(
+            tagged.canonical(&EmailPolicy::default()),
+            plain.canonical(&EmailPolicy::default())
+        );
+
+        // Domains not covered by the policy are untouched
+        let other = Email::new("a+1@example.com").unwrap();
+        assert_eq!(other.canonical(&gmail_policy()), "a+1@example.com");
+    }
 }
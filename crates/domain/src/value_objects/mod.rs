@@ -1,5 +1,11 @@
 pub mod email;
+pub mod password;
+pub mod slug;
+pub mod url;
 pub mod username;
 
 pub use email::Email;
+pub use password::Password;
+pub use slug::Slug;
+pub use url::Url;
 pub use username::Username;
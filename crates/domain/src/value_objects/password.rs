@@ -0,0 +1,130 @@
+use shared::AppError;
+use shared::config::PasswordPolicy;
+
+/// Plaintext password value object validated against a `PasswordPolicy`
+///
+/// The raw value is never exposed through `Debug` to keep it out of logs.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Password(String);
+
+impl Password {
+    /// Create a new password, enforcing every rule of the given policy
+    pub fn new(password: impl Into<String>, policy: &PasswordPolicy) -> Result<Self, AppError> {
+        let password = password.into();
+        Self::validate(&password, policy)?;
+        Ok(Self(password))
+    }
+
+    /// Validate password against the policy
+    fn validate(password: &str, policy: &PasswordPolicy) -> Result<(), AppError> {
+        let length = password.chars().count();
+
+        if length < policy.min_length {
+            return Err(AppError::ValidationError(format!(
+                "Password must be at least {} characters",
+                policy.min_length
+            )));
+        }
+
+        if length > policy.max_length {
+            return Err(AppError::ValidationError(format!(
+                "Password cannot exceed {} characters",
+                policy.max_length
+            )));
+        }
+
+        if policy.require_upper && !password.chars().any(|c| c.is_uppercase()) {
+            return Err(AppError::ValidationError(
+                "Password must contain at least one uppercase letter".to_string(),
+            ));
+        }
+
+        if policy.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            return Err(AppError::ValidationError(
+                "Password must contain at least one digit".to_string(),
+            ));
+        }
+
+        if policy.require_symbol && !password.chars().any(|c| !c.is_alphanumeric()) {
+            return Err(AppError::ValidationError(
+                "Password must contain at least one symbol".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Get the password as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for Password {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Password(***)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strict_policy() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 8,
+            max_length: 16,
+            require_upper: true,
+            require_digit: true,
+            require_symbol: true,
+        }
+    }
+
+    fn error_message(result: Result<Password, AppError>) -> String {
+        match result {
+            Err(AppError::ValidationError(msg)) => msg,
+            other => panic!("expected validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_compliant_password() {
+        assert!(Password::new("Secr3t!pass", &strict_policy()).is_ok());
+    }
+
+    #[test]
+    fn test_password_too_short() {
+        let msg = error_message(Password::new("S3c!", &strict_policy()));
+        assert!(msg.contains("at least 8 characters"));
+    }
+
+    #[test]
+    fn test_password_too_long() {
+        let msg = error_message(Password::new("Secr3t!password-too-long", &strict_policy()));
+        assert!(msg.contains("cannot exceed 16 characters"));
+    }
+
+    #[test]
+    fn test_password_missing_upper() {
+        let msg = error_message(Password::new("secr3t!pass", &strict_policy()));
+        assert!(msg.contains("uppercase"));
+    }
+
+    #[test]
+    fn test_password_missing_digit() {
+        let msg = error_message(Password::new("Secret!pass", &strict_policy()));
+        assert!(msg.contains("digit"));
+    }
+
+    #[test]
+    fn test_password_missing_symbol() {
+        let msg = error_message(Password::new("Secr3tpass", &strict_policy()));
+        assert!(msg.contains("symbol"));
+    }
+
+    #[test]
+    fn test_password_debug_is_redacted() {
+        let password = Password::new("Secr3t!pass", &strict_policy()).unwrap();
+        assert_eq!(format!("{:?}", password), "Password(***)");
+    }
+}
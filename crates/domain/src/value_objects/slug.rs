@@ -0,0 +1,135 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use shared::AppError;
+use std::sync::OnceLock;
+
+static SLUG_REGEX: OnceLock<Regex> = OnceLock::new();
+
+fn get_slug_regex() -> &'static Regex {
+    SLUG_REGEX
+        .get_or_init(|| Regex::new(r"^[a-z0-9]+(?:-[a-z0-9]+)*$").expect("Invalid slug regex"))
+}
+
+/// Maximum slug length (fits a DNS label)
+pub const MAX_SLUG_LENGTH: usize = 63;
+
+/// URL-safe identifier for tenants and other named resources
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Slug(String);
+
+impl Slug {
+    /// Create a slug from an already URL-safe value
+    pub fn new(slug: impl Into<String>) -> Result<Self, AppError> {
+        let slug = slug.into();
+        Self::validate(&slug)?;
+        Ok(Self(slug))
+    }
+
+    /// Slugify arbitrary input: lowercase, trim, and collapse every run of
+    /// non-alphanumeric characters into a single hyphen
+    pub fn from_title(title: &str) -> Result<Self, AppError> {
+        let mut slug = String::with_capacity(title.len());
+        let mut pending_separator = false;
+        for c in title.trim().chars().flat_map(char::to_lowercase) {
+            if c.is_ascii_alphanumeric() {
+                if pending_separator && !slug.is_empty() {
+                    slug.push('-');
+                }
+                pending_separator = false;
+                slug.push(c);
+            } else {
+                pending_separator = true;
+            }
+        }
+
+        // Truncating may leave a trailing hyphen
+        slug.truncate(MAX_SLUG_LENGTH);
+        let slug = slug.trim_end_matches('-').to_string();
+
+        Self::new(slug)
+    }
+
+    /// Validate slug format
+    fn validate(slug: &str) -> Result<(), AppError> {
+        if slug.is_empty() {
+            return Err(AppError::ValidationError(
+                "Slug cannot be empty".to_string(),
+            ));
+        }
+
+        if slug.len() > MAX_SLUG_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "Slug cannot exceed {} characters",
+                MAX_SLUG_LENGTH
+            )));
+        }
+
+        if !get_slug_regex().is_match(slug) {
+            return Err(AppError::ValidationError(
+                "Slug can only contain lowercase letters, digits, and single hyphens between them"
+                    .to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Get the slug as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for Slug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for Slug {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_slugs() {
+        assert!(Slug::new("acme").is_ok());
+        assert!(Slug::new("acme-corp-2").is_ok());
+        assert!(Slug::new("a".repeat(MAX_SLUG_LENGTH)).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_slugs() {
+        assert!(Slug::new("Acme").is_err());
+        assert!(Slug::new("acme--corp").is_err());
+        assert!(Slug::new("-acme").is_err());
+        assert!(Slug::new("").is_err());
+        assert!(Slug::new("a".repeat(MAX_SLUG_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_from_title() {
+        assert_eq!(
+            Slug::from_title("Hello World!").unwrap().as_str(),
+            "hello-world"
+        );
+        assert_eq!(
+            Slug::from_title("  Acme -- Corp__2024 ").unwrap().as_str(),
+            "acme-corp-2024"
+        );
+        assert!(Slug::from_title("!!!").is_err());
+    }
+
+    #[test]
+    fn test_from_title_truncates() {
+        let slug = Slug::from_title(&"ab ".repeat(40)).unwrap();
+        assert!(slug.as_str().len() <= MAX_SLUG_LENGTH);
+        assert!(!slug.as_str().ends_with('-'));
+    }
+}
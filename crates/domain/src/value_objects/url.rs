@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use shared::AppError;
+
+/// Maximum URL length accepted from clients
+pub const MAX_URL_LENGTH: usize = 2048;
+
+/// Absolute http(s) URL value object with validation
+///
+/// Any other scheme (`javascript:`, `data:`, `ftp:` ...) is rejected so the
+/// value is always safe to render as a link or image source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Url(String);
+
+impl Url {
+    /// Create a new URL with validation
+    pub fn new(url: impl Into<String>) -> Result<Self, AppError> {
+        let url = url.into();
+        let parsed = Self::validate(url.trim())?;
+        Ok(Self(parsed.into()))
+    }
+
+    /// Validate URL format, scheme, and host
+    fn validate(url: &str) -> Result<url::Url, AppError> {
+        if url.is_empty() {
+            return Err(AppError::ValidationError("URL cannot be empty".to_string()));
+        }
+
+        if url.len() > MAX_URL_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "URL cannot exceed {} characters",
+                MAX_URL_LENGTH
+            )));
+        }
+
+        let parsed = url::Url::parse(url)
+            .map_err(|_| AppError::ValidationError(format!("Invalid URL format: {}", url)))?;
+
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(AppError::ValidationError(format!(
+                "URL scheme must be http or https, got '{}'",
+                parsed.scheme()
+            )));
+        }
+
+        if parsed.host_str().is_none_or(str::is_empty) {
+            return Err(AppError::ValidationError(
+                "URL must include a host".to_string(),
+            ));
+        }
+
+        Ok(parsed)
+    }
+
+    /// Get the URL as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for Url {
+    type Error = AppError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Url> for String {
+    fn from(url: Url) -> Self {
+        url.0
+    }
+}
+
+impl std::fmt::Display for Url {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for Url {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_https_url() {
+        let url = Url::new("https://cdn.example.com/avatars/1.png").unwrap();
+        assert_eq!(url.as_str(), "https://cdn.example.com/avatars/1.png");
+        assert!(Url::new("http://example.com").is_ok());
+    }
+
+    #[test]
+    fn test_rejects_other_schemes() {
+        assert!(Url::new("ftp://example.com/avatar.png").is_err());
+        assert!(Url::new("javascript:alert(1)").is_err());
+        assert!(Url::new("data:image/png;base64,AAAA").is_err());
+    }
+
+    #[test]
+    fn test_rejects_malformed_urls() {
+        assert!(Url::new("").is_err());
+        assert!(Url::new("not a url").is_err());
+        assert!(Url::new("https://").is_err());
+        assert!(Url::new("/relative/path.png").is_err());
+    }
+}
@@ -1,13 +1,14 @@
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use shared::AppError;
+use shared::config::ValidationConfig;
 use std::sync::OnceLock;
+use unicode_security::confusable_detection::skeleton;
 
 static USERNAME_REGEX: OnceLock<Regex> = OnceLock::new();
 
 fn get_username_regex() -> &'static Regex {
-    USERNAME_REGEX
-        .get_or_init(|| Regex::new(r"^[a-zA-Z0-9_-]{3,30}$").expect("Invalid username regex"))
+    USERNAME_REGEX.get_or_init(|| Regex::new(r"^[a-zA-Z0-9_-]+$").expect("Invalid username regex"))
 }
 
 /// Username value object with validation
@@ -16,33 +17,73 @@ fn get_username_regex() -> &'static Regex {
 pub struct Username(String);
 
 impl Username {
-    /// Create a new username with validation
+    /// Create a new username with validation against the default bounds
     pub fn new(username: impl Into<String>) -> Result<Self, AppError> {
+        Self::with_policy(username, &ValidationConfig::default())
+    }
+
+    /// Create a new username with validation against configured bounds
+    pub fn with_policy(
+        username: impl Into<String>,
+        policy: &ValidationConfig,
+    ) -> Result<Self, AppError> {
         let username = username.into();
-        Self::validate(&username)?;
+        Self::validate(&username, policy)?;
         Ok(Self(username))
     }
 
+    /// Rebuild a username from storage without re-validating, so rows written
+    /// under looser bounds stay readable
+    pub fn from_persistence(username: impl Into<String>) -> Self {
+        Self(username.into())
+    }
+
     /// Validate username format
-    fn validate(username: &str) -> Result<(), AppError> {
+    fn validate(username: &str, policy: &ValidationConfig) -> Result<(), AppError> {
         if username.is_empty() {
             return Err(AppError::InvalidUsername(
                 "Username cannot be empty".to_string(),
             ));
         }
 
-        if username.len() < 3 {
-            return Err(AppError::InvalidUsername(
-                "Username must be at least 3 characters".to_string(),
-            ));
+        if username.len() < policy.username_min_length {
+            return Err(AppError::InvalidUsername(format!(
+                "Username must be at least {} characters",
+                policy.username_min_length
+            )));
         }
 
-        if username.len() > 30 {
+        if username.len() > policy.username_max_length {
+            return Err(AppError::InvalidUsername(format!(
+                "Username cannot exceed {} characters",
+                policy.username_max_length
+            )));
+        }
+
+        if policy
+            .reserved_usernames
+            .iter()
+            .any(|reserved| reserved.eq_ignore_ascii_case(username))
+        {
             return Err(AppError::InvalidUsername(
-                "Username cannot exceed 30 characters".to_string(),
+                "Username is reserved".to_string(),
             ));
         }
 
+        // Checked before the character set so a look-alike is reported as such
+        if policy.detect_confusable_usernames {
+            let candidate = confusable_skeleton(username);
+            if policy
+                .reserved_usernames
+                .iter()
+                .any(|reserved| confusable_skeleton(reserved) == candidate)
+            {
+                return Err(AppError::InvalidUsername(
+                    "Username is too similar to a reserved name".to_string(),
+                ));
+            }
+        }
+
         if !get_username_regex().is_match(username) {
             return Err(AppError::InvalidUsername(
                 "Username can only contain alphanumeric characters, underscores, and hyphens"
@@ -59,6 +100,15 @@ impl Username {
     }
 }
 
+/// Case-folded UTS #39 skeleton: names that render alike share one
+///
+/// Folded on both sides since prototypes may be uppercase (`0` maps to `O`).
+fn confusable_skeleton(name: &str) -> String {
+    skeleton(&name.to_lowercase())
+        .collect::<String>()
+        .to_lowercase()
+}
+
 impl std::fmt::Display for Username {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -91,4 +141,54 @@ mod tests {
         assert!(Username::new("user name").is_err()); // Contains space
         assert!(Username::new("user@name").is_err()); // Contains special char
     }
+
+    #[test]
+    fn test_reserved_names_and_their_look_alikes_are_rejected() {
+        assert!(Username::new("support").is_err());
+        assert!(Username::new("Support").is_err());
+
+        // Cyrillic "а" and "о", and digits standing in for letters
+        for look_alike in ["\u{430}dmin", "r\u{43e}\u{43e}t", "r00t", "adrnin"] {
+            match Username::new(look_alike) {
+                Err(AppError::InvalidUsername(msg)) => {
+                    assert!(msg.contains("too similar"), "{}: {}", look_alike, msg)
+                }
+                other => panic!("{} was not rejected: {:?}", look_alike, other),
+            }
+        }
+
+        assert!(Username::new("admiral").is_ok());
+        assert!(Username::new("rooted").is_ok());
+    }
+
+    #[test]
+    fn test_confusable_detection_can_be_disabled() {
+        let policy = ValidationConfig {
+            reserved_usernames: vec!["paypal".to_string()],
+            detect_confusable_usernames: false,
+            ..ValidationConfig::default()
+        };
+        assert!(Username::with_policy("paypa1", &policy).is_ok());
+        assert!(Username::with_policy("paypal", &policy).is_err());
+
+        let policy = ValidationConfig {
+            detect_confusable_usernames: true,
+            ..policy
+        };
+        assert!(Username::with_policy("paypa1", &policy).is_err());
+        assert!(Username::with_policy("paypal_fan", &policy).is_ok());
+    }
+
+    #[test]
+    fn test_configured_max_length() {
+        let name = "a".repeat(40);
+        assert!(Username::new(name.clone()).is_err());
+
+        let policy = ValidationConfig {
+            username_max_length: 50,
+            ..ValidationConfig::default()
+        };
+        assert!(Username::with_policy(name, &policy).is_ok());
+        assert!(Username::with_policy("a".repeat(51), &policy).is_err());
+    }
 }
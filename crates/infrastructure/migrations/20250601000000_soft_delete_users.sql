@@ -0,0 +1,10 @@
+-- Soft delete: rows are kept with a deletion timestamp instead of removed
+ALTER TABLE users ADD COLUMN deleted_at TIMESTAMPTZ;
+
+-- Usernames and emails only need to be unique among live users, so a
+-- soft-deleted account does not block its handle from being reused.
+-- The index names match the old constraints so error mapping is unchanged.
+ALTER TABLE users DROP CONSTRAINT users_username_key;
+ALTER TABLE users DROP CONSTRAINT users_email_key;
+CREATE UNIQUE INDEX users_username_key ON users(username) WHERE deleted_at IS NULL;
+CREATE UNIQUE INDEX users_email_key ON users(email) WHERE deleted_at IS NULL;
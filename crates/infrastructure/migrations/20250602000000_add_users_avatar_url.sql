@@ -0,0 +1,2 @@
+-- Optional avatar image; validated as an absolute http(s) URL by the domain
+ALTER TABLE users ADD COLUMN avatar_url VARCHAR(2048);
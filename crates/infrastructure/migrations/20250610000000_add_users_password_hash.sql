@@ -0,0 +1,2 @@
+-- Argon2 PHC string; NULL until the user sets a password
+ALTER TABLE users ADD COLUMN password_hash TEXT;
@@ -0,0 +1,6 @@
+-- Mirror the domain invariants so rows written around the application are
+-- held to the same rules. The username bounds match the default
+-- validation policy (3..30); status is already checked by the create migration.
+ALTER TABLE users
+    ADD CONSTRAINT users_username_length CHECK (char_length(username) BETWEEN 3 AND 30),
+    ADD CONSTRAINT users_email_length CHECK (char_length(email) BETWEEN 1 AND 255);
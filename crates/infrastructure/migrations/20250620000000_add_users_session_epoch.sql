@@ -0,0 +1,2 @@
+-- Bumped to revoke every access token issued for the user before it
+ALTER TABLE users ADD COLUMN session_epoch BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,18 @@
+-- Store user timestamps at millisecond precision, the precision the
+-- application generates and the API renders, so a written row reads back
+-- identical to the value that was written
+ALTER TABLE users
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ(3),
+    ALTER COLUMN updated_at TYPE TIMESTAMPTZ(3);
+
+-- Keep an updated_at the application set itself; only stamp rows changed
+-- without one (e.g. password or session updates)
+CREATE OR REPLACE FUNCTION update_updated_at_column()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.updated_at IS NOT DISTINCT FROM OLD.updated_at THEN
+        NEW.updated_at = date_trunc('milliseconds', NOW());
+    END IF;
+    RETURN NEW;
+END;
+$$ language 'plpgsql';
@@ -0,0 +1,21 @@
+-- Indexes for the lookups and listings the repository runs on live users
+
+-- Usernames and emails are unique regardless of case; lookups compare
+-- LOWER() on both sides so they use these indexes. The index names match
+-- the old ones so constraint error mapping is unchanged. Live rows that
+-- differ only by case must be resolved before this migration can apply.
+DROP INDEX users_username_key;
+DROP INDEX users_email_key;
+CREATE UNIQUE INDEX users_username_key ON users (LOWER(username)) WHERE deleted_at IS NULL;
+CREATE UNIQUE INDEX users_email_key ON users (LOWER(email)) WHERE deleted_at IS NULL;
+DROP INDEX idx_users_username;
+DROP INDEX idx_users_email;
+
+-- Keyset pagination orders by (created_at, id); scanned backwards for
+-- newest-first pages
+DROP INDEX idx_users_created_at;
+CREATE INDEX idx_users_created_at_id ON users (created_at, id) WHERE deleted_at IS NULL;
+
+-- Status-filtered listings, in the same order
+DROP INDEX idx_users_status;
+CREATE INDEX idx_users_status ON users (status, created_at, id) WHERE deleted_at IS NULL;
@@ -0,0 +1,113 @@
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+
+use application::ports::PasswordHasher;
+use shared::config::PasswordHashing;
+use shared::{AppError, AppResult};
+
+/// Argon2id implementation of the `PasswordHasher` port
+#[derive(Clone)]
+pub struct Argon2PasswordHasher {
+    params: Params,
+}
+
+impl Argon2PasswordHasher {
+    pub fn new(config: &PasswordHashing) -> AppResult<Self> {
+        let params = Params::new(
+            config.memory_kib,
+            config.iterations,
+            config.parallelism,
+            None,
+        )
+        .map_err(|e| {
+            AppError::ConfigurationError(format!("Invalid password hashing parameters: {}", e))
+        })?;
+        Ok(Self { params })
+    }
+
+    fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+}
+
+impl PasswordHasher for Argon2PasswordHasher {
+    fn hash(&self, password: &str) -> AppResult<String> {
+        let salt = SaltString::generate(&mut OsRng);
+        self.argon2()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| AppError::InternalError(format!("Failed to hash password: {}", e)))
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> AppResult<bool> {
+        let parsed = PasswordHash::new(hash)
+            .map_err(|e| AppError::InternalError(format!("Malformed password hash: {}", e)))?;
+        // Verification uses the parameters recorded in the hash itself
+        Ok(self
+            .argon2()
+            .verify_password(password.as_bytes(), &parsed)
+            .is_ok())
+    }
+
+    fn needs_rehash(&self, hash: &str) -> bool {
+        let Ok(parsed) = PasswordHash::new(hash) else {
+            return true;
+        };
+        if parsed.algorithm != Algorithm::Argon2id.ident()
+            || parsed.version != Some(Version::V0x13.into())
+        {
+            return true;
+        }
+        match Params::try_from(&parsed) {
+            Ok(stored) => {
+                stored.m_cost() < self.params.m_cost()
+                    || stored.t_cost() < self.params.t_cost()
+                    || stored.p_cost() < self.params.p_cost()
+            }
+            Err(_) => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hasher(memory_kib: u32, iterations: u32) -> Argon2PasswordHasher {
+        Argon2PasswordHasher::new(&PasswordHashing {
+            memory_kib,
+            iterations,
+            parallelism: 1,
+            rehash_on_login: true,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_hash_with_weaker_parameters_needs_rehash() {
+        let old = hasher(8, 1);
+        let current = hasher(16, 2);
+        let stored = old.hash("Secret123").unwrap();
+
+        assert!(current.verify("Secret123", &stored).unwrap());
+        assert!(!current.verify("Wrong123", &stored).unwrap());
+        assert!(current.needs_rehash(&stored));
+
+        let upgraded = current.hash("Secret123").unwrap();
+        assert!(!current.needs_rehash(&upgraded));
+        // Lowering the policy never forces a downgrade
+        assert!(!old.needs_rehash(&upgraded));
+    }
+
+    #[test]
+    fn test_rejects_invalid_parameters() {
+        assert!(matches!(
+            Argon2PasswordHasher::new(&PasswordHashing {
+                memory_kib: 0,
+                ..PasswordHashing::default()
+            }),
+            Err(AppError::ConfigurationError(_))
+        ));
+    }
+}
@@ -0,0 +1,450 @@
+use std::collections::HashMap;
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, EllipticCurveKeyParameters,
+    EllipticCurveKeyType, Jwk, JwkSet, KeyAlgorithm, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+};
+use jsonwebtoken::{
+    Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, decode_header, encode,
+    get_current_timestamp,
+};
+use rsa::RsaPublicKey;
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::pkcs8::DecodePublicKey;
+use rsa::traits::PublicKeyParts;
+
+use application::auth::Claims;
+use application::ports::TokenService;
+use shared::config::{JwtAlgorithm, JwtConfig};
+use shared::{AppError, AppResult};
+
+/// DER prefix of a P-256 SubjectPublicKeyInfo, up to the uncompressed point
+/// marker; the 64 bytes that follow are the X and Y coordinates
+const P256_SPKI_PREFIX: [u8; 27] = [
+    0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+    0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00, 0x04,
+];
+
+/// JWT implementation of the `TokenService` port
+///
+/// Signs with HS256 from the shared secret, or with RS256/ES256 from a PEM
+/// private key whose public half is published through [`Self::jwks`].
+/// Tokens carry the current key's `kid`; verification picks the current or
+/// a previous key by that `kid`. Tokens without a `kid` (signed before key
+/// ids were introduced) are checked against the current key.
+#[derive(Clone)]
+pub struct JwtTokenService {
+    algorithm: Algorithm,
+    key_id: String,
+    encoding_key: EncodingKey,
+    decoding_keys: HashMap<String, DecodingKey>,
+    public_keys: Vec<Jwk>,
+    validation: Validation,
+}
+
+impl JwtTokenService {
+    /// Build the service, reading PEM key files for RS256/ES256
+    ///
+    /// Fails with `ConfigurationError` if an HS256 secret is empty, or a key
+    /// file is missing, unreadable or does not match the configured algorithm.
+    pub fn new(config: &JwtConfig) -> AppResult<Self> {
+        let algorithm = match config.algorithm {
+            JwtAlgorithm::HS256 => Algorithm::HS256,
+            JwtAlgorithm::RS256 => Algorithm::RS256,
+            JwtAlgorithm::ES256 => Algorithm::ES256,
+        };
+        let mut validation = Validation::new(algorithm);
+        validation.set_issuer(&[&config.issuer]);
+        validation.set_required_spec_claims(&["exp", "iss", "sub"]);
+        validation.validate_nbf = true;
+        validation.leeway = config.leeway_seconds;
+
+        let mut decoding_keys = HashMap::new();
+        let mut public_keys = Vec::new();
+        let encoding_key = if config.algorithm.is_asymmetric() {
+            let private_pem = read_key(config.private_key_path.as_deref(), "jwt.private_key_path")?;
+            let encoding_key = match config.algorithm {
+                JwtAlgorithm::RS256 => EncodingKey::from_rsa_pem(&private_pem),
+                _ => EncodingKey::from_ec_pem(&private_pem),
+            }
+            .map_err(|e| {
+                AppError::ConfigurationError(format!("Invalid jwt.private_key_path: {}", e))
+            })?;
+
+            let keys = config
+                .previous_keys
+                .iter()
+                .map(|key| (&key.kid, key.public_key_path.as_deref()))
+                .chain([(&config.key_id, config.public_key_path.as_deref())]);
+            for (kid, path) in keys {
+                let public_pem = read_key(path, &format!("public_key_path of key {:?}", kid))?;
+                let jwk = public_jwk(config.algorithm, kid, &public_pem)?;
+                let decoding_key = DecodingKey::from_jwk(&jwk).map_err(|e| {
+                    AppError::ConfigurationError(format!("Invalid public key {:?}: {}", kid, e))
+                })?;
+                decoding_keys.insert(kid.clone(), decoding_key);
+                public_keys.retain(|known: &Jwk| known.common.key_id.as_ref() != Some(kid));
+                public_keys.push(jwk);
+            }
+            encoding_key
+        } else {
+            if config.secret.is_empty() {
+                return Err(AppError::ConfigurationError(
+                    "jwt.secret is required for HS256".to_string(),
+                ));
+            }
+            for key in &config.previous_keys {
+                if key.secret.is_empty() {
+                    return Err(AppError::ConfigurationError(format!(
+                        "secret of previous key {:?} is required for HS256",
+                        key.kid
+                    )));
+                }
+                decoding_keys.insert(
+                    key.kid.clone(),
+                    DecodingKey::from_secret(key.secret.as_bytes()),
+                );
+            }
+            decoding_keys.insert(
+                config.key_id.clone(),
+                DecodingKey::from_secret(config.secret.as_bytes()),
+            );
+            EncodingKey::from_secret(config.secret.as_bytes())
+        };
+
+        Ok(Self {
+            algorithm,
+            key_id: config.key_id.clone(),
+            encoding_key,
+            decoding_keys,
+            public_keys,
+            validation,
+        })
+    }
+
+    /// Public verification keys, current and retired, for
+    /// `/.well-known/jwks.json`; empty with HS256
+    pub fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self.public_keys.clone(),
+        }
+    }
+
+    /// Verification key for `token`, chosen by its `kid` header
+    fn decoding_key(&self, token: &str) -> AppResult<&DecodingKey> {
+        let header = decode_header(token)
+            .map_err(|e| AppError::Unauthorized(format!("Invalid token: {}", e)))?;
+        let kid = header.kid.as_deref().unwrap_or(&self.key_id);
+        self.decoding_keys
+            .get(kid)
+            .ok_or_else(|| AppError::Unauthorized("Invalid token: unknown signing key".to_string()))
+    }
+}
+
+fn read_key(path: Option<&str>, setting: &str) -> AppResult<Vec<u8>> {
+    let path = path.ok_or_else(|| {
+        AppError::ConfigurationError(format!("{} is required for RS256/ES256", setting))
+    })?;
+    std::fs::read(path).map_err(|e| {
+        AppError::ConfigurationError(format!("Failed to read JWT key {}: {}", path, e))
+    })
+}
+
+/// JWK for a PEM public key (SPKI, or PKCS#1 for RSA)
+fn public_jwk(algorithm: JwtAlgorithm, kid: &str, pem: &[u8]) -> AppResult<Jwk> {
+    let invalid =
+        |e: String| AppError::ConfigurationError(format!("Invalid public key {:?}: {}", kid, e));
+    let pem = std::str::from_utf8(pem).map_err(|e| invalid(e.to_string()))?;
+
+    let (key_algorithm, parameters) = match algorithm {
+        JwtAlgorithm::RS256 => {
+            let key = RsaPublicKey::from_public_key_pem(pem)
+                .or_else(|_| RsaPublicKey::from_pkcs1_pem(pem))
+                .map_err(|e| invalid(e.to_string()))?;
+            let parameters = AlgorithmParameters::RSA(RSAKeyParameters {
+                key_type: RSAKeyType::RSA,
+                n: URL_SAFE_NO_PAD.encode(key.n().to_bytes_be()),
+                e: URL_SAFE_NO_PAD.encode(key.e().to_bytes_be()),
+            });
+            (KeyAlgorithm::RS256, parameters)
+        }
+        JwtAlgorithm::ES256 => {
+            let der = pem::parse(pem).map_err(|e| invalid(e.to_string()))?;
+            let point = der
+                .contents()
+                .strip_prefix(&P256_SPKI_PREFIX[..])
+                .filter(|point| point.len() == 64)
+                .ok_or_else(|| invalid("expected an uncompressed P-256 public key".to_string()))?;
+            let (x, y) = point.split_at(32);
+            let parameters = AlgorithmParameters::EllipticCurve(EllipticCurveKeyParameters {
+                key_type: EllipticCurveKeyType::EC,
+                curve: EllipticCurve::P256,
+                x: URL_SAFE_NO_PAD.encode(x),
+                y: URL_SAFE_NO_PAD.encode(y),
+            });
+            (KeyAlgorithm::ES256, parameters)
+        }
+        JwtAlgorithm::HS256 => return Err(invalid("HS256 has no public key".to_string())),
+    };
+
+    Ok(Jwk {
+        common: CommonParameters {
+            public_key_use: Some(PublicKeyUse::Signature),
+            key_algorithm: Some(key_algorithm),
+            key_id: Some(kid.to_string()),
+            ..CommonParameters::default()
+        },
+        algorithm: parameters,
+    })
+}
+
+impl TokenService for JwtTokenService {
+    fn issue(&self, claims: &Claims) -> AppResult<String> {
+        let header = Header {
+            kid: Some(self.key_id.clone()),
+            ..Header::new(self.algorithm)
+        };
+        encode(&header, claims, &self.encoding_key)
+            .map_err(|e| AppError::InternalError(format!("Failed to sign token: {}", e)))
+    }
+
+    fn verify(&self, token: &str) -> AppResult<Claims> {
+        let claims = decode::<Claims>(token, self.decoding_key(token)?, &self.validation)
+            .map(|data| data.claims)
+            .map_err(|e| AppError::Unauthorized(format!("Invalid token: {}", e)))?;
+
+        // jsonwebtoken leaves `iat` unchecked; apply the same leeway to it
+        let latest_iat = get_current_timestamp().saturating_add(self.validation.leeway);
+        if claims.iat > i64::try_from(latest_iat).unwrap_or(i64::MAX) {
+            return Err(AppError::Unauthorized(
+                "Invalid token: issued in the future".to_string(),
+            ));
+        }
+        Ok(claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use application::Role;
+    use shared::UserId;
+    use shared::config::JwtVerificationKey;
+
+    fn service(leeway_seconds: u64) -> JwtTokenService {
+        JwtTokenService::new(&JwtConfig {
+            leeway_seconds,
+            ..JwtConfig::default()
+        })
+        .unwrap()
+    }
+
+    /// Claims issued an hour ago that expire `exp_offset` seconds from now
+    fn claims(exp_offset: i64) -> Claims {
+        let now = get_current_timestamp() as i64;
+        Claims {
+            sub: UserId::new(),
+            role: Role::User,
+            iss: JwtConfig::default().issuer,
+            iat: now - 3600,
+            exp: now + exp_offset,
+            act: None,
+            epoch: 0,
+        }
+    }
+
+    #[test]
+    fn test_token_expired_within_leeway_still_validates() {
+        let service = service(30);
+        let token = service.issue(&claims(-10)).unwrap();
+        assert!(service.verify(&token).is_ok());
+    }
+
+    #[test]
+    fn test_token_expired_beyond_leeway_is_rejected() {
+        let service = service(30);
+        let token = service.issue(&claims(-60)).unwrap();
+        assert!(matches!(
+            service.verify(&token),
+            Err(AppError::Unauthorized(_))
+        ));
+    }
+
+    /// Service signing with `kid`/`secret` that still accepts `previous`
+    fn rotated(kid: &str, secret: &str, previous: &[(&str, &str)]) -> JwtTokenService {
+        JwtTokenService::new(&JwtConfig {
+            key_id: kid.to_string(),
+            secret: secret.to_string(),
+            previous_keys: previous
+                .iter()
+                .map(|(kid, secret)| JwtVerificationKey {
+                    kid: kid.to_string(),
+                    secret: secret.to_string(),
+                    public_key_path: None,
+                })
+                .collect(),
+            ..JwtConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rotation_keeps_tokens_from_retired_keys_valid() {
+        let before = rotated("2025-01", "old-secret", &[]);
+        let old_token = before.issue(&claims(900)).unwrap();
+
+        let after = rotated("2025-06", "new-secret", &[("2025-01", "old-secret")]);
+        assert!(after.verify(&old_token).is_ok());
+
+        let new_token = after.issue(&claims(900)).unwrap();
+        assert_eq!(
+            decode_header(&new_token).unwrap().kid.as_deref(),
+            Some("2025-06")
+        );
+        assert!(after.verify(&new_token).is_ok());
+        // Services not yet rotated reject the new key
+        assert!(before.verify(&new_token).is_err());
+
+        // Once the retired key is dropped its tokens stop verifying
+        let retired = rotated("2025-06", "new-secret", &[]);
+        assert!(matches!(
+            retired.verify(&old_token),
+            Err(AppError::Unauthorized(msg)) if msg.contains("unknown signing key")
+        ));
+    }
+
+    #[test]
+    fn test_token_without_kid_is_checked_against_current_key() {
+        let current = JwtConfig::default();
+        let legacy = encode(
+            &Header::new(Algorithm::HS256),
+            &claims(900),
+            &EncodingKey::from_secret(current.secret.as_bytes()),
+        )
+        .unwrap();
+
+        assert!(service(30).verify(&legacy).is_ok());
+    }
+
+    #[test]
+    fn test_future_iat_is_checked_against_leeway() {
+        let service = service(30);
+        let now = get_current_timestamp() as i64;
+
+        let skewed = Claims {
+            iat: now + 10,
+            ..claims(900)
+        };
+        let token = service.issue(&skewed).unwrap();
+        assert!(service.verify(&token).is_ok());
+
+        let future = Claims {
+            iat: now + 120,
+            ..claims(900)
+        };
+        let token = service.issue(&future).unwrap();
+        assert!(service.verify(&token).is_err());
+    }
+
+    fn testdata(file: &str) -> String {
+        format!("{}/src/auth/testdata/{}", env!("CARGO_MANIFEST_DIR"), file)
+    }
+
+    fn asymmetric(algorithm: JwtAlgorithm, name: &str) -> JwtConfig {
+        JwtConfig {
+            algorithm,
+            private_key_path: Some(testdata(&format!("{}_private.pem", name))),
+            public_key_path: Some(testdata(&format!("{}_public.pem", name))),
+            key_id: format!("{}-1", name),
+            ..JwtConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_rs256_token_round_trips_and_verifies_from_jwks() {
+        let rsa = JwtTokenService::new(&asymmetric(JwtAlgorithm::RS256, "rs256")).unwrap();
+        let token = rsa.issue(&claims(900)).unwrap();
+
+        let header = decode_header(&token).unwrap();
+        assert_eq!(header.alg, Algorithm::RS256);
+        assert_eq!(header.kid.as_deref(), Some("rs256-1"));
+        assert!(rsa.verify(&token).is_ok());
+
+        // A relying party holding only the published JWKS can verify it
+        let jwks = rsa.jwks();
+        let jwk = jwks.find("rs256-1").unwrap();
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.set_issuer(&[JwtConfig::default().issuer]);
+        let key = DecodingKey::from_jwk(jwk).unwrap();
+        assert!(decode::<Claims>(&token, &key, &validation).is_ok());
+
+        // HMAC tokens are not accepted in place of RSA signatures
+        assert!(service(30).verify(&token).is_err());
+        let forged = service(30).issue(&claims(900)).unwrap();
+        assert!(rsa.verify(&forged).is_err());
+    }
+
+    #[test]
+    fn test_es256_token_round_trips() {
+        let service = JwtTokenService::new(&asymmetric(JwtAlgorithm::ES256, "es256")).unwrap();
+        let token = service.issue(&claims(900)).unwrap();
+
+        assert!(service.verify(&token).is_ok());
+        let jwk = &service.jwks().keys[0];
+        assert!(matches!(
+            &jwk.algorithm,
+            AlgorithmParameters::EllipticCurve(ec) if ec.curve == EllipticCurve::P256
+        ));
+    }
+
+    #[test]
+    fn test_asymmetric_key_errors_are_configuration_errors() {
+        let missing = JwtConfig {
+            private_key_path: None,
+            ..asymmetric(JwtAlgorithm::RS256, "rs256")
+        };
+        assert!(matches!(
+            JwtTokenService::new(&missing),
+            Err(AppError::ConfigurationError(_))
+        ));
+
+        // An EC key configured for RS256
+        let mismatched = asymmetric(JwtAlgorithm::RS256, "es256");
+        assert!(matches!(
+            JwtTokenService::new(&mismatched),
+            Err(AppError::ConfigurationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_empty_hs256_secrets_are_configuration_errors() {
+        let empty = JwtConfig {
+            secret: String::new(),
+            ..JwtConfig::default()
+        };
+        assert!(matches!(
+            JwtTokenService::new(&empty),
+            Err(AppError::ConfigurationError(_))
+        ));
+
+        let empty_previous = JwtConfig {
+            previous_keys: vec![JwtVerificationKey {
+                kid: "2025-01".to_string(),
+                secret: String::new(),
+                public_key_path: None,
+            }],
+            ..JwtConfig::default()
+        };
+        assert!(matches!(
+            JwtTokenService::new(&empty_previous),
+            Err(AppError::ConfigurationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_hs256_publishes_no_keys() {
+        assert!(service(30).jwks().keys.is_empty());
+    }
+}
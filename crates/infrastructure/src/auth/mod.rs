@@ -0,0 +1,6 @@
+pub mod argon2;
+pub mod jwt;
+
+pub use argon2::Argon2PasswordHasher;
+pub use jsonwebtoken::jwk::JwkSet;
+pub use jwt::JwtTokenService;
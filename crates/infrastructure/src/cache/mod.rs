@@ -1,4 +1,7 @@
 pub mod redis;
+pub mod redis_cache_store;
+
+pub use redis_cache_store::RedisCacheStore;
 
 pub enum CachePoolType {
     Redis,
@@ -1,10 +1,31 @@
-use deadpool_redis::{Config, CreatePoolError, Pool, Runtime};
+use deadpool_redis::{Config, CreatePoolError, Pool, Runtime, redis};
+use shared::AppResult;
 use shared::config::cache::CacheConfig;
 
+/// Create the Redis pool; `rediss://` URLs connect over TLS (rustls)
 pub async fn create_redis_pool(config: CacheConfig) -> Result<Pool, CreatePoolError> {
+    if config.uses_tls() {
+        tracing::info!("Redis connections use TLS");
+    }
     let cfg = Config::from_url(config.url.clone());
 
     let pool = cfg.create_pool(Some(Runtime::Tokio1))?;
 
     Ok(pool)
 }
+
+/// Prime the pool by opening and pinging up to `pool_size` connections
+///
+/// Connections are held at the same time so each ping goes over a distinct
+/// connection, which then stays in the pool for later requests.
+pub async fn warm_up_redis_pool(pool: &Pool, pool_size: usize) -> AppResult<()> {
+    let target = pool_size.min(pool.status().max_size);
+    let mut held = Vec::with_capacity(target);
+    for _ in 0..target {
+        let mut conn = pool.get().await?;
+        redis::cmd("PING").query_async::<()>(&mut conn).await?;
+        held.push(conn);
+    }
+    tracing::info!(connections = target, "Redis pool warmed up");
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use deadpool_redis::{Pool, redis};
+use std::time::Duration;
+
+use application::ports::CacheStore;
+use shared::AppResult;
+
+/// Redis-backed implementation of the `CacheStore` port
+#[derive(Clone)]
+pub struct RedisCacheStore {
+    pool: Pool,
+}
+
+impl RedisCacheStore {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CacheStore for RedisCacheStore {
+    async fn get(&self, key: &str) -> AppResult<Option<Vec<u8>>> {
+        let mut conn = self.pool.get().await?;
+        let value: Option<Vec<u8>> = redis::cmd("GET").arg(key).query_async(&mut conn).await?;
+        Ok(value)
+    }
+
+    async fn set(&self, key: &str, value: &[u8], ttl: Duration) -> AppResult<()> {
+        let mut conn = self.pool.get().await?;
+        redis::cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("EX")
+            .arg(ttl.as_secs().max(1))
+            .query_async::<()>(&mut conn)
+            .await?;
+        Ok(())
+    }
+
+    async fn incr(&self, key: &str) -> AppResult<i64> {
+        let mut conn = self.pool.get().await?;
+        let value: i64 = redis::cmd("INCR").arg(key).query_async(&mut conn).await?;
+        Ok(value)
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        let mut conn = self.pool.get().await?;
+        redis::cmd("DEL")
+            .arg(key)
+            .query_async::<()>(&mut conn)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
 pub mod postgres;
+pub mod retry;
+pub(crate) mod timeout;
+pub mod transaction;
 
 pub enum DbPoolType {
     Postgres,
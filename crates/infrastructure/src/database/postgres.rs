@@ -1,27 +1,12 @@
 use core::time;
 
 use shared::config::database::DatabaseConfig;
-use sqlx::{PgPool, postgres::PgPoolOptions};
+use shared::{AppEnv, AppError, AppResult};
+use sqlx::migrate::MigrateDatabase;
+use sqlx::{PgPool, Postgres, postgres::PgPoolOptions};
 
 pub async fn create_postgres_pool(config: DatabaseConfig) -> Result<PgPool, sqlx::Error> {
-    let pool = PgPoolOptions::new()
-        .min_connections(config.min_connections)
-        .max_connections(config.max_connections)
-        .acquire_timeout(time::Duration::from_secs(3))
-        .max_lifetime(time::Duration::from_secs(config.max_lifetime_seconds))
-        .idle_timeout(time::Duration::from_secs(config.idle_timeout_seconds))
-        .after_connect(|conn, _meta| {
-            Box::pin(async move {
-                // PostgreSQL doesn't support parameterized SET commands
-                // Use format! to create the query string
-                let app_name = env!("CARGO_PKG_NAME");
-                let query = format!("SET application_name = '{}'", app_name);
-                sqlx::query(&query)
-                    .execute(&mut *conn)
-                    .await?;
-                Ok(())
-            })
-        })
+    let pool = pool_options(&config)
         .connect(&config.connection_string)
         .await;
 
@@ -40,14 +25,242 @@ pub async fn create_postgres_pool(config: DatabaseConfig) -> Result<PgPool, sqlx
         }
     };
 
-    if config.run_migrations {
-        tracing::info!("Running database migrations...");
-        if let Err(e) = sqlx::migrate!("./migrations").run(&pool).await {
-            tracing::error!("Migration error: {}", e);
-            return Err(e.into());
+    Ok(pool)
+}
+
+/// Create the database named in `connection_string` when it does not exist
+///
+/// Only honoured when `create_database_if_missing` is set and `env` is dev;
+/// enabling it anywhere else is refused so a typo in a production
+/// connection string cannot silently create an empty database. Returns
+/// whether the database was created.
+pub async fn create_database_if_missing(config: &DatabaseConfig, env: AppEnv) -> AppResult<bool> {
+    if !config.create_database_if_missing {
+        return Ok(false);
+    }
+    if !env.is_dev() {
+        return Err(AppError::ConfigurationError(format!(
+            "database.create_database_if_missing is only allowed in dev, not {}",
+            env
+        )));
+    }
+    if Postgres::database_exists(&config.connection_string).await? {
+        return Ok(false);
+    }
+    Postgres::create_database(&config.connection_string).await?;
+    tracing::info!("Created missing PostgreSQL database");
+    Ok(true)
+}
+
+/// Create the read-replica pool when `replica_connection_string` is set
+///
+/// The replica shares the primary's pool settings.
+pub async fn create_replica_pool(config: &DatabaseConfig) -> Result<Option<PgPool>, sqlx::Error> {
+    let Some(url) = &config.replica_connection_string else {
+        return Ok(None);
+    };
+    let pool = pool_options(config).connect(url).await.inspect_err(|e| {
+        tracing::error!("Failed to connect to PostgreSQL read replica: {}", e);
+    })?;
+    tracing::info!("PostgreSQL read replica pool established.");
+    Ok(Some(pool))
+}
+
+/// Create a pool that opens its first connection on demand
+///
+/// Used for secondary named databases so an unavailable one does not block
+/// startup; only a malformed connection string fails here.
+pub fn create_lazy_postgres_pool(config: &DatabaseConfig) -> Result<PgPool, sqlx::Error> {
+    pool_options(config).connect_lazy(&config.connection_string)
+}
+
+fn pool_options(config: &DatabaseConfig) -> PgPoolOptions {
+    PgPoolOptions::new()
+        .min_connections(config.min_connections)
+        .max_connections(config.max_connections)
+        .acquire_timeout(time::Duration::from_secs(3))
+        .max_lifetime(time::Duration::from_secs(config.max_lifetime_seconds))
+        .idle_timeout(time::Duration::from_secs(config.idle_timeout_seconds))
+        .after_connect(|conn, _meta| {
+            Box::pin(async move {
+                // PostgreSQL doesn't support parameterized SET commands
+                // Use format! to create the query string
+                let app_name = env!("CARGO_PKG_NAME");
+                let query = format!("SET application_name = '{}'", app_name);
+                sqlx::query(&query).execute(&mut *conn).await?;
+                Ok(())
+            })
+        })
+}
+
+/// Prime the pool by opening `min_connections` connections up front
+///
+/// The connections are held at the same time so each one is a distinct
+/// physical connection, then handed back to the pool as idle.
+pub async fn warm_up_postgres_pool(pool: &PgPool, min_connections: u32) -> Result<(), sqlx::Error> {
+    let target = min_connections.min(pool.options().get_max_connections());
+    let mut held = Vec::with_capacity(target as usize);
+    for _ in 0..target {
+        held.push(pool.acquire().await?);
+    }
+    for mut conn in held {
+        conn.return_to_pool().await;
+    }
+    tracing::info!(connections = target, "PostgreSQL pool warmed up");
+    Ok(())
+}
+
+/// Run the embedded migrations against the given pool.
+///
+/// Kept separate from pool creation so callers can gate readiness on completion.
+pub async fn run_migrations(pool: &PgPool) -> Result<(), sqlx::migrate::MigrateError> {
+    tracing::info!("Running database migrations...");
+    if let Err(e) = sqlx::migrate!("./migrations").run(pool).await {
+        tracing::error!("Migration error: {}", e);
+        return Err(e);
+    }
+    tracing::info!("Database migrations complete.");
+    Ok(())
+}
+
+/// Fail when an embedded migration has not been applied to the database
+///
+/// Checked at startup when `run_migrations` is off, so a schema older than
+/// the binary is reported up front instead of through failing queries.
+pub async fn ensure_migrations_applied(pool: &PgPool) -> AppResult<()> {
+    let embedded: Vec<(i64, String)> = sqlx::migrate!("./migrations")
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .map(|migration| (migration.version, migration.description.to_string()))
+        .collect();
+    check_migrations_applied(pool, &embedded).await
+}
+
+async fn check_migrations_applied(pool: &PgPool, expected: &[(i64, String)]) -> AppResult<()> {
+    let applied: Vec<i64> =
+        match sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success")
+            .fetch_all(pool)
+            .await
+        {
+            Ok(applied) => applied,
+            // No migration has ever run against this database
+            Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some("42P01") => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+    let pending: Vec<String> = expected
+        .iter()
+        .filter(|(version, _)| !applied.contains(version))
+        .map(|(version, description)| format!("{} ({})", version, description))
+        .collect();
+    if pending.is_empty() {
+        return Ok(());
+    }
+    Err(AppError::ConfigurationError(format!(
+        "Database schema is behind this build; pending migrations: {}. \
+         Apply them or enable database.run_migrations",
+        pending.join(", ")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_create_database_is_refused_outside_dev() {
+        // Unreachable: reaching the server would fail differently
+        let config = DatabaseConfig {
+            connection_string: "postgres://postgres@127.0.0.1:1/missing".to_string(),
+            create_database_if_missing: true,
+            ..DatabaseConfig::default()
+        };
+
+        for env in [AppEnv::Test, AppEnv::Staging, AppEnv::Prod] {
+            assert!(matches!(
+                create_database_if_missing(&config, env).await,
+                Err(AppError::ConfigurationError(_))
+            ));
         }
-        tracing::info!("Database migrations complete.");
+
+        let disabled = DatabaseConfig {
+            create_database_if_missing: false,
+            ..config
+        };
+        assert!(
+            !create_database_if_missing(&disabled, AppEnv::Prod)
+                .await
+                .unwrap()
+        );
     }
 
-    Ok(pool)
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_missing_database_is_created_in_dev() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let (server, _) = url.rsplit_once('/').unwrap();
+        let name = format!("create_if_missing_{}", std::process::id());
+        let config = DatabaseConfig {
+            connection_string: format!("{}/{}", server, name),
+            create_database_if_missing: true,
+            ..DatabaseConfig::default()
+        };
+
+        assert!(
+            create_database_if_missing(&config, AppEnv::Dev)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !create_database_if_missing(&config, AppEnv::Dev)
+                .await
+                .unwrap()
+        );
+        Postgres::drop_database(&config.connection_string)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_warmup_leaves_min_connections_idle() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect_lazy(&url)
+            .unwrap();
+        assert_eq!(pool.num_idle(), 0);
+
+        warm_up_postgres_pool(&pool, 3).await.unwrap();
+
+        assert_eq!(pool.size(), 3);
+        assert_eq!(pool.num_idle(), 3);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_startup_check_reports_pending_migrations() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+
+        ensure_migrations_applied(&pool).await.unwrap();
+
+        let mut expected: Vec<(i64, String)> = sqlx::migrate!("./migrations")
+            .iter()
+            .map(|migration| (migration.version, migration.description.to_string()))
+            .collect();
+        expected.push((29991231000000, "add future column".to_string()));
+        match check_migrations_applied(&pool, &expected).await {
+            Err(AppError::ConfigurationError(msg)) => {
+                assert!(
+                    msg.contains("29991231000000 (add future column)"),
+                    "{}",
+                    msg
+                );
+                assert!(!msg.contains("create users table"), "{}", msg);
+            }
+            other => panic!("expected a configuration error, got {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,234 @@
+use std::time::Duration;
+
+use shared::config::database::DatabaseConfig;
+use shared::defaults::database;
+use sqlx::{PgPool, Postgres, pool::PoolConnection};
+
+/// SQLSTATE codes for failures that a fresh attempt can succeed past
+const RETRYABLE_SQLSTATES: &[&str] = &[
+    "40001", // serialization_failure
+    "40P01", // deadlock_detected
+    "53300", // too_many_connections
+    "57P01", // admin_shutdown
+    "57P03", // cannot_connect_now
+];
+
+/// Whether `err` is transient, so repeating the operation may succeed
+///
+/// Connection-level failures (I/O, pool timeouts, SQLSTATE class 08) and
+/// serialization/deadlock aborts are retryable. Constraint violations,
+/// missing rows, decoding errors and a closed pool are permanent.
+pub fn is_retryable(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut => true,
+        sqlx::Error::Database(db) => db
+            .code()
+            .is_some_and(|code| code.starts_with("08") || RETRYABLE_SQLSTATES.contains(&&*code)),
+        _ => false,
+    }
+}
+
+/// Retry policy for acquiring a connection from the pool.
+///
+/// Errors classified by `is_retryable` are retried; every other error is
+/// returned on the first attempt.
+#[derive(Debug, Clone, Copy)]
+pub struct AcquireRetry {
+    retries: u32,
+    backoff: Duration,
+}
+
+impl AcquireRetry {
+    pub fn new(retries: u32, backoff: Duration) -> Self {
+        Self { retries, backoff }
+    }
+
+    /// Never retry; the first acquire error is returned as-is
+    pub fn disabled() -> Self {
+        Self::new(0, Duration::ZERO)
+    }
+
+    pub fn from_config(config: &DatabaseConfig) -> Self {
+        Self::new(
+            config.acquire_retries,
+            Duration::from_millis(config.acquire_retry_backoff_ms),
+        )
+    }
+
+    /// Acquire a connection, retrying timed-out attempts with doubling backoff
+    pub async fn acquire(&self, pool: &PgPool) -> Result<PoolConnection<Postgres>, sqlx::Error> {
+        let mut attempt = 0;
+        loop {
+            match pool.acquire().await {
+                Err(e) if is_retryable(&e) && attempt < self.retries => {
+                    let delay = self.backoff.saturating_mul(1 << attempt.min(16));
+                    attempt += 1;
+                    tracing::warn!(
+                        attempt,
+                        max_retries = self.retries,
+                        delay_ms = delay.as_millis() as u64,
+                        "Transient error acquiring a database connection, retrying: {}",
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Default for AcquireRetry {
+    fn default() -> Self {
+        Self::new(
+            database::DEFAULT_DATABASE_ACQUIRE_RETRIES,
+            Duration::from_millis(database::DEFAULT_DATABASE_ACQUIRE_RETRY_BACKOFF_MS),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::error::{DatabaseError, ErrorKind};
+    use sqlx::postgres::PgPoolOptions;
+    use std::borrow::Cow;
+    use std::fmt;
+
+    /// Stand-in for a Postgres error carrying `code`
+    #[derive(Debug)]
+    struct PgError {
+        code: &'static str,
+    }
+
+    impl fmt::Display for PgError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "database error {}", self.code)
+        }
+    }
+
+    impl std::error::Error for PgError {}
+
+    impl DatabaseError for PgError {
+        fn message(&self) -> &str {
+            "database error"
+        }
+
+        fn code(&self) -> Option<Cow<'_, str>> {
+            Some(Cow::Borrowed(self.code))
+        }
+
+        fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+            self
+        }
+
+        fn kind(&self) -> ErrorKind {
+            match self.code {
+                "23505" => ErrorKind::UniqueViolation,
+                "23503" => ErrorKind::ForeignKeyViolation,
+                "23514" => ErrorKind::CheckViolation,
+                _ => ErrorKind::Other,
+            }
+        }
+    }
+
+    fn database(code: &'static str) -> sqlx::Error {
+        sqlx::Error::Database(Box::new(PgError { code }))
+    }
+
+    #[test]
+    fn test_transient_errors_are_retryable() {
+        let reset = std::io::Error::from(std::io::ErrorKind::ConnectionReset);
+        for err in [
+            sqlx::Error::Io(reset),
+            sqlx::Error::PoolTimedOut,
+            database("08006"), // connection_failure
+            database("40001"),
+            database("40P01"),
+            database("57P01"),
+        ] {
+            assert!(is_retryable(&err), "{:?} should be retryable", err);
+        }
+    }
+
+    #[test]
+    fn test_permanent_errors_are_not_retryable() {
+        for err in [
+            sqlx::Error::RowNotFound,
+            sqlx::Error::PoolClosed,
+            sqlx::Error::ColumnNotFound("email".to_string()),
+            database("23505"),
+            database("23503"),
+            database("23514"),
+            database("42P01"), // undefined_table
+        ] {
+            assert!(!is_retryable(&err), "{:?} should not be retryable", err);
+        }
+    }
+
+    async fn single_connection_pool() -> PgPool {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        PgPoolOptions::new()
+            .max_connections(1)
+            .acquire_timeout(Duration::from_millis(200))
+            .connect(&url)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_retried_acquire_succeeds_once_connection_is_released() {
+        let pool = single_connection_pool().await;
+        let held = pool.acquire().await.unwrap();
+
+        // Released after the first attempt has already timed out
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            drop(held);
+        });
+
+        let mut conn = AcquireRetry::new(2, Duration::from_millis(50))
+            .acquire(&pool)
+            .await
+            .expect("retried acquire should succeed");
+        let one: i32 = sqlx::query_scalar("SELECT 1")
+            .fetch_one(&mut *conn)
+            .await
+            .unwrap();
+        assert_eq!(one, 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_disabled_retry_surfaces_pool_timeout() {
+        let pool = single_connection_pool().await;
+        let _held = pool.acquire().await.unwrap();
+
+        let err = AcquireRetry::disabled().acquire(&pool).await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::PoolTimedOut));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_non_transient_errors_are_not_retried() {
+        let pool = single_connection_pool().await;
+        pool.close().await;
+
+        let started = std::time::Instant::now();
+        let err = AcquireRetry::new(3, Duration::from_secs(1))
+            .acquire(&pool)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, sqlx::Error::PoolClosed));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use shared::{AppError, AppResult};
+
+/// Bound a sqlx query future by the per-query timeout
+///
+/// On expiry the query future is dropped, which hands its connection back to
+/// sqlx: a pooled connection is checked before it is reused (waiting out the
+/// abandoned statement) and a request transaction rolls back as usual, so
+/// nothing is leaked.
+pub(crate) trait QueryTimeout<T>: Future<Output = Result<T, sqlx::Error>> + Sized {
+    /// Await the query, failing with `DatabaseError("query timed out")`
+    /// once `timeout` elapses; `None` waits indefinitely
+    async fn with_timeout(self, timeout: Option<Duration>) -> AppResult<T> {
+        let Some(timeout) = timeout else {
+            return Ok(self.await?);
+        };
+        match tokio::time::timeout(timeout, self).await {
+            Ok(result) => Ok(result?),
+            Err(_) => {
+                tracing::warn!(
+                    timeout_ms = timeout.as_millis() as u64,
+                    "Database query timed out"
+                );
+                Err(AppError::DatabaseError("query timed out".to_string()))
+            }
+        }
+    }
+}
+
+impl<T, F> QueryTimeout<T> for F where F: Future<Output = Result<T, sqlx::Error>> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_expired_query_maps_to_database_error() {
+        let query = std::future::pending::<Result<(), sqlx::Error>>();
+
+        let result = query.with_timeout(Some(Duration::from_millis(10))).await;
+
+        assert!(matches!(result, Err(AppError::DatabaseError(msg)) if msg == "query timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_query_errors_pass_through() {
+        let query = async { Err::<(), _>(sqlx::Error::RowNotFound) };
+
+        let result = query.with_timeout(Some(Duration::from_secs(1))).await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+}
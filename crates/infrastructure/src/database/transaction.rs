@@ -0,0 +1,199 @@
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use application::AfterCommit;
+use sqlx::{PgConnection, PgPool, Postgres, Transaction, pool::PoolConnection};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+tokio::task_local! {
+    static CURRENT: RequestTransaction;
+}
+
+type Slot = Option<Transaction<'static, Postgres>>;
+
+/// A transaction shared by every repository call made within its `scope`
+///
+/// Repositories pick it up through `DbConnection::acquire`, so use cases run
+/// unchanged inside or outside a request transaction. Work spawned onto other
+/// tasks does not inherit it. Effects the use cases defer with `AfterCommit`
+/// run only once `commit` succeeds.
+#[derive(Clone)]
+pub struct RequestTransaction {
+    slot: Arc<Mutex<Slot>>,
+    after_commit: AfterCommit,
+}
+
+impl RequestTransaction {
+    pub async fn begin(pool: &PgPool) -> Result<Self, sqlx::Error> {
+        let tx = pool.begin().await?;
+        Ok(Self {
+            slot: Arc::new(Mutex::new(Some(tx))),
+            after_commit: AfterCommit::new(),
+        })
+    }
+
+    /// Run `future` with this transaction as the one repositories use
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        CURRENT
+            .scope(self.clone(), self.after_commit.scope(future))
+            .await
+    }
+
+    /// The transaction in scope for the current task, if any
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(Clone::clone).ok()
+    }
+
+    /// Commit, then run the deferred effects; a no-op once the transaction
+    /// has finished. Effects are dropped if the commit fails.
+    pub async fn commit(&self) -> Result<(), sqlx::Error> {
+        let Some(tx) = self.slot.lock().await.take() else {
+            return Ok(());
+        };
+        if let Err(e) = tx.commit().await {
+            self.after_commit.discard();
+            return Err(e);
+        }
+        self.after_commit.run().await;
+        Ok(())
+    }
+
+    /// Roll back, dropping the deferred effects; a no-op once the
+    /// transaction has finished
+    pub async fn rollback(&self) -> Result<(), sqlx::Error> {
+        self.after_commit.discard();
+        match self.slot.lock().await.take() {
+            Some(tx) => tx.rollback().await,
+            None => Ok(()),
+        }
+    }
+
+    /// Borrow the transaction's connection until the guard is dropped
+    async fn connection(&self) -> Result<TransactionConnection, sqlx::Error> {
+        let guard = self.slot.clone().lock_owned().await;
+        if guard.is_none() {
+            return Err(sqlx::Error::Protocol(
+                "request transaction already finished".to_string(),
+            ));
+        }
+        Ok(TransactionConnection(guard))
+    }
+}
+
+/// Exclusive access to a live `RequestTransaction`
+pub struct TransactionConnection(OwnedMutexGuard<Slot>);
+
+impl Deref for TransactionConnection {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &PgConnection {
+        self.0.as_ref().expect("checked when locked")
+    }
+}
+
+impl DerefMut for TransactionConnection {
+    fn deref_mut(&mut self) -> &mut PgConnection {
+        self.0.as_mut().expect("checked when locked")
+    }
+}
+
+/// Connection for one repository call
+pub enum DbConnection {
+    Pooled(PoolConnection<Postgres>),
+    Transaction(TransactionConnection),
+}
+
+impl DbConnection {
+    /// The request transaction's connection when one is in scope, otherwise
+    /// `pooled` is awaited for a connection of its own
+    pub async fn acquire<F>(pooled: F) -> Result<Self, sqlx::Error>
+    where
+        F: Future<Output = Result<PoolConnection<Postgres>, sqlx::Error>>,
+    {
+        match RequestTransaction::current() {
+            Some(tx) => Ok(DbConnection::Transaction(tx.connection().await?)),
+            None => Ok(DbConnection::Pooled(pooled.await?)),
+        }
+    }
+}
+
+impl Deref for DbConnection {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &PgConnection {
+        match self {
+            DbConnection::Pooled(conn) => conn,
+            DbConnection::Transaction(conn) => conn,
+        }
+    }
+}
+
+impl DerefMut for DbConnection {
+    fn deref_mut(&mut self) -> &mut PgConnection {
+        match self {
+            DbConnection::Pooled(conn) => conn,
+            DbConnection::Transaction(conn) => conn,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn test_pool() -> PgPool {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        PgPool::connect(&url).await.unwrap()
+    }
+
+    async fn txid(pool: &PgPool) -> i64 {
+        let mut conn = DbConnection::acquire(pool.acquire()).await.unwrap();
+        sqlx::query_scalar("SELECT txid_current()")
+            .fetch_one(&mut *conn)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_calls_in_scope_share_the_transaction() {
+        let pool = test_pool().await;
+        let tx = RequestTransaction::begin(&pool).await.unwrap();
+
+        let (first, second) = tx
+            .scope(async { (txid(&pool).await, txid(&pool).await) })
+            .await;
+        assert_eq!(first, second);
+        assert_ne!(txid(&pool).await, first);
+
+        tx.rollback().await.unwrap();
+        assert!(tx.connection().await.is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_deferred_effects_run_only_after_commit() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let pool = test_pool().await;
+        let ran = Arc::new(AtomicUsize::new(0));
+        let defer = |ran: &Arc<AtomicUsize>| {
+            let ran = ran.clone();
+            AfterCommit::defer(async move {
+                ran.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+
+        let rolled_back = RequestTransaction::begin(&pool).await.unwrap();
+        rolled_back.scope(defer(&ran)).await;
+        rolled_back.rollback().await.unwrap();
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+
+        let committed = RequestTransaction::begin(&pool).await.unwrap();
+        committed.scope(defer(&ran)).await;
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+        committed.commit().await.unwrap();
+        assert_eq!(ran.load(Ordering::SeqCst), 1);
+    }
+}
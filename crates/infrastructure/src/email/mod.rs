@@ -0,0 +1,3 @@
+pub mod smtp;
+
+pub use smtp::SmtpEmailSender;
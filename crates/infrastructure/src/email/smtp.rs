@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::time::Duration;
+
+use application::ports::{EmailMessage, EmailSender};
+use shared::config::EmailConfig;
+use shared::{AppError, AppResult};
+
+/// SMTP implementation of the `EmailSender` port
+#[derive(Clone)]
+pub struct SmtpEmailSender {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpEmailSender {
+    /// Build the transport from configuration; no connection is opened yet
+    pub fn new(config: &EmailConfig) -> AppResult<Self> {
+        let builder = if config.use_tls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host)
+                .map_err(email_error)?
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.smtp_host)
+        };
+
+        let mut builder = builder
+            .port(config.smtp_port)
+            .timeout(Some(Duration::from_secs(config.timeout_seconds)));
+        if let (Some(username), Some(password)) = (&config.smtp_username, &config.smtp_password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        let from = config.from_address.parse::<Mailbox>().map_err(|e| {
+            AppError::ConfigurationError(format!("Invalid email from_address: {}", e))
+        })?;
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl EmailSender for SmtpEmailSender {
+    async fn send(&self, message: EmailMessage) -> AppResult<()> {
+        let to = message
+            .to
+            .parse::<Mailbox>()
+            .map_err(|e| AppError::InvalidEmail(e.to_string()))?;
+        let email = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(message.subject)
+            .body(message.body)
+            .map_err(email_error)?;
+
+        self.transport.send(email).await.map_err(email_error)?;
+        Ok(())
+    }
+
+    async fn verify_connection(&self) -> AppResult<()> {
+        // Opens a session, issues NOOP and closes it with QUIT
+        match self.transport.test_connection().await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(AppError::EmailError(
+                "SMTP server did not respond to NOOP".to_string(),
+            )),
+            Err(e) => Err(email_error(e)),
+        }
+    }
+}
+
+fn email_error(err: impl std::fmt::Display) -> AppError {
+    AppError::EmailError(err.to_string())
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+use application::ports::EventBus;
+use domain::UserEvent;
+
+/// In-process `EventBus` over a `tokio::sync::broadcast` channel
+///
+/// Every subscriber receives each event published after it subscribed; one
+/// that falls more than `capacity` events behind skips the oldest.
+#[derive(Clone)]
+pub struct InProcessEventBus {
+    sender: broadcast::Sender<UserEvent>,
+    closed: Arc<AtomicBool>,
+}
+
+/// How often `flush` re-checks for events still queued for subscribers
+const FLUSH_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+impl InProcessEventBus {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self {
+            sender,
+            closed: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<UserEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Events published but not yet received by every subscriber
+    pub fn pending(&self) -> usize {
+        self.sender.len()
+    }
+
+    /// Wait until every subscriber has received all published events
+    ///
+    /// Does not return while a subscriber stops reading; callers bound it
+    /// with a timeout.
+    pub async fn flush(&self) {
+        while self.pending() > 0 {
+            tokio::time::sleep(FLUSH_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Stop accepting events; later publishes are dropped
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+}
+
+impl EventBus for InProcessEventBus {
+    fn publish(&self, event: UserEvent) {
+        if self.is_closed() {
+            tracing::debug!("Event bus closed, dropping {}", event.event_type());
+            return;
+        }
+        let _ = self.sender.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::{Email, SystemClock, User, Username};
+
+    #[tokio::test]
+    async fn test_each_subscriber_receives_published_event() {
+        let bus = InProcessEventBus::new(16);
+        let mut first = bus.subscribe();
+        let mut second = bus.subscribe();
+        let user = User::new(
+            Username::new("bususer").unwrap(),
+            Email::new("bus@example.com").unwrap(),
+            &SystemClock,
+        );
+
+        bus.publish(UserEvent::created(&user));
+
+        for receiver in [&mut first, &mut second] {
+            let event = receiver.recv().await.unwrap();
+            assert_eq!(event.event_type(), "user.created");
+            assert_eq!(event.user_id(), user.id());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flush_waits_for_subscribers_and_close_drops_later_events() {
+        let bus = InProcessEventBus::new(16);
+        let mut receiver = bus.subscribe();
+        bus.publish(UserEvent::deleted(shared::UserId::new()));
+        assert_eq!(bus.pending(), 1);
+
+        let reader = tokio::spawn(async move {
+            receiver.recv().await.unwrap();
+            receiver
+        });
+        bus.flush().await;
+        assert_eq!(bus.pending(), 0);
+
+        bus.close();
+        bus.publish(UserEvent::deleted(shared::UserId::new()));
+        let mut receiver = reader.await.unwrap();
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_publish_without_subscribers_is_ignored() {
+        InProcessEventBus::new(1).publish(UserEvent::deleted(shared::UserId::new()));
+    }
+}
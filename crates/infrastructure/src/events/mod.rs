@@ -0,0 +1,3 @@
+pub mod in_process;
+
+pub use in_process::InProcessEventBus;
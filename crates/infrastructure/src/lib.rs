@@ -1,5 +1,8 @@
+pub mod auth;
 pub mod cache;
 pub mod database;
+pub mod email;
+pub mod events;
 pub mod repositories;
 
 pub use repositories::PostgresUserRepository;
@@ -1,3 +1,4 @@
 pub mod postgres_user_repository;
+mod where_builder;
 
 pub use postgres_user_repository::PostgresUserRepository;
@@ -1,18 +1,146 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use futures_util::{Stream, StreamExt};
+use sqlx::{Connection, PgPool, Postgres, pool::PoolConnection};
+use std::time::Duration;
 
-use domain::{Email, User, UserRepository, UserStatus, Username};
+use domain::{
+    Email, SortDirection, Url, User, UserFilter, UserRepository, UserSort, UserSortField,
+    UserStatus, Username,
+};
+use shared::config::EmailPolicy;
 use shared::{AppError, AppResult, UserId};
 
+use crate::database::retry::AcquireRetry;
+use crate::database::timeout::QueryTimeout;
+use crate::database::transaction::DbConnection;
+use crate::repositories::where_builder::WhereBuilder;
+
 /// PostgreSQL implementation of UserRepository
+///
+/// Lookups (`find_*`, `list`, `count`) go to the read replica when one is
+/// set; writes and the existence checks guarding them stay on the primary so
+/// replication lag cannot hide a row that was just written.
 pub struct PostgresUserRepository {
     pool: PgPool,
+    replica: Option<PgPool>,
+    acquire_retry: AcquireRetry,
+    query_timeout: Option<Duration>,
+    read_acquire_timeout: Option<Duration>,
+    write_acquire_timeout: Option<Duration>,
 }
 
 impl PostgresUserRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            replica: None,
+            acquire_retry: AcquireRetry::default(),
+            query_timeout: None,
+            read_acquire_timeout: None,
+            write_acquire_timeout: None,
+        }
+    }
+
+    /// Route lookups to `replica` and everything else to `primary`
+    pub fn new_with_replica(primary: PgPool, replica: PgPool) -> Self {
+        Self {
+            replica: Some(replica),
+            ..Self::new(primary)
+        }
+    }
+
+    /// Override the retry policy used when acquiring pooled connections
+    pub fn with_acquire_retry(mut self, acquire_retry: AcquireRetry) -> Self {
+        self.acquire_retry = acquire_retry;
+        self
+    }
+
+    /// Fail each query with `DatabaseError("query timed out")` once it runs
+    /// longer than `timeout`; zero leaves queries unbounded
+    pub fn with_query_timeout(mut self, timeout: Duration) -> Self {
+        self.query_timeout = (!timeout.is_zero()).then_some(timeout);
+        self
+    }
+
+    /// Bound the wait for a connection separately for lookups (`read`) and
+    /// for writes and their existence checks (`write`), retries included
+    ///
+    /// Expiry fails the call with `DatabaseError` naming the class that
+    /// timed out. Zero leaves that class to the pool's acquire timeout.
+    pub fn with_acquire_timeouts(mut self, read: Duration, write: Duration) -> Self {
+        self.read_acquire_timeout = (!read.is_zero()).then_some(read);
+        self.write_acquire_timeout = (!write.is_zero()).then_some(write);
+        self
+    }
+
+    /// Connection on the primary, or the request transaction in scope
+    async fn acquire(&self) -> AppResult<DbConnection> {
+        acquire_within(
+            self.acquire_retry.acquire(&self.pool),
+            self.write_acquire_timeout,
+            "write",
+        )
+        .await
+    }
+
+    /// Connection for a lookup: the request transaction in scope so it sees
+    /// its own writes, else the replica if configured, else the primary
+    async fn acquire_read(&self) -> AppResult<DbConnection> {
+        let pool = self.replica.as_ref().unwrap_or(&self.pool);
+        acquire_within(
+            self.acquire_retry.acquire(pool),
+            self.read_acquire_timeout,
+            "read",
+        )
+        .await
+    }
+
+    /// Stream every live user, oldest first, without loading them all
+    ///
+    /// Rows are fetched lazily over a single connection (from the replica if
+    /// configured) held until the stream is dropped. A row that fails to map
+    /// yields an `Err` item and the stream carries on with the next one. Meant
+    /// for exports and backfills, so the per-query timeout does not apply and
+    /// a request transaction in scope is not joined.
+    pub fn stream_all(&self) -> impl Stream<Item = AppResult<User>> + '_ {
+        let pool = self.replica.as_ref().unwrap_or(&self.pool);
+        sqlx::query_as::<_, UserRow>(
+            r#"
+            SELECT id, username, email, full_name, avatar_url, status, created_at, updated_at
+            FROM users
+            WHERE deleted_at IS NULL
+            ORDER BY created_at, id
+            "#,
+        )
+        .fetch(pool)
+        .map(|row| row.map_err(AppError::from).and_then(User::try_from))
+    }
+}
+
+/// Await a connection, failing with `DatabaseError` once `timeout` elapses
+async fn acquire_within(
+    pooled: impl Future<Output = Result<PoolConnection<Postgres>, sqlx::Error>>,
+    timeout: Option<Duration>,
+    class: &'static str,
+) -> AppResult<DbConnection> {
+    let acquire = DbConnection::acquire(pooled);
+    let Some(timeout) = timeout else {
+        return Ok(acquire.await?);
+    };
+    match tokio::time::timeout(timeout, acquire).await {
+        Ok(result) => Ok(result?),
+        Err(_) => {
+            tracing::warn!(
+                class,
+                timeout_ms = timeout.as_millis() as u64,
+                "Timed out acquiring a database connection"
+            );
+            Err(AppError::DatabaseError(format!(
+                "{} connection acquire timed out",
+                class
+            )))
+        }
     }
 }
 
@@ -23,6 +151,7 @@ struct UserRow {
     username: String,
     email: String,
     full_name: Option<String>,
+    avatar_url: Option<String>,
     status: String,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
@@ -32,8 +161,9 @@ impl TryFrom<UserRow> for User {
     type Error = AppError;
 
     fn try_from(row: UserRow) -> Result<Self, Self::Error> {
-        let username = Username::new(row.username)?;
+        let username = Username::from_persistence(row.username);
         let email = Email::new(row.email)?;
+        let avatar_url = row.avatar_url.map(Url::new).transpose()?;
         let status = match row.status.as_str() {
             "active" => UserStatus::Active,
             "inactive" => UserStatus::Inactive,
@@ -54,173 +184,1865 @@ impl TryFrom<UserRow> for User {
             status,
             row.created_at,
             row.updated_at,
-        ))
+        )
+        .with_avatar_url(avatar_url))
+    }
+}
+
+fn status_str(status: UserStatus) -> &'static str {
+    match status {
+        UserStatus::Active => "active",
+        UserStatus::Inactive => "inactive",
+        UserStatus::Suspended => "suspended",
+    }
+}
+
+/// Escape LIKE wildcards so `value` only matches itself
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Build an ILIKE pattern for a substring search, escaping wildcards
+fn search_pattern(search: &str) -> String {
+    format!("%{}%", escape_like(search))
+}
+
+/// Translate a `UserFilter` into WHERE conditions
+fn filter_conditions(filter: &UserFilter) -> WhereBuilder {
+    let mut conditions = WhereBuilder::new();
+    conditions
+        .and_sql("deleted_at IS NULL")
+        .and_opt("status = {}", filter.status.map(status_str))
+        .and_opt(
+            "status = ANY({})",
+            filter.include_statuses.as_ref().map(|statuses| {
+                statuses
+                    .iter()
+                    .map(|status| status_str(*status).to_string())
+                    .collect::<Vec<_>>()
+            }),
+        )
+        .and_opt(
+            "username ILIKE {} OR email ILIKE {}",
+            filter.search.as_deref().map(search_pattern),
+        );
+    conditions
+}
+
+/// Translate a `UserSort` into an ORDER BY list
+///
+/// Columns come from a fixed mapping, never from the request, and the
+/// newest-first tie-breakers keep the order total.
+fn order_by(sort: &UserSort) -> String {
+    let mut keys: Vec<String> = sort
+        .keys()
+        .iter()
+        .map(|key| {
+            let column = match key.field {
+                UserSortField::Username => "username",
+                UserSortField::Email => "email",
+                UserSortField::Status => "status",
+                UserSortField::CreatedAt => "created_at",
+                UserSortField::UpdatedAt => "updated_at",
+            };
+            let direction = match key.direction {
+                SortDirection::Asc => "ASC",
+                SortDirection::Desc => "DESC",
+            };
+            format!("{} {}", column, direction)
+        })
+        .collect();
+    if !sort
+        .keys()
+        .iter()
+        .any(|key| key.field == UserSortField::CreatedAt)
+    {
+        keys.push("created_at DESC".to_string());
     }
+    keys.push("id DESC".to_string());
+    keys.join(", ")
 }
 
 #[async_trait]
 impl UserRepository for PostgresUserRepository {
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(query = "create", user_id = %user.id())
+    )]
     async fn create(&self, user: &User) -> AppResult<()> {
-        let status_str = match user.status() {
-            UserStatus::Active => "active",
-            UserStatus::Inactive => "inactive",
-            UserStatus::Suspended => "suspended",
-        };
+        let mut conn = self.acquire().await?;
+        let status_str = status_str(user.status());
 
         sqlx::query(
             r#"
-            INSERT INTO users (id, username, email, full_name, status, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            INSERT INTO users (id, username, email, full_name, avatar_url, status, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             "#,
         )
         .bind(user.id().as_uuid())
         .bind(user.username().as_str())
         .bind(user.email().as_str())
         .bind(user.full_name())
+        .bind(user.avatar_url().map(|url| url.as_str()))
         .bind(status_str)
         .bind(user.created_at())
         .bind(user.updated_at())
-        .execute(&self.pool)
+        .execute(&mut *conn)
+        .with_timeout(self.query_timeout)
         .await?;
 
         Ok(())
     }
 
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(query = "create_many", rows = users.len(), dry_run = dry_run)
+    )]
+    async fn create_many(&self, users: &[User], dry_run: bool) -> AppResult<()> {
+        let mut conn = self.acquire().await?;
+        let mut tx = conn.begin().await?;
+
+        for user in users {
+            sqlx::query(
+                r#"
+                INSERT INTO users (id, username, email, full_name, avatar_url, status, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                "#,
+            )
+            .bind(user.id().as_uuid())
+            .bind(user.username().as_str())
+            .bind(user.email().as_str())
+            .bind(user.full_name())
+            .bind(user.avatar_url().map(|url| url.as_str()))
+            .bind(status_str(user.status()))
+            .bind(user.created_at())
+            .bind(user.updated_at())
+            .execute(&mut *tx)
+            .with_timeout(self.query_timeout)
+            .await?;
+        }
+
+        if dry_run {
+            tx.rollback().await?;
+        } else {
+            tx.commit().await?;
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "db.query", skip_all, fields(query = "find_by_id", user_id = %id))]
     async fn find_by_id(&self, id: UserId) -> AppResult<Option<User>> {
+        let mut conn = self.acquire_read().await?;
         let row: Option<UserRow> = sqlx::query_as(
             r#"
-            SELECT id, username, email, full_name, status, created_at, updated_at
+            SELECT id, username, email, full_name, avatar_url, status, created_at, updated_at
             FROM users
-            WHERE id = $1
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
         )
         .bind(id.as_uuid())
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *conn)
+        .with_timeout(self.query_timeout)
         .await?;
 
         row.map(|r| r.try_into()).transpose()
     }
 
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(query = "find_by_ids", count = ids.len())
+    )]
+    async fn find_by_ids(&self, ids: &[UserId]) -> AppResult<Vec<User>> {
+        let mut conn = self.acquire_read().await?;
+        let ids: Vec<uuid::Uuid> = ids.iter().map(|id| *id.as_uuid()).collect();
+        let rows: Vec<UserRow> = sqlx::query_as(
+            r#"
+            SELECT id, username, email, full_name, avatar_url, status, created_at, updated_at
+            FROM users
+            WHERE id = ANY($1) AND deleted_at IS NULL
+            "#,
+        )
+        .bind(&ids)
+        .fetch_all(&mut *conn)
+        .with_timeout(self.query_timeout)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    #[tracing::instrument(name = "db.query", skip_all, fields(query = "find_by_username"))]
     async fn find_by_username(&self, username: &Username) -> AppResult<Option<User>> {
+        let mut conn = self.acquire_read().await?;
         let row: Option<UserRow> = sqlx::query_as(
             r#"
-            SELECT id, username, email, full_name, status, created_at, updated_at
+            SELECT id, username, email, full_name, avatar_url, status, created_at, updated_at
             FROM users
-            WHERE username = $1
+            WHERE LOWER(username) = LOWER($1) AND deleted_at IS NULL
             "#,
         )
         .bind(username.as_str())
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *conn)
+        .with_timeout(self.query_timeout)
         .await?;
 
         row.map(|r| r.try_into()).transpose()
     }
 
+    #[tracing::instrument(name = "db.query", skip_all, fields(query = "find_by_email"))]
     async fn find_by_email(&self, email: &Email) -> AppResult<Option<User>> {
+        let mut conn = self.acquire_read().await?;
         let row: Option<UserRow> = sqlx::query_as(
             r#"
-            SELECT id, username, email, full_name, status, created_at, updated_at
+            SELECT id, username, email, full_name, avatar_url, status, created_at, updated_at
             FROM users
-            WHERE email = $1
+            WHERE LOWER(email) = LOWER($1) AND deleted_at IS NULL
             "#,
         )
         .bind(email.as_str())
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *conn)
+        .with_timeout(self.query_timeout)
         .await?;
 
         row.map(|r| r.try_into()).transpose()
     }
 
-    async fn update(&self, user: &User) -> AppResult<()> {
-        let status_str = match user.status() {
-            UserStatus::Active => "active",
-            UserStatus::Inactive => "inactive",
-            UserStatus::Suspended => "suspended",
+    #[tracing::instrument(name = "db.query", skip_all, fields(query = "find_by_canonical_email"))]
+    async fn find_by_canonical_email(
+        &self,
+        email: &Email,
+        policy: &EmailPolicy,
+    ) -> AppResult<Option<User>> {
+        let (Some(local), Some(domain)) = (email.local_part(), email.domain()) else {
+            return self.find_by_email(email).await;
+        };
+        let Some(rule) = policy.rule_for(domain) else {
+            return self.find_by_email(email).await;
         };
+        let canonical = email.canonical(policy);
+        let canonical_local = canonical.split('@').next().unwrap_or(local);
+
+        let mut conn = self.acquire_read().await?;
+        // Mirrors Email::canonical for stored addresses at the same domain
+        let row: Option<UserRow> = sqlx::query_as(
+            r#"
+            SELECT id, username, email, full_name, avatar_url, status, created_at, updated_at
+            FROM users
+            WHERE deleted_at IS NULL
+              AND split_part(email, '@', 2) = $1
+              AND (
+                  SELECT CASE WHEN $3 THEN replace(l, '.', '') ELSE l END
+                  FROM (
+                      SELECT CASE
+                          WHEN $2 THEN split_part(split_part(email, '@', 1), '+', 1)
+                          ELSE split_part(email, '@', 1)
+                      END AS l
+                  ) AS local_part
+              ) = $4
+            ORDER BY created_at, id
+            LIMIT 1
+            "#,
+        )
+        .bind(domain)
+        .bind(rule.strip_plus_tag)
+        .bind(rule.ignore_dots)
+        .bind(canonical_local)
+        .fetch_optional(&mut *conn)
+        .with_timeout(self.query_timeout)
+        .await?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(query = "find_by_email_domain", limit = limit, offset = offset)
+    )]
+    async fn find_by_email_domain(
+        &self,
+        domain: &str,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<User>> {
+        let mut conn = self.acquire_read().await?;
+        // The leading wildcard rules out a plain btree index on email; on large
+        // tables back this with a trigram index:
+        //   CREATE INDEX idx_users_email_trgm ON users USING gin (email gin_trgm_ops);
+        let rows: Vec<UserRow> = sqlx::query_as(
+            r#"
+            SELECT id, username, email, full_name, avatar_url, status, created_at, updated_at
+            FROM users
+            WHERE deleted_at IS NULL AND email LIKE '%@' || $1
+            ORDER BY created_at DESC, id DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(escape_like(domain))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&mut *conn)
+        .with_timeout(self.query_timeout)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| row.try_into())
+            .collect::<Result<Vec<_>, _>>()
+    }
+
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(query = "update", user_id = %user.id())
+    )]
+    async fn update(&self, user: &User) -> AppResult<()> {
+        let mut conn = self.acquire().await?;
+        let status_str = status_str(user.status());
 
         sqlx::query(
             r#"
             UPDATE users
-            SET username = $2, email = $3, full_name = $4, status = $5, updated_at = $6
-            WHERE id = $1
+            SET username = $2, email = $3, full_name = $4, avatar_url = $5, status = $6,
+                updated_at = $7
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
         )
         .bind(user.id().as_uuid())
         .bind(user.username().as_str())
         .bind(user.email().as_str())
         .bind(user.full_name())
+        .bind(user.avatar_url().map(|url| url.as_str()))
         .bind(status_str)
         .bind(user.updated_at())
-        .execute(&self.pool)
+        .execute(&mut *conn)
+        .with_timeout(self.query_timeout)
         .await?;
 
         Ok(())
     }
 
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(query = "update_claiming_username", user_id = %user.id())
+    )]
+    async fn update_claiming_username(&self, user: &User) -> AppResult<()> {
+        let mut conn = self.acquire().await?;
+        let status_str = status_str(user.status());
+        // Two claims racing past NOT EXISTS both try to write the name; the
+        // unique index blocks the second until the first commits and then
+        // rejects it, which also surfaces as AlreadyExists
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET username = $2, email = $3, full_name = $4, avatar_url = $5, status = $6,
+                updated_at = $7
+            WHERE id = $1 AND deleted_at IS NULL
+              AND NOT EXISTS (
+                  SELECT 1 FROM users
+                  WHERE LOWER(username) = LOWER($2) AND id <> $1 AND deleted_at IS NULL
+              )
+            "#,
+        )
+        .bind(user.id().as_uuid())
+        .bind(user.username().as_str())
+        .bind(user.email().as_str())
+        .bind(user.full_name())
+        .bind(user.avatar_url().map(|url| url.as_str()))
+        .bind(status_str)
+        .bind(user.updated_at())
+        .execute(&mut *conn)
+        .with_timeout(self.query_timeout)
+        .await?;
+
+        if result.rows_affected() > 0 {
+            return Ok(());
+        }
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND deleted_at IS NULL)",
+        )
+        .bind(user.id().as_uuid())
+        .fetch_one(&mut *conn)
+        .with_timeout(self.query_timeout)
+        .await?;
+        Err(if exists {
+            AppError::AlreadyExists(format!("Username '{}' already exists", user.username()))
+        } else {
+            AppError::NotFound(format!("User with ID {} not found", user.id()))
+        })
+    }
+
+    #[tracing::instrument(name = "db.query", skip_all, fields(query = "delete", user_id = %id))]
     async fn delete(&self, id: UserId) -> AppResult<()> {
+        let mut conn = self.acquire().await?;
         sqlx::query(
+            r#"
+            UPDATE users
+            SET deleted_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id.as_uuid())
+        .execute(&mut *conn)
+        .with_timeout(self.query_timeout)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(query = "delete_many", count = ids.len())
+    )]
+    async fn delete_many(&self, ids: &[UserId]) -> AppResult<Vec<UserId>> {
+        let mut conn = self.acquire().await?;
+        let ids: Vec<uuid::Uuid> = ids.iter().map(|id| *id.as_uuid()).collect();
+        let deleted: Vec<uuid::Uuid> = sqlx::query_scalar(
+            r#"
+            UPDATE users
+            SET deleted_at = NOW()
+            WHERE id = ANY($1) AND deleted_at IS NULL
+            RETURNING id
+            "#,
+        )
+        .bind(&ids)
+        .fetch_all(&mut *conn)
+        .with_timeout(self.query_timeout)
+        .await?;
+
+        Ok(deleted.into_iter().map(UserId::from_uuid).collect())
+    }
+
+    #[tracing::instrument(name = "db.query", skip_all, fields(query = "restore", user_id = %id))]
+    async fn restore(&self, id: UserId) -> AppResult<User> {
+        let mut conn = self.acquire().await?;
+        // A username or email reused since the delete trips the partial
+        // unique indexes and surfaces as AlreadyExists
+        let row: Option<UserRow> = sqlx::query_as(
+            r#"
+            UPDATE users
+            SET deleted_at = NULL
+            WHERE id = $1 AND deleted_at IS NOT NULL
+            RETURNING id, username, email, full_name, avatar_url, status, created_at, updated_at
+            "#,
+        )
+        .bind(id.as_uuid())
+        .fetch_optional(&mut *conn)
+        .with_timeout(self.query_timeout)
+        .await?;
+
+        row.ok_or_else(|| AppError::NotFound(format!("No deleted user with ID {}", id)))?
+            .try_into()
+    }
+
+    #[tracing::instrument(name = "db.query", skip_all, fields(query = "purge_deleted"))]
+    async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> AppResult<u64> {
+        let mut conn = self.acquire().await?;
+        let result = sqlx::query(
             r#"
             DELETE FROM users
-            WHERE id = $1
+            WHERE deleted_at IS NOT NULL AND deleted_at < $1
+            "#,
+        )
+        .bind(deleted_before)
+        .execute(&mut *conn)
+        .with_timeout(self.query_timeout)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(query = "find_password_hash", user_id = %id)
+    )]
+    async fn find_password_hash(&self, id: UserId) -> AppResult<Option<String>> {
+        // Read from the primary: a rehash may have just been written
+        let mut conn = self.acquire().await?;
+        let hash: Option<Option<String>> = sqlx::query_scalar(
+            r#"
+            SELECT password_hash FROM users
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
         )
         .bind(id.as_uuid())
-        .execute(&self.pool)
+        .fetch_optional(&mut *conn)
+        .with_timeout(self.query_timeout)
         .await?;
 
+        Ok(hash.flatten())
+    }
+
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(query = "set_password_hash", user_id = %id)
+    )]
+    async fn set_password_hash(&self, id: UserId, password_hash: &str) -> AppResult<()> {
+        let mut conn = self.acquire().await?;
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET password_hash = $2
+            WHERE id = $1 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id.as_uuid())
+        .bind(password_hash)
+        .execute(&mut *conn)
+        .with_timeout(self.query_timeout)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("User with ID {} not found", id)));
+        }
         Ok(())
     }
 
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(query = "change_password_hash", user_id = %id)
+    )]
+    async fn change_password_hash(&self, id: UserId, password_hash: &str) -> AppResult<i64> {
+        let mut conn = self.acquire().await?;
+        let epoch: Option<i64> = sqlx::query_scalar(
+            r#"
+            UPDATE users
+            SET password_hash = $2, session_epoch = session_epoch + 1
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING session_epoch
+            "#,
+        )
+        .bind(id.as_uuid())
+        .bind(password_hash)
+        .fetch_optional(&mut *conn)
+        .with_timeout(self.query_timeout)
+        .await?;
+
+        epoch.ok_or_else(|| AppError::NotFound(format!("User with ID {} not found", id)))
+    }
+
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(query = "find_session_epoch", user_id = %id)
+    )]
+    async fn find_session_epoch(&self, id: UserId) -> AppResult<Option<i64>> {
+        // Read from the primary: a just-bumped epoch must revoke at once
+        let mut conn = self.acquire().await?;
+        let epoch: Option<i64> = sqlx::query_scalar(
+            r#"
+            SELECT session_epoch FROM users
+            WHERE id = $1 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id.as_uuid())
+        .fetch_optional(&mut *conn)
+        .with_timeout(self.query_timeout)
+        .await?;
+
+        Ok(epoch)
+    }
+
+    #[tracing::instrument(name = "db.query", skip_all, fields(query = "username_exists"))]
     async fn username_exists(&self, username: &Username) -> AppResult<bool> {
+        let mut conn = self.acquire().await?;
         let result: Option<bool> = sqlx::query_scalar(
             r#"
-            SELECT EXISTS(SELECT 1 FROM users WHERE username = $1)
+            SELECT EXISTS(SELECT 1 FROM users WHERE LOWER(username) = LOWER($1) AND deleted_at IS NULL)
             "#,
         )
         .bind(username.as_str())
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *conn)
+        .with_timeout(self.query_timeout)
         .await?;
 
         Ok(result.unwrap_or(false))
     }
 
+    #[tracing::instrument(name = "db.query", skip_all, fields(query = "email_exists"))]
     async fn email_exists(&self, email: &Email) -> AppResult<bool> {
+        let mut conn = self.acquire().await?;
         let result: Option<bool> = sqlx::query_scalar(
             r#"
-            SELECT EXISTS(SELECT 1 FROM users WHERE email = $1)
+            SELECT EXISTS(SELECT 1 FROM users WHERE LOWER(email) = LOWER($1) AND deleted_at IS NULL)
             "#,
         )
         .bind(email.as_str())
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *conn)
+        .with_timeout(self.query_timeout)
         .await?;
 
         Ok(result.unwrap_or(false))
     }
 
-    async fn list(&self, limit: i64, offset: i64) -> AppResult<Vec<User>> {
-        let rows: Vec<UserRow> = sqlx::query_as(
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(query = "username_or_email_exists")
+    )]
+    async fn username_or_email_exists(
+        &self,
+        username: &Username,
+        email: &Email,
+    ) -> AppResult<(bool, bool)> {
+        let mut conn = self.acquire().await?;
+        let result: (bool, bool) = sqlx::query_as(
             r#"
-            SELECT id, username, email, full_name, status, created_at, updated_at
-            FROM users
-            ORDER BY created_at DESC
-            LIMIT $1 OFFSET $2
+            SELECT
+                EXISTS(SELECT 1 FROM users WHERE LOWER(username) = LOWER($1) AND deleted_at IS NULL),
+                EXISTS(SELECT 1 FROM users WHERE LOWER(email) = LOWER($2) AND deleted_at IS NULL)
             "#,
         )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
+        .bind(username.as_str())
+        .bind(email.as_str())
+        .fetch_one(&mut *conn)
+        .with_timeout(self.query_timeout)
         .await?;
 
+        Ok(result)
+    }
+
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(query = "list", limit = limit, offset = offset)
+    )]
+    async fn list(
+        &self,
+        filter: &UserFilter,
+        sort: &UserSort,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<User>> {
+        let mut conn = self.acquire_read().await?;
+        let mut conditions = filter_conditions(filter);
+        let limit = conditions.bind(limit);
+        let offset = conditions.bind(offset);
+        let sql = format!(
+            r#"
+            SELECT id, username, email, full_name, avatar_url, status, created_at, updated_at
+            FROM users
+            {}
+            ORDER BY {}
+            LIMIT {} OFFSET {}
+            "#,
+            conditions.sql(),
+            order_by(sort),
+            limit,
+            offset
+        );
+        let rows: Vec<UserRow> = sqlx::query_as_with(&sql, conditions.into_arguments()?)
+            .fetch_all(&mut *conn)
+            .with_timeout(self.query_timeout)
+            .await?;
+
         rows.into_iter()
             .map(|row| row.try_into())
             .collect::<Result<Vec<_>, _>>()
     }
 
-    async fn count(&self) -> AppResult<i64> {
-        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
-            .fetch_one(&self.pool)
+    #[tracing::instrument(name = "db.query", skip_all, fields(query = "list_after", limit = limit))]
+    async fn list_after(
+        &self,
+        filter: &UserFilter,
+        after: (DateTime<Utc>, UserId),
+        limit: i64,
+    ) -> AppResult<Vec<User>> {
+        let mut conn = self.acquire_read().await?;
+        let mut conditions = filter_conditions(filter);
+        let created_at = conditions.bind(after.0);
+        let id = conditions.bind(*after.1.as_uuid());
+        conditions.and_sql(&format!("(created_at, id) < ({}, {})", created_at, id));
+        let limit = conditions.bind(limit);
+        let sql = format!(
+            r#"
+            SELECT id, username, email, full_name, avatar_url, status, created_at, updated_at
+            FROM users
+            {}
+            ORDER BY created_at DESC, id DESC
+            LIMIT {}
+            "#,
+            conditions.sql(),
+            limit
+        );
+        let rows: Vec<UserRow> = sqlx::query_as_with(&sql, conditions.into_arguments()?)
+            .fetch_all(&mut *conn)
+            .with_timeout(self.query_timeout)
+            .await?;
+
+        rows.into_iter()
+            .map(|row| row.try_into())
+            .collect::<Result<Vec<_>, _>>()
+    }
+
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(query = "list_created_since", limit = limit)
+    )]
+    async fn list_created_since(
+        &self,
+        filter: &UserFilter,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<User>> {
+        let mut conn = self.acquire_read().await?;
+        let mut conditions = filter_conditions(filter);
+        conditions.and("created_at > {}", since);
+        let limit = conditions.bind(limit);
+        let sql = format!(
+            r#"
+            SELECT id, username, email, full_name, avatar_url, status, created_at, updated_at
+            FROM users
+            {}
+            ORDER BY created_at DESC, id DESC
+            LIMIT {}
+            "#,
+            conditions.sql(),
+            limit
+        );
+        let rows: Vec<UserRow> = sqlx::query_as_with(&sql, conditions.into_arguments()?)
+            .fetch_all(&mut *conn)
+            .with_timeout(self.query_timeout)
+            .await?;
+
+        rows.into_iter()
+            .map(|row| row.try_into())
+            .collect::<Result<Vec<_>, _>>()
+    }
+
+    #[tracing::instrument(name = "db.query", skip_all, fields(query = "count"))]
+    async fn count(&self, filter: &UserFilter) -> AppResult<i64> {
+        let mut conn = self.acquire_read().await?;
+        let conditions = filter_conditions(filter);
+        let sql = format!("SELECT COUNT(*) FROM users {}", conditions.sql());
+        let count: i64 = sqlx::query_scalar_with(&sql, conditions.into_arguments()?)
+            .fetch_one(&mut *conn)
+            .with_timeout(self.query_timeout)
             .await?;
 
         Ok(count)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use application::UserService;
+    use domain::SystemClock;
+    use sqlx::postgres::PgPoolOptions;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id};
+    use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+    use tracing_subscriber::registry::{LookupSpan, Registry};
+
+    /// A pool that never connects; acquiring from it times out quickly
+    fn unreachable_pool() -> PgPool {
+        PgPoolOptions::new()
+            .acquire_timeout(Duration::from_millis(100))
+            .connect_lazy("postgres://postgres@127.0.0.1:1/unreachable")
+            .unwrap()
+    }
+
+    /// Which pool served a call, told apart by closing the "replica" one
+    fn served_by<T>(result: AppResult<T>) -> &'static str {
+        match result {
+            Err(AppError::DatabaseError(msg)) if msg.contains("closed pool") => "closed",
+            Err(_) => "open",
+            Ok(_) => panic!("unreachable pools cannot serve queries"),
+        }
+    }
+
+    fn sample_user() -> User {
+        User::new(
+            Username::new("replica").unwrap(),
+            Email::new("replica@example.com").unwrap(),
+            &SystemClock,
+        )
+    }
+
+    /// A captured span: (name, parent name, fields)
+    type SpanRecord = (String, Option<String>, String);
+
+    /// Records every new span
+    #[derive(Clone, Default)]
+    struct SpanTree(Arc<Mutex<Vec<SpanRecord>>>);
+
+    impl<S> Layer<S> for SpanTree
+    where
+        S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+    {
+        fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+            struct Fields<'a>(&'a mut String);
+            impl Visit for Fields<'_> {
+                fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                    self.0.push_str(&format!("{}={:?} ", field.name(), value));
+                }
+            }
+
+            let span = ctx.span(id).unwrap();
+            let parent = span.parent().map(|parent| parent.name().to_string());
+            let mut fields = String::new();
+            attrs.record(&mut Fields(&mut fields));
+            self.0.lock().unwrap().push((
+                span.name().to_string(),
+                parent,
+                fields.trim_end().to_string(),
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_user_span_nests_repository_query() {
+        let pool = unreachable_pool();
+        pool.close().await;
+        let repo = PostgresUserRepository::new(pool).with_acquire_retry(AcquireRetry::disabled());
+        let service = UserService::new(Arc::new(repo));
+        let spans = SpanTree::default();
+        let _guard = tracing::subscriber::set_default(Registry::default().with(spans.clone()));
+
+        let id = UserId::new();
+        assert!(service.get_user(id).await.is_err());
+
+        let spans = spans.0.lock().unwrap();
+        assert_eq!(
+            *spans,
+            [
+                ("get_user".to_string(), None, format!("user_id={}", id)),
+                (
+                    "db.query".to_string(),
+                    Some("get_user".to_string()),
+                    format!("query=\"find_by_id\" user_id={}", id)
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reads_use_replica_and_writes_use_primary() {
+        let replica = unreachable_pool();
+        replica.close().await;
+        let repo = PostgresUserRepository::new_with_replica(unreachable_pool(), replica)
+            .with_acquire_retry(AcquireRetry::disabled());
+        let user = sample_user();
+        let filter = UserFilter::default();
+
+        assert_eq!(served_by(repo.find_by_id(user.id()).await), "closed");
+        assert_eq!(
+            served_by(repo.find_by_username(user.username()).await),
+            "closed"
+        );
+        assert_eq!(served_by(repo.find_by_email(user.email()).await), "closed");
+        assert_eq!(
+            served_by(repo.find_by_email_domain("example.com", 10, 0).await),
+            "closed"
+        );
+        assert_eq!(
+            served_by(repo.list(&filter, &UserSort::default(), 10, 0).await),
+            "closed"
+        );
+        assert_eq!(served_by(repo.count(&filter).await), "closed");
+
+        assert_eq!(served_by(repo.create(&user).await), "open");
+        assert_eq!(served_by(repo.update(&user).await), "open");
+        assert_eq!(served_by(repo.delete(user.id()).await), "open");
+        assert_eq!(
+            served_by(repo.username_exists(user.username()).await),
+            "open"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reads_fall_back_to_primary_without_replica() {
+        let primary = unreachable_pool();
+        primary.close().await;
+        let repo =
+            PostgresUserRepository::new(primary).with_acquire_retry(AcquireRetry::disabled());
+
+        assert_eq!(served_by(repo.find_by_id(UserId::new()).await), "closed");
+        assert_eq!(
+            served_by(repo.count(&UserFilter::default()).await),
+            "closed"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_acquire_timeout_fires_independently_of_write_timeout() {
+        // Refused connections keep the pool retrying until its own timeout
+        let pool = PgPoolOptions::new()
+            .acquire_timeout(Duration::from_secs(10))
+            .connect_lazy("postgres://postgres@127.0.0.1:1/unreachable")
+            .unwrap();
+        let repo = PostgresUserRepository::new(pool)
+            .with_acquire_retry(AcquireRetry::disabled())
+            .with_acquire_timeouts(Duration::from_millis(50), Duration::from_millis(400));
+        let user = sample_user();
+
+        let started = std::time::Instant::now();
+        let read = repo.find_by_id(user.id()).await;
+        let read_elapsed = started.elapsed();
+        assert!(
+            matches!(&read, Err(AppError::DatabaseError(msg)) if msg == "read connection acquire timed out"),
+            "{:?}",
+            read.map(|_| ())
+        );
+        assert!(
+            read_elapsed < Duration::from_millis(400),
+            "{:?}",
+            read_elapsed
+        );
+
+        let started = std::time::Instant::now();
+        let write = repo.create(&user).await;
+        assert!(
+            matches!(&write, Err(AppError::DatabaseError(msg)) if msg == "write connection acquire timed out"),
+            "{:?}",
+            write
+        );
+        assert!(started.elapsed() >= Duration::from_millis(400));
+    }
+
+    async fn test_pool() -> PgPool {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        crate::database::postgres::run_migrations(&pool)
+            .await
+            .unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_write_waits_out_a_busy_pool_that_read_gives_up_on() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPoolOptions::new()
+            .max_connections(1)
+            .acquire_timeout(Duration::from_secs(5))
+            .connect(&url)
+            .await
+            .unwrap();
+        let repo = PostgresUserRepository::new(pool.clone())
+            .with_acquire_retry(AcquireRetry::disabled())
+            .with_acquire_timeouts(Duration::from_millis(100), Duration::from_secs(2));
+        let held = pool.acquire().await.unwrap();
+
+        let read = repo.find_by_id(UserId::new()).await;
+        assert!(matches!(read, Err(AppError::DatabaseError(msg)) if msg.starts_with("read ")));
+
+        // Released well within the write timeout
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            drop(held);
+        });
+        let user = sample_user();
+        repo.create(&user).await.unwrap();
+        repo.delete(user.id()).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_list_order_is_stable_for_equal_timestamps() {
+        let pool = test_pool().await;
+        let repo = PostgresUserRepository::new(pool.clone());
+
+        // Bulk-inserted rows commonly share created_at
+        let now = Utc::now();
+        let tag = &UserId::new().to_string()[..8];
+        let users: Vec<User> = (0..6)
+            .map(|i| {
+                User::from_persistence(
+                    UserId::new(),
+                    Username::new(format!("tie{}_{}", tag, i)).unwrap(),
+                    Email::new(format!("tie{}_{}@example.com", tag, i)).unwrap(),
+                    None,
+                    UserStatus::Active,
+                    now,
+                    now,
+                )
+            })
+            .collect();
+        repo.create_many(&users, false).await.unwrap();
+
+        let filter = UserFilter {
+            search: Some(format!("tie{}_", tag)),
+            ..Default::default()
+        };
+        let ids = |page: Vec<User>| page.iter().map(|u| *u.id().as_uuid()).collect::<Vec<_>>();
+
+        let first = [
+            ids(repo
+                .list(&filter, &UserSort::default(), 3, 0)
+                .await
+                .unwrap()),
+            ids(repo
+                .list(&filter, &UserSort::default(), 3, 3)
+                .await
+                .unwrap()),
+        ]
+        .concat();
+        let second = [
+            ids(repo
+                .list(&filter, &UserSort::default(), 3, 0)
+                .await
+                .unwrap()),
+            ids(repo
+                .list(&filter, &UserSort::default(), 3, 3)
+                .await
+                .unwrap()),
+        ]
+        .concat();
+
+        assert_eq!(first, second);
+        let mut expected = first.clone();
+        expected.sort_by(|a, b| b.cmp(a));
+        assert_eq!(first, expected, "ties must be broken by id DESC");
+
+        for user in &users {
+            repo.delete(user.id()).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_list_orders_by_each_sort_key_in_turn() {
+        let pool = test_pool().await;
+        let repo = PostgresUserRepository::new(pool.clone());
+
+        let tag = &UserId::new().to_string()[..8];
+        let users: Vec<User> = [
+            ("a", UserStatus::Suspended),
+            ("b", UserStatus::Active),
+            ("c", UserStatus::Active),
+        ]
+        .into_iter()
+        .map(|(name, status)| {
+            let now = Utc::now();
+            User::from_persistence(
+                UserId::new(),
+                Username::new(format!("sort{}_{}", tag, name)).unwrap(),
+                Email::new(format!("sort{}_{}@example.com", tag, name)).unwrap(),
+                None,
+                status,
+                now,
+                now,
+            )
+        })
+        .collect();
+        repo.create_many(&users, false).await.unwrap();
+
+        let filter = UserFilter {
+            search: Some(format!("sort{}_", tag)),
+            ..Default::default()
+        };
+        let sort = UserSort::parse("status,-username", 3).unwrap();
+        let listed = repo.list(&filter, &sort, 10, 0).await.unwrap();
+        let names: Vec<&str> = listed.iter().map(|u| u.username().as_str()).collect();
+
+        assert_eq!(
+            names,
+            ["c", "b", "a"].map(|name| format!("sort{}_{}", tag, name))
+        );
+        for user in &users {
+            repo.delete(user.id()).await.unwrap();
+        }
+    }
+
+    #[test]
+    fn test_order_by_maps_keys_and_appends_tie_breakers() {
+        let sort = UserSort::parse("status,-username", 3).unwrap();
+        assert_eq!(
+            order_by(&sort),
+            "status ASC, username DESC, created_at DESC, id DESC"
+        );
+        assert_eq!(order_by(&UserSort::default()), "created_at DESC, id DESC");
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_list_filters_by_included_statuses() {
+        let pool = test_pool().await;
+        let repo = PostgresUserRepository::new(pool.clone());
+
+        let now = Utc::now();
+        let tag = &UserId::new().to_string()[..8];
+        let users: Vec<User> = [
+            UserStatus::Active,
+            UserStatus::Inactive,
+            UserStatus::Suspended,
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, status)| {
+            User::from_persistence(
+                UserId::new(),
+                Username::new(format!("st{}_{}", tag, i)).unwrap(),
+                Email::new(format!("st{}_{}@example.com", tag, i)).unwrap(),
+                None,
+                status,
+                now,
+                now,
+            )
+        })
+        .collect();
+        repo.create_many(&users, false).await.unwrap();
+
+        let filter = |include_statuses| UserFilter {
+            search: Some(format!("st{}_", tag)),
+            include_statuses,
+            ..Default::default()
+        };
+        let active = filter(Some(vec![UserStatus::Active]));
+        let listed = repo
+            .list(&active, &UserSort::default(), 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].status(), UserStatus::Active);
+        assert_eq!(repo.count(&active).await.unwrap(), 1);
+        assert_eq!(repo.count(&filter(None)).await.unwrap(), 3);
+
+        for user in &users {
+            repo.delete(user.id()).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_list_created_since_is_exclusive_and_newest_first() {
+        let pool = test_pool().await;
+        let repo = PostgresUserRepository::new(pool.clone());
+
+        let since = Utc::now() - chrono::TimeDelta::days(1);
+        let tag = &UserId::new().to_string()[..8];
+        let users: Vec<User> = [-60, 0, 60, 120]
+            .into_iter()
+            .enumerate()
+            .map(|(i, minutes)| {
+                let created_at = since + chrono::TimeDelta::minutes(minutes);
+                User::from_persistence(
+                    UserId::new(),
+                    Username::new(format!("rc{}_{}", tag, i)).unwrap(),
+                    Email::new(format!("rc{}_{}@example.com", tag, i)).unwrap(),
+                    None,
+                    UserStatus::Active,
+                    created_at,
+                    created_at,
+                )
+            })
+            .collect();
+        repo.create_many(&users, false).await.unwrap();
+
+        let filter = UserFilter {
+            search: Some(format!("rc{}_", tag)),
+            ..Default::default()
+        };
+        let recent = repo.list_created_since(&filter, since, 10).await.unwrap();
+        let ids: Vec<UserId> = recent.iter().map(User::id).collect();
+        assert_eq!(ids, [users[3].id(), users[2].id()]);
+        assert_eq!(
+            repo.list_created_since(&filter, since, 1)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        for user in &users {
+            repo.delete(user.id()).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_database_rejects_rows_breaking_domain_rules() {
+        let pool = test_pool().await;
+        let tag = &UserId::new().to_string()[..8];
+        let insert = |username: String, email: String, status: &'static str| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query(
+                    "INSERT INTO users (id, username, email, status) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(uuid::Uuid::new_v4())
+                .bind(username)
+                .bind(email)
+                .bind(status)
+                .execute(&pool)
+                .await
+                .map(|_| ())
+            }
+        };
+        let code = |result: Result<(), sqlx::Error>| match result {
+            Err(sqlx::Error::Database(e)) => e.code().map(|code| code.into_owned()),
+            other => panic!("expected a database error, got {:?}", other),
+        };
+        let email = |local: &str| format!("{}@example.com", local);
+
+        // Check violations
+        assert_eq!(
+            code(insert("ab".to_string(), email(&format!("short{}", tag)), "active").await),
+            Some("23514".to_string())
+        );
+        assert_eq!(
+            code(insert(format!("ck{}", tag), String::new(), "active").await),
+            Some("23514".to_string())
+        );
+        assert_eq!(
+            code(insert(format!("ck{}", tag), email(&format!("ck{}", tag)), "banned").await),
+            Some("23514".to_string())
+        );
+        // Over-long values are rejected by the column type before the check
+        assert!(
+            insert("u".repeat(31), email(&format!("long{}", tag)), "active")
+                .await
+                .is_err()
+        );
+        assert!(
+            insert(format!("ck{}", tag), "e".repeat(256), "active")
+                .await
+                .is_err()
+        );
+
+        let valid = format!("ck{}", tag);
+        insert(valid.clone(), email(&valid), "active")
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM users WHERE username = $1")
+            .bind(valid)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_lookups_and_pages_use_indexes() {
+        let pool = test_pool().await;
+        let mut tx = pool.begin().await.unwrap();
+        // The test table is tiny; rule out sequential scans and sorts so the
+        // plan shows whether an index can serve the lookup or order at all
+        for setting in ["enable_seqscan", "enable_sort"] {
+            sqlx::query(&format!("SET LOCAL {} = off", setting))
+                .execute(&mut *tx)
+                .await
+                .unwrap();
+        }
+
+        let cases = [
+            (
+                "SELECT id FROM users WHERE LOWER(username) = LOWER($1) AND deleted_at IS NULL",
+                "users_username_key",
+            ),
+            (
+                "SELECT id FROM users WHERE LOWER(email) = LOWER($1) AND deleted_at IS NULL",
+                "users_email_key",
+            ),
+            (
+                "SELECT id FROM users WHERE deleted_at IS NULL AND $1 <> ''
+                 ORDER BY created_at DESC, id DESC LIMIT 20",
+                "idx_users_created_at_id",
+            ),
+            (
+                "SELECT id FROM users WHERE deleted_at IS NULL AND status = $1
+                 ORDER BY created_at DESC, id DESC LIMIT 20",
+                "idx_users_status",
+            ),
+        ];
+        for (query, index) in cases {
+            let plan: Vec<String> = sqlx::query_scalar(&format!("EXPLAIN {}", query))
+                .bind("suspended")
+                .fetch_all(&mut *tx)
+                .await
+                .unwrap();
+            let plan = plan.join("\n");
+            assert!(plan.contains(index), "{} not used:\n{}", index, plan);
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_usernames_are_unique_regardless_of_case() {
+        let pool = test_pool().await;
+        let repo = PostgresUserRepository::new(pool.clone());
+
+        let tag = &UserId::new().to_string()[..8];
+        let user = User::new(
+            Username::new(format!("Case{}", tag)).unwrap(),
+            Email::new(format!("case{}@example.com", tag)).unwrap(),
+            &SystemClock,
+        );
+        repo.create(&user).await.unwrap();
+        let lower = Username::new(format!("case{}", tag)).unwrap();
+        assert!(repo.username_exists(&lower).await.unwrap());
+
+        let clash = User::new(
+            lower,
+            Email::new(format!("other{}@example.com", tag)).unwrap(),
+            &SystemClock,
+        );
+        assert!(matches!(
+            repo.create(&clash).await,
+            Err(AppError::AlreadyExists(msg)) if msg == "Username already exists"
+        ));
+
+        repo.delete(user.id()).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_concurrent_username_claims_let_exactly_one_win() {
+        use crate::database::transaction::RequestTransaction;
+        use domain::FixedClock;
+
+        let pool = test_pool().await;
+        let repo = Arc::new(PostgresUserRepository::new(pool.clone()));
+        let tag = &UserId::new().to_string()[..8];
+        let users: Vec<User> = ["a", "b"]
+            .into_iter()
+            .map(|name| {
+                User::new(
+                    Username::new(format!("claim{}_{}", tag, name)).unwrap(),
+                    Email::new(format!("claim{}_{}@example.com", tag, name)).unwrap(),
+                    &SystemClock,
+                )
+            })
+            .collect();
+        repo.create_many(&users, false).await.unwrap();
+        let wanted = Username::new(format!("Claimed{}", tag)).unwrap();
+        let claimed_at = users[0].updated_at() + chrono::Duration::minutes(5);
+        let later = FixedClock::new(claimed_at);
+        let claiming = |user: &User, username: &Username| {
+            let mut user = user.clone();
+            user.update_full_name(
+                Some("Claimant".to_string()),
+                &shared::config::ValidationConfig::default(),
+                &later,
+            )
+            .unwrap();
+            user.update_username(username.clone(), &later);
+            user
+        };
+
+        // The first claim holds the name uncommitted while the second runs
+        let first = RequestTransaction::begin(&pool).await.unwrap();
+        first
+            .scope(repo.update_claiming_username(&claiming(&users[0], &wanted)))
+            .await
+            .unwrap();
+        let second = {
+            let repo = repo.clone();
+            let rival = claiming(
+                &users[1],
+                &Username::new(format!("claimed{}", tag)).unwrap(),
+            );
+            let tx = RequestTransaction::begin(&pool).await.unwrap();
+            tokio::spawn(async move {
+                let result = tx.scope(repo.update_claiming_username(&rival)).await;
+                tx.commit().await.unwrap();
+                result
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(
+            !second.is_finished(),
+            "second claim should wait on the first"
+        );
+        first.commit().await.unwrap();
+
+        assert!(matches!(
+            second.await.unwrap(),
+            Err(AppError::AlreadyExists(_))
+        ));
+        // Once committed, a later claim is refused by the check itself
+        assert!(matches!(
+            repo.update_claiming_username(&claiming(&users[1], &wanted)).await,
+            Err(AppError::AlreadyExists(msg)) if msg.contains(wanted.as_str())
+        ));
+        // The winner's fields and updated_at were written with the name; the
+        // loser's were not written at all
+        let owner = repo.find_by_username(&wanted).await.unwrap().unwrap();
+        assert_eq!(owner.id(), users[0].id());
+        assert_eq!(owner.full_name(), Some("Claimant"));
+        assert_eq!(owner.updated_at(), claimed_at);
+        let loser = repo.find_by_id(users[1].id()).await.unwrap().unwrap();
+        assert_eq!(loser.username(), users[1].username());
+        assert_eq!(loser.full_name(), None);
+        assert_eq!(loser.updated_at(), users[1].updated_at());
+
+        let missing = User::new(
+            Username::new(format!("ghost{}", tag)).unwrap(),
+            Email::new(format!("ghost{}@example.com", tag)).unwrap(),
+            &SystemClock,
+        );
+        assert!(matches!(
+            repo.update_claiming_username(&missing).await,
+            Err(AppError::NotFound(_))
+        ));
+
+        repo.delete_many(&[users[0].id(), users[1].id()])
+            .await
+            .unwrap();
+    }
+
+    /// The user as the API renders it, and an ETag over those bytes
+    fn rendered(user: &User) -> (Vec<u8>, String) {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+
+        let body = serde_json::to_vec(&application::UserResponse::from(user.clone())).unwrap();
+        let mut hasher = DefaultHasher::new();
+        body.hash(&mut hasher);
+        let etag = format!("\"{:016x}\"", hasher.finish());
+        (body, etag)
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_timestamps_survive_a_write_and_read_unchanged() {
+        let pool = test_pool().await;
+        let repo = PostgresUserRepository::new(pool.clone());
+
+        let tag = &UserId::new().to_string()[..8];
+        let mut user = User::new(
+            Username::new(format!("ts{}", tag)).unwrap(),
+            Email::new(format!("ts{}@example.com", tag)).unwrap(),
+            &SystemClock,
+        );
+        repo.create(&user).await.unwrap();
+        let stored = repo.find_by_id(user.id()).await.unwrap().unwrap();
+        assert_eq!(rendered(&stored), rendered(&user));
+
+        user.update_full_name(
+            Some("Round Trip".to_string()),
+            &shared::config::ValidationConfig::default(),
+            &SystemClock,
+        )
+        .unwrap();
+        repo.update(&user).await.unwrap();
+        let stored = repo.find_by_id(user.id()).await.unwrap().unwrap();
+        assert_eq!(stored.updated_at(), user.updated_at());
+        assert_eq!(rendered(&stored), rendered(&user));
+
+        repo.delete(user.id()).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_find_by_ids_skips_missing_and_deleted_users() {
+        let pool = test_pool().await;
+        let repo = PostgresUserRepository::new(pool.clone());
+
+        let tag = &UserId::new().to_string()[..8];
+        let users: Vec<User> = (0..3)
+            .map(|i| {
+                User::new(
+                    Username::new(format!("get{}_{}", tag, i)).unwrap(),
+                    Email::new(format!("get{}_{}@example.com", tag, i)).unwrap(),
+                    &SystemClock,
+                )
+            })
+            .collect();
+        repo.create_many(&users, false).await.unwrap();
+        repo.delete(users[1].id()).await.unwrap();
+
+        let ids: Vec<UserId> = users.iter().map(User::id).chain([UserId::new()]).collect();
+        let mut found: Vec<UserId> = repo
+            .find_by_ids(&ids)
+            .await
+            .unwrap()
+            .iter()
+            .map(User::id)
+            .collect();
+        found.sort_by_key(|id| *id.as_uuid());
+        let mut expected = vec![users[0].id(), users[2].id()];
+        expected.sort_by_key(|id| *id.as_uuid());
+
+        assert_eq!(found, expected);
+
+        repo.delete_many(&expected).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_delete_many_returns_only_existing_ids() {
+        let pool = test_pool().await;
+        let repo = PostgresUserRepository::new(pool.clone());
+
+        let tag = &UserId::new().to_string()[..8];
+        let users: Vec<User> = (0..3)
+            .map(|i| {
+                User::new(
+                    Username::new(format!("del{}_{}", tag, i)).unwrap(),
+                    Email::new(format!("del{}_{}@example.com", tag, i)).unwrap(),
+                    &SystemClock,
+                )
+            })
+            .collect();
+        repo.create_many(&users, false).await.unwrap();
+
+        let missing = UserId::new();
+        let mut deleted = repo
+            .delete_many(&[users[0].id(), missing, users[2].id()])
+            .await
+            .unwrap();
+        deleted.sort_by_key(|id| *id.as_uuid());
+        let mut expected = vec![users[0].id(), users[2].id()];
+        expected.sort_by_key(|id| *id.as_uuid());
+
+        assert_eq!(deleted, expected);
+        assert!(repo.find_by_id(users[1].id()).await.unwrap().is_some());
+
+        repo.delete(users[1].id()).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_stream_all_yields_each_user_once() {
+        let pool = test_pool().await;
+        let repo = PostgresUserRepository::new(pool.clone());
+
+        let tag = &UserId::new().to_string()[..8];
+        let users: Vec<User> = (0..5)
+            .map(|i| {
+                User::new(
+                    Username::new(format!("str{}_{}", tag, i)).unwrap(),
+                    Email::new(format!("str{}_{}@example.com", tag, i)).unwrap(),
+                    &SystemClock,
+                )
+            })
+            .collect();
+        repo.create_many(&users, false).await.unwrap();
+        repo.delete(users[4].id()).await.unwrap();
+
+        let mut streamed: Vec<UserId> = repo
+            .stream_all()
+            .map(|user| user.unwrap())
+            .filter(|user| {
+                std::future::ready(user.username().as_str().starts_with(&format!("str{}", tag)))
+            })
+            .map(|user| user.id())
+            .collect()
+            .await;
+        streamed.sort_by_key(|id| *id.as_uuid());
+        let mut expected: Vec<UserId> = users[..4].iter().map(User::id).collect();
+        expected.sort_by_key(|id| *id.as_uuid());
+
+        assert_eq!(streamed, expected);
+        for user in &users[..4] {
+            repo.delete(user.id()).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_unique_violation_names_the_constraint() {
+        let pool = test_pool().await;
+        let repo = PostgresUserRepository::new(pool.clone());
+
+        let tag = &UserId::new().to_string()[..8];
+        let original = User::new(
+            Username::new(format!("uq{}", tag)).unwrap(),
+            Email::new(format!("uq{}@example.com", tag)).unwrap(),
+            &SystemClock,
+        );
+        repo.create(&original).await.unwrap();
+
+        let same_email = User::new(
+            Username::new(format!("uq{}_b", tag)).unwrap(),
+            Email::new(format!("uq{}@example.com", tag)).unwrap(),
+            &SystemClock,
+        );
+        let same_username = User::new(
+            Username::new(format!("uq{}", tag)).unwrap(),
+            Email::new(format!("uq{}_b@example.com", tag)).unwrap(),
+            &SystemClock,
+        );
+
+        assert!(matches!(
+            repo.create(&same_email).await,
+            Err(AppError::AlreadyExists(msg)) if msg == "Email already exists"
+        ));
+        assert!(matches!(
+            repo.create(&same_username).await,
+            Err(AppError::AlreadyExists(msg)) if msg == "Username already exists"
+        ));
+
+        repo.delete(original.id()).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_restore_clears_soft_delete_unless_handle_was_reused() {
+        let pool = test_pool().await;
+        let repo = PostgresUserRepository::new(pool.clone());
+
+        let tag = &UserId::new().to_string()[..8];
+        let user = User::new(
+            Username::new(format!("rst{}", tag)).unwrap(),
+            Email::new(format!("rst{}@example.com", tag)).unwrap(),
+            &SystemClock,
+        );
+        repo.create(&user).await.unwrap();
+        repo.delete(user.id()).await.unwrap();
+        assert!(repo.find_by_id(user.id()).await.unwrap().is_none());
+
+        let restored = repo.restore(user.id()).await.unwrap();
+        assert_eq!(restored.id(), user.id());
+        assert!(repo.find_by_id(user.id()).await.unwrap().is_some());
+        assert!(matches!(
+            repo.restore(user.id()).await,
+            Err(AppError::NotFound(_))
+        ));
+
+        repo.delete(user.id()).await.unwrap();
+        let reused = User::new(
+            user.username().clone(),
+            Email::new(format!("rst{}_new@example.com", tag)).unwrap(),
+            &SystemClock,
+        );
+        repo.create(&reused).await.unwrap();
+        match repo.restore(user.id()).await {
+            Err(AppError::AlreadyExists(msg)) => assert_eq!(msg, "Username already exists"),
+            other => panic!("expected AlreadyExists, got {:?}", other.map(|u| u.id())),
+        }
+
+        repo.delete(reused.id()).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_find_by_canonical_email_matches_policy_variants() {
+        let pool = test_pool().await;
+        let repo = PostgresUserRepository::new(pool.clone());
+        let policy = EmailPolicy {
+            rules: vec![shared::config::EmailDomainRule {
+                domains: vec!["gmail.com".to_string()],
+                strip_plus_tag: true,
+                ignore_dots: true,
+            }],
+        };
+
+        let tag = &UserId::new().to_string()[..8];
+        let user = User::new(
+            Username::new(format!("can{}", tag)).unwrap(),
+            Email::new(format!("can.{}+old@gmail.com", tag)).unwrap(),
+            &SystemClock,
+        );
+        repo.create(&user).await.unwrap();
+
+        let variant = Email::new(format!("can{}+new@gmail.com", tag)).unwrap();
+        let found = repo
+            .find_by_canonical_email(&variant, &policy)
+            .await
+            .unwrap()
+            .expect("variant should match");
+        assert_eq!(found.id(), user.id());
+        assert!(
+            repo.find_by_canonical_email(&variant, &EmailPolicy::default())
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        repo.delete(user.id()).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_find_by_email_domain_matches_exact_domain() {
+        let pool = test_pool().await;
+        let repo = PostgresUserRepository::new(pool.clone());
+
+        let tag = &UserId::new().to_string()[..8];
+        let domain = format!("acme{}.com", tag);
+        let users: Vec<User> = [
+            ("in", domain.clone()),
+            ("sub", format!("sub.{}", domain)),
+            ("out", format!("other{}.com", tag)),
+        ]
+        .iter()
+        .map(|(name, at)| {
+            User::new(
+                Username::new(format!("dom{}{}", name, tag)).unwrap(),
+                Email::new(format!("dom{}{}@{}", name, tag, at)).unwrap(),
+                &SystemClock,
+            )
+        })
+        .collect();
+        repo.create_many(&users, false).await.unwrap();
+
+        let found = repo.find_by_email_domain(&domain, 20, 0).await.unwrap();
+        let ids: Vec<UserId> = found.iter().map(User::id).collect();
+        assert_eq!(ids, vec![users[0].id()]);
+
+        let ids: Vec<UserId> = users.iter().map(User::id).collect();
+        repo.delete_many(&ids).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_purge_deleted_keeps_recent_deletions() {
+        let pool = test_pool().await;
+        let repo = PostgresUserRepository::new(pool.clone());
+
+        let tag = &UserId::new().to_string()[..8];
+        let users: Vec<User> = ["old", "new"]
+            .iter()
+            .map(|age| {
+                User::new(
+                    Username::new(format!("prg{}{}", age, tag)).unwrap(),
+                    Email::new(format!("prg{}{}@example.com", age, tag)).unwrap(),
+                    &SystemClock,
+                )
+            })
+            .collect();
+        repo.create_many(&users, false).await.unwrap();
+        repo.delete_many(&[users[0].id(), users[1].id()])
+            .await
+            .unwrap();
+        sqlx::query("UPDATE users SET deleted_at = NOW() - INTERVAL '40 days' WHERE id = $1")
+            .bind(users[0].id().as_uuid())
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let purged = repo
+            .purge_deleted(Utc::now() - chrono::TimeDelta::days(30))
+            .await
+            .unwrap();
+        assert!(purged >= 1);
+
+        let remaining: Vec<uuid::Uuid> =
+            sqlx::query_scalar("SELECT id FROM users WHERE id = ANY($1)")
+                .bind(vec![*users[0].id().as_uuid(), *users[1].id().as_uuid()])
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(remaining, vec![*users[1].id().as_uuid()]);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_list_after_continues_where_the_page_ended() {
+        let pool = test_pool().await;
+        let repo = PostgresUserRepository::new(pool);
+
+        let tag = &UserId::new().to_string()[..8];
+        let users: Vec<User> = (0..3)
+            .map(|i| {
+                User::new(
+                    Username::new(format!("key{}{}", i, tag)).unwrap(),
+                    Email::new(format!("key{}{}@example.com", i, tag)).unwrap(),
+                    &SystemClock,
+                )
+            })
+            .collect();
+        repo.create_many(&users, false).await.unwrap();
+        let filter = UserFilter {
+            search: Some(tag.to_string()),
+            ..UserFilter::default()
+        };
+
+        let first = repo
+            .list(&filter, &UserSort::default(), 2, 0)
+            .await
+            .unwrap();
+        let last = first.last().unwrap();
+        let rest = repo
+            .list_after(&filter, (last.created_at(), last.id()), 2)
+            .await
+            .unwrap();
+
+        let all = repo
+            .list(&filter, &UserSort::default(), 3, 0)
+            .await
+            .unwrap();
+        let paged: Vec<UserId> = first.iter().chain(&rest).map(User::id).collect();
+        assert_eq!(paged, all.iter().map(User::id).collect::<Vec<_>>());
+
+        let ids: Vec<UserId> = users.iter().map(User::id).collect();
+        repo.delete_many(&ids).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_password_hash_round_trips() {
+        let pool = test_pool().await;
+        let repo = PostgresUserRepository::new(pool);
+
+        let tag = &UserId::new().to_string()[..8];
+        let user = User::new(
+            Username::new(format!("pwd{}", tag)).unwrap(),
+            Email::new(format!("pwd{}@example.com", tag)).unwrap(),
+            &SystemClock,
+        );
+        repo.create(&user).await.unwrap();
+        assert_eq!(repo.find_password_hash(user.id()).await.unwrap(), None);
+
+        repo.set_password_hash(user.id(), "$argon2id$stub")
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.find_password_hash(user.id()).await.unwrap().as_deref(),
+            Some("$argon2id$stub")
+        );
+
+        assert_eq!(repo.find_session_epoch(user.id()).await.unwrap(), Some(0));
+        let epoch = repo
+            .change_password_hash(user.id(), "$argon2id$rotated")
+            .await
+            .unwrap();
+        assert_eq!(epoch, 1);
+        assert_eq!(repo.find_session_epoch(user.id()).await.unwrap(), Some(1));
+        assert_eq!(
+            repo.find_password_hash(user.id()).await.unwrap().as_deref(),
+            Some("$argon2id$rotated")
+        );
+
+        repo.delete(user.id()).await.unwrap();
+        assert_eq!(repo.find_session_epoch(user.id()).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_query_timeout_fires_and_returns_the_connection() {
+        let pool = test_pool().await;
+        // A single connection, so a leaked one would starve the next query
+        let url = std::env::var("DATABASE_URL").unwrap();
+        let repo_pool = PgPoolOptions::new()
+            .max_connections(1)
+            .acquire_timeout(Duration::from_secs(5))
+            .connect(&url)
+            .await
+            .unwrap();
+        let repo =
+            PostgresUserRepository::new(repo_pool).with_query_timeout(Duration::from_millis(200));
+
+        // Block every read of users until the lock is released
+        let mut lock = pool.begin().await.unwrap();
+        sqlx::query("LOCK TABLE users IN ACCESS EXCLUSIVE MODE")
+            .execute(&mut *lock)
+            .await
+            .unwrap();
+
+        let result = repo.count(&UserFilter::default()).await;
+        assert!(
+            matches!(&result, Err(AppError::DatabaseError(msg)) if msg == "query timed out"),
+            "{:?}",
+            result
+        );
+
+        lock.rollback().await.unwrap();
+        assert!(repo.count(&UserFilter::default()).await.is_ok());
+    }
+}
@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use sqlx::Arguments;
+use sqlx::postgres::PgArguments;
+
+use shared::{AppError, AppResult};
+
+/// A value bound to a `WhereBuilder` placeholder
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum BindValue {
+    Text(String),
+    BigInt(i64),
+    Uuid(uuid::Uuid),
+    Timestamp(DateTime<Utc>),
+    TextArray(Vec<String>),
+}
+
+impl From<String> for BindValue {
+    fn from(value: String) -> Self {
+        BindValue::Text(value)
+    }
+}
+
+impl From<&str> for BindValue {
+    fn from(value: &str) -> Self {
+        BindValue::Text(value.to_string())
+    }
+}
+
+impl From<i64> for BindValue {
+    fn from(value: i64) -> Self {
+        BindValue::BigInt(value)
+    }
+}
+
+impl From<uuid::Uuid> for BindValue {
+    fn from(value: uuid::Uuid) -> Self {
+        BindValue::Uuid(value)
+    }
+}
+
+impl From<DateTime<Utc>> for BindValue {
+    fn from(value: DateTime<Utc>) -> Self {
+        BindValue::Timestamp(value)
+    }
+}
+
+impl From<Vec<String>> for BindValue {
+    fn from(value: Vec<String>) -> Self {
+        BindValue::TextArray(value)
+    }
+}
+
+/// Accumulates `AND`-ed conditions together with their bind values.
+///
+/// Placeholders are numbered as values are added, so the SQL fragment and
+/// the argument list can never drift apart.
+#[derive(Debug, Default)]
+pub(crate) struct WhereBuilder {
+    conditions: Vec<String>,
+    args: Vec<BindValue>,
+}
+
+impl WhereBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a condition; every `{}` in `template` refers to the same bound value
+    pub fn and(&mut self, template: &str, value: impl Into<BindValue>) -> &mut Self {
+        let placeholder = self.bind(value);
+        self.conditions.push(template.replace("{}", &placeholder));
+        self
+    }
+
+    /// Add a condition that binds no value
+    pub fn and_sql(&mut self, condition: &str) -> &mut Self {
+        self.conditions.push(condition.to_string());
+        self
+    }
+
+    /// Add a condition only when `value` is present
+    pub fn and_opt<T: Into<BindValue>>(&mut self, template: &str, value: Option<T>) -> &mut Self {
+        if let Some(value) = value {
+            self.and(template, value);
+        }
+        self
+    }
+
+    /// Bind a value used outside the WHERE clause (e.g. LIMIT), returning its placeholder
+    pub fn bind(&mut self, value: impl Into<BindValue>) -> String {
+        self.args.push(value.into());
+        format!("${}", self.arg_count())
+    }
+
+    /// The `WHERE ...` fragment, or an empty string when there are no conditions
+    pub fn sql(&self) -> String {
+        if self.conditions.is_empty() {
+            return String::new();
+        }
+        let conditions: Vec<String> = self
+            .conditions
+            .iter()
+            .map(|condition| format!("({})", condition))
+            .collect();
+        format!("WHERE {}", conditions.join(" AND "))
+    }
+
+    pub fn arg_count(&self) -> usize {
+        self.args.len()
+    }
+
+    /// Consume the builder into arguments matching the placeholder order
+    pub fn into_arguments(self) -> AppResult<PgArguments> {
+        let mut arguments = PgArguments::default();
+        for arg in self.args {
+            let result = match arg {
+                BindValue::Text(value) => arguments.add(value),
+                BindValue::BigInt(value) => arguments.add(value),
+                BindValue::Uuid(value) => arguments.add(value),
+                BindValue::Timestamp(value) => arguments.add(value),
+                BindValue::TextArray(value) => arguments.add(value),
+            };
+            result
+                .map_err(|e| AppError::DatabaseError(format!("Failed to bind argument: {}", e)))?;
+        }
+        Ok(arguments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_conditions_produce_empty_fragment() {
+        let builder = WhereBuilder::new();
+        assert_eq!(builder.sql(), "");
+        assert_eq!(builder.arg_count(), 0);
+    }
+
+    #[test]
+    fn test_single_condition() {
+        let mut builder = WhereBuilder::new();
+        builder.and("status = {}", "active");
+        assert_eq!(builder.sql(), "WHERE (status = $1)");
+        assert_eq!(builder.arg_count(), 1);
+    }
+
+    #[test]
+    fn test_three_conditions_are_numbered_in_order() {
+        let mut builder = WhereBuilder::new();
+        builder
+            .and("status = {}", "active")
+            .and("username ILIKE {} OR email ILIKE {}", "%ann%")
+            .and_opt("created_at >= {}", None::<DateTime<Utc>>)
+            .and("id <> {}", uuid::Uuid::nil());
+        assert_eq!(
+            builder.sql(),
+            "WHERE (status = $1) AND (username ILIKE $2 OR email ILIKE $2) AND (id <> $3)"
+        );
+        assert_eq!(builder.arg_count(), 3);
+        assert_eq!(builder.bind(10_i64), "$4");
+        assert_eq!(builder.into_arguments().unwrap().len(), 4);
+    }
+}
@@ -0,0 +1,252 @@
+use actix_web::{
+    FromRequest, HttpMessage, HttpRequest, HttpResponse,
+    dev::Payload,
+    error::{InternalError, JsonPayloadError},
+    web,
+};
+use futures_util::future::LocalBoxFuture;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use shared::{AppError, AppResult};
+
+use super::validated_json::payload_error;
+
+/// Media type of an RFC 6902 JSON Patch document
+pub const JSON_PATCH_MEDIA_TYPE: &str = "application/json-patch+json";
+
+/// One RFC 6902 operation; paths are RFC 6901 JSON Pointers
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase", deny_unknown_fields)]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+impl PatchOperation {
+    /// Pointers the operation changes; `copy` and `test` only read `from`
+    fn written_paths(&self) -> Vec<&str> {
+        match self {
+            Self::Add { path, .. } | Self::Remove { path } | Self::Replace { path, .. } => {
+                vec![path]
+            }
+            Self::Move { from, path } => vec![from, path],
+            Self::Copy { path, .. } => vec![path],
+            Self::Test { .. } => Vec::new(),
+        }
+    }
+}
+
+/// An RFC 6902 JSON Patch request body
+///
+/// As an extractor it accepts only `application/json-patch+json` bodies,
+/// answering 415 otherwise; body errors are reported like `ValidatedJson`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(transparent)]
+pub struct JsonPatch(pub Vec<PatchOperation>);
+
+impl JsonPatch {
+    /// Whether any operation writes to the top-level `field` or replaces the
+    /// whole document
+    pub fn writes_field(&self, field: &str) -> bool {
+        self.0
+            .iter()
+            .flat_map(PatchOperation::written_paths)
+            .any(|path| path.is_empty() || top_level(path).as_deref() == Some(field))
+    }
+
+    /// Apply the operations in order to a copy of `doc`
+    ///
+    /// All or nothing: the first failing operation (including a failed
+    /// `test`) rejects the whole patch.
+    pub fn apply(&self, doc: &Value) -> AppResult<Value> {
+        let mut doc = doc.clone();
+        for op in &self.0 {
+            match op {
+                PatchOperation::Add { path, value } => add(&mut doc, path, value.clone())?,
+                PatchOperation::Remove { path } => {
+                    remove(&mut doc, path)?;
+                }
+                PatchOperation::Replace { path, value } => {
+                    *doc.pointer_mut(path).ok_or_else(|| missing(path))? = value.clone();
+                }
+                PatchOperation::Move { from, path } => {
+                    if path.starts_with(&format!("{}/", from)) {
+                        return Err(patch_error(format!(
+                            "cannot move '{}' into its own child '{}'",
+                            from, path
+                        )));
+                    }
+                    let value = remove(&mut doc, from)?;
+                    add(&mut doc, path, value)?;
+                }
+                PatchOperation::Copy { from, path } => {
+                    let value = doc.pointer(from).ok_or_else(|| missing(from))?.clone();
+                    add(&mut doc, path, value)?;
+                }
+                PatchOperation::Test { path, value } => {
+                    if doc.pointer(path) != Some(value) {
+                        return Err(patch_error(format!("test failed at '{}'", path)));
+                    }
+                }
+            }
+        }
+        Ok(doc)
+    }
+}
+
+impl FromRequest for JsonPatch {
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let is_patch = req
+            .mime_type()
+            .ok()
+            .flatten()
+            .is_some_and(|mime| mime.essence_str() == JSON_PATCH_MEDIA_TYPE);
+        let json = web::Json::<Vec<PatchOperation>>::from_request(req, payload);
+        Box::pin(async move {
+            if !is_patch {
+                let message = format!("Content-Type must be {}", JSON_PATCH_MEDIA_TYPE);
+                let response = HttpResponse::UnsupportedMediaType().json(json!({
+                    "error": { "message": message, "code": 415 }
+                }));
+                return Err(InternalError::from_response(message, response).into());
+            }
+            let ops = json.await.map_err(|e| {
+                match e.as_error::<JsonPayloadError>().and_then(payload_error) {
+                    Some(err) => err.into(),
+                    None => e,
+                }
+            })?;
+            Ok(JsonPatch(ops.into_inner()))
+        })
+    }
+}
+
+/// First reference token of a pointer, unescaped
+fn top_level(path: &str) -> Option<String> {
+    let rest = path.strip_prefix('/')?;
+    let token = rest.split('/').next().unwrap_or_default();
+    Some(token.replace("~1", "/").replace("~0", "~"))
+}
+
+/// Split a pointer into its parent pointer and unescaped last token
+fn split_last(path: &str) -> AppResult<(&str, String)> {
+    let (parent, last) = path
+        .rsplit_once('/')
+        .ok_or_else(|| patch_error(format!("invalid JSON Pointer '{}'", path)))?;
+    if !parent.is_empty() && !parent.starts_with('/') {
+        return Err(patch_error(format!("invalid JSON Pointer '{}'", path)));
+    }
+    Ok((parent, last.replace("~1", "/").replace("~0", "~")))
+}
+
+fn add(doc: &mut Value, path: &str, value: Value) -> AppResult<()> {
+    if path.is_empty() {
+        *doc = value;
+        return Ok(());
+    }
+    let (parent, key) = split_last(path)?;
+    match doc.pointer_mut(parent).ok_or_else(|| missing(parent))? {
+        Value::Object(map) => {
+            map.insert(key, value);
+        }
+        Value::Array(items) if key == "-" => items.push(value),
+        Value::Array(items) => {
+            let index = array_index(&key, items.len() + 1, path)?;
+            items.insert(index, value);
+        }
+        _ => return Err(missing(path)),
+    }
+    Ok(())
+}
+
+fn remove(doc: &mut Value, path: &str) -> AppResult<Value> {
+    let (parent, key) = split_last(path)?;
+    match doc.pointer_mut(parent).ok_or_else(|| missing(parent))? {
+        Value::Object(map) => map.remove(&key).ok_or_else(|| missing(path)),
+        Value::Array(items) => {
+            let index = array_index(&key, items.len(), path)?;
+            Ok(items.remove(index))
+        }
+        _ => Err(missing(path)),
+    }
+}
+
+/// Parse an array index below `len`
+fn array_index(token: &str, len: usize, path: &str) -> AppResult<usize> {
+    token
+        .parse::<usize>()
+        .ok()
+        .filter(|index| *index < len && (token == "0" || !token.starts_with('0')))
+        .ok_or_else(|| missing(path))
+}
+
+fn missing(path: &str) -> AppError {
+    patch_error(format!("no value at '{}'", path))
+}
+
+fn patch_error(message: String) -> AppError {
+    AppError::ValidationError(format!("Invalid JSON Patch: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn patch(ops: Value) -> JsonPatch {
+        serde_json::from_value(ops).unwrap()
+    }
+
+    #[test]
+    fn test_applies_each_operation_in_order() {
+        let doc = json!({ "name": "a", "tags": ["x"], "meta": {} });
+        let ops = patch(json!([
+            { "op": "test", "path": "/name", "value": "a" },
+            { "op": "replace", "path": "/name", "value": "b" },
+            { "op": "add", "path": "/tags/-", "value": "y" },
+            { "op": "add", "path": "/tags/0", "value": "w" },
+            { "op": "copy", "from": "/name", "path": "/meta/previous" },
+            { "op": "move", "from": "/meta/previous", "path": "/alias" },
+            { "op": "remove", "path": "/tags/1" },
+        ]));
+
+        assert_eq!(
+            ops.apply(&doc).unwrap(),
+            json!({ "name": "b", "tags": ["w", "y"], "meta": {}, "alias": "b" })
+        );
+    }
+
+    #[test]
+    fn test_failed_operation_rejects_the_whole_patch() {
+        let doc = json!({ "name": "a" });
+
+        for ops in [
+            json!([{ "op": "test", "path": "/name", "value": "z" }]),
+            json!([{ "op": "replace", "path": "/missing", "value": 1 }]),
+            json!([{ "op": "remove", "path": "/name/deeper" }]),
+        ] {
+            let err = patch(ops).apply(&doc).unwrap_err();
+            assert!(err.to_string().contains("Invalid JSON Patch"), "{}", err);
+        }
+        assert!(
+            serde_json::from_value::<JsonPatch>(json!([{ "op": "swap", "path": "/a" }])).is_err()
+        );
+    }
+
+    #[test]
+    fn test_writes_field_sees_moves_and_whole_document_replacement() {
+        assert!(patch(json!([{ "op": "replace", "path": "/id", "value": 1 }])).writes_field("id"));
+        assert!(patch(json!([{ "op": "move", "from": "/id", "path": "/x" }])).writes_field("id"));
+        assert!(patch(json!([{ "op": "replace", "path": "", "value": {} }])).writes_field("id"));
+        assert!(!patch(json!([{ "op": "copy", "from": "/id", "path": "/x" }])).writes_field("id"));
+        assert!(!patch(json!([{ "op": "test", "path": "/id", "value": 1 }])).writes_field("id"));
+    }
+}
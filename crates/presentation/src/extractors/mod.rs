@@ -0,0 +1,5 @@
+pub mod json_patch;
+pub mod validated_json;
+
+pub use json_patch::{JSON_PATCH_MEDIA_TYPE, JsonPatch, PatchOperation};
+pub use validated_json::{ValidatedJson, json_error};
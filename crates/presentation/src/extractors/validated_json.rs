@@ -0,0 +1,247 @@
+use actix_web::{FromRequest, HttpRequest, dev::Payload, error::JsonPayloadError, web};
+use futures_util::future::LocalBoxFuture;
+use serde::de::DeserializeOwned;
+use validator::{Validate, ValidationErrorsKind};
+
+use shared::{AppError, ValidationErrors};
+
+use crate::middleware::json_limits::JsonLimitExceeded;
+
+/// JSON body extractor that runs the type's `validator` rules after
+/// deserializing, rejecting the request with field-level errors before the
+/// handler (and so the service) is invoked
+#[derive(Debug)]
+pub struct ValidatedJson<T>(pub T);
+
+impl<T> ValidatedJson<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> std::ops::Deref for ValidatedJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: DeserializeOwned + Validate + 'static> FromRequest for ValidatedJson<T> {
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let json = web::Json::<T>::from_request(req, payload);
+        Box::pin(async move {
+            let body = json
+                .await
+                .map_err(
+                    |e| match e.as_error::<JsonPayloadError>().and_then(payload_error) {
+                        Some(err) => err.into(),
+                        None => e,
+                    },
+                )?
+                .into_inner();
+            body.validate()
+                .map_err(|e| AppError::Validation(field_errors(&e)))?;
+            Ok(ValidatedJson(body))
+        })
+    }
+}
+
+/// `JsonConfig` error handler reporting body shape errors as validation errors
+///
+/// Register with `web::JsonConfig::default().error_handler(json_error)` so
+/// plain `web::Json` extractors answer like `ValidatedJson` does.
+pub fn json_error(err: JsonPayloadError, _req: &HttpRequest) -> actix_web::Error {
+    match payload_error(&err) {
+        Some(err) => err.into(),
+        None => err.into(),
+    }
+}
+
+/// Describe a body that is valid JSON but does not fit the DTO, or that
+/// broke the `JsonLimits` while being read
+///
+/// Unknown fields (rejected by `deny_unknown_fields`) are named explicitly
+/// so a typo like `emial` is not reported as a missing `email`.
+pub(crate) fn payload_error(err: &JsonPayloadError) -> Option<AppError> {
+    if let JsonPayloadError::Payload(err) = err
+        && let Some(exceeded) = JsonLimitExceeded::from_payload_error(err)
+    {
+        return Some(AppError::ValidationError(exceeded.to_string()));
+    }
+    let JsonPayloadError::Deserialize(err) = err else {
+        return None;
+    };
+    if !err.is_data() {
+        return None;
+    }
+
+    let message = err.to_string();
+    let message = message
+        .split_once(" at line ")
+        .map_or(message.as_str(), |(head, _)| head);
+    if let Some(rest) = message.strip_prefix("unknown field `")
+        && let Some((field, expected)) = rest.split_once('`')
+    {
+        let expected = expected.trim_start_matches(", ");
+        return Some(AppError::ValidationError(format!(
+            "Unknown field '{}'; {}",
+            field, expected
+        )));
+    }
+    Some(AppError::ValidationError(format!(
+        "Invalid request body: {}",
+        message
+    )))
+}
+
+/// Flatten `validator` errors into the API's field error list, sorted by field
+pub(crate) fn field_errors(errors: &validator::ValidationErrors) -> ValidationErrors {
+    let mut fields: Vec<_> = errors.errors().iter().collect();
+    fields.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut out = ValidationErrors::new();
+    for (field, kind) in fields {
+        let ValidationErrorsKind::Field(failures) = kind else {
+            out.add(field.as_ref(), "invalid", format!("Invalid {}", field));
+            continue;
+        };
+        for failure in failures {
+            let message = failure
+                .message
+                .as_ref()
+                .map(|m| m.to_string())
+                .unwrap_or_else(|| format!("Invalid {}", field));
+            out.add(field.as_ref(), failure.code.as_ref(), message);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, HttpResponse, http::StatusCode, test};
+    use std::sync::Arc;
+
+    use application::{CreateUserRequest, UserService};
+
+    use crate::handlers::user_handlers::create_user;
+    use crate::test_support::InMemoryUserRepository;
+
+    #[actix_web::test]
+    async fn test_invalid_fields_are_reported_before_the_service_runs() {
+        let service = web::Data::new(UserService::new(
+            Arc::new(InMemoryUserRepository::default()),
+        ));
+        let app = test::init_service(
+            App::new()
+                .app_data(service.clone())
+                .route("/users", web::post().to(create_user)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/users")
+            .set_json(serde_json::json!({
+                "username": "",
+                "email": "valid@example.com",
+                "full_name": "x".repeat(101),
+            }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        let fields: Vec<&str> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["field"].as_str().unwrap())
+            .collect();
+        assert_eq!(fields, vec!["full_name", "username"]);
+        assert_eq!(body["errors"][0]["code"], "length");
+        assert_eq!(
+            service.list_users(None, None, 20, 0).await.unwrap().total,
+            0
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_valid_body_reaches_the_handler() {
+        let app = test::init_service(App::new().route(
+            "/echo",
+            web::post().to(|body: ValidatedJson<CreateUserRequest>| async move {
+                HttpResponse::Ok().body(body.into_inner().username)
+            }),
+        ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/echo")
+            .set_json(serde_json::json!({
+                "username": "validuser",
+                "email": "valid@example.com",
+            }))
+            .to_request();
+        let body = test::call_and_read_body(&app, req).await;
+        assert_eq!(body, "validuser");
+    }
+
+    #[actix_web::test]
+    async fn test_unknown_field_is_named_in_the_error() {
+        let app = test::init_service(App::new().route(
+            "/echo",
+            web::post().to(|body: ValidatedJson<CreateUserRequest>| async move {
+                HttpResponse::Ok().body(body.into_inner().username)
+            }),
+        ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/echo")
+            .set_json(serde_json::json!({
+                "username": "validuser",
+                "emial": "valid@example.com",
+            }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        let message = body["error"]["message"].as_str().unwrap();
+        assert!(message.contains("Unknown field 'emial'"), "{}", message);
+        assert!(message.contains("`email`"), "{}", message);
+    }
+
+    #[actix_web::test]
+    async fn test_json_config_handler_names_unknown_fields() {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::JsonConfig::default().error_handler(json_error))
+                .route(
+                    "/batch",
+                    web::post().to(|_: web::Json<application::BatchDeleteRequest>| async {
+                        HttpResponse::Ok().finish()
+                    }),
+                ),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/batch")
+            .set_json(serde_json::json!({ "ids": [], "force": true }))
+            .to_request();
+        let body: serde_json::Value =
+            test::read_body_json(test::call_service(&app, req).await).await;
+        assert!(
+            body["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("Unknown field 'force'")
+        );
+    }
+}
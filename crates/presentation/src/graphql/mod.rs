@@ -0,0 +1,4 @@
+pub mod schema;
+pub mod types;
+
+pub use schema::{MutationRoot, QueryRoot, UserSchema, build_schema};
@@ -0,0 +1,352 @@
+use async_graphql::{Context, EmptySubscription, Error, ErrorExtensions, ID, Object, Schema};
+use std::sync::Arc;
+
+use application::{AuthContext, UserService};
+use shared::{AppError, Locale, UserId};
+
+use crate::graphql::types::{
+    CreateUserInput, UpdateUserInput, UserFilterInput, UserListType, UserView,
+};
+
+pub type UserSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+/// Build the GraphQL schema backed by the given user service
+pub fn build_schema(service: Arc<UserService>) -> UserSchema {
+    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .data(service)
+        .finish()
+}
+
+/// Map an `AppError` to a GraphQL error carrying the stable error code, the
+/// shared error kind (and field errors for validation failures) in its extensions
+fn graphql_error(err: AppError) -> Error {
+    let message = err.localized_message(Locale::default());
+    Error::new(message).extend_with(|_, ext| {
+        ext.set("code", err.code());
+        ext.set("kind", err.kind().graphql_code());
+        if let AppError::Validation(errors) = &err
+            && let Ok(value) = serde_json::to_value(errors)
+            && let Ok(value) = async_graphql::Value::from_json(value)
+        {
+            ext.set("errors", value);
+        }
+    })
+}
+
+fn parse_user_id(id: &ID) -> Result<UserId, Error> {
+    uuid::Uuid::parse_str(id.as_str())
+        .map(UserId::from_uuid)
+        .map_err(|_| {
+            graphql_error(AppError::ValidationError(
+                "Invalid user ID format".to_string(),
+            ))
+        })
+}
+
+fn service<'a>(ctx: &Context<'a>) -> &'a Arc<UserService> {
+    ctx.data_unchecked::<Arc<UserService>>()
+}
+
+fn caller<'a>(ctx: &Context<'a>) -> Option<&'a AuthContext> {
+    ctx.data_opt::<AuthContext>()
+}
+
+/// The caller, rejecting anonymous requests
+fn authenticated<'a>(ctx: &Context<'a>) -> Result<&'a AuthContext, Error> {
+    caller(ctx)
+        .ok_or_else(|| graphql_error(AppError::Unauthorized("Missing bearer token".to_string())))
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Fetch a single user by ID
+    ///
+    /// Admins get a `User`; everyone else a `PublicUser`.
+    async fn user(&self, ctx: &Context<'_>, id: ID) -> Result<UserView, Error> {
+        let user_id = parse_user_id(&id)?;
+        let user = service(ctx)
+            .get_user(user_id)
+            .await
+            .map_err(graphql_error)?;
+        Ok(UserView::for_caller(caller(ctx), user))
+    }
+
+    /// List users with pagination and an optional filter
+    ///
+    /// Suspended and inactive users, and the admin-only fields, are listed
+    /// only for admins.
+    async fn users(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20)] limit: i64,
+        #[graphql(default = 0)] offset: i64,
+        filter: Option<UserFilterInput>,
+    ) -> Result<UserListType, Error> {
+        let mut filter: domain::UserFilter = filter.unwrap_or_default().into();
+        filter.include_statuses = AuthContext::visible_statuses(caller(ctx));
+        let list = service(ctx)
+            .search_users(filter, None, limit, offset)
+            .await
+            .map_err(graphql_error)?;
+        Ok(UserListType::for_caller(caller(ctx), list))
+    }
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn create_user(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateUserInput,
+    ) -> Result<UserView, Error> {
+        let user = service(ctx)
+            .create_user(input.into())
+            .await
+            .map_err(graphql_error)?;
+        Ok(UserView::for_caller(caller(ctx), user))
+    }
+
+    /// Update a user; only the user themselves or an admin may
+    async fn update_user(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        input: UpdateUserInput,
+    ) -> Result<UserView, Error> {
+        let user_id = parse_user_id(&id)?;
+        if !authenticated(ctx)?.may_modify(user_id) {
+            return Err(graphql_error(AppError::Forbidden(
+                "Only admins can update other users".to_string(),
+            )));
+        }
+        let user = service(ctx)
+            .update_user(user_id, input.into())
+            .await
+            .map_err(graphql_error)?;
+        Ok(UserView::for_caller(caller(ctx), user))
+    }
+
+    /// Delete a user, returning `true` on success (admin only)
+    async fn delete_user(&self, ctx: &Context<'_>, id: ID) -> Result<bool, Error> {
+        if !authenticated(ctx)?.is_admin() {
+            return Err(graphql_error(AppError::Forbidden(
+                "Only admins can delete users".to_string(),
+            )));
+        }
+        let user_id = parse_user_id(&id)?;
+        service(ctx)
+            .delete_user(user_id)
+            .await
+            .map_err(graphql_error)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::InMemoryUserRepository;
+
+    fn schema() -> UserSchema {
+        let service = UserService::new(Arc::new(InMemoryUserRepository::default()));
+        build_schema(Arc::new(service))
+    }
+
+    fn as_role(query: impl Into<String>, role: application::Role) -> async_graphql::Request {
+        async_graphql::Request::new(query).data(AuthContext {
+            user_id: UserId::new(),
+            role,
+            actor_id: None,
+            epoch: 0,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_then_query_user() {
+        let schema = schema();
+
+        let created = schema
+            .execute(
+                r#"mutation {
+                    createUser(input: { username: "graphuser", email: "graph@example.com" }) {
+                        id username
+                    }
+                }"#,
+            )
+            .await;
+        assert!(created.errors.is_empty(), "{:?}", created.errors);
+        let data = created.data.into_json().unwrap();
+        assert_eq!(data["createUser"]["username"], "graphuser");
+        let id = data["createUser"]["id"].as_str().unwrap().to_string();
+
+        let fetched = schema
+            .execute(format!(r#"{{ user(id: "{}") {{ email }} }}"#, id))
+            .await;
+        assert!(fetched.errors.is_empty(), "{:?}", fetched.errors);
+        assert_eq!(
+            fetched.data.into_json().unwrap()["user"]["email"],
+            "graph@example.com"
+        );
+
+        let listed = schema
+            .execute(r#"{ users(filter: { search: "GRAPH" }) { total users { username } } }"#)
+            .await;
+        assert!(listed.errors.is_empty(), "{:?}", listed.errors);
+        assert_eq!(listed.data.into_json().unwrap()["users"]["total"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_admin_only_fields_are_shown_to_admins_only() {
+        use application::Role;
+
+        let schema = schema();
+        let created = schema
+            .execute(
+                r#"mutation {
+                    createUser(input: { username: "shaped", email: "shaped@example.com" }) { id }
+                }"#,
+            )
+            .await;
+        let id = created.data.into_json().unwrap()["createUser"]["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let query = format!(
+            r#"{{
+                user(id: "{}") {{ __typename username ... on User {{ status updatedAt }} }}
+                users {{ users {{ __typename ... on User {{ status }} }} }}
+            }}"#,
+            id
+        );
+
+        for response in [
+            schema.execute(query.as_str()).await,
+            schema.execute(as_role(query.as_str(), Role::User)).await,
+        ] {
+            assert!(response.errors.is_empty(), "{:?}", response.errors);
+            let data = response.data.into_json().unwrap();
+            assert_eq!(data["user"]["__typename"], "PublicUser");
+            assert_eq!(data["user"]["username"], "shaped");
+            assert!(data["user"].get("status").is_none());
+            assert!(data["user"].get("updatedAt").is_none());
+            assert_eq!(data["users"]["users"][0]["__typename"], "PublicUser");
+        }
+
+        let response = schema.execute(as_role(query.as_str(), Role::Admin)).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["user"]["__typename"], "User");
+        assert_eq!(data["user"]["status"], "ACTIVE");
+        assert!(data["user"]["updatedAt"].is_string());
+        assert_eq!(data["users"]["users"][0]["status"], "ACTIVE");
+    }
+
+    #[tokio::test]
+    async fn test_update_user_is_limited_to_the_user_and_admins() {
+        use application::Role;
+
+        let schema = schema();
+        let created = schema
+            .execute(
+                r#"mutation {
+                    createUser(input: { username: "owner", email: "owner@example.com" }) { id }
+                }"#,
+            )
+            .await;
+        let id = created.data.into_json().unwrap()["createUser"]["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let rename = |name: &str| {
+            format!(
+                r#"mutation {{ updateUser(id: "{}", input: {{ fullName: "{}" }}) {{ fullName }} }}"#,
+                id, name
+            )
+        };
+        let error_code = |response: async_graphql::Response| {
+            serde_json::to_value(&response).unwrap()["errors"][0]["extensions"]["code"].clone()
+        };
+
+        let anonymous = schema.execute(rename("Intruder")).await;
+        assert_eq!(error_code(anonymous), "unauthorized");
+        let other = schema
+            .execute(as_role(rename("Intruder"), Role::User))
+            .await;
+        assert_eq!(error_code(other), "forbidden");
+
+        let own = async_graphql::Request::new(rename("Own Edit")).data(AuthContext {
+            user_id: UserId::from_uuid(id.parse().unwrap()),
+            role: Role::User,
+            actor_id: None,
+            epoch: 0,
+        });
+        let response = schema.execute(own).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let response = schema
+            .execute(as_role(rename("Admin Edit"), Role::Admin))
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap()["updateUser"]["fullName"],
+            "Admin Edit"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_is_admin_only() {
+        use application::Role;
+
+        let schema = schema();
+        let created = schema
+            .execute(
+                r#"mutation {
+                    createUser(input: { username: "doomed", email: "doomed@example.com" }) { id }
+                }"#,
+            )
+            .await;
+        let id = created.data.into_json().unwrap()["createUser"]["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let delete = format!(r#"mutation {{ deleteUser(id: "{}") }}"#, id);
+        let error_code = |response: async_graphql::Response| {
+            serde_json::to_value(&response).unwrap()["errors"][0]["extensions"]["code"].clone()
+        };
+
+        assert_eq!(
+            error_code(schema.execute(delete.as_str()).await),
+            "unauthorized"
+        );
+        assert_eq!(
+            error_code(schema.execute(as_role(delete.as_str(), Role::User)).await),
+            "forbidden"
+        );
+
+        let response = schema.execute(as_role(delete.as_str(), Role::Admin)).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(response.data.into_json().unwrap()["deleteUser"], true);
+    }
+
+    #[tokio::test]
+    async fn test_errors_carry_app_error_code() {
+        let schema = schema();
+
+        let response = schema
+            .execute(r#"{ user(id: "00000000-0000-0000-0000-000000000000") { id } }"#)
+            .await;
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["errors"][0]["extensions"]["code"], "not_found");
+
+        let response = schema
+            .execute(r#"mutation { createUser(input: { username: "x", email: "bad" }) { id } }"#)
+            .await;
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["errors"][0]["extensions"]["code"], "validation_error");
+        assert!(json["errors"][0]["extensions"]["errors"].is_array());
+    }
+}
@@ -0,0 +1,179 @@
+use async_graphql::{Enum, ID, InputObject, Interface, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use application::{
+    AuthContext, CreateUserRequest, PublicUserResponse, UpdateUserRequest, UserListResponse,
+    UserResponse,
+};
+
+/// User status as exposed over GraphQL
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[graphql(name = "UserStatus", remote = "domain::UserStatus")]
+pub enum UserStatusType {
+    Active,
+    Inactive,
+    Suspended,
+}
+
+/// User as exposed over GraphQL
+#[derive(SimpleObject, Debug)]
+#[graphql(name = "User")]
+pub struct UserType {
+    pub id: ID,
+    pub username: String,
+    pub email: String,
+    pub full_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub status: UserStatusType,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<UserResponse> for UserType {
+    fn from(user: UserResponse) -> Self {
+        Self {
+            id: ID(user.id.to_string()),
+            username: user.username,
+            email: user.email,
+            full_name: user.full_name,
+            avatar_url: user.avatar_url,
+            status: user.status.into(),
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
+/// User as exposed to non-admins, without the admin-only `status` and
+/// `updatedAt`
+#[derive(SimpleObject, Debug)]
+#[graphql(name = "PublicUser")]
+pub struct PublicUserType {
+    pub id: ID,
+    pub username: String,
+    pub email: String,
+    pub full_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<PublicUserResponse> for PublicUserType {
+    fn from(user: PublicUserResponse) -> Self {
+        Self {
+            id: ID(user.id.to_string()),
+            username: user.username,
+            email: user.email,
+            full_name: user.full_name,
+            avatar_url: user.avatar_url,
+            created_at: user.created_at,
+        }
+    }
+}
+
+/// A user in the shape the caller may see: `User` for admins, `PublicUser`
+/// for everyone else
+// Fields sharing a type repeat `ty = ...`, which the lint mistakes for duplicates
+#[allow(clippy::duplicated_attributes)]
+#[derive(Interface, Debug)]
+#[graphql(
+    name = "UserNode",
+    field(name = "id", ty = "&ID"),
+    field(name = "username", ty = "&String"),
+    field(name = "email", ty = "&String"),
+    field(name = "full_name", ty = "&Option<String>"),
+    field(name = "avatar_url", ty = "&Option<String>"),
+    field(name = "created_at", ty = "&DateTime<Utc>")
+)]
+pub enum UserView {
+    User(UserType),
+    PublicUser(PublicUserType),
+}
+
+impl UserView {
+    pub fn for_caller(caller: Option<&AuthContext>, user: UserResponse) -> Self {
+        if caller.is_some_and(AuthContext::is_admin) {
+            Self::User(user.into())
+        } else {
+            Self::PublicUser(PublicUserResponse::from(user).into())
+        }
+    }
+}
+
+/// Paginated user list
+#[derive(SimpleObject, Debug)]
+#[graphql(name = "UserList")]
+pub struct UserListType {
+    pub users: Vec<UserView>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+impl UserListType {
+    pub fn for_caller(caller: Option<&AuthContext>, list: UserListResponse) -> Self {
+        Self {
+            users: list
+                .users
+                .into_iter()
+                .map(|user| UserView::for_caller(caller, user))
+                .collect(),
+            total: list.total,
+            limit: list.limit,
+            offset: list.offset,
+        }
+    }
+}
+
+/// Filter for the `users` query
+#[derive(InputObject, Debug, Default)]
+pub struct UserFilterInput {
+    pub status: Option<UserStatusType>,
+    /// Case-insensitive substring match on username or email
+    pub search: Option<String>,
+}
+
+impl From<UserFilterInput> for domain::UserFilter {
+    fn from(input: UserFilterInput) -> Self {
+        Self {
+            status: input.status.map(Into::into),
+            include_statuses: None,
+            search: input.search,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct CreateUserInput {
+    pub username: String,
+    pub email: String,
+    pub full_name: Option<String>,
+}
+
+impl From<CreateUserInput> for CreateUserRequest {
+    fn from(input: CreateUserInput) -> Self {
+        Self {
+            username: input.username,
+            email: input.email,
+            full_name: input.full_name,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct UpdateUserInput {
+    pub username: Option<String>,
+    pub email: Option<String>,
+    pub full_name: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+impl From<UpdateUserInput> for UpdateUserRequest {
+    fn from(input: UpdateUserInput) -> Self {
+        Self {
+            username: input.username,
+            email: input.email,
+            full_name: input.full_name,
+            avatar_url: input.avatar_url,
+        }
+    }
+}
@@ -0,0 +1,394 @@
+use actix_web::{HttpRequest, HttpResponse, Result, http::StatusCode, web};
+
+use application::{AuthService, ChangePasswordRequest};
+use shared::{AppError, UserId};
+
+use crate::extractors::ValidatedJson;
+use crate::middleware::Authenticated;
+use crate::responses::respond;
+
+/// POST /api/v1/users/:id/impersonate - Issue a short-lived token acting as the user (admin only)
+pub async fn impersonate_user(
+    req: HttpRequest,
+    service: web::Data<AuthService>,
+    caller: Authenticated,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let user_id_str = path.into_inner();
+    let user_id = uuid::Uuid::parse_str(&user_id_str)
+        .map_err(|_| AppError::ValidationError("Invalid user ID format".to_string()))?;
+
+    let token = service
+        .impersonate(&caller.0, UserId::from_uuid(user_id))
+        .await?;
+    Ok(respond(&req, StatusCode::OK, &token)?)
+}
+
+/// POST /api/v1/auth/refresh - Exchange the current token for a fresh one
+pub async fn refresh_token(
+    req: HttpRequest,
+    service: web::Data<AuthService>,
+    caller: Authenticated,
+) -> Result<HttpResponse> {
+    let token = service.refresh(&caller.0)?;
+    Ok(respond(&req, StatusCode::OK, &token)?)
+}
+
+/// POST /api/v1/users/me/password - Change the caller's password
+///
+/// Revokes every other session; the response carries the token that
+/// replaces the caller's own.
+pub async fn change_password(
+    req: HttpRequest,
+    service: web::Data<AuthService>,
+    caller: Authenticated,
+    request: ValidatedJson<ChangePasswordRequest>,
+) -> Result<HttpResponse> {
+    let request = request.into_inner();
+    let token = service
+        .change_password(&caller.0, &request.current_password, &request.new_password)
+        .await?;
+    Ok(respond(&req, StatusCode::OK, &token)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, http::header, middleware::from_fn, test};
+    use std::sync::Arc;
+
+    use application::{CreateUserRequest, Role, TokenResponse, TokenService, UserService};
+    use infrastructure::auth::JwtTokenService;
+    use shared::config::JwtConfig;
+
+    use crate::middleware::authenticate;
+    use crate::test_support::InMemoryUserRepository;
+
+    struct Fixture {
+        auth: web::Data<AuthService>,
+        tokens: Arc<JwtTokenService>,
+        repo: Arc<InMemoryUserRepository>,
+        target: UserId,
+    }
+
+    impl Fixture {
+        /// Store another user, so tokens issued for it authenticate
+        async fn account(&self, username: &str) -> UserId {
+            create_account(&self.repo, username).await
+        }
+    }
+
+    async fn create_account(repo: &Arc<InMemoryUserRepository>, username: &str) -> UserId {
+        UserService::new(repo.clone())
+            .create_user(CreateUserRequest {
+                username: username.to_string(),
+                email: format!("{}@example.com", username),
+                full_name: None,
+            })
+            .await
+            .unwrap()
+            .id
+    }
+
+    async fn fixture() -> Fixture {
+        let repo = Arc::new(InMemoryUserRepository::default());
+        let target = create_account(&repo, "customer").await;
+        let config = JwtConfig::default();
+        let tokens = Arc::new(JwtTokenService::new(&config).unwrap());
+        let auth = web::Data::new(AuthService::new(repo.clone(), tokens.clone(), config));
+        Fixture {
+            auth,
+            tokens,
+            repo,
+            target,
+        }
+    }
+
+    fn impersonate_request(target: UserId, token: &str) -> test::TestRequest {
+        test::TestRequest::post()
+            .uri(&format!("/users/{}/impersonate", target))
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+    }
+
+    #[actix_web::test]
+    async fn test_impersonation_token_carries_subject_and_actor() {
+        let f = fixture().await;
+        let admin_id = f.account("operator").await;
+        let admin = f.auth.issue_token(admin_id, Role::Admin).await.unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(f.auth.clone())
+                .wrap(from_fn(authenticate))
+                .route("/users/{id}/impersonate", web::post().to(impersonate_user))
+                .route("/auth/refresh", web::post().to(refresh_token)),
+        )
+        .await;
+
+        let req = impersonate_request(f.target, &admin.access_token).to_request();
+        let issued: TokenResponse = test::call_and_read_body_json(&app, req).await;
+
+        let claims = f.tokens.verify(&issued.access_token).unwrap();
+        assert_eq!(claims.sub, f.target);
+        assert_eq!(claims.act.map(|actor| actor.sub), Some(admin_id));
+        assert_eq!(claims.role, Role::User);
+        assert_eq!(issued.actor_id, Some(admin_id));
+        assert!(claims.exp - claims.iat <= JwtConfig::default().impersonation_ttl_seconds as i64);
+    }
+
+    #[actix_web::test]
+    async fn test_non_admin_cannot_impersonate() {
+        let f = fixture().await;
+        let user_id = f.account("regular").await;
+        let user = f.auth.issue_token(user_id, Role::User).await.unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(f.auth.clone())
+                .wrap(from_fn(authenticate))
+                .route("/users/{id}/impersonate", web::post().to(impersonate_user))
+                .route("/auth/refresh", web::post().to(refresh_token)),
+        )
+        .await;
+
+        let req = impersonate_request(f.target, &user.access_token).to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[actix_web::test]
+    async fn test_impersonation_token_cannot_be_refreshed() {
+        let f = fixture().await;
+        let admin_id = f.account("operator").await;
+        let admin = f.auth.issue_token(admin_id, Role::Admin).await.unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(f.auth.clone())
+                .wrap(from_fn(authenticate))
+                .route("/users/{id}/impersonate", web::post().to(impersonate_user))
+                .route("/auth/refresh", web::post().to(refresh_token)),
+        )
+        .await;
+        let req = impersonate_request(f.target, &admin.access_token).to_request();
+        let issued: TokenResponse = test::call_and_read_body_json(&app, req).await;
+
+        let refresh = |token: &str| {
+            test::TestRequest::post()
+                .uri("/auth/refresh")
+                .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+                .to_request()
+        };
+
+        let resp = test::call_service(&app, refresh(&issued.access_token)).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        let resp = test::call_service(&app, refresh(&admin.access_token)).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_deleted_user_token_is_rejected() {
+        use domain::UserRepository;
+
+        let f = fixture().await;
+        let admin_id = f.account("operator").await;
+        let admin = f.auth.issue_token(admin_id, Role::Admin).await.unwrap();
+        f.auth.authenticate(&admin.access_token).await.unwrap();
+
+        f.repo.delete(admin_id).await.unwrap();
+
+        assert!(matches!(
+            f.auth.authenticate(&admin.access_token).await,
+            Err(AppError::Unauthorized(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_password_with_outdated_parameters_is_rehashed() {
+        use application::PasswordHasher;
+        use domain::UserRepository;
+        use infrastructure::auth::Argon2PasswordHasher;
+        use shared::config::PasswordHashing;
+
+        let hashing = |memory_kib| PasswordHashing {
+            memory_kib,
+            iterations: 1,
+            parallelism: 1,
+            rehash_on_login: true,
+        };
+        let current = hashing(16);
+        let hasher = Arc::new(Argon2PasswordHasher::new(&current).unwrap());
+        let repo = Arc::new(InMemoryUserRepository::default());
+        let user = UserService::new(repo.clone())
+            .create_user(CreateUserRequest {
+                username: "legacy".to_string(),
+                email: "legacy@example.com".to_string(),
+                full_name: None,
+            })
+            .await
+            .unwrap();
+        let old_hash = Argon2PasswordHasher::new(&hashing(8))
+            .unwrap()
+            .hash("Secret123")
+            .unwrap();
+        repo.set_password_hash(user.id, &old_hash).await.unwrap();
+        let config = JwtConfig::default();
+        let auth = AuthService::new(
+            repo.clone(),
+            Arc::new(JwtTokenService::new(&config).unwrap()),
+            config,
+        )
+        .with_password_hasher(hasher.clone(), &current);
+
+        auth.verify_password(user.id, "Secret123").await.unwrap();
+
+        let stored = repo.find_password_hash(user.id).await.unwrap().unwrap();
+        assert_ne!(stored, old_hash);
+        assert!(!hasher.needs_rehash(&stored));
+        auth.verify_password(user.id, "Secret123").await.unwrap();
+        assert_eq!(
+            repo.find_password_hash(user.id).await.unwrap().unwrap(),
+            stored
+        );
+        assert!(matches!(
+            auth.verify_password(user.id, "Wrong123").await,
+            Err(AppError::Unauthorized(_))
+        ));
+    }
+
+    struct PasswordFixture {
+        auth: web::Data<AuthService>,
+        user: UserId,
+    }
+
+    /// A user whose password is `Secret123`, with a fast hasher
+    async fn password_fixture() -> PasswordFixture {
+        use application::PasswordHasher;
+        use domain::UserRepository;
+        use infrastructure::auth::Argon2PasswordHasher;
+        use shared::config::{PasswordHashing, PasswordPolicy};
+
+        let hashing = PasswordHashing {
+            memory_kib: 16,
+            iterations: 1,
+            parallelism: 1,
+            rehash_on_login: false,
+        };
+        let hasher = Arc::new(Argon2PasswordHasher::new(&hashing).unwrap());
+        let repo = Arc::new(InMemoryUserRepository::default());
+        let user = UserService::new(repo.clone())
+            .create_user(CreateUserRequest {
+                username: "rotator".to_string(),
+                email: "rotator@example.com".to_string(),
+                full_name: None,
+            })
+            .await
+            .unwrap()
+            .id;
+        repo.set_password_hash(user, &hasher.hash("Secret123").unwrap())
+            .await
+            .unwrap();
+        let config = JwtConfig::default();
+        let auth = web::Data::new(
+            AuthService::new(
+                repo,
+                Arc::new(JwtTokenService::new(&config).unwrap()),
+                config,
+            )
+            .with_password_hasher(hasher, &hashing)
+            .with_password_policy(PasswordPolicy {
+                min_length: 8,
+                max_length: 64,
+                require_upper: false,
+                require_digit: true,
+                require_symbol: false,
+            }),
+        );
+        PasswordFixture { auth, user }
+    }
+
+    fn change_password_request(token: &str, current: &str, new: &str) -> test::TestRequest {
+        test::TestRequest::post()
+            .uri("/users/me/password")
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+            .set_json(serde_json::json!({
+                "current_password": current,
+                "new_password": new,
+            }))
+    }
+
+    #[actix_web::test]
+    async fn test_change_password_revokes_other_sessions() {
+        let f = password_fixture().await;
+        let other_session = f.auth.issue_token(f.user, Role::User).await.unwrap();
+        let session = f.auth.issue_token(f.user, Role::User).await.unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(f.auth.clone())
+                .wrap(from_fn(authenticate))
+                .route("/users/me/password", web::post().to(change_password))
+                .route("/auth/refresh", web::post().to(refresh_token)),
+        )
+        .await;
+
+        let req =
+            change_password_request(&session.access_token, "Secret123", "Rotated456").to_request();
+        let issued: TokenResponse = test::call_and_read_body_json(&app, req).await;
+
+        f.auth.verify_password(f.user, "Rotated456").await.unwrap();
+        let refresh = |token: &str| {
+            test::TestRequest::post()
+                .uri("/auth/refresh")
+                .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+                .to_request()
+        };
+        // Rejected by the middleware, before any response is built
+        let Err(err) = test::try_call_service(&app, refresh(&other_session.access_token)).await
+        else {
+            panic!("a revoked session must be rejected");
+        };
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::UNAUTHORIZED
+        );
+        let resp = test::call_service(&app, refresh(&issued.access_token)).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn test_change_password_rejects_wrong_current_password() {
+        let f = password_fixture().await;
+        let session = f.auth.issue_token(f.user, Role::User).await.unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(f.auth.clone())
+                .wrap(from_fn(authenticate))
+                .route("/users/me/password", web::post().to(change_password)),
+        )
+        .await;
+
+        let req =
+            change_password_request(&session.access_token, "Wrong123", "Rotated456").to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        f.auth.verify_password(f.user, "Secret123").await.unwrap();
+    }
+
+    #[actix_web::test]
+    async fn test_change_password_enforces_policy_on_new_password() {
+        let f = password_fixture().await;
+        let session = f.auth.issue_token(f.user, Role::User).await.unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(f.auth.clone())
+                .wrap(from_fn(authenticate))
+                .route("/users/me/password", web::post().to(change_password)),
+        )
+        .await;
+
+        let req = change_password_request(&session.access_token, "Secret123", "weak").to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        f.auth.verify_password(f.user, "Secret123").await.unwrap();
+    }
+}
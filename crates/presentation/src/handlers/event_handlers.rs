@@ -0,0 +1,383 @@
+use actix_web::http::header;
+use actix_web::web::Bytes;
+use actix_web::{HttpRequest, HttpResponse, Result, web};
+use actix_ws::{Message, MessageStream, Session};
+use chrono::{DateTime, Utc};
+use futures_util::stream;
+use serde::Serialize;
+use std::collections::VecDeque;
+use tokio::sync::broadcast::{Receiver, error::RecvError};
+
+use application::{AuthContext, PublicUserResponse, UserResponse};
+use domain::{UserEvent, UserEventPayload};
+use shared::UserId;
+
+use crate::middleware::Authenticated;
+use crate::states::{AppState, SequencedEvent};
+
+/// `UserEvent` as sent to non-admins, with users in their public shape
+#[derive(Serialize)]
+struct PublicUserEvent {
+    #[serde(flatten)]
+    payload: PublicUserEventPayload,
+    occurred_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", content = "data")]
+enum PublicUserEventPayload {
+    #[serde(rename = "user.created")]
+    Created(PublicUserResponse),
+    #[serde(rename = "user.updated")]
+    Updated(PublicUserResponse),
+    #[serde(rename = "user.deleted")]
+    Deleted { id: UserId },
+}
+
+impl From<&UserEvent> for PublicUserEvent {
+    fn from(event: &UserEvent) -> Self {
+        let public =
+            |user: &domain::User| PublicUserResponse::from(UserResponse::from(user.clone()));
+        let payload = match &event.payload {
+            UserEventPayload::Created(user) => PublicUserEventPayload::Created(public(user)),
+            UserEventPayload::Updated(user) => PublicUserEventPayload::Updated(public(user)),
+            UserEventPayload::Deleted { id } => PublicUserEventPayload::Deleted { id: *id },
+        };
+        Self {
+            payload,
+            occurred_at: event.occurred_at,
+        }
+    }
+}
+
+/// Serialize `event` in the shape `caller` may see: admins get the full
+/// user, everyone else the public fields
+fn event_json(caller: Option<&AuthContext>, event: &UserEvent) -> serde_json::Result<String> {
+    if caller.is_some_and(AuthContext::is_admin) {
+        serde_json::to_string(event)
+    } else {
+        serde_json::to_string(&PublicUserEvent::from(event))
+    }
+}
+
+/// GET /api/v1/users/events - Stream user lifecycle events as Server-Sent Events
+///
+/// Clients reconnecting with `Last-Event-ID` first receive the buffered
+/// events they missed. Users are sent in full to admins only.
+pub async fn user_events_sse(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    caller: Option<Authenticated>,
+) -> HttpResponse {
+    let caller = caller.map(|caller| caller.0);
+    let last_event_id = req
+        .headers()
+        .get("Last-Event-ID")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok());
+
+    let (backlog, receiver) = state.events.history().subscribe_after(last_event_id);
+    let backlog: VecDeque<SequencedEvent> = backlog.into();
+
+    let frames = stream::unfold(
+        (backlog, receiver),
+        move |(mut backlog, mut receiver)| async move {
+            let event = match backlog.pop_front() {
+                Some(event) => event,
+                None => loop {
+                    match receiver.recv().await {
+                        Ok(event) => break event,
+                        Err(RecvError::Lagged(skipped)) => {
+                            tracing::warn!("SSE client lagged, skipped {} events", skipped);
+                        }
+                        Err(RecvError::Closed) => return None,
+                    }
+                },
+            };
+            let frame = sse_frame(caller.as_ref(), &event);
+            Some((Ok::<_, actix_web::Error>(frame), (backlog, receiver)))
+        },
+    );
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(frames)
+}
+
+/// Render one event as an SSE frame with `id:`, `event:` and JSON `data:`
+fn sse_frame(caller: Option<&AuthContext>, sequenced: &SequencedEvent) -> Bytes {
+    let data = event_json(caller, &sequenced.event).unwrap_or_else(|e| {
+        tracing::error!("Failed to serialize user event: {}", e);
+        "{}".to_string()
+    });
+    Bytes::from(format!(
+        "id: {}\nevent: {}\ndata: {}\n\n",
+        sequenced.id,
+        sequenced.event.event_type(),
+        data
+    ))
+}
+
+/// GET /ws/users - Stream user lifecycle events over a WebSocket
+///
+/// Users are sent in full to admins only.
+pub async fn user_events_ws(
+    req: HttpRequest,
+    body: web::Payload,
+    state: web::Data<AppState>,
+    caller: Option<Authenticated>,
+) -> Result<HttpResponse> {
+    let (response, session, messages) = actix_ws::handle(&req, body)?;
+    let events = state.events.subscribe();
+
+    actix_web::rt::spawn(forward_events(
+        session,
+        messages,
+        events,
+        caller.map(|caller| caller.0),
+    ));
+
+    Ok(response)
+}
+
+/// Push events to the client until either side goes away
+async fn forward_events(
+    mut session: Session,
+    mut messages: MessageStream,
+    mut events: Receiver<UserEvent>,
+    caller: Option<AuthContext>,
+) {
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => {
+                    let json = match event_json(caller.as_ref(), &event) {
+                        Ok(json) => json,
+                        Err(e) => {
+                            tracing::error!("Failed to serialize user event: {}", e);
+                            continue;
+                        }
+                    };
+                    if session.text(json).await.is_err() {
+                        // Client already disconnected
+                        return;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("WebSocket client lagged, skipped {} events", skipped);
+                }
+                Err(RecvError::Closed) => break,
+            },
+            message = messages.recv() => match message {
+                Some(Ok(Message::Ping(bytes))) => {
+                    if session.pong(&bytes).await.is_err() {
+                        return;
+                    }
+                }
+                Some(Ok(Message::Close(reason))) => {
+                    let _ = session.close(reason).await;
+                    return;
+                }
+                Some(Ok(_)) => {}
+                Some(Err(_)) | None => return,
+            },
+        }
+    }
+
+    let _ = session.close(None).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::App;
+    use awc::ws::Frame;
+    use futures_util::StreamExt;
+    use std::sync::Arc;
+
+    use application::{CreateUserRequest, UserService};
+
+    use crate::test_support::InMemoryUserRepository;
+
+    #[actix_web::test]
+    async fn test_created_user_is_pushed_to_websocket_client() {
+        let state = AppState::new();
+        let service = Arc::new(
+            UserService::new(Arc::new(InMemoryUserRepository::default()))
+                .with_event_bus(state.events.bus()),
+        );
+
+        let app_state = web::Data::new(state);
+        let mut srv = actix_test::start(move || {
+            App::new()
+                .app_data(app_state.clone())
+                .configure(crate::routes::events::configure)
+        });
+
+        let mut framed = srv.ws_at("/ws/users").await.unwrap();
+
+        service
+            .create_user(CreateUserRequest {
+                username: "wsuser".to_string(),
+                email: "ws@example.com".to_string(),
+                full_name: None,
+            })
+            .await
+            .unwrap();
+
+        let frame = framed.next().await.unwrap().unwrap();
+        let Frame::Text(bytes) = frame else {
+            panic!("expected text frame, got {:?}", frame);
+        };
+        let message: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(message["type"], "user.created");
+        assert_eq!(message["data"]["username"], "wsuser");
+    }
+
+    #[actix_web::test]
+    async fn test_created_user_produces_sse_frame() {
+        let state = AppState::new();
+        state.events.spawn_history_recorder();
+        let service = Arc::new(
+            UserService::new(Arc::new(InMemoryUserRepository::default()))
+                .with_event_bus(state.events.bus()),
+        );
+
+        let app_state = web::Data::new(state);
+        let srv = actix_test::start(move || {
+            App::new()
+                .app_data(app_state.clone())
+                .route("/users/events", web::get().to(user_events_sse))
+        });
+
+        let mut response = srv.get("/users/events").send().await.unwrap();
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/event-stream"
+        );
+
+        service
+            .create_user(CreateUserRequest {
+                username: "sseuser".to_string(),
+                email: "sse@example.com".to_string(),
+                full_name: None,
+            })
+            .await
+            .unwrap();
+
+        let chunk = response.next().await.unwrap().unwrap();
+        let frame = std::str::from_utf8(&chunk).unwrap();
+        assert!(frame.starts_with("id: 1\nevent: user.created\ndata: {"));
+        assert!(frame.contains("\"username\":\"sseuser\""));
+        assert!(frame.ends_with("\n\n"));
+    }
+
+    #[actix_web::test]
+    async fn test_sse_sends_admin_only_fields_to_admins_only() {
+        use actix_web::{HttpMessage, dev::Service};
+        use application::Role;
+
+        let state = AppState::new();
+        state.events.spawn_history_recorder();
+        let service = Arc::new(
+            UserService::new(Arc::new(InMemoryUserRepository::default()))
+                .with_event_bus(state.events.bus()),
+        );
+
+        let app_state = web::Data::new(state);
+        let srv = actix_test::start(move || {
+            App::new()
+                .app_data(app_state.clone())
+                .wrap_fn(|req, srv| {
+                    let role = match req.headers().get(header::AUTHORIZATION) {
+                        Some(value) if value == "Bearer admin" => Some(Role::Admin),
+                        Some(_) => Some(Role::User),
+                        None => None,
+                    };
+                    if let Some(role) = role {
+                        req.extensions_mut().insert(AuthContext {
+                            user_id: UserId::new(),
+                            role,
+                            actor_id: None,
+                            epoch: 0,
+                        });
+                    }
+                    srv.call(req)
+                })
+                .route("/users/events", web::get().to(user_events_sse))
+        });
+
+        let mut anonymous = srv.get("/users/events").send().await.unwrap();
+        let mut user = srv
+            .get("/users/events")
+            .insert_header((header::AUTHORIZATION, "Bearer user"))
+            .send()
+            .await
+            .unwrap();
+        let mut admin = srv
+            .get("/users/events")
+            .insert_header((header::AUTHORIZATION, "Bearer admin"))
+            .send()
+            .await
+            .unwrap();
+
+        service
+            .create_user(CreateUserRequest {
+                username: "watched".to_string(),
+                email: "watched@example.com".to_string(),
+                full_name: None,
+            })
+            .await
+            .unwrap();
+
+        let data = |chunk: Bytes| -> serde_json::Value {
+            let frame = std::str::from_utf8(&chunk).unwrap().to_string();
+            let data = frame
+                .lines()
+                .find_map(|line| line.strip_prefix("data: "))
+                .unwrap();
+            serde_json::from_str(data).unwrap()
+        };
+        for response in [&mut anonymous, &mut user] {
+            let event = data(response.next().await.unwrap().unwrap());
+            assert_eq!(event["type"], "user.created");
+            assert_eq!(event["data"]["username"], "watched");
+            assert!(event["data"].get("status").is_none());
+            assert!(event["data"].get("updated_at").is_none());
+        }
+        let event = data(admin.next().await.unwrap().unwrap());
+        assert_eq!(event["data"]["username"], "watched");
+        assert_eq!(event["data"]["status"], "active");
+        assert!(event["data"]["updated_at"].is_string());
+    }
+
+    #[actix_web::test]
+    async fn test_sse_replays_events_after_last_event_id() {
+        let state = AppState::new();
+        for _ in 0..3 {
+            state
+                .events
+                .history()
+                .record(UserEvent::deleted(UserId::new()));
+        }
+
+        let app_state = web::Data::new(state);
+        let srv = actix_test::start(move || {
+            App::new()
+                .app_data(app_state.clone())
+                .route("/users/events", web::get().to(user_events_sse))
+        });
+
+        let mut response = srv
+            .get("/users/events")
+            .insert_header(("Last-Event-ID", "2"))
+            .send()
+            .await
+            .unwrap();
+
+        let chunk = response.next().await.unwrap().unwrap();
+        let frame = std::str::from_utf8(&chunk).unwrap();
+        assert!(frame.starts_with("id: 3\nevent: user.deleted\n"));
+    }
+}
@@ -0,0 +1,239 @@
+use actix_multipart::Multipart;
+use actix_web::{HttpRequest, HttpResponse, Result, http::StatusCode, web};
+use futures_util::TryStreamExt;
+use serde::Deserialize;
+
+use application::{BulkCreateRow, BulkRowResult, CreateUserRequest, UserService};
+use shared::{AppError, FieldError};
+
+use crate::handlers::user_handlers::BulkQuery;
+use crate::middleware::in_transaction;
+use crate::responses::respond;
+
+/// Largest CSV upload accepted by the import endpoint
+const MAX_IMPORT_BYTES: usize = 5 * 1024 * 1024;
+
+/// Expected CSV columns; the header row is required
+#[derive(Debug, Deserialize)]
+struct CsvUserRow {
+    username: String,
+    email: String,
+    #[serde(default)]
+    full_name: Option<String>,
+}
+
+/// POST /api/v1/users/import - Import users from an uploaded CSV file
+///
+/// Expects a multipart upload whose first file part is a CSV with a
+/// `username,email,full_name` header. Responds with a per-row report;
+/// malformed rows are reported rather than aborting the import.
+/// `?dry_run=true` previews the report without writing anything. The rows
+/// are written in one transaction, begun only after the upload is read.
+pub async fn import_users(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    query: web::Query<BulkQuery>,
+    mut payload: Multipart,
+) -> Result<HttpResponse> {
+    let mut content: Option<Vec<u8>> = None;
+    while let Some(mut field) = payload.try_next().await? {
+        if content.is_some() {
+            // Only the first part is imported; drain the rest
+            while field.try_next().await?.is_some() {}
+            continue;
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.try_next().await? {
+            if bytes.len() + chunk.len() > MAX_IMPORT_BYTES {
+                return Err(AppError::ValidationError(format!(
+                    "CSV upload exceeds {} bytes",
+                    MAX_IMPORT_BYTES
+                ))
+                .into());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        content = Some(bytes);
+    }
+
+    let content =
+        content.ok_or_else(|| AppError::ValidationError("Missing CSV file upload".to_string()))?;
+
+    let (rows, failures) = parse_user_csv(&content)?;
+    in_transaction(&req, async {
+        let report = service
+            .bulk_create_users(rows, query.dry_run)
+            .await?
+            .with_failures(failures);
+        Ok(respond(&req, StatusCode::OK, &report)?)
+    })
+    .await
+}
+
+/// Parse CSV content into bulk rows; unparseable records become failed rows.
+/// Rows are numbered from 1, not counting the header.
+fn parse_user_csv(content: &[u8]) -> Result<(Vec<BulkCreateRow>, Vec<BulkRowResult>), AppError> {
+    // Flexible so a missing trailing optional column is accepted; extra
+    // columns are rejected per row below
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(content);
+
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::ValidationError(format!("Invalid CSV header: {}", e)))?
+        .clone();
+    for required in ["username", "email"] {
+        if !headers.iter().any(|h| h == required) {
+            return Err(AppError::ValidationError(format!(
+                "CSV header is missing the '{}' column",
+                required
+            )));
+        }
+    }
+
+    let mut rows = Vec::new();
+    let mut failures = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let row = index + 1;
+        let record = record.map_err(|e| e.to_string()).and_then(|record| {
+            if record.len() > headers.len() {
+                return Err(format!(
+                    "expected at most {} fields, found {}",
+                    headers.len(),
+                    record.len()
+                ));
+            }
+            record
+                .deserialize::<CsvUserRow>(Some(&headers))
+                .map_err(|e| e.to_string())
+        });
+        match record {
+            Ok(record) => rows.push(BulkCreateRow {
+                row,
+                request: CreateUserRequest {
+                    username: record.username,
+                    email: record.email,
+                    full_name: record.full_name.filter(|name| !name.is_empty()),
+                },
+            }),
+            Err(e) => failures.push(BulkRowResult::failed(
+                row,
+                vec![FieldError {
+                    field: "row".to_string(),
+                    code: "malformed_row".to_string(),
+                    message: e,
+                }],
+            )),
+        }
+    }
+
+    Ok((rows, failures))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, http::header, test};
+    use std::sync::Arc;
+
+    use crate::test_support::InMemoryUserRepository;
+
+    fn multipart_body(boundary: &str, csv: &str) -> String {
+        format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"users.csv\"\r\n\
+             Content-Type: text/csv\r\n\r\n{csv}\r\n--{b}--\r\n",
+            b = boundary,
+            csv = csv
+        )
+    }
+
+    #[actix_web::test]
+    async fn test_import_reports_mixed_rows() {
+        let service = web::Data::new(UserService::new(
+            Arc::new(InMemoryUserRepository::default()),
+        ));
+        let app = test::init_service(
+            App::new()
+                .app_data(service.clone())
+                .route("/users/import", web::post().to(import_users)),
+        )
+        .await;
+
+        let csv = "username,email,full_name\n\
+                   alice,alice@example.com,Alice\n\
+                   bob,not-an-email,\n\
+                   carol,carol@example.com\n\
+                   dave,dave@example.com,Dave,extra\n";
+        let boundary = "XBOUNDARYX";
+        let req = test::TestRequest::post()
+            .uri("/users/import")
+            .insert_header((
+                header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={}", boundary),
+            ))
+            .set_payload(multipart_body(boundary, csv))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let report: serde_json::Value = test::read_body_json(resp).await;
+
+        assert_eq!(report["total"], 4);
+        assert_eq!(report["created"], 2);
+        assert_eq!(report["failed"], 2);
+        let rows = report["rows"].as_array().unwrap();
+        assert_eq!(rows[0]["status"], "created");
+        assert_eq!(rows[1]["status"], "failed");
+        assert_eq!(rows[1]["errors"][0]["field"], "email");
+        assert_eq!(rows[2]["status"], "created");
+        assert_eq!(rows[3]["row"], 4);
+        assert_eq!(rows[3]["errors"][0]["code"], "malformed_row");
+
+        assert_eq!(
+            service.list_users(None, None, 20, 0).await.unwrap().total,
+            2
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_dry_run_import_writes_nothing() {
+        let service = web::Data::new(UserService::new(
+            Arc::new(InMemoryUserRepository::default()),
+        ));
+        let app = test::init_service(
+            App::new()
+                .app_data(service.clone())
+                .route("/users/import", web::post().to(import_users)),
+        )
+        .await;
+
+        let boundary = "XBOUNDARYX";
+        let req = test::TestRequest::post()
+            .uri("/users/import?dry_run=true")
+            .insert_header((
+                header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={}", boundary),
+            ))
+            .set_payload(multipart_body(
+                boundary,
+                "username,email\nalice,alice@example.com\n",
+            ))
+            .to_request();
+
+        let report: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(report["dry_run"], true);
+        assert_eq!(report["created"], 1);
+        assert_eq!(
+            service.list_users(None, None, 20, 0).await.unwrap().total,
+            0
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_missing_required_column_is_rejected() {
+        let result = parse_user_csv(b"username,full_name\nalice,Alice\n");
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
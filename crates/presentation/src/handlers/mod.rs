@@ -1,3 +1,6 @@
+pub mod auth_handlers;
+pub mod event_handlers;
+pub mod import_handlers;
 pub mod user_handlers;
 
 pub use user_handlers::*;
@@ -1,75 +1,369 @@
-use actix_web::{HttpResponse, Result, web};
+use actix_web::{HttpRequest, HttpResponse, Result, http::StatusCode, web};
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
-use application::{CreateUserRequest, UpdateUserRequest, UserService};
-use shared::{AppError, UserId};
+use application::{
+    AuthContext, BatchDeleteRequest, BatchGetRequest, BulkCreateRow, CreateUserRequest,
+    PublicUserResponse, UpdateProfileRequest, UpdateUserRequest, UserListResponse, UserResponse,
+    UserService,
+};
+use serde_json::Value;
+use shared::{AppError, AppResult, UserId};
+use validator::Validate;
+
+use crate::extractors::validated_json::field_errors;
+use crate::extractors::{JsonPatch, ValidatedJson};
+use crate::middleware::Authenticated;
+use crate::responses::{
+    FieldSelection, PagePosition, USER_FIELDS, respond, respond_cacheable, respond_per_caller,
+    select_user, select_user_list, set_pagination_headers,
+};
 
 /// Query parameters for user listing
+///
+/// `cursor` selects keyset pagination and `offset` offset pagination; they
+/// cannot be combined. With neither, the first page is returned.
 #[derive(Debug, Deserialize)]
 pub struct ListUsersQuery {
     #[serde(default = "default_limit")]
     pub limit: i64,
-    #[serde(default)]
-    pub offset: i64,
+    pub offset: Option<i64>,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    /// Comma-separated subset of user fields to return
+    pub fields: Option<String>,
+    /// Comma-separated sort keys, `-` for descending (e.g. `status,-username`);
+    /// offset pagination only
+    pub sort: Option<String>,
 }
 
 fn default_limit() -> i64 {
     20
 }
 
+/// Query parameters for paged lookups without field selection
+#[derive(Debug, Deserialize)]
+pub struct PageQuery {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+/// Query parameters for the recent-signups listing
+#[derive(Debug, Deserialize)]
+pub struct RecentUsersQuery {
+    /// RFC 3339 timestamp; only users created after it are returned
+    pub since: String,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+/// Query parameters for single-user reads
+#[derive(Debug, Deserialize)]
+pub struct FieldsQuery {
+    /// Comma-separated subset of user fields to return
+    pub fields: Option<String>,
+}
+
+/// Query parameters for bulk create and import
+#[derive(Debug, Deserialize)]
+pub struct BulkQuery {
+    /// Validate and check uniqueness, but roll back instead of writing
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Whether `caller` sees the full `UserResponse`; everyone else, the user
+/// themselves included, gets `PublicUserResponse`. Responses shaped by it
+/// are sent with `respond_per_caller` so caches keep the views apart.
+fn sees_admin_fields(caller: Option<&AuthContext>) -> bool {
+    caller.is_some_and(AuthContext::is_admin)
+}
+
+/// Serialize `user` in the shape `caller` may see
+fn user_view(
+    caller: Option<&AuthContext>,
+    user: UserResponse,
+    selection: Option<&FieldSelection>,
+) -> AppResult<Value> {
+    if sees_admin_fields(caller) {
+        select_user(&user, selection)
+    } else {
+        select_user(&PublicUserResponse::from(user), selection)
+    }
+}
+
+/// Serialize a page of users in the shape `caller` may see
+fn user_list_view(
+    caller: Option<&AuthContext>,
+    users: UserListResponse,
+    selection: Option<&FieldSelection>,
+) -> AppResult<Value> {
+    if sees_admin_fields(caller) {
+        select_user_list(&users, selection)
+    } else {
+        select_user_list(&users.into_public(), selection)
+    }
+}
+
 /// POST /api/v1/users - Create a new user
 pub async fn create_user(
+    req: HttpRequest,
     service: web::Data<UserService>,
-    request: web::Json<CreateUserRequest>,
+    request: ValidatedJson<CreateUserRequest>,
 ) -> Result<HttpResponse> {
     let user = service.create_user(request.into_inner()).await?;
-    Ok(HttpResponse::Created().json(user))
+    Ok(respond(&req, StatusCode::CREATED, &user)?)
+}
+
+/// POST /api/v1/users/bulk - Create many users, reporting the outcome per row
+pub async fn bulk_create_users(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    query: web::Query<BulkQuery>,
+    request: web::Json<Vec<CreateUserRequest>>,
+) -> Result<HttpResponse> {
+    let rows = request
+        .into_inner()
+        .into_iter()
+        .enumerate()
+        .map(|(index, request)| BulkCreateRow {
+            row: index + 1,
+            request,
+        })
+        .collect();
+    let report = service.bulk_create_users(rows, query.dry_run).await?;
+    Ok(respond(&req, StatusCode::OK, &report)?)
 }
 
 /// GET /api/v1/users/:id - Get user by ID
 pub async fn get_user(
+    req: HttpRequest,
     service: web::Data<UserService>,
+    caller: Option<Authenticated>,
     path: web::Path<String>,
+    query: web::Query<FieldsQuery>,
 ) -> Result<HttpResponse> {
+    let selection = FieldSelection::parse(query.fields.as_deref(), USER_FIELDS)?;
     let user_id_str = path.into_inner();
     let user_id = uuid::Uuid::parse_str(&user_id_str)
         .map_err(|_| AppError::ValidationError("Invalid user ID format".to_string()))?;
 
     let user = service.get_user(UserId::from_uuid(user_id)).await?;
-    Ok(HttpResponse::Ok().json(user))
+    let caller = caller.map(|caller| caller.0);
+    let body = user_view(caller.as_ref(), user, selection.as_ref())?;
+    Ok(respond_per_caller(&req, StatusCode::OK, &body)?)
+}
+
+/// GET /api/v1/users/me - The authenticated caller's own profile
+pub async fn me(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    caller: Authenticated,
+) -> Result<HttpResponse> {
+    let user = service.get_user(caller.0.user_id).await?;
+    Ok(respond(
+        &req,
+        StatusCode::OK,
+        &PublicUserResponse::from(user),
+    )?)
+}
+
+/// PATCH /api/v1/users/me - Update the caller's own profile fields
+pub async fn update_me(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    caller: Authenticated,
+    request: ValidatedJson<UpdateProfileRequest>,
+) -> Result<HttpResponse> {
+    let user = service
+        .update_user(caller.0.user_id, request.into_inner().into())
+        .await?;
+    Ok(respond(
+        &req,
+        StatusCode::OK,
+        &PublicUserResponse::from(user),
+    )?)
 }
 
 /// GET /api/v1/users/username/:username - Get user by username
 pub async fn get_user_by_username(
+    req: HttpRequest,
     service: web::Data<UserService>,
+    caller: Option<Authenticated>,
     path: web::Path<String>,
+    query: web::Query<FieldsQuery>,
 ) -> Result<HttpResponse> {
+    let selection = FieldSelection::parse(query.fields.as_deref(), USER_FIELDS)?;
     let username = path.into_inner();
     let user = service.get_user_by_username(username).await?;
-    Ok(HttpResponse::Ok().json(user))
+    let caller = caller.map(|caller| caller.0);
+    let body = user_view(caller.as_ref(), user, selection.as_ref())?;
+    Ok(respond_per_caller(&req, StatusCode::OK, &body)?)
+}
+
+/// GET /api/v1/users/domain/:domain - List users with an email at `domain` (admin only)
+pub async fn users_by_domain(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    caller: Authenticated,
+    path: web::Path<String>,
+    query: web::Query<PageQuery>,
+) -> Result<HttpResponse> {
+    if !caller.0.is_admin() {
+        return Err(
+            AppError::Forbidden("Only admins can search users by domain".to_string()).into(),
+        );
+    }
+
+    let users = service
+        .users_by_domain(path.into_inner(), query.limit, query.offset)
+        .await?;
+    Ok(respond(&req, StatusCode::OK, &users)?)
+}
+
+/// GET /api/v1/users/recent?since=&limit= - Users created after `since`, newest first
+pub async fn recent_users(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    caller: Option<Authenticated>,
+    query: web::Query<RecentUsersQuery>,
+) -> Result<HttpResponse> {
+    let since = DateTime::parse_from_rfc3339(&query.since)
+        .map_err(|_| AppError::ValidationError("since must be an RFC 3339 timestamp".to_string()))?
+        .with_timezone(&Utc);
+
+    let caller = caller.map(|caller| caller.0);
+    let users = service
+        .recent_users(caller.as_ref(), since, query.limit)
+        .await?;
+    if sees_admin_fields(caller.as_ref()) {
+        return Ok(respond_per_caller(&req, StatusCode::OK, &users)?);
+    }
+    let users: Vec<PublicUserResponse> = users.into_iter().map(Into::into).collect();
+    Ok(respond_per_caller(&req, StatusCode::OK, &users)?)
 }
 
 /// PUT /api/v1/users/:id - Update user
+///
+/// Only the user themselves or an admin may update a user.
 pub async fn update_user(
+    req: HttpRequest,
     service: web::Data<UserService>,
+    caller: Authenticated,
     path: web::Path<String>,
-    request: web::Json<UpdateUserRequest>,
+    request: ValidatedJson<UpdateUserRequest>,
 ) -> Result<HttpResponse> {
     let user_id_str = path.into_inner();
     let user_id = uuid::Uuid::parse_str(&user_id_str)
         .map_err(|_| AppError::ValidationError("Invalid user ID format".to_string()))?;
+    let user_id = UserId::from_uuid(user_id);
+    if !caller.0.may_modify(user_id) {
+        return Err(AppError::Forbidden("Only admins can update other users".to_string()).into());
+    }
 
-    let user = service
-        .update_user(UserId::from_uuid(user_id), request.into_inner())
-        .await?;
-    Ok(HttpResponse::Ok().json(user))
+    let user = service.update_user(user_id, request.into_inner()).await?;
+    Ok(respond(&req, StatusCode::OK, &user)?)
+}
+
+/// PATCH /api/v1/users/:id - Apply an RFC 6902 JSON Patch to a user
+///
+/// The operations run against the user's current JSON representation. The
+/// fields they changed are then validated as an `UpdateUserRequest` and
+/// saved through `update_user`. `id` and `created_at` cannot be written,
+/// nor can fields that `update_user` does not accept, such as `status`.
+/// Only the user themselves or an admin may patch a user.
+pub async fn patch_user(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    caller: Authenticated,
+    path: web::Path<String>,
+    patch: JsonPatch,
+) -> Result<HttpResponse> {
+    let user_id_str = path.into_inner();
+    let user_id = uuid::Uuid::parse_str(&user_id_str)
+        .map_err(|_| AppError::ValidationError("Invalid user ID format".to_string()))?;
+    let user_id = UserId::from_uuid(user_id);
+    if !caller.0.may_modify(user_id) {
+        return Err(AppError::Forbidden("Only admins can patch other users".to_string()).into());
+    }
+
+    if let Some(field) = IMMUTABLE_FIELDS
+        .into_iter()
+        .find(|field| patch.writes_field(field))
+    {
+        return Err(
+            AppError::ValidationError(format!("Field '{}' cannot be changed", field)).into(),
+        );
+    }
+    let current = serde_json::to_value(service.get_user(user_id).await?)
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+    let patched = patch.apply(&current)?;
+    let request = patched_fields(&current, &patched)?;
+
+    let user = service.update_user(user_id, request).await?;
+    Ok(respond(&req, StatusCode::OK, &user)?)
+}
+
+/// User fields a JSON Patch may never write
+const IMMUTABLE_FIELDS: [&str; 2] = ["id", "created_at"];
+
+/// The update a patched user representation asks for
+///
+/// Only fields whose value changed are included. A removed or nulled
+/// `avatar_url` clears it; the other fields cannot be removed.
+fn patched_fields(current: &Value, patched: &Value) -> AppResult<UpdateUserRequest> {
+    const PATCHABLE: [&str; 4] = ["username", "email", "full_name", "avatar_url"];
+    let (Value::Object(current), Value::Object(patched)) = (current, patched) else {
+        return Err(AppError::ValidationError(
+            "Invalid JSON Patch: the result must be a user object".to_string(),
+        ));
+    };
+
+    let mut changes = serde_json::Map::new();
+    for field in current.keys().chain(patched.keys()) {
+        let value = patched.get(field).unwrap_or(&Value::Null);
+        if current.get(field).unwrap_or(&Value::Null) == value || changes.contains_key(field) {
+            continue;
+        }
+        if !PATCHABLE.contains(&field.as_str()) {
+            return Err(AppError::ValidationError(format!(
+                "Field '{}' cannot be changed",
+                field
+            )));
+        }
+        let value = match value {
+            Value::Null if field == "avatar_url" => Value::String(String::new()),
+            Value::Null => {
+                return Err(AppError::ValidationError(format!(
+                    "Field '{}' cannot be removed",
+                    field
+                )));
+            }
+            value => value.clone(),
+        };
+        changes.insert(field.clone(), value);
+    }
+
+    let request: UpdateUserRequest = serde_json::from_value(Value::Object(changes))
+        .map_err(|e| AppError::ValidationError(format!("Invalid request body: {}", e)))?;
+    request
+        .validate()
+        .map_err(|e| AppError::Validation(field_errors(&e)))?;
+    Ok(request)
 }
 
-/// DELETE /api/v1/users/:id - Delete user
+/// DELETE /api/v1/users/:id - Delete user (admin only)
 pub async fn delete_user(
     service: web::Data<UserService>,
+    caller: Authenticated,
     path: web::Path<String>,
 ) -> Result<HttpResponse> {
+    if !caller.0.is_admin() {
+        return Err(AppError::Forbidden("Only admins can delete users".to_string()).into());
+    }
+
     let user_id_str = path.into_inner();
     let user_id = uuid::Uuid::parse_str(&user_id_str)
         .map_err(|_| AppError::ValidationError("Invalid user ID format".to_string()))?;
@@ -78,11 +372,1097 @@ pub async fn delete_user(
     Ok(HttpResponse::NoContent().finish())
 }
 
+/// POST /api/v1/users/:id/restore - Undo a user's soft delete (admin only)
+pub async fn restore_user(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    caller: Authenticated,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    if !caller.0.is_admin() {
+        return Err(AppError::Forbidden("Only admins can restore users".to_string()).into());
+    }
+
+    let user_id_str = path.into_inner();
+    let user_id = uuid::Uuid::parse_str(&user_id_str)
+        .map_err(|_| AppError::ValidationError("Invalid user ID format".to_string()))?;
+
+    let user = service.restore_user(UserId::from_uuid(user_id)).await?;
+    Ok(respond(&req, StatusCode::OK, &user)?)
+}
+
+/// POST /api/v1/users/batch-get - Look up many users by ID in one request
+///
+/// Ids that match no user are listed in `not_found`; admin-only fields are
+/// returned only to admins.
+pub async fn batch_get_users(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    caller: Option<Authenticated>,
+    request: web::Json<BatchGetRequest>,
+) -> Result<HttpResponse> {
+    let found = service.get_users(request.into_inner().ids).await?;
+    if sees_admin_fields(caller.map(|caller| caller.0).as_ref()) {
+        Ok(respond_per_caller(&req, StatusCode::OK, &found)?)
+    } else {
+        Ok(respond_per_caller(
+            &req,
+            StatusCode::OK,
+            &found.into_public(),
+        )?)
+    }
+}
+
+/// POST /api/v1/users/batch-delete - Delete many users in one request (admin only)
+pub async fn batch_delete_users(
+    req: HttpRequest,
+    service: web::Data<UserService>,
+    caller: Authenticated,
+    request: web::Json<BatchDeleteRequest>,
+) -> Result<HttpResponse> {
+    if !caller.0.is_admin() {
+        return Err(AppError::Forbidden("Only admins can delete users in bulk".to_string()).into());
+    }
+
+    let report = service.delete_users(request.into_inner().ids).await?;
+    Ok(respond(&req, StatusCode::OK, &report)?)
+}
+
 /// GET /api/v1/users - List users with pagination
+///
+/// Pages by `cursor` when given, otherwise by `offset` (default 0); passing
+/// both is rejected, as is `sort` with `cursor`. Suspended and inactive users, and the admin-only
+/// fields, are listed only for admins. Cacheable, validated by an `ETag` over the page; the
+/// view depends on the caller, so authenticated responses are cached privately.
+/// Besides the body fields, `X-Total-Count` and a `Link` header describe the page.
 pub async fn list_users(
+    req: HttpRequest,
     service: web::Data<UserService>,
+    caller: Option<Authenticated>,
     query: web::Query<ListUsersQuery>,
 ) -> Result<HttpResponse> {
-    let users = service.list_users(query.limit, query.offset).await?;
-    Ok(HttpResponse::Ok().json(users))
+    let caller = caller.map(|caller| caller.0);
+    let selection = FieldSelection::parse(query.fields.as_deref(), USER_FIELDS)?;
+    let users = match (query.cursor.as_deref(), query.offset) {
+        (Some(_), Some(_)) => {
+            return Err(AppError::ValidationError(
+                "Pass either cursor or offset, not both".to_string(),
+            )
+            .into());
+        }
+        (Some(_), None) if query.sort.is_some() => {
+            return Err(AppError::ValidationError(
+                "Cursor pagination always lists newest first; sort needs offset".to_string(),
+            )
+            .into());
+        }
+        (Some(cursor), None) => {
+            service
+                .list_users_after(caller.as_ref(), cursor, query.limit)
+                .await?
+        }
+        (None, offset) => {
+            service
+                .list_users(
+                    caller.as_ref(),
+                    query.sort.as_deref(),
+                    query.limit,
+                    offset.unwrap_or(0),
+                )
+                .await?
+        }
+    };
+    let last_modified = users.users.iter().map(|user| user.updated_at).max();
+    let position = PagePosition::of(&users, query.cursor.is_some());
+    let body = user_list_view(caller.as_ref(), users, selection.as_ref())?;
+    let mut response = respond_cacheable(&req, &body, last_modified)?;
+    set_pagination_headers(&req, &mut response, &position);
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, test};
+    use std::sync::Arc;
+
+    use crate::test_support::InMemoryUserRepository;
+
+    async fn service_with_user() -> web::Data<UserService> {
+        let service = web::Data::new(UserService::new(
+            Arc::new(InMemoryUserRepository::default()),
+        ));
+        service
+            .create_user(CreateUserRequest {
+                username: "sparse".to_string(),
+                email: "sparse@example.com".to_string(),
+                full_name: Some("Sparse User".to_string()),
+            })
+            .await
+            .unwrap();
+        service
+    }
+
+    #[actix_web::test]
+    async fn test_list_returns_only_requested_fields() {
+        let app = test::init_service(
+            App::new()
+                .app_data(service_with_user().await)
+                .route("/users", web::get().to(list_users)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/users?fields=id,email")
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+
+        let user = body["users"][0].as_object().unwrap();
+        let mut keys: Vec<&str> = user.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        assert_eq!(keys, vec!["email", "id"]);
+        assert_eq!(user["email"], "sparse@example.com");
+        assert_eq!(body["total"], 1);
+    }
+
+    #[actix_web::test]
+    async fn test_list_sets_cache_headers_and_honors_if_none_match() {
+        use actix_web::http::header;
+
+        let service = service_with_user().await;
+        let app = test::init_service(
+            App::new()
+                .app_data(service.clone())
+                .route("/users", web::get().to(list_users)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/users").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().contains_key(header::CACHE_CONTROL));
+        assert!(resp.headers().contains_key(header::LAST_MODIFIED));
+        let etag = resp.headers().get(header::ETAG).unwrap().clone();
+
+        let req = test::TestRequest::get()
+            .uri("/users")
+            .insert_header((header::IF_NONE_MATCH, etag.clone()))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        assert!(resp.headers().contains_key(header::LAST_MODIFIED));
+
+        // Deleting an older user leaves the newest updated_at alone but
+        // changes the page, so the old validator no longer matches
+        let newer = service
+            .create_user(CreateUserRequest {
+                username: "newer".to_string(),
+                email: "newer@example.com".to_string(),
+                full_name: None,
+            })
+            .await
+            .unwrap();
+        let req = test::TestRequest::get().uri("/users").to_request();
+        let resp = test::call_service(&app, req).await;
+        let etag = resp.headers().get(header::ETAG).unwrap().clone();
+        let older = service
+            .list_users(None, None, 20, 0)
+            .await
+            .unwrap()
+            .users
+            .into_iter()
+            .find(|user| user.id != newer.id)
+            .unwrap();
+        service.delete_user(older.id).await.unwrap();
+
+        let req = test::TestRequest::get()
+            .uri("/users")
+            .insert_header((header::IF_NONE_MATCH, etag))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn test_admin_list_is_not_publicly_cacheable() {
+        use actix_web::{HttpMessage, dev::Service, http::header};
+        use application::Role;
+
+        let app = test::init_service(
+            App::new()
+                .app_data(service_with_user().await)
+                .wrap_fn(|req, srv| {
+                    req.extensions_mut().insert(AuthContext {
+                        user_id: UserId::new(),
+                        role: Role::Admin,
+                        actor_id: None,
+                        epoch: 0,
+                    });
+                    srv.call(req)
+                })
+                .route("/users", web::get().to(list_users)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/users")
+            .insert_header((header::AUTHORIZATION, "Bearer admin"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let cache_control = resp.headers().get(header::CACHE_CONTROL).unwrap();
+        let cache_control = cache_control.to_str().unwrap();
+        assert!(cache_control.contains("private"), "{}", cache_control);
+        assert!(!cache_control.contains("public"), "{}", cache_control);
+        let vary = resp.headers().get(header::VARY).unwrap().to_str().unwrap();
+        assert!(vary.contains("Authorization"), "{}", vary);
+    }
+
+    #[actix_web::test]
+    async fn test_list_pages_by_cursor_or_offset_but_not_both() {
+        let service = service_with_user().await;
+        for name in ["second", "third"] {
+            service
+                .create_user(CreateUserRequest {
+                    username: name.to_string(),
+                    email: format!("{}@example.com", name),
+                    full_name: None,
+                })
+                .await
+                .unwrap();
+        }
+        let app = test::init_service(
+            App::new()
+                .app_data(service)
+                .route("/users", web::get().to(list_users)),
+        )
+        .await;
+        let get = |uri: String| test::TestRequest::get().uri(&uri).to_request();
+        let ids = |body: &serde_json::Value| -> Vec<String> {
+            body["users"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|user| user["id"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        // Neither: the first page
+        let first: serde_json::Value =
+            test::call_and_read_body_json(&app, get("/users?limit=2".to_string())).await;
+        assert_eq!(ids(&first).len(), 2);
+        assert_eq!(first["offset"], 0);
+        let cursor = first["next_cursor"].as_str().unwrap().to_string();
+
+        let by_cursor: serde_json::Value =
+            test::call_and_read_body_json(&app, get(format!("/users?limit=2&cursor={}", cursor)))
+                .await;
+        let by_offset: serde_json::Value =
+            test::call_and_read_body_json(&app, get("/users?limit=2&offset=2".to_string())).await;
+        assert_eq!(ids(&by_cursor).len(), 1);
+        assert!(!ids(&first).contains(&ids(&by_cursor)[0]));
+        assert_eq!(ids(&by_cursor), ids(&by_offset));
+        assert!(by_cursor.get("next_cursor").is_none());
+
+        let both = get(format!("/users?offset=0&cursor={}", cursor));
+        let resp = test::call_service(&app, both).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        // Custom sorts page by offset only
+        let sorted = get(format!("/users?sort=username&cursor={}", cursor));
+        let resp = test::call_service(&app, sorted).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let sorted: serde_json::Value =
+            test::call_and_read_body_json(&app, get("/users?sort=-username".to_string())).await;
+        let names: Vec<&str> = sorted["users"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|user| user["username"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["third", "sparse", "second"]);
+        assert!(sorted.get("next_cursor").is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_list_sets_total_count_and_link_headers() {
+        use actix_web::http::header;
+
+        let service = service_with_user().await;
+        for name in ["second", "third"] {
+            service
+                .create_user(CreateUserRequest {
+                    username: name.to_string(),
+                    email: format!("{}@example.com", name),
+                    full_name: None,
+                })
+                .await
+                .unwrap();
+        }
+        let app = test::init_service(
+            App::new()
+                .app_data(service)
+                .route("/users", web::get().to(list_users)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/users?limit=2&fields=id")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        let total = resp.headers().get("x-total-count").cloned().unwrap();
+        let link = resp.headers().get(header::LINK).cloned().unwrap();
+        let body: serde_json::Value = test::read_body_json(resp).await;
+
+        assert_eq!(total.to_str().unwrap(), body["total"].to_string());
+        let link = link.to_str().unwrap();
+        assert!(
+            link.contains(
+                r#"<http://localhost:8080/users?fields=id&limit=2&offset=2>; rel="next""#
+            ),
+            "{}",
+            link
+        );
+        assert!(!link.contains(r#"rel="prev""#));
+    }
+
+    #[actix_web::test]
+    async fn test_json_patch_replaces_full_name_but_not_id() {
+        let service = service_with_user().await;
+        let id = service
+            .get_user_by_username("sparse".to_string())
+            .await
+            .unwrap()
+            .id;
+        let app = test::init_service(app_as_user(service, id)).await;
+        let patch = |content_type: &str, ops: serde_json::Value| {
+            test::TestRequest::patch()
+                .uri(&format!("/users/{}", id))
+                .insert_header(("content-type", content_type))
+                .set_payload(ops.to_string())
+                .to_request()
+        };
+
+        let rename = serde_json::json!([
+            { "op": "test", "path": "/full_name", "value": "Sparse User" },
+            { "op": "replace", "path": "/full_name", "value": "Patched Name" },
+        ]);
+        let resp = test::call_service(&app, patch("application/json-patch+json", rename)).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["full_name"], "Patched Name");
+        assert_eq!(body["id"], id.to_string());
+
+        let new_id = serde_json::json!([
+            { "op": "replace", "path": "/id", "value": uuid::Uuid::new_v4() },
+        ]);
+        let resp = test::call_service(&app, patch("application/json-patch+json", new_id)).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(
+            body["error"]["message"],
+            "Validation error: Field 'id' cannot be changed"
+        );
+
+        let status = serde_json::json!([
+            { "op": "replace", "path": "/status", "value": "suspended" },
+        ]);
+        let resp = test::call_service(&app, patch("application/json-patch+json", status)).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let merge = serde_json::json!({ "full_name": "Merged" });
+        let resp = test::call_service(&app, patch("application/json", merge)).await;
+        assert_eq!(resp.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[actix_web::test]
+    async fn test_users_by_domain_is_admin_only() {
+        use actix_web::{HttpMessage, dev::Service};
+        use application::{AuthContext, Role};
+
+        let service = service_with_user().await;
+        service
+            .create_user(CreateUserRequest {
+                username: "acme".to_string(),
+                email: "buyer@acme.com".to_string(),
+                full_name: None,
+            })
+            .await
+            .unwrap();
+        let app_as = |role: Role| {
+            let service = service.clone();
+            async move {
+                test::init_service(
+                    App::new()
+                        .app_data(service)
+                        .wrap_fn(move |req, srv| {
+                            req.extensions_mut().insert(AuthContext {
+                                user_id: UserId::new(),
+                                role,
+                                actor_id: None,
+                                epoch: 0,
+                            });
+                            srv.call(req)
+                        })
+                        .route("/users/domain/{domain}", web::get().to(users_by_domain)),
+                )
+                .await
+            }
+        };
+
+        let app = app_as(Role::Admin).await;
+        let req = test::TestRequest::get()
+            .uri("/users/domain/acme.com")
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        let emails: Vec<&str> = body
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|user| user["email"].as_str().unwrap())
+            .collect();
+        assert_eq!(emails, vec!["buyer@acme.com"]);
+
+        let app = app_as(Role::User).await;
+        let req = test::TestRequest::get()
+            .uri("/users/domain/acme.com")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[actix_web::test]
+    async fn test_non_admins_get_the_public_shape_and_admins_the_full_one() {
+        use actix_web::{HttpMessage, dev::Service};
+        use application::Role;
+
+        let service = service_with_user().await;
+        let user = service
+            .get_user_by_username("sparse".to_string())
+            .await
+            .unwrap();
+        let app_as = |role: Role| {
+            let service = service.clone();
+            async move {
+                test::init_service(
+                    App::new()
+                        .app_data(service)
+                        .wrap_fn(move |req, srv| {
+                            // The user viewing their own profile
+                            req.extensions_mut().insert(AuthContext {
+                                user_id: user.id,
+                                role,
+                                actor_id: None,
+                                epoch: 0,
+                            });
+                            srv.call(req)
+                        })
+                        .route("/users/{id}", web::get().to(get_user))
+                        .route("/users", web::get().to(list_users)),
+                )
+                .await
+            }
+        };
+        let keys = |user: &serde_json::Value| -> Vec<String> {
+            let mut keys: Vec<String> = user.as_object().unwrap().keys().cloned().collect();
+            keys.sort_unstable();
+            keys
+        };
+        let public = [
+            "avatar_url",
+            "created_at",
+            "email",
+            "full_name",
+            "id",
+            "username",
+        ];
+        let full = [
+            "avatar_url",
+            "created_at",
+            "email",
+            "full_name",
+            "id",
+            "status",
+            "updated_at",
+            "username",
+        ];
+
+        for (role, expected) in [(Role::User, &public[..]), (Role::Admin, &full[..])] {
+            let app = app_as(role).await;
+            let req = test::TestRequest::get()
+                .uri(&format!("/users/{}", user.id))
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            let headers = resp.headers();
+            assert_eq!(headers.get("Cache-Control").unwrap(), "private");
+            assert!(
+                headers
+                    .get("Vary")
+                    .unwrap()
+                    .to_str()
+                    .unwrap()
+                    .contains("Authorization")
+            );
+            let body: serde_json::Value = test::read_body_json(resp).await;
+            assert_eq!(keys(&body), expected, "{:?}", role);
+
+            let req = test::TestRequest::get().uri("/users").to_request();
+            let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+            assert_eq!(keys(&body["users"][0]), expected, "{:?}", role);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_envelope_wraps_get_user_and_list_users() {
+        use shared::config::ResponseConfig;
+
+        let service = service_with_user().await;
+        let user = service
+            .get_user_by_username("sparse".to_string())
+            .await
+            .unwrap();
+        let app_with = |envelope: bool| {
+            let service = service.clone();
+            async move {
+                test::init_service(
+                    App::new()
+                        .app_data(service)
+                        .app_data(web::Data::new(ResponseConfig {
+                            envelope,
+                            ..ResponseConfig::default()
+                        }))
+                        .route("/users/{id}", web::get().to(get_user))
+                        .route("/users", web::get().to(list_users)),
+                )
+                .await
+            }
+        };
+        let raw_app = app_with(false).await;
+        let enveloped_app = app_with(true).await;
+        let get = |uri: &str| test::TestRequest::get().uri(uri).to_request();
+        let user_uri = format!("/users/{}", user.id);
+
+        let raw: Value = test::call_and_read_body_json(&raw_app, get(&user_uri)).await;
+        let enveloped: Value = test::call_and_read_body_json(&enveloped_app, get(&user_uri)).await;
+        assert_eq!(enveloped, serde_json::json!({ "data": raw }));
+
+        let mut raw: Value = test::call_and_read_body_json(&raw_app, get("/users")).await;
+        let enveloped: Value = test::call_and_read_body_json(&enveloped_app, get("/users")).await;
+        let users = raw.as_object_mut().unwrap().remove("users").unwrap();
+        assert_eq!(enveloped, serde_json::json!({ "data": users, "meta": raw }));
+
+        // Clients can opt in per request without the config switch
+        let req = test::TestRequest::get()
+            .uri(&user_uri)
+            .insert_header(("Accept", "application/json; envelope=true"))
+            .to_request();
+        let opted_in: Value = test::call_and_read_body_json(&raw_app, req).await;
+        assert_eq!(opted_in["data"]["id"], user.id.to_string());
+    }
+
+    /// App whose requests are authenticated as `user_id`
+    fn app_as_user(
+        service: web::Data<UserService>,
+        user_id: UserId,
+    ) -> App<
+        impl actix_web::dev::ServiceFactory<
+            actix_web::dev::ServiceRequest,
+            Config = (),
+            Response = actix_web::dev::ServiceResponse,
+            Error = actix_web::Error,
+            InitError = (),
+        >,
+    > {
+        use actix_web::{HttpMessage, dev::Service};
+        use application::Role;
+
+        App::new()
+            .app_data(service)
+            .wrap_fn(move |req, srv| {
+                req.extensions_mut().insert(AuthContext {
+                    user_id,
+                    role: Role::User,
+                    actor_id: None,
+                    epoch: 0,
+                });
+                srv.call(req)
+            })
+            .route("/users/me", web::get().to(me))
+            .route("/users/me", web::patch().to(update_me))
+            .route("/users/{id}", web::put().to(update_user))
+            .route("/users/{id}", web::patch().to(patch_user))
+    }
+
+    /// App whose requests are authenticated as a caller with `role`, or
+    /// anonymous when `role` is `None`
+    fn app_with_role(
+        service: web::Data<UserService>,
+        role: Option<application::Role>,
+    ) -> App<
+        impl actix_web::dev::ServiceFactory<
+            actix_web::dev::ServiceRequest,
+            Config = (),
+            Response = actix_web::dev::ServiceResponse,
+            Error = actix_web::Error,
+            InitError = (),
+        >,
+    > {
+        use actix_web::{HttpMessage, dev::Service};
+
+        App::new()
+            .app_data(service)
+            .wrap_fn(move |req, srv| {
+                if let Some(role) = role {
+                    req.extensions_mut().insert(AuthContext {
+                        user_id: UserId::new(),
+                        role,
+                        actor_id: None,
+                        epoch: 0,
+                    });
+                }
+                srv.call(req)
+            })
+            .route("/users/batch-delete", web::post().to(batch_delete_users))
+            .route("/users/{id}/restore", web::post().to(restore_user))
+            .route("/users/{id}", web::put().to(update_user))
+            .route("/users/{id}", web::patch().to(patch_user))
+            .route("/users/{id}", web::delete().to(delete_user))
+    }
+
+    #[actix_web::test]
+    async fn test_batch_delete_is_admin_only() {
+        use application::Role;
+
+        let service = service_with_user().await;
+        let user = service
+            .get_user_by_username("sparse".to_string())
+            .await
+            .unwrap();
+        let batch_delete = || {
+            test::TestRequest::post()
+                .uri("/users/batch-delete")
+                .set_json(serde_json::json!({ "ids": [user.id] }))
+                .to_request()
+        };
+
+        for (role, status) in [
+            (None, StatusCode::UNAUTHORIZED),
+            (Some(Role::User), StatusCode::FORBIDDEN),
+        ] {
+            let app = test::init_service(app_with_role(service.clone(), role)).await;
+            let resp = test::call_service(&app, batch_delete()).await;
+            assert_eq!(resp.status(), status, "{:?}", role);
+        }
+        assert!(service.get_user(user.id).await.is_ok());
+
+        let app = test::init_service(app_with_role(service.clone(), Some(Role::Admin))).await;
+        let body: serde_json::Value = test::call_and_read_body_json(&app, batch_delete()).await;
+        assert_eq!(body["deleted"], 1);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_is_admin_only() {
+        use application::Role;
+
+        let service = service_with_user().await;
+        let user = service
+            .get_user_by_username("sparse".to_string())
+            .await
+            .unwrap();
+        let delete = || {
+            test::TestRequest::delete()
+                .uri(&format!("/users/{}", user.id))
+                .to_request()
+        };
+
+        for (role, status) in [
+            (None, StatusCode::UNAUTHORIZED),
+            (Some(Role::User), StatusCode::FORBIDDEN),
+        ] {
+            let app = test::init_service(app_with_role(service.clone(), role)).await;
+            let resp = test::call_service(&app, delete()).await;
+            assert_eq!(resp.status(), status, "{:?}", role);
+        }
+        assert!(service.get_user(user.id).await.is_ok());
+
+        let app = test::init_service(app_with_role(service.clone(), Some(Role::Admin))).await;
+        let resp = test::call_service(&app, delete()).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert!(service.get_user(user.id).await.is_err());
+    }
+
+    #[actix_web::test]
+    async fn test_restore_is_admin_only() {
+        use application::Role;
+
+        let service = service_with_user().await;
+        let user = service
+            .get_user_by_username("sparse".to_string())
+            .await
+            .unwrap();
+        service.delete_user(user.id).await.unwrap();
+        let uri = format!("/users/{}/restore", user.id);
+
+        for (role, status) in [
+            (None, StatusCode::UNAUTHORIZED),
+            (Some(Role::User), StatusCode::FORBIDDEN),
+        ] {
+            let app = test::init_service(app_with_role(service.clone(), role)).await;
+            let req = test::TestRequest::post().uri(&uri).to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), status, "{:?}", role);
+        }
+        assert!(service.get_user(user.id).await.is_err());
+    }
+
+    #[actix_web::test]
+    async fn test_patch_is_limited_to_the_user_and_admins() {
+        use application::Role;
+
+        let service = service_with_user().await;
+        let id = service
+            .get_user_by_username("sparse".to_string())
+            .await
+            .unwrap()
+            .id;
+        let rename = |name: &str| {
+            test::TestRequest::patch()
+                .uri(&format!("/users/{}", id))
+                .insert_header(("content-type", "application/json-patch+json"))
+                .set_payload(
+                    serde_json::json!([
+                        { "op": "replace", "path": "/full_name", "value": name },
+                    ])
+                    .to_string(),
+                )
+                .to_request()
+        };
+
+        for (role, status) in [
+            (None, StatusCode::UNAUTHORIZED),
+            (Some(Role::User), StatusCode::FORBIDDEN),
+        ] {
+            let app = test::init_service(app_with_role(service.clone(), role)).await;
+            let resp = test::call_service(&app, rename("Intruder")).await;
+            assert_eq!(resp.status(), status, "{:?}", role);
+        }
+
+        let app = test::init_service(app_with_role(service.clone(), Some(Role::Admin))).await;
+        let resp = test::call_service(&app, rename("Admin Edit")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let user = service.get_user(id).await.unwrap();
+        assert_eq!(user.full_name.as_deref(), Some("Admin Edit"));
+    }
+
+    #[actix_web::test]
+    async fn test_put_is_limited_to_the_user_and_admins() {
+        use application::Role;
+
+        let service = service_with_user().await;
+        let id = service
+            .get_user_by_username("sparse".to_string())
+            .await
+            .unwrap()
+            .id;
+        let rename = |name: &str| {
+            test::TestRequest::put()
+                .uri(&format!("/users/{}", id))
+                .set_json(serde_json::json!({ "full_name": name }))
+                .to_request()
+        };
+
+        for (role, status) in [
+            (None, StatusCode::UNAUTHORIZED),
+            (Some(Role::User), StatusCode::FORBIDDEN),
+        ] {
+            let app = test::init_service(app_with_role(service.clone(), role)).await;
+            let resp = test::call_service(&app, rename("Intruder")).await;
+            assert_eq!(resp.status(), status, "{:?}", role);
+        }
+        let user = service.get_user(id).await.unwrap();
+        assert_eq!(user.full_name.as_deref(), Some("Sparse User"));
+
+        let app = test::init_service(app_as_user(service.clone(), id)).await;
+        let resp = test::call_service(&app, rename("Own Edit")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let app = test::init_service(app_with_role(service.clone(), Some(Role::Admin))).await;
+        let resp = test::call_service(&app, rename("Admin Edit")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let user = service.get_user(id).await.unwrap();
+        assert_eq!(user.full_name.as_deref(), Some("Admin Edit"));
+    }
+
+    #[actix_web::test]
+    async fn test_me_returns_the_token_user() {
+        let service = service_with_user().await;
+        let other = service
+            .create_user(CreateUserRequest {
+                username: "other".to_string(),
+                email: "other@example.com".to_string(),
+                full_name: None,
+            })
+            .await
+            .unwrap();
+        let app = test::init_service(app_as_user(service, other.id)).await;
+
+        let req = test::TestRequest::get().uri("/users/me").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+
+        assert_eq!(body["id"], other.id.to_string());
+        assert_eq!(body["username"], "other");
+        assert!(body.get("status").is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_self_patch_cannot_change_status() {
+        use domain::{SystemClock, UserRepository, UserStatus};
+
+        let repo = Arc::new(InMemoryUserRepository::default());
+        let service = web::Data::new(UserService::new(repo.clone()));
+        let created = service
+            .create_user(CreateUserRequest {
+                username: "benched".to_string(),
+                email: "benched@example.com".to_string(),
+                full_name: None,
+            })
+            .await
+            .unwrap();
+        let mut user = repo.find_by_id(created.id).await.unwrap().unwrap();
+        user.suspend(&SystemClock);
+        repo.update(&user).await.unwrap();
+        let app = test::init_service(app_as_user(service.clone(), created.id)).await;
+        let patch = |body: serde_json::Value| {
+            test::TestRequest::patch()
+                .uri("/users/me")
+                .set_json(body)
+                .to_request()
+        };
+
+        let escalate = serde_json::json!({ "full_name": "Benched", "status": "active" });
+        let resp = test::call_service(&app, patch(escalate)).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let rename = serde_json::json!({ "full_name": "Benched" });
+        let body: serde_json::Value = test::call_and_read_body_json(&app, patch(rename)).await;
+        assert_eq!(body["full_name"], "Benched");
+
+        let stored = service.get_user(created.id).await.unwrap();
+        assert_eq!(stored.status, UserStatus::Suspended);
+        assert_eq!(stored.full_name.as_deref(), Some("Benched"));
+    }
+
+    #[actix_web::test]
+    async fn test_unknown_field_is_bad_request() {
+        let app = test::init_service(
+            App::new()
+                .app_data(service_with_user().await)
+                .route("/users", web::get().to(list_users))
+                .route(
+                    "/users/username/{username}",
+                    web::get().to(get_user_by_username),
+                ),
+        )
+        .await;
+
+        for uri in ["/users?fields=bogus", "/users/username/sparse?fields=bogus"] {
+            let req = test::TestRequest::get().uri(uri).to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{}", uri);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_restore_returns_user_or_conflict() {
+        let service = service_with_user().await;
+        let user = service
+            .get_user_by_username("sparse".to_string())
+            .await
+            .unwrap();
+        service.delete_user(user.id).await.unwrap();
+        let app = test::init_service(app_with_role(
+            service.clone(),
+            Some(application::Role::Admin),
+        ))
+        .await;
+        let uri = format!("/users/{}/restore", user.id);
+
+        let req = test::TestRequest::post().uri(&uri).to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["username"], "sparse");
+
+        service.delete_user(user.id).await.unwrap();
+        service
+            .create_user(CreateUserRequest {
+                username: "sparse".to_string(),
+                email: "other@example.com".to_string(),
+                full_name: None,
+            })
+            .await
+            .unwrap();
+        let req = test::TestRequest::post().uri(&uri).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+    }
+
+    #[actix_web::test]
+    async fn test_recent_users_returns_only_newer_users_newest_first() {
+        use chrono::TimeZone;
+        use domain::FixedClock;
+
+        let start = Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap();
+        let clock = Arc::new(FixedClock::new(start));
+        let service = web::Data::new(
+            UserService::new(Arc::new(InMemoryUserRepository::default())).with_clock(clock.clone()),
+        );
+        for name in ["oldest", "middle", "newest"] {
+            service
+                .create_user(CreateUserRequest {
+                    username: name.to_string(),
+                    email: format!("{}@example.com", name),
+                    full_name: None,
+                })
+                .await
+                .unwrap();
+            clock.advance(chrono::TimeDelta::hours(1));
+        }
+        let app = test::init_service(
+            App::new()
+                .app_data(service)
+                .route("/users/recent", web::get().to(recent_users)),
+        )
+        .await;
+
+        // `oldest` was created exactly at `since`, which is excluded
+        let req = test::TestRequest::get()
+            .uri("/users/recent?since=2025-03-01T12:00:00Z&limit=10")
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        let names: Vec<&str> = body
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|user| user["username"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["newest", "middle"]);
+
+        for uri in [
+            "/users/recent?since=yesterday",
+            "/users/recent?since=2025-03-01T12:00:00Z&limit=0",
+            "/users/recent",
+        ] {
+            let req = test::TestRequest::get().uri(uri).to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{}", uri);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_batch_get_reports_missing_ids_in_request_order() {
+        let service = service_with_user().await;
+        let second = service
+            .create_user(CreateUserRequest {
+                username: "second".to_string(),
+                email: "second@example.com".to_string(),
+                full_name: None,
+            })
+            .await
+            .unwrap()
+            .id;
+        let first = service
+            .get_user_by_username("sparse".to_string())
+            .await
+            .unwrap()
+            .id;
+        let missing = UserId::new();
+        let app = test::init_service(
+            App::new()
+                .app_data(service)
+                .route("/users/batch-get", web::post().to(batch_get_users)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/users/batch-get")
+            .set_json(serde_json::json!({ "ids": [second, missing, first, second] }))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+
+        let ids: Vec<&str> = body["users"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|user| user["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, vec![second.to_string(), first.to_string()]);
+        assert_eq!(body["not_found"], serde_json::json!([missing]));
+        // Anonymous callers get the public shape
+        assert!(body["users"][0].get("status").is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_batch_get_over_cap_is_bad_request() {
+        let service = web::Data::new(
+            UserService::new(Arc::new(InMemoryUserRepository::default())).with_validation(
+                shared::config::ValidationConfig {
+                    max_batch_get_ids: 2,
+                    ..Default::default()
+                },
+            ),
+        );
+        let app = test::init_service(
+            App::new()
+                .app_data(service)
+                .route("/users/batch-get", web::post().to(batch_get_users)),
+        )
+        .await;
+
+        let ids: Vec<UserId> = (0..3).map(|_| UserId::new()).collect();
+        let req = test::TestRequest::post()
+            .uri("/users/batch-get")
+            .set_json(serde_json::json!({ "ids": ids }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_is_forbidden_when_user_deletion_is_disabled() {
+        let service = web::Data::new(
+            UserService::new(Arc::new(InMemoryUserRepository::default())).with_features(
+                shared::config::FeatureFlags {
+                    allow_user_deletion: false,
+                },
+            ),
+        );
+        let user = service
+            .create_user(CreateUserRequest {
+                username: "retained".to_string(),
+                email: "retained@example.com".to_string(),
+                full_name: None,
+            })
+            .await
+            .unwrap();
+        let app = test::init_service(app_with_role(
+            service.clone(),
+            Some(application::Role::Admin),
+        ))
+        .await;
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/users/{}", user.id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::post()
+            .uri("/users/batch-delete")
+            .set_json(serde_json::json!({ "ids": [user.id] }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        assert!(service.get_user(user.id).await.is_ok());
+    }
 }
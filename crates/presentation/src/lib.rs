@@ -1,3 +1,10 @@
+pub mod extractors;
+pub mod graphql;
 pub mod handlers;
+pub mod middleware;
+pub mod responses;
 pub mod routes;
 pub mod states;
+
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
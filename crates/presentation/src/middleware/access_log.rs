@@ -0,0 +1,191 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::time::Instant;
+
+use actix_web::{
+    Error, HttpMessage,
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    http::header,
+    middleware::Next,
+    web,
+};
+
+use crate::middleware::{RequestId, client_ip};
+
+/// Access log settings; register as `web::Data<AccessLog>`
+///
+/// Without it every request is logged.
+#[derive(Debug, Clone, Copy)]
+pub struct AccessLog {
+    sampling_rate: f64,
+}
+
+impl AccessLog {
+    /// Log `sampling_rate` (clamped to 0.0-1.0) of the successful requests
+    pub fn new(sampling_rate: f64) -> Self {
+        Self {
+            sampling_rate: sampling_rate.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Whether a request's success lines are logged
+    ///
+    /// Decided from the request id, so the start and end lines of a request
+    /// are kept or dropped together.
+    fn samples(&self, request_id: &str) -> bool {
+        if self.sampling_rate >= 1.0 {
+            return true;
+        }
+        let mut hasher = DefaultHasher::new();
+        request_id.hash(&mut hasher);
+        (hasher.finish() as f64 / u64::MAX as f64) < self.sampling_rate
+    }
+}
+
+impl Default for AccessLog {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+/// Log each request with the resolved client address
+///
+/// A sampled request gets a start and an end line; 4xx and 5xx responses
+/// are logged whatever the sampling. Register inside `request_id` with
+/// `App::wrap(middleware::from_fn(access_log))`.
+pub async fn access_log(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let config = req
+        .app_data::<web::Data<AccessLog>>()
+        .map(|config| *config.get_ref())
+        .unwrap_or_default();
+    let sampled = match req.extensions().get::<RequestId>() {
+        Some(RequestId(id)) => config.samples(id),
+        None => config.samples(&uuid::Uuid::new_v4().to_string()),
+    };
+
+    let client = client_ip(req.request()).map_or_else(|| "-".to_string(), |ip| ip.to_string());
+    let line = format!(
+        "{} {}{} {:?}",
+        req.method(),
+        req.path(),
+        match req.query_string() {
+            "" => String::new(),
+            query => format!("?{}", query),
+        },
+        req.version()
+    );
+    let header = |name: header::HeaderName| {
+        req.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("-")
+            .to_string()
+    };
+    let referer = header(header::REFERER);
+    let user_agent = header(header::USER_AGENT);
+
+    if sampled {
+        tracing::info!(target: "access_log", %client, request = %line, "request started");
+    }
+    let started = Instant::now();
+    let res = next.call(req).await;
+
+    let status = match &res {
+        Ok(res) => res.status(),
+        Err(err) => err.as_response_error().status_code(),
+    };
+    let elapsed = started.elapsed().as_secs_f64();
+    if status.is_client_error() || status.is_server_error() {
+        tracing::warn!(
+            target: "access_log",
+            %client, request = %line, status = status.as_u16(), %referer, %user_agent, elapsed,
+            "request finished"
+        );
+    } else if sampled {
+        tracing::info!(
+            target: "access_log",
+            %client, request = %line, status = status.as_u16(), %referer, %user_agent, elapsed,
+            "request finished"
+        );
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, HttpResponse, middleware::from_fn, test};
+
+    use crate::middleware::request_id;
+    use crate::test_support::CapturedLogs;
+
+    async fn statuses_logged(sampling_rate: f64) -> Vec<(String, Option<u64>)> {
+        let logs = CapturedLogs::default();
+        let _guard = logs.capture();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(AccessLog::new(sampling_rate)))
+                .wrap(from_fn(access_log))
+                .wrap(from_fn(request_id))
+                .route("/ok", web::get().to(HttpResponse::Ok))
+                .route("/missing", web::get().to(HttpResponse::NotFound))
+                .route("/broken", web::get().to(HttpResponse::InternalServerError)),
+        )
+        .await;
+        for uri in ["/ok", "/ok", "/missing", "/broken"] {
+            test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+        }
+
+        logs.events()
+            .into_iter()
+            .filter(|event| event["target"] == "access_log")
+            .map(|event| {
+                (
+                    event["fields"]["message"].as_str().unwrap().to_string(),
+                    event["fields"]["status"].as_u64(),
+                )
+            })
+            .collect()
+    }
+
+    #[actix_web::test]
+    async fn test_zero_sampling_logs_only_errors() {
+        let logged = statuses_logged(0.0).await;
+
+        assert_eq!(
+            logged,
+            [
+                ("request finished".to_string(), Some(404)),
+                ("request finished".to_string(), Some(500)),
+            ]
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_full_sampling_logs_start_and_end_of_every_request() {
+        let logged = statuses_logged(1.0).await;
+
+        let started = logged.iter().filter(|(msg, _)| msg == "request started");
+        let finished: Vec<_> = logged
+            .iter()
+            .filter(|(msg, _)| msg == "request finished")
+            .map(|(_, status)| status.unwrap())
+            .collect();
+        assert_eq!(started.count(), 4);
+        assert_eq!(finished, [200, 200, 404, 500]);
+    }
+
+    #[actix_web::test]
+    async fn test_sampling_is_stable_per_request_id() {
+        let config = AccessLog::new(0.5);
+        let decisions: Vec<bool> = (0..200).map(|i| config.samples(&i.to_string())).collect();
+        let again: Vec<bool> = (0..200).map(|i| config.samples(&i.to_string())).collect();
+
+        assert_eq!(decisions, again);
+        let kept = decisions.iter().filter(|kept| **kept).count();
+        assert!((50..150).contains(&kept), "kept {} of 200", kept);
+    }
+}
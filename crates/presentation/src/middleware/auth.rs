@@ -0,0 +1,74 @@
+use actix_web::{
+    Error, FromRequest, HttpMessage, HttpRequest,
+    body::MessageBody,
+    dev::{Payload, ServiceRequest, ServiceResponse},
+    http::header::{AUTHORIZATION, HeaderMap},
+    middleware::Next,
+    web,
+};
+use std::future::{Ready, ready};
+use tracing::Instrument;
+
+use application::{AuthContext, AuthService};
+use shared::AppError;
+
+/// Verify a `Bearer` token when present and attach its `AuthContext`
+///
+/// Requests without a token pass through anonymously; handlers that need a
+/// caller use the `Authenticated` extractor. The rest of the request runs in
+/// a span carrying the effective user and, when impersonated, the real actor.
+///
+/// Register with `App::wrap(middleware::from_fn(authenticate))` alongside
+/// `web::Data<AuthService>`.
+pub async fn authenticate(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let Some(token) = bearer_token(req.headers()) else {
+        return next.call(req).await;
+    };
+    let service = req
+        .app_data::<web::Data<AuthService>>()
+        .ok_or_else(|| AppError::ConfigurationError("AuthService is not registered".to_string()))?;
+    let context = service.authenticate(token).await?;
+
+    let span = tracing::info_span!(
+        "auth",
+        user_id = %context.user_id,
+        actor_id = tracing::field::Empty
+    );
+    if let Some(actor_id) = context.actor_id {
+        span.record("actor_id", tracing::field::display(actor_id));
+    }
+
+    req.extensions_mut().insert(context);
+    next.call(req).instrument(span).await
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+/// Extractor for the authenticated caller; rejects anonymous requests
+#[derive(Debug, Clone, Copy)]
+pub struct Authenticated(pub AuthContext);
+
+impl FromRequest for Authenticated {
+    type Error = AppError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(
+            req.extensions()
+                .get::<AuthContext>()
+                .copied()
+                .map(Authenticated)
+                .ok_or_else(|| AppError::Unauthorized("Missing bearer token".to_string())),
+        )
+    }
+}
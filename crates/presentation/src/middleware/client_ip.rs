@@ -0,0 +1,192 @@
+use actix_web::{
+    FromRequest, HttpRequest,
+    dev::Payload,
+    http::header::{HeaderMap, HeaderName},
+    web,
+};
+use std::future::{Ready, ready};
+use std::net::IpAddr;
+
+use shared::{AppError, AppResult};
+
+const X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+const X_REAL_IP: HeaderName = HeaderName::from_static("x-real-ip");
+
+/// Proxies whose `X-Forwarded-For` / `X-Real-IP` headers are believed
+///
+/// Register as `web::Data<TrustedProxies>`; without it forwarding headers
+/// are ignored and the socket peer is used.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    networks: Vec<(IpAddr, u8)>,
+}
+
+impl TrustedProxies {
+    /// Parse a list of IPs (`10.0.0.1`) and CIDRs (`10.0.0.0/8`)
+    pub fn parse(entries: &[String]) -> AppResult<Self> {
+        let networks = entries
+            .iter()
+            .map(|entry| parse_network(entry.trim()))
+            .collect::<AppResult<Vec<_>>>()?;
+        Ok(Self { networks })
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        self.networks
+            .iter()
+            .any(|(network, prefix)| in_network(ip, *network, *prefix))
+    }
+}
+
+/// Resolve the originating client address for a request
+///
+/// Forwarding headers are only honoured when the immediate peer is a
+/// trusted proxy. `X-Forwarded-For` is walked right to left, skipping
+/// trusted hops, so a client cannot inject an address ahead of the proxy's.
+pub fn resolve_client_ip(
+    peer: Option<IpAddr>,
+    headers: &HeaderMap,
+    proxies: &TrustedProxies,
+) -> Option<IpAddr> {
+    let peer = peer?;
+    if !proxies.contains(peer) {
+        return Some(peer);
+    }
+
+    let forwarded: Vec<IpAddr> = headers
+        .get_all(X_FORWARDED_FOR)
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|hop| hop.trim().parse().ok())
+        .collect();
+    if let Some(first) = forwarded.first() {
+        let client = forwarded
+            .iter()
+            .rev()
+            .find(|hop| !proxies.contains(**hop))
+            .unwrap_or(first);
+        return Some(*client);
+    }
+
+    headers
+        .get(X_REAL_IP)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .or(Some(peer))
+}
+
+/// Client address for the request, honouring trusted proxies
+pub fn client_ip(req: &HttpRequest) -> Option<IpAddr> {
+    let peer = req.peer_addr().map(|addr| addr.ip());
+    match req.app_data::<web::Data<TrustedProxies>>() {
+        Some(proxies) => resolve_client_ip(peer, req.headers(), proxies),
+        None => peer,
+    }
+}
+
+/// Extractor for the resolved client address (e.g. for rate limiting)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+impl FromRequest for ClientIp {
+    type Error = AppError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(
+            client_ip(req)
+                .map(ClientIp)
+                .ok_or_else(|| AppError::InternalError("Client address unavailable".to_string())),
+        )
+    }
+}
+
+fn parse_network(entry: &str) -> AppResult<(IpAddr, u8)> {
+    let invalid = || AppError::ConfigurationError(format!("Invalid trusted proxy '{}'", entry));
+    let (addr, prefix) = match entry.split_once('/') {
+        Some((addr, prefix)) => (addr, Some(prefix)),
+        None => (entry, None),
+    };
+    let addr: IpAddr = addr.parse().map_err(|_| invalid())?;
+    let max = if addr.is_ipv4() { 32 } else { 128 };
+    let prefix = match prefix {
+        Some(prefix) => prefix.parse::<u8>().map_err(|_| invalid())?,
+        None => max,
+    };
+    if prefix > max {
+        return Err(invalid());
+    }
+    Ok((addr, prefix))
+}
+
+fn in_network(ip: IpAddr, network: IpAddr, prefix: u8) -> bool {
+    match (ip, network) {
+        (IpAddr::V4(ip), IpAddr::V4(network)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            u32::from(ip) & mask == u32::from(network) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(network)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            u128::from(ip) & mask == u128::from(network) & mask
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::header::HeaderValue;
+
+    fn proxies() -> TrustedProxies {
+        TrustedProxies::parse(&["10.0.0.0/8".to_string(), "192.168.1.10".to_string()]).unwrap()
+    }
+
+    fn headers(forwarded_for: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            X_FORWARDED_FOR,
+            HeaderValue::from_str(forwarded_for).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_trusted_proxy_chain_yields_originating_client() {
+        let peer = "10.1.2.3".parse().ok();
+        // client -> edge proxy (192.168.1.10) -> internal LB (10.1.2.3)
+        let headers = headers("203.0.113.7, 192.168.1.10");
+
+        let ip = resolve_client_ip(peer, &headers, &proxies());
+
+        assert_eq!(ip, "203.0.113.7".parse().ok());
+    }
+
+    #[test]
+    fn test_spoofed_header_from_untrusted_peer_is_ignored() {
+        let peer = "198.51.100.20".parse().ok();
+        let mut headers = headers("1.2.3.4");
+        headers.insert(X_REAL_IP, HeaderValue::from_static("5.6.7.8"));
+
+        let ip = resolve_client_ip(peer, &headers, &proxies());
+
+        assert_eq!(ip, peer);
+    }
+
+    #[test]
+    fn test_spoofed_prefix_behind_trusted_proxy_is_skipped() {
+        let peer = "10.1.2.3".parse().ok();
+        // The client sent its own X-Forwarded-For; the proxy appended the real address
+        let headers = headers("1.2.3.4, 203.0.113.7");
+
+        let ip = resolve_client_ip(peer, &headers, &proxies());
+
+        assert_eq!(ip, "203.0.113.7".parse().ok());
+    }
+
+    #[test]
+    fn test_invalid_proxy_entry_is_rejected() {
+        let result = TrustedProxies::parse(&["10.0.0.0/40".to_string()]);
+        assert!(matches!(result, Err(AppError::ConfigurationError(_))));
+    }
+}
@@ -0,0 +1,157 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use actix_web::{
+    Error, HttpResponse,
+    body::{BodySize, BoxBody, MessageBody},
+    dev::{ServiceRequest, ServiceResponse},
+    middleware::Next,
+    web::{self, Bytes},
+};
+use serde_json::json;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Global cap on the number of requests being served at once
+///
+/// Shared by every worker; register as `web::Data<ConcurrencyLimit>`.
+pub struct ConcurrencyLimit {
+    permits: Arc<Semaphore>,
+}
+
+impl ConcurrencyLimit {
+    pub fn new(max_inflight_requests: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(max_inflight_requests)),
+        }
+    }
+}
+
+/// Reject requests with 503 once `ConcurrencyLimit` is saturated
+///
+/// A permit is held until the response body has been fully sent, except for
+/// streamed bodies such as SSE and WebSocket, which may stay open
+/// indefinitely: they release it once the response head is ready. Health
+/// probes bypass the limit so a busy instance is not reported dead.
+/// Register with `App::wrap(middleware::from_fn(limit_concurrency))`, inside
+/// `retry_after` so rejections carry the `Retry-After` hint.
+pub async fn limit_concurrency(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let limit = match req.app_data::<web::Data<ConcurrencyLimit>>() {
+        Some(limit) if !req.path().starts_with("/health") => limit.clone(),
+        _ => return Ok(next.call(req).await?.map_into_boxed_body()),
+    };
+
+    let Ok(permit) = limit.permits.clone().try_acquire_owned() else {
+        tracing::warn!("Rejecting request: too many requests in flight");
+        let response = HttpResponse::ServiceUnavailable().json(json!({
+            "error": {
+                "message": "Server is busy, please retry later",
+                "code": 503,
+            }
+        }));
+        return Ok(req.into_response(response));
+    };
+
+    let res = next.call(req).await?;
+    if matches!(res.response().body().size(), BodySize::Stream) {
+        drop(permit);
+        return Ok(res.map_into_boxed_body());
+    }
+    Ok(res.map_body(|_, body| {
+        BoxBody::new(PermitBody {
+            body: body.boxed(),
+            _permit: permit,
+        })
+    }))
+}
+
+/// Response body that keeps its concurrency permit until dropped
+struct PermitBody {
+    body: BoxBody,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl MessageBody for PermitBody {
+    type Error = Box<dyn std::error::Error>;
+
+    fn size(&self) -> BodySize {
+        self.body.size()
+    }
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        Pin::new(&mut self.body).poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, http::header::RETRY_AFTER, middleware::from_fn, test};
+
+    use crate::middleware::{RetryAfter, retry_after};
+
+    #[actix_web::test]
+    async fn test_request_beyond_limit_gets_503_until_a_permit_frees() {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(ConcurrencyLimit::new(2)))
+                .app_data(web::Data::new(RetryAfter::new(3)))
+                .wrap(from_fn(limit_concurrency))
+                .wrap(from_fn(retry_after))
+                .route("/work", web::get().to(HttpResponse::Ok))
+                .route("/health", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+        let call = || test::call_service(&app, test::TestRequest::get().uri("/work").to_request());
+
+        // Bodies not yet sent keep their requests in flight
+        let first = call().await;
+        let second = call().await;
+        assert_eq!(first.status(), 200);
+        assert_eq!(second.status(), 200);
+
+        let rejected = call().await;
+        assert_eq!(rejected.status(), 503);
+        assert_eq!(rejected.headers().get(RETRY_AFTER).unwrap(), "3");
+
+        let probe = test::TestRequest::get().uri("/health").to_request();
+        assert_eq!(test::call_service(&app, probe).await.status(), 200);
+
+        test::read_body(first).await;
+        assert_eq!(call().await.status(), 200);
+    }
+
+    #[actix_web::test]
+    async fn test_open_event_stream_does_not_hold_a_permit() {
+        use crate::handlers::event_handlers::user_events_sse;
+        use crate::states::AppState;
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(ConcurrencyLimit::new(1)))
+                .app_data(web::Data::new(AppState::new()))
+                .wrap(from_fn(limit_concurrency))
+                .route("/users/events", web::get().to(user_events_sse))
+                .route("/work", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        // Never read, so the stream stays open for the rest of the test
+        let stream = test::call_service(
+            &app,
+            test::TestRequest::get().uri("/users/events").to_request(),
+        )
+        .await;
+        assert_eq!(stream.status(), 200);
+
+        let work = test::TestRequest::get().uri("/work").to_request();
+        assert_eq!(test::call_service(&app, work).await.status(), 200);
+        drop(stream);
+    }
+}
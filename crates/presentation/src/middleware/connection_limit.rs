@@ -0,0 +1,177 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+
+use actix_web::{
+    Error, HttpResponse,
+    body::{EitherBody, MessageBody},
+    dev::{Extensions, ServiceRequest, ServiceResponse},
+    middleware::Next,
+    rt::net::TcpStream,
+};
+use serde_json::json;
+
+use super::TrustedProxies;
+
+/// Cap on concurrent connections from a single remote IP
+///
+/// Shared by every worker. Connections are counted as they are accepted by
+/// `on_connect`, which stores a `ConnectionSlot` in the connection's data;
+/// the slot is released when the connection closes. Connections from
+/// trusted proxies are not counted, since they carry many clients.
+#[derive(Debug, Clone)]
+pub struct ConnectionLimit {
+    max_per_ip: usize,
+    trusted_proxies: TrustedProxies,
+    open: Arc<Mutex<HashMap<IpAddr, usize>>>,
+}
+
+impl ConnectionLimit {
+    /// `max_per_ip` of 0 disables the limit
+    pub fn new(max_per_ip: usize, trusted_proxies: TrustedProxies) -> Self {
+        Self {
+            max_per_ip,
+            trusted_proxies,
+            open: Arc::default(),
+        }
+    }
+
+    /// Count a new connection from `ip`; the slot says whether it was admitted
+    pub fn acquire(&self, ip: IpAddr) -> ConnectionSlot {
+        if self.max_per_ip == 0 || self.trusted_proxies.contains(ip) {
+            return ConnectionSlot::Unlimited;
+        }
+        let mut open = self.open.lock().unwrap();
+        let count = open.entry(ip).or_default();
+        if *count >= self.max_per_ip {
+            return ConnectionSlot::Refused;
+        }
+        *count += 1;
+        ConnectionSlot::Admitted {
+            ip,
+            open: self.open.clone(),
+        }
+    }
+
+    /// `HttpServer::on_connect` callback registering each TCP connection
+    ///
+    /// Unix socket connections have no remote IP and are not counted.
+    pub fn on_connect(&self, connection: &dyn Any, data: &mut Extensions) {
+        let Some(ip) = connection
+            .downcast_ref::<TcpStream>()
+            .and_then(|stream| stream.peer_addr().ok())
+            .map(|addr| addr.ip())
+        else {
+            return;
+        };
+        data.insert(self.acquire(ip));
+    }
+}
+
+/// A connection's share of its IP's `ConnectionLimit`
+#[derive(Debug)]
+pub enum ConnectionSlot {
+    /// Counted against the IP until dropped with the connection
+    Admitted {
+        ip: IpAddr,
+        open: Arc<Mutex<HashMap<IpAddr, usize>>>,
+    },
+    /// Over the limit when accepted; its requests are refused
+    Refused,
+    /// Not subject to the limit
+    Unlimited,
+}
+
+impl ConnectionSlot {
+    pub fn is_refused(&self) -> bool {
+        matches!(self, Self::Refused)
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        let Self::Admitted { ip, open } = self else {
+            return;
+        };
+        let mut open = open.lock().unwrap();
+        if let Some(count) = open.get_mut(ip) {
+            *count -= 1;
+            if *count == 0 {
+                open.remove(ip);
+            }
+        }
+    }
+}
+
+/// Refuse requests on connections accepted over the per-IP limit
+///
+/// Answers 429 and closes the connection before any routing or handler
+/// work. Requests on connections without a `ConnectionSlot` pass through.
+/// Register with `App::wrap(middleware::from_fn(limit_connections))` as the
+/// outermost layer, alongside `HttpServer::on_connect` calling
+/// `ConnectionLimit::on_connect`.
+pub async fn limit_connections(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let refused = req
+        .conn_data::<ConnectionSlot>()
+        .is_some_and(ConnectionSlot::is_refused);
+    if !refused {
+        return Ok(next.call(req).await?.map_into_left_body());
+    }
+
+    tracing::warn!(
+        peer = ?req.peer_addr().map(|addr| addr.ip()),
+        "Refusing connection: too many connections from this address"
+    );
+    let response = HttpResponse::TooManyRequests().force_close().json(json!({
+        "error": {
+            "message": "Too many connections from this address",
+            "code": 429,
+        }
+    }));
+    Ok(req.into_response(response).map_into_right_body())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_over_the_per_ip_limit_is_refused() {
+        let limit = ConnectionLimit::new(2, TrustedProxies::default());
+        let client: IpAddr = "203.0.113.7".parse().unwrap();
+        let other: IpAddr = "203.0.113.8".parse().unwrap();
+
+        let first = limit.acquire(client);
+        let second = limit.acquire(client);
+        assert!(!first.is_refused() && !second.is_refused());
+        assert!(limit.acquire(client).is_refused());
+        assert!(!limit.acquire(other).is_refused());
+
+        // Closing a connection frees its slot
+        drop(first);
+        let third = limit.acquire(client);
+        assert!(!third.is_refused());
+        assert!(limit.acquire(client).is_refused());
+
+        drop((second, third));
+        assert!(limit.open.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_trusted_proxies_and_zero_limit_are_not_counted() {
+        let proxies = TrustedProxies::parse(&["10.0.0.0/8".to_string()]).unwrap();
+        let limit = ConnectionLimit::new(1, proxies);
+        let proxy: IpAddr = "10.1.2.3".parse().unwrap();
+        let slots: Vec<_> = (0..3).map(|_| limit.acquire(proxy)).collect();
+        assert!(slots.iter().all(|slot| !slot.is_refused()));
+
+        let unlimited = ConnectionLimit::new(0, TrustedProxies::default());
+        let client: IpAddr = "203.0.113.7".parse().unwrap();
+        let slots: Vec<_> = (0..3).map(|_| unlimited.acquire(client)).collect();
+        assert!(slots.iter().all(|slot| !slot.is_refused()));
+    }
+}
@@ -0,0 +1,161 @@
+use actix_web::{
+    Error,
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::{HeaderName, HeaderValue, LINK},
+    middleware::Next,
+    web,
+};
+use chrono::{DateTime, Utc};
+
+/// `Deprecation` response header (RFC 9745)
+pub const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+
+/// `Sunset` response header (RFC 8594)
+pub const SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// Sunset schedule for a deprecated route
+///
+/// Register on the deprecated resource or scope together with the
+/// middleware, so each route carries its own dates:
+/// `.app_data(web::Data::new(Deprecation::new(sunset))).wrap(from_fn(deprecated))`.
+#[derive(Debug, Clone)]
+pub struct Deprecation {
+    since: Option<DateTime<Utc>>,
+    sunset: DateTime<Utc>,
+    link: Option<String>,
+}
+
+impl Deprecation {
+    /// Deprecated now, removed at `sunset`
+    pub fn new(sunset: DateTime<Utc>) -> Self {
+        Self {
+            since: None,
+            sunset,
+            link: None,
+        }
+    }
+
+    /// When the route became deprecated; sent instead of `Deprecation: true`
+    pub fn since(mut self, since: DateTime<Utc>) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Documentation describing the replacement, sent as a `Link` header
+    pub fn with_link(mut self, url: impl Into<String>) -> Self {
+        self.link = Some(url.into());
+        self
+    }
+
+    fn deprecation_value(&self) -> String {
+        match self.since {
+            Some(since) => format!("@{}", since.timestamp()),
+            None => "true".to_string(),
+        }
+    }
+
+    fn sunset_value(&self) -> String {
+        // HTTP-date, always in GMT
+        self.sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+    }
+
+    fn link_value(&self) -> Option<String> {
+        self.link
+            .as_ref()
+            .map(|url| format!("<{}>; rel=\"deprecation\"; type=\"text/html\"", url))
+    }
+}
+
+/// Mark every response of the wrapped routes as deprecated
+///
+/// Adds `Deprecation`, plus `Sunset` and a `Link` to the docs from the
+/// nearest `web::Data<Deprecation>`. Without one only `Deprecation: true` is
+/// sent. Errors are marked too, so clients see the warning on any outcome.
+pub async fn deprecated(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let deprecation = req
+        .app_data::<web::Data<Deprecation>>()
+        .map(|config| config.get_ref().clone());
+
+    let mut res = next.call(req).await?;
+    let headers = res.headers_mut();
+    let Some(deprecation) = deprecation else {
+        headers.insert(DEPRECATION, HeaderValue::from_static("true"));
+        return Ok(res);
+    };
+
+    if let Ok(value) = HeaderValue::try_from(deprecation.deprecation_value()) {
+        headers.insert(DEPRECATION, value);
+    }
+    if let Ok(value) = HeaderValue::try_from(deprecation.sunset_value()) {
+        headers.insert(SUNSET, value);
+    }
+    match deprecation.link_value().map(HeaderValue::try_from) {
+        Some(Ok(value)) => headers.append(LINK, value),
+        Some(Err(_)) => tracing::warn!("Invalid deprecation link: {:?}", deprecation.link),
+        None => {}
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, HttpResponse, middleware::from_fn, test};
+    use chrono::TimeZone;
+
+    #[actix_web::test]
+    async fn test_only_deprecated_routes_get_deprecation_headers() {
+        let sunset = Utc.with_ymd_and_hms(2027, 1, 31, 0, 0, 0).unwrap();
+        let since = Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap();
+        let app = test::init_service(
+            App::new()
+                .service(
+                    web::resource("/old")
+                        .app_data(web::Data::new(
+                            Deprecation::new(sunset)
+                                .since(since)
+                                .with_link("https://docs.example.com/migrate"),
+                        ))
+                        .wrap(from_fn(deprecated))
+                        .route(web::get().to(HttpResponse::Ok)),
+                )
+                .route("/new", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let old = test::call_service(&app, test::TestRequest::get().uri("/old").to_request()).await;
+        assert_eq!(old.headers().get(DEPRECATION).unwrap(), "@1780272000");
+        assert_eq!(
+            old.headers().get(SUNSET).unwrap(),
+            "Sun, 31 Jan 2027 00:00:00 GMT"
+        );
+        assert_eq!(
+            old.headers().get(LINK).unwrap(),
+            "<https://docs.example.com/migrate>; rel=\"deprecation\"; type=\"text/html\""
+        );
+
+        let new = test::call_service(&app, test::TestRequest::get().uri("/new").to_request()).await;
+        assert!(new.headers().get(DEPRECATION).is_none());
+        assert!(new.headers().get(SUNSET).is_none());
+        assert!(new.headers().get(LINK).is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_without_schedule_only_flags_deprecation() {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(deprecated))
+                .route("/old", web::get().to(HttpResponse::NotFound)),
+        )
+        .await;
+
+        let res = test::call_service(&app, test::TestRequest::get().uri("/old").to_request()).await;
+        assert_eq!(res.status(), 404);
+        assert_eq!(res.headers().get(DEPRECATION).unwrap(), "true");
+        assert!(res.headers().get(SUNSET).is_none());
+    }
+}
@@ -0,0 +1,139 @@
+use actix_web::{
+    Error,
+    body::{BoxBody, MessageBody},
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::{ACCEPT_LANGUAGE, HeaderName},
+    middleware::Next,
+};
+
+use shared::{AppError, ErrorId, Locale};
+
+/// Re-render `AppError` responses in the language requested via `Accept-Language`
+///
+/// Headers set by inner layers, such as CORS, are carried over to the
+/// re-rendered response. Register with
+/// `App::wrap(middleware::from_fn(localize_errors))`.
+pub async fn localize_errors(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let locale = req
+        .headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .map(Locale::from_accept_language)
+        .unwrap_or_default();
+
+    let res = next.call(req).await?;
+
+    if locale == Locale::default() {
+        return Ok(res.map_into_boxed_body());
+    }
+
+    let error_id = res.response().extensions().get::<ErrorId>().copied();
+    let localized = res
+        .response()
+        .error()
+        .and_then(|err| err.as_error::<AppError>())
+        .map(|app_err| app_err.localized_error_response(locale, error_id));
+
+    match localized {
+        Some(mut response) => {
+            let (req, original) = res.into_parts();
+            let rendered: Vec<HeaderName> = response.headers().keys().cloned().collect();
+            for (name, value) in original.headers() {
+                if !rendered.contains(name) {
+                    response.headers_mut().append(name.clone(), value.clone());
+                }
+            }
+            Ok(ServiceResponse::new(req, response))
+        }
+        None => Ok(res.map_into_boxed_body()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, HttpResponse, middleware::from_fn, test, web};
+
+    async fn missing_user() -> actix_web::Result<HttpResponse> {
+        Err(AppError::NotFound("User 42".to_string()).into())
+    }
+
+    async fn message_for(accept_language: Option<&str>) -> String {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(localize_errors))
+                .route("/users/42", web::get().to(missing_user)),
+        )
+        .await;
+
+        let mut req = test::TestRequest::get().uri("/users/42");
+        if let Some(lang) = accept_language {
+            req = req.insert_header((ACCEPT_LANGUAGE, lang));
+        }
+        let res = test::call_service(&app, req.to_request()).await;
+        assert_eq!(res.status(), 404);
+
+        let body: serde_json::Value = test::read_body_json(res).await;
+        body["error"]["message"].as_str().unwrap().to_string()
+    }
+
+    #[actix_web::test]
+    async fn test_not_found_rendered_per_language() {
+        let en = message_for(Some("en-US")).await;
+        let tr = message_for(Some("tr-TR,tr;q=0.9")).await;
+
+        assert_eq!(en, "Not found: User 42");
+        assert_eq!(tr, "Bulunamadı: User 42");
+    }
+
+    #[actix_web::test]
+    async fn test_localized_response_keeps_inner_headers() {
+        use actix_web::{dev::Service, http::header};
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(localize_errors))
+                .wrap_fn(|req, srv| {
+                    let res = srv.call(req);
+                    async move {
+                        let mut res = res.await?;
+                        res.headers_mut().insert(
+                            header::ACCESS_CONTROL_ALLOW_ORIGIN,
+                            header::HeaderValue::from_static("https://app.example.com"),
+                        );
+                        Ok(res)
+                    }
+                })
+                .route("/users/42", web::get().to(missing_user)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/users/42")
+            .insert_header((ACCEPT_LANGUAGE, "tr"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(
+            res.headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .unwrap(),
+            "https://app.example.com"
+        );
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+        let body: serde_json::Value = test::read_body_json(res).await;
+        assert_eq!(body["error"]["message"], "Bulunamadı: User 42");
+    }
+
+    #[actix_web::test]
+    async fn test_defaults_to_english() {
+        assert_eq!(message_for(None).await, "Not found: User 42");
+        assert_eq!(message_for(Some("de")).await, "Not found: User 42");
+    }
+}
@@ -0,0 +1,273 @@
+use std::fmt;
+use std::io;
+
+use actix_web::{
+    Error, HttpMessage,
+    body::MessageBody,
+    dev::{Decompress, Payload, ServiceRequest, ServiceResponse},
+    error::PayloadError,
+    http::header::{CONTENT_ENCODING, CONTENT_LENGTH},
+    middleware::Next,
+    web::{self, Bytes},
+};
+use futures_util::StreamExt;
+
+/// Structural limits on JSON request bodies
+///
+/// Register as `web::Data<JsonLimits>` together with the `limit_json`
+/// middleware.
+#[derive(Debug, Clone, Copy)]
+pub struct JsonLimits {
+    max_depth: usize,
+    max_array_len: usize,
+}
+
+impl JsonLimits {
+    pub fn new(max_depth: usize, max_array_len: usize) -> Self {
+        Self {
+            max_depth,
+            max_array_len,
+        }
+    }
+}
+
+/// A JSON body broke one of the `JsonLimits`
+///
+/// Carried inside `PayloadError::Io` so the JSON extractors can report it as
+/// a validation error.
+#[derive(Debug)]
+pub(crate) struct JsonLimitExceeded(String);
+
+impl fmt::Display for JsonLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for JsonLimitExceeded {}
+
+impl JsonLimitExceeded {
+    /// The limit violation behind a body read error, if that is what it was
+    pub(crate) fn from_payload_error(err: &PayloadError) -> Option<&Self> {
+        let PayloadError::Io(err) = err else {
+            return None;
+        };
+        err.get_ref()?.downcast_ref()
+    }
+}
+
+/// Open object or array while scanning
+struct Frame {
+    array: bool,
+    elements: usize,
+    /// An array element may start at the next significant byte
+    expect_element: bool,
+}
+
+/// Incremental scan of a JSON body tracking nesting and array lengths
+///
+/// Only the structure is followed, the body is not validated; malformed
+/// JSON is left for the deserializer to reject.
+struct JsonScanner {
+    limits: JsonLimits,
+    stack: Vec<Frame>,
+    in_string: bool,
+    escaped: bool,
+}
+
+impl JsonScanner {
+    fn new(limits: JsonLimits) -> Self {
+        Self {
+            limits,
+            stack: Vec::new(),
+            in_string: false,
+            escaped: false,
+        }
+    }
+
+    fn feed(&mut self, chunk: &[u8]) -> Result<(), JsonLimitExceeded> {
+        for &byte in chunk {
+            if self.in_string {
+                match byte {
+                    _ if self.escaped => self.escaped = false,
+                    b'\\' => self.escaped = true,
+                    b'"' => self.in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            if byte.is_ascii_whitespace() {
+                continue;
+            }
+
+            if let Some(top) = self.stack.last_mut()
+                && top.array
+                && top.expect_element
+                && byte != b']'
+            {
+                top.elements += 1;
+                top.expect_element = false;
+                if top.elements > self.limits.max_array_len {
+                    return Err(JsonLimitExceeded(format!(
+                        "JSON array exceeds {} elements",
+                        self.limits.max_array_len
+                    )));
+                }
+            }
+
+            match byte {
+                b'"' => self.in_string = true,
+                b'[' | b'{' => {
+                    self.stack.push(Frame {
+                        array: byte == b'[',
+                        elements: 0,
+                        expect_element: byte == b'[',
+                    });
+                    if self.stack.len() > self.limits.max_depth {
+                        return Err(JsonLimitExceeded(format!(
+                            "JSON nesting exceeds {} levels",
+                            self.limits.max_depth
+                        )));
+                    }
+                }
+                b']' | b'}' => {
+                    self.stack.pop();
+                }
+                b',' => {
+                    if let Some(top) = self.stack.last_mut() {
+                        top.expect_element = top.array;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Enforce `JsonLimits` on JSON request bodies as they stream in
+///
+/// The body is scanned chunk by chunk ahead of the extractor, so an
+/// oversized structure is rejected without being read in full or
+/// deserialized; `ValidatedJson` and `json_error` report it as a 400
+/// validation error. Compressed bodies are decoded here first. Without a
+/// `web::Data<JsonLimits>` requests pass through untouched.
+pub async fn limit_json(
+    mut req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let limits = req
+        .app_data::<web::Data<JsonLimits>>()
+        .map(|limits| *limits.get_ref());
+    let is_json =
+        req.mime_type().ok().flatten().is_some_and(|mime| {
+            mime.subtype() == "json" || mime.suffix().is_some_and(|s| s == "json")
+        });
+    let Some(limits) = limits.filter(|_| is_json) else {
+        return next.call(req).await;
+    };
+
+    let body = Decompress::from_headers(req.take_payload(), req.headers());
+    if req.headers_mut().remove(CONTENT_ENCODING).next().is_some() {
+        req.headers_mut().remove(CONTENT_LENGTH);
+    }
+
+    let mut scanner = JsonScanner::new(limits);
+    let body = body.map(move |chunk: Result<Bytes, PayloadError>| {
+        let chunk = chunk?;
+        scanner
+            .feed(&chunk)
+            .map_err(|e| PayloadError::Io(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+        Ok(chunk)
+    });
+    req.set_payload(Payload::Stream {
+        payload: Box::pin(body),
+    });
+    next.call(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::{TestRequest, call_service, init_service, read_body_json};
+    use actix_web::{App, HttpResponse, http::StatusCode, middleware::from_fn};
+    use serde_json::{Value, json};
+
+    use crate::extractors::json_error;
+
+    async fn post(body: String) -> (StatusCode, Value) {
+        let app = init_service(
+            App::new()
+                .app_data(web::Data::new(JsonLimits::new(8, 100)))
+                .app_data(web::JsonConfig::default().error_handler(json_error))
+                .wrap(from_fn(limit_json))
+                .route(
+                    "/echo",
+                    web::post().to(|body: web::Json<Value>| async move {
+                        HttpResponse::Ok().json(body.into_inner())
+                    }),
+                ),
+        )
+        .await;
+        let req = TestRequest::post()
+            .uri("/echo")
+            .insert_header(("content-type", "application/json"))
+            .set_payload(body)
+            .to_request();
+        let res = call_service(&app, req).await;
+        let status = res.status();
+        (status, read_body_json(res).await)
+    }
+
+    fn nested(depth: usize) -> String {
+        format!("{}{}", r#"{"a":"#.repeat(depth), "}".repeat(depth)).replacen(
+            r#"{"a":}"#,
+            r#"{"a":1}"#,
+            1,
+        )
+    }
+
+    #[actix_web::test]
+    async fn test_deeply_nested_object_is_rejected() {
+        let (status, _) = post(nested(8)).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, body) = post(nested(9)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body["error"]["message"],
+            "Validation error: JSON nesting exceeds 8 levels"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_large_array_is_rejected() {
+        let (status, _) = post(json!({ "ids": vec![1; 100] }).to_string()).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, body) = post(json!({ "ids": vec![1; 101] }).to_string()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body["error"]["message"],
+            "Validation error: JSON array exceeds 100 elements"
+        );
+    }
+
+    #[test]
+    fn test_scanner_ignores_brackets_inside_strings() {
+        let mut scanner = JsonScanner::new(JsonLimits::new(2, 2));
+        let body = r#"[{"s": "[[[[,,,,\"]]]"}, ["x", "y"]]"#;
+
+        assert!(scanner.feed(body.as_bytes()).is_ok());
+        assert!(scanner.feed(b"").is_ok());
+    }
+
+    #[test]
+    fn test_scanner_counts_across_chunks() {
+        let mut scanner = JsonScanner::new(JsonLimits::new(4, 3));
+
+        assert!(scanner.feed(b"[1, [2, 3, 4]").is_ok());
+        assert!(scanner.feed(b", 5").is_ok());
+        assert!(scanner.feed(b", 6]").is_err());
+    }
+}
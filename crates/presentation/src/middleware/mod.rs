@@ -0,0 +1,23 @@
+pub mod access_log;
+pub mod auth;
+pub mod client_ip;
+pub mod concurrency;
+pub mod connection_limit;
+pub mod deprecation;
+pub mod i18n;
+pub mod json_limits;
+pub mod request_id;
+pub mod retry_after;
+pub mod transaction;
+
+pub use access_log::{AccessLog, access_log};
+pub use auth::{Authenticated, authenticate};
+pub use client_ip::{ClientIp, TrustedProxies, client_ip, resolve_client_ip};
+pub use concurrency::{ConcurrencyLimit, limit_concurrency};
+pub use connection_limit::{ConnectionLimit, ConnectionSlot, limit_connections};
+pub use deprecation::{Deprecation, deprecated};
+pub use i18n::localize_errors;
+pub use json_limits::{JsonLimits, limit_json};
+pub use request_id::{RequestId, request_id};
+pub use retry_after::{RetryAfter, retry_after};
+pub use transaction::{in_transaction, transactional};
@@ -0,0 +1,136 @@
+use actix_web::{
+    Error, HttpMessage,
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::{HeaderName, HeaderValue},
+    middleware::Next,
+};
+use tracing::Instrument;
+
+/// Header carrying the request correlation id in both directions
+pub const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest caller-supplied id that is kept; longer ones are replaced
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Correlation id of the current request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// Tag each request with an id and serve it inside a `request` span
+///
+/// The id comes from `X-Request-Id` when the caller (or a proxy) sent one,
+/// otherwise a UUID is generated. It is echoed back in the response header,
+/// stored in the request extensions and recorded on every log line of the
+/// request, including the one an error's `error_id` is logged with.
+/// Register with `App::wrap(middleware::from_fn(request_id))`.
+pub async fn request_id(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let id = req
+        .headers()
+        .get(&X_REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty() && value.len() <= MAX_REQUEST_ID_LEN)
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    req.extensions_mut().insert(RequestId(id.clone()));
+
+    let span = tracing::info_span!("request", request_id = %id);
+    let mut res = next.call(req).instrument(span).await?;
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        res.headers_mut().insert(X_REQUEST_ID, value);
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, HttpResponse, middleware::from_fn, test, web};
+
+    use crate::test_support::CapturedLogs;
+    use shared::AppError;
+
+    async fn failing() -> actix_web::Result<HttpResponse> {
+        Err(AppError::DatabaseError("connection reset".to_string()).into())
+    }
+
+    #[actix_web::test]
+    async fn test_error_id_in_body_matches_logged_event() {
+        let logs = CapturedLogs::default();
+        let _guard = logs.capture();
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(request_id))
+                .route("/fail", web::get().to(failing)),
+        )
+        .await;
+        let req = test::TestRequest::get()
+            .uri("/fail")
+            .insert_header((X_REQUEST_ID, "req-42"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.headers().get(&X_REQUEST_ID).unwrap(), "req-42");
+        let body: serde_json::Value = test::read_body_json(res).await;
+        let error_id = body["error"]["error_id"].as_str().unwrap();
+
+        let events = logs.events();
+        let logged = events
+            .iter()
+            .find(|event| event["fields"]["message"] == "Request failed")
+            .expect("the error is logged");
+        assert_eq!(logged["level"], "ERROR");
+        assert_eq!(logged["fields"]["error_id"], error_id);
+        assert_eq!(
+            logged["fields"]["error"],
+            "Database error: connection reset"
+        );
+        assert_eq!(logged["span"]["request_id"], "req-42");
+    }
+
+    #[actix_web::test]
+    async fn test_client_errors_are_logged_below_error_level() {
+        async fn missing() -> actix_web::Result<HttpResponse> {
+            Err(AppError::NotFound("User not found".to_string()).into())
+        }
+
+        let logs = CapturedLogs::default();
+        let _guard = logs.capture();
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(request_id))
+                .route("/missing", web::get().to(missing)),
+        )
+        .await;
+        let req = test::TestRequest::get().uri("/missing").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), 404);
+        let body: serde_json::Value = test::read_body_json(res).await;
+
+        let events = logs.events();
+        let logged = events
+            .iter()
+            .find(|event| event["fields"]["message"] == "Request failed")
+            .expect("the error is logged");
+        assert_eq!(logged["level"], "INFO");
+        assert_eq!(logged["fields"]["error_id"], body["error"]["error_id"]);
+    }
+
+    #[actix_web::test]
+    async fn test_missing_request_id_is_generated() {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(request_id))
+                .route("/ok", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+        let res = test::call_service(&app, test::TestRequest::get().uri("/ok").to_request()).await;
+
+        let id = res.headers().get(&X_REQUEST_ID).unwrap().to_str().unwrap();
+        assert!(uuid::Uuid::parse_str(id).is_ok());
+    }
+}
@@ -0,0 +1,99 @@
+use actix_web::{
+    Error,
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    http::{
+        StatusCode,
+        header::{HeaderValue, RETRY_AFTER},
+    },
+    middleware::Next,
+    web,
+};
+
+use shared::defaults::server::DEFAULT_OVERLOAD_RETRY_AFTER_SECONDS;
+
+/// Seconds clients are told to wait before retrying a 503
+///
+/// Register as `web::Data<RetryAfter>`; without it the default is used.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryAfter {
+    seconds: u64,
+}
+
+impl RetryAfter {
+    pub fn new(seconds: u64) -> Self {
+        Self { seconds }
+    }
+}
+
+impl Default for RetryAfter {
+    fn default() -> Self {
+        Self::new(DEFAULT_OVERLOAD_RETRY_AFTER_SECONDS)
+    }
+}
+
+/// Add `Retry-After` to every 503 response that does not already carry one
+///
+/// Backpressure sources (the concurrency limit, failing readiness checks)
+/// only pick the status; this keeps the client hint consistent across them.
+/// Register outermost with `App::wrap(middleware::from_fn(retry_after))`.
+pub async fn retry_after(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let retry_after = req
+        .app_data::<web::Data<RetryAfter>>()
+        .map(|config| *config.get_ref())
+        .unwrap_or_default();
+
+    let mut res = next.call(req).await?;
+    if res.status() == StatusCode::SERVICE_UNAVAILABLE && !res.headers().contains_key(RETRY_AFTER) {
+        res.headers_mut()
+            .insert(RETRY_AFTER, HeaderValue::from(retry_after.seconds));
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, HttpResponse, middleware::from_fn, test};
+
+    fn header<B>(res: &ServiceResponse<B>) -> Option<u64> {
+        res.headers()
+            .get(RETRY_AFTER)
+            .map(|value| value.to_str().unwrap().parse().unwrap())
+    }
+
+    #[actix_web::test]
+    async fn test_every_503_gets_a_numeric_retry_after() {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(RetryAfter::new(7)))
+                .wrap(from_fn(retry_after))
+                .route("/busy", web::get().to(HttpResponse::ServiceUnavailable))
+                .route(
+                    "/hinted",
+                    web::get().to(|| async {
+                        HttpResponse::ServiceUnavailable()
+                            .insert_header((RETRY_AFTER, "30"))
+                            .finish()
+                    }),
+                )
+                .route("/ok", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let busy =
+            test::call_service(&app, test::TestRequest::get().uri("/busy").to_request()).await;
+        assert_eq!(busy.status(), 503);
+        assert_eq!(header(&busy), Some(7));
+
+        let hinted =
+            test::call_service(&app, test::TestRequest::get().uri("/hinted").to_request()).await;
+        assert_eq!(header(&hinted), Some(30));
+
+        let ok = test::call_service(&app, test::TestRequest::get().uri("/ok").to_request()).await;
+        assert_eq!(header(&ok), None);
+    }
+}
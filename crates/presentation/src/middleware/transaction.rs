@@ -0,0 +1,165 @@
+use std::future::Future;
+
+use actix_web::{
+    Error, HttpMessage, HttpRequest, HttpResponse,
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    middleware::Next,
+    web,
+};
+
+use infrastructure::database::transaction::RequestTransaction;
+use shared::AppError;
+
+use crate::states::AppState;
+
+/// Run the request inside one transaction on the default database pool
+///
+/// Every repository call the handler makes joins the transaction, which is
+/// committed for a 2xx response and rolled back for any other status or an
+/// error. Cache invalidation and events run only after the commit. The
+/// transaction is also stored in the request extensions. Without a default
+/// pool in `AppState` the request runs untransacted.
+/// Register per resource with `wrap(middleware::from_fn(transactional))`.
+/// Handlers that read a large body should use `in_transaction` instead.
+pub async fn transactional(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let Some(pool) = default_pool(req.app_data::<web::Data<AppState>>()) else {
+        return next.call(req).await;
+    };
+
+    let tx = RequestTransaction::begin(&pool)
+        .await
+        .map_err(database_error)?;
+    req.extensions_mut().insert(tx.clone());
+
+    let res = tx.scope(next.call(req)).await;
+    finish(&tx, matches!(&res, Ok(res) if res.status().is_success())).await?;
+    res
+}
+
+/// Run `work` inside one transaction, as `transactional` does for a request
+///
+/// For handlers that read the request body first, so a slow upload does not
+/// hold a transaction and its pooled connection open.
+pub async fn in_transaction<F>(req: &HttpRequest, work: F) -> Result<HttpResponse, Error>
+where
+    F: Future<Output = Result<HttpResponse, Error>>,
+{
+    let Some(pool) = default_pool(req.app_data::<web::Data<AppState>>()) else {
+        return work.await;
+    };
+
+    let tx = RequestTransaction::begin(&pool)
+        .await
+        .map_err(database_error)?;
+    req.extensions_mut().insert(tx.clone());
+
+    let res = tx.scope(work).await;
+    finish(&tx, matches!(&res, Ok(res) if res.status().is_success())).await?;
+    res
+}
+
+fn default_pool(state: Option<&web::Data<AppState>>) -> Option<sqlx::PgPool> {
+    state.and_then(|state| state.db.get("default").cloned())
+}
+
+/// Commit a successful request's transaction, roll back any other
+async fn finish(tx: &RequestTransaction, success: bool) -> Result<(), AppError> {
+    if success {
+        return tx.commit().await.map_err(database_error);
+    }
+    if let Err(e) = tx.rollback().await {
+        tracing::warn!("Failed to roll back request transaction: {}", e);
+    }
+    Ok(())
+}
+
+fn database_error(e: sqlx::Error) -> AppError {
+    AppError::DatabaseError(format!("Request transaction failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, HttpResponse, middleware::from_fn, test};
+    use std::sync::Arc;
+
+    use application::{CreateUserRequest, UserService};
+    use infrastructure::PostgresUserRepository;
+    use sqlx::PgPool;
+
+    async fn create_then(
+        service: web::Data<UserService>,
+        path: web::Path<(String, bool)>,
+    ) -> actix_web::Result<HttpResponse> {
+        let (name, fail) = path.into_inner();
+        for suffix in ["a", "b"] {
+            service
+                .create_user(CreateUserRequest {
+                    username: format!("{}{}", name, suffix),
+                    email: format!("{}{}@example.com", name, suffix),
+                    full_name: None,
+                })
+                .await?;
+        }
+        if fail {
+            return Err(AppError::InternalError("failed after writing".to_string()).into());
+        }
+        Ok(HttpResponse::Created().finish())
+    }
+
+    #[actix_web::test]
+    #[ignore = "requires a PostgreSQL database (DATABASE_URL)"]
+    async fn test_error_response_rolls_back_every_write() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        infrastructure::database::postgres::run_migrations(&pool)
+            .await
+            .unwrap();
+        let mut state = AppState::new();
+        state.db.add_db_pool("default".to_string(), pool.clone());
+        let service = UserService::new(Arc::new(PostgresUserRepository::new(pool.clone())));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(state))
+                .app_data(web::Data::new(service))
+                .wrap(from_fn(transactional))
+                .route("/users/{name}/{fail}", web::post().to(create_then)),
+        )
+        .await;
+        let tag = &shared::UserId::new().to_string()[..8];
+        let stored = |name: String| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM users WHERE username LIKE $1")
+                    .bind(format!("{}%", name))
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let failed = format!("txfail{}", tag);
+        let req = test::TestRequest::post()
+            .uri(&format!("/users/{}/true", failed))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 500);
+        assert_eq!(stored(failed).await, 0);
+
+        let committed = format!("txok{}", tag);
+        let req = test::TestRequest::post()
+            .uri(&format!("/users/{}/false", committed))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 201);
+        assert_eq!(stored(committed.clone()).await, 2);
+
+        sqlx::query("DELETE FROM users WHERE username LIKE $1")
+            .bind(format!("{}%", committed))
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+}
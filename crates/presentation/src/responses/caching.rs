@@ -0,0 +1,249 @@
+use std::time::SystemTime;
+
+use actix_web::{
+    HttpMessage, HttpRequest, HttpResponse,
+    body::MessageBody,
+    http::{
+        StatusCode,
+        header::{
+            self, CacheControl, CacheDirective, EntityTag, HeaderValue, HttpDate, IfNoneMatch,
+        },
+    },
+    web,
+};
+use application::AuthContext;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use shared::config::ResponseConfig;
+use shared::defaults::response::DEFAULT_READ_CACHE_MAX_AGE_SECONDS;
+use shared::{AppError, AppResult};
+
+use super::respond;
+
+/// Request headers a user response can differ by: format, language and caller
+const VARY_BY_CALLER: &str = "Accept, Accept-Language, Authorization";
+
+/// Respond to a cacheable read
+///
+/// Sets `Cache-Control` from the registered `ResponseConfig`, `private` when
+/// the request carries credentials and `public` otherwise, and varies by
+/// caller. The validator is an `ETag` over the encoded body, so removed rows
+/// and a changed `total` revalidate too; a request whose `If-None-Match`
+/// matches gets an empty 304. `last_modified`, when known, is sent as
+/// `Last-Modified` for information only: the newest `updated_at` does not
+/// change when a row is deleted, so `If-Modified-Since` is not honored.
+pub fn respond_cacheable<T: Serialize>(
+    req: &HttpRequest,
+    body: &T,
+    last_modified: Option<DateTime<Utc>>,
+) -> AppResult<HttpResponse> {
+    let max_age = req
+        .app_data::<web::Data<ResponseConfig>>()
+        .map_or(DEFAULT_READ_CACHE_MAX_AGE_SECONDS, |config| {
+            config.read_cache_max_age_seconds
+        });
+    let visibility = if has_credentials(req) {
+        CacheDirective::Private
+    } else {
+        CacheDirective::Public
+    };
+
+    let (response, body) = respond(req, StatusCode::OK, body)?.into_parts();
+    let body = body
+        .try_into_bytes()
+        .map_err(|_| AppError::InternalError("Response body is not buffered".to_string()))?;
+    let etag = EntityTag::new_strong(format!("{:016x}", fnv1a(&body)));
+    let mut response = if none_match(req, &etag) {
+        HttpResponse::NotModified().finish()
+    } else {
+        response.set_body(body).map_into_boxed_body()
+    };
+
+    let headers = response.headers_mut();
+    headers.insert(
+        header::CACHE_CONTROL,
+        CacheControl(vec![visibility, CacheDirective::MaxAge(max_age as u32)])
+            .to_string()
+            .parse()
+            .expect("Cache-Control is a valid header value"),
+    );
+    headers.insert(
+        header::ETAG,
+        etag.to_string()
+            .parse()
+            .expect("entity tag is a valid header value"),
+    );
+    // HTTP dates have one-second resolution
+    if let Some(at) = last_modified.and_then(|at| DateTime::from_timestamp(at.timestamp(), 0)) {
+        headers.insert(
+            header::LAST_MODIFIED,
+            HttpDate::from(SystemTime::from(at))
+                .to_string()
+                .parse()
+                .expect("HTTP date is a valid header value"),
+        );
+    }
+    vary_by_caller(&mut response);
+    Ok(response)
+}
+
+/// Respond with a body whose shape depends on the caller
+///
+/// Varies by caller and, when the request is authenticated, is marked
+/// `private` so shared caches never hand one caller's view to another.
+pub fn respond_per_caller<T: Serialize>(
+    req: &HttpRequest,
+    status: StatusCode,
+    body: &T,
+) -> AppResult<HttpResponse> {
+    let mut response = respond(req, status, body)?;
+    if has_credentials(req) {
+        response.headers_mut().insert(
+            header::CACHE_CONTROL,
+            CacheControl(vec![CacheDirective::Private])
+                .to_string()
+                .parse()
+                .expect("Cache-Control is a valid header value"),
+        );
+    }
+    vary_by_caller(&mut response);
+    Ok(response)
+}
+
+fn vary_by_caller(response: &mut HttpResponse) {
+    response
+        .headers_mut()
+        .insert(header::VARY, HeaderValue::from_static(VARY_BY_CALLER));
+}
+
+/// Whether the request carries credentials or an authenticated caller,
+/// either of which can change its response
+fn has_credentials(req: &HttpRequest) -> bool {
+    req.headers().contains_key(header::AUTHORIZATION) || req.extensions().contains::<AuthContext>()
+}
+
+/// Whether the client's `If-None-Match` already covers `etag`
+fn none_match(req: &HttpRequest, etag: &EntityTag) -> bool {
+    match req.get_header::<IfNoneMatch>() {
+        Some(IfNoneMatch::Any) => true,
+        Some(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(etag)),
+        None => false,
+    }
+}
+
+/// FNV-1a 64-bit hash; stable across processes unlike `DefaultHasher`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    bytes
+        .iter()
+        .fold(OFFSET, |hash, b| (hash ^ u64::from(*b)).wrapping_mul(PRIME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+    use chrono::TimeZone;
+
+    fn updated_at() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_sets_cache_control_vary_etag_and_last_modified() {
+        let req = TestRequest::default()
+            .app_data(web::Data::new(ResponseConfig {
+                read_cache_max_age_seconds: 60,
+                ..ResponseConfig::default()
+            }))
+            .to_http_request();
+
+        let response = respond_cacheable(&req, &"body", Some(updated_at())).unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(
+            headers.get(header::CACHE_CONTROL).unwrap(),
+            "public, max-age=60"
+        );
+        assert_eq!(
+            headers.get(header::VARY).unwrap(),
+            "Accept, Accept-Language, Authorization"
+        );
+        assert!(headers.contains_key(header::ETAG));
+        assert_eq!(
+            headers.get(header::LAST_MODIFIED).unwrap(),
+            "Sun, 01 Jun 2025 12:00:00 GMT"
+        );
+    }
+
+    #[test]
+    fn test_credentialed_requests_are_cached_privately() {
+        let req = TestRequest::default()
+            .insert_header((header::AUTHORIZATION, "Bearer token"))
+            .to_http_request();
+
+        let response = respond_cacheable(&req, &"body", None).unwrap();
+
+        let cache_control = response.headers().get(header::CACHE_CONTROL).unwrap();
+        let cache_control = cache_control.to_str().unwrap();
+        assert!(cache_control.starts_with("private"));
+        assert!(!cache_control.contains("public"));
+    }
+
+    #[test]
+    fn test_matching_if_none_match_returns_not_modified() {
+        let first =
+            respond_cacheable(&TestRequest::default().to_http_request(), &"body", None).unwrap();
+        let etag = first.headers().get(header::ETAG).unwrap().clone();
+
+        let req = TestRequest::default()
+            .insert_header((header::IF_NONE_MATCH, etag.clone()))
+            .to_http_request();
+        let response = respond_cacheable(&req, &"body", None).unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get(header::ETAG), Some(&etag));
+        assert!(response.headers().contains_key(header::VARY));
+
+        // A changed body, e.g. after a delete, no longer matches
+        let req = TestRequest::default()
+            .insert_header((header::IF_NONE_MATCH, etag))
+            .to_http_request();
+        let response = respond_cacheable(&req, &"other body", None).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_per_caller_responses_vary_and_are_private_when_authenticated() {
+        let anonymous = TestRequest::default().to_http_request();
+        let response = respond_per_caller(&anonymous, StatusCode::OK, &"body").unwrap();
+        assert_eq!(
+            response.headers().get(header::VARY).unwrap(),
+            "Accept, Accept-Language, Authorization"
+        );
+        assert!(!response.headers().contains_key(header::CACHE_CONTROL));
+
+        let authenticated = TestRequest::default()
+            .insert_header((header::AUTHORIZATION, "Bearer token"))
+            .to_http_request();
+        let response = respond_per_caller(&authenticated, StatusCode::OK, &"body").unwrap();
+        assert_eq!(
+            response.headers().get(header::CACHE_CONTROL).unwrap(),
+            "private"
+        );
+        assert!(response.headers().contains_key(header::VARY));
+    }
+
+    #[test]
+    fn test_if_modified_since_alone_returns_full_response() {
+        let req = TestRequest::default()
+            .insert_header((header::IF_MODIFIED_SINCE, "Sun, 01 Jun 2025 12:00:01 GMT"))
+            .to_http_request();
+
+        let response = respond_cacheable(&req, &"body", Some(updated_at())).unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,121 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use application::UserListResponse;
+use shared::{AppError, AppResult};
+
+/// Fields a client may select on a `UserResponse`
+pub const USER_FIELDS: &[&str] = &[
+    "id",
+    "username",
+    "email",
+    "full_name",
+    "avatar_url",
+    "status",
+    "created_at",
+    "updated_at",
+];
+
+/// Sparse fieldset requested through `?fields=a,b,c`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSelection {
+    fields: Vec<String>,
+}
+
+impl FieldSelection {
+    /// Parse a comma-separated field list against the allowed names.
+    ///
+    /// A missing or blank parameter selects every field (`None`); any
+    /// unknown name is rejected.
+    pub fn parse(raw: Option<&str>, allowed: &[&str]) -> AppResult<Option<Self>> {
+        let Some(raw) = raw.map(str::trim).filter(|raw| !raw.is_empty()) else {
+            return Ok(None);
+        };
+
+        let mut fields: Vec<String> = Vec::new();
+        for name in raw
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            if !allowed.contains(&name) {
+                return Err(AppError::ValidationError(format!(
+                    "Unknown field '{}'; expected one of: {}",
+                    name,
+                    allowed.join(", ")
+                )));
+            }
+            if !fields.iter().any(|f| f == name) {
+                fields.push(name.to_string());
+            }
+        }
+
+        Ok(Some(Self { fields }))
+    }
+
+    /// Keep only the selected keys of a serialized object
+    pub fn filter(&self, value: Value) -> Value {
+        match value {
+            Value::Object(mut object) => {
+                let mut selected = Map::new();
+                for field in &self.fields {
+                    if let Some(v) = object.remove(field) {
+                        selected.insert(field.clone(), v);
+                    }
+                }
+                Value::Object(selected)
+            }
+            other => other,
+        }
+    }
+}
+
+/// Serialize a user, restricted to the selected fields
+///
+/// Works for either shape (`UserResponse` or `PublicUserResponse`); selected
+/// fields the shape lacks are left out.
+pub fn select_user<U: Serialize>(user: &U, selection: Option<&FieldSelection>) -> AppResult<Value> {
+    let value = to_value(user)?;
+    Ok(match selection {
+        Some(selection) => selection.filter(value),
+        None => value,
+    })
+}
+
+/// Serialize a user list, restricting each entry to the selected fields
+pub fn select_user_list<U: Serialize>(
+    list: &UserListResponse<U>,
+    selection: Option<&FieldSelection>,
+) -> AppResult<Value> {
+    let mut value = to_value(list)?;
+    if let Some(selection) = selection
+        && let Some(Value::Array(users)) = value.get_mut("users")
+    {
+        for user in users.iter_mut() {
+            *user = selection.filter(user.take());
+        }
+    }
+    Ok(value)
+}
+
+fn to_value<T: Serialize>(body: &T) -> AppResult<Value> {
+    serde_json::to_value(body)
+        .map_err(|e| AppError::InternalError(format!("Failed to serialize response: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_or_blank_selects_all() {
+        assert_eq!(FieldSelection::parse(None, USER_FIELDS).unwrap(), None);
+        assert_eq!(FieldSelection::parse(Some(" "), USER_FIELDS).unwrap(), None);
+    }
+
+    #[test]
+    fn test_unknown_field_is_rejected() {
+        let result = FieldSelection::parse(Some("id,bogus"), USER_FIELDS);
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
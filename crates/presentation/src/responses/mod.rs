@@ -0,0 +1,9 @@
+pub mod caching;
+pub mod fields;
+pub mod negotiation;
+pub mod pagination;
+
+pub use caching::{respond_cacheable, respond_per_caller};
+pub use fields::{FieldSelection, USER_FIELDS, select_user, select_user_list};
+pub use negotiation::{ResponseFormat, respond};
+pub use pagination::{PagePosition, X_TOTAL_COUNT, set_pagination_headers};
@@ -0,0 +1,380 @@
+use actix_web::{
+    HttpRequest, HttpResponse,
+    http::{
+        StatusCode,
+        header::{self, ContentType, Header, HeaderValue},
+    },
+    web,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use shared::config::{FieldCase, ResponseConfig};
+use shared::{AppError, AppResult};
+
+pub const MSGPACK_CONTENT_TYPE: &str = "application/x-msgpack";
+
+/// Serialization formats the API can produce
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseFormat {
+    Json,
+    MsgPack,
+}
+
+impl ResponseFormat {
+    /// Pick the response format from the request's `Accept` header.
+    ///
+    /// The highest ranked supported media type wins; a missing header,
+    /// `*/*`, or only unsupported types fall back to JSON.
+    pub fn from_request(req: &HttpRequest) -> Self {
+        let Ok(accept) = header::Accept::parse(req) else {
+            return ResponseFormat::Json;
+        };
+
+        for mime in accept.ranked() {
+            match (mime.type_().as_str(), mime.subtype().as_str()) {
+                ("application", "x-msgpack") | ("application", "msgpack") => {
+                    return ResponseFormat::MsgPack;
+                }
+                ("application", "json") | ("application", "*") | ("*", "*") => {
+                    return ResponseFormat::Json;
+                }
+                _ => continue,
+            }
+        }
+
+        ResponseFormat::Json
+    }
+}
+
+/// Keys of a list response that describe the page rather than its items
+const PAGINATION_KEYS: [&str; 4] = ["total", "limit", "offset", "next_cursor"];
+
+/// Whether the client asked for an enveloped body via an `envelope=true`
+/// parameter on an accepted media type
+fn envelope_requested(req: &HttpRequest) -> bool {
+    header::Accept::parse(req).is_ok_and(|accept| {
+        accept.iter().any(|item| {
+            item.item
+                .get_param("envelope")
+                .is_some_and(|value| value.as_str().eq_ignore_ascii_case("true"))
+        })
+    })
+}
+
+/// Wrap a success body as `{ "data": <body> }`
+///
+/// A paginated list (`total` and `limit` alongside the items) is split: its
+/// items become `data` and the page fields move to `meta`.
+pub fn envelope(value: Value) -> Value {
+    match value {
+        Value::Object(mut map) if map.contains_key("total") && map.contains_key("limit") => {
+            let meta: serde_json::Map<String, Value> = PAGINATION_KEYS
+                .iter()
+                .filter_map(|key| map.remove_entry(*key))
+                .collect();
+            let data = match map.values().next() {
+                Some(Value::Array(_)) if map.len() == 1 => map.into_iter().next().unwrap().1,
+                _ => Value::Object(map),
+            };
+            serde_json::json!({ "data": data, "meta": meta })
+        }
+        other => serde_json::json!({ "data": other }),
+    }
+}
+
+/// Encode `body` as JSON, indented when `pretty` is set
+pub fn to_json<T: Serialize>(body: &T, pretty: bool) -> AppResult<String> {
+    let encoded = if pretty {
+        serde_json::to_string_pretty(body)
+    } else {
+        serde_json::to_string(body)
+    };
+    encoded.map_err(|e| AppError::InternalError(format!("Failed to encode JSON response: {}", e)))
+}
+
+/// Rename every object key in `value` from snake_case to camelCase
+pub fn camel_case_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (camel_case(&key), camel_case_keys(value)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(camel_case_keys).collect()),
+        other => other,
+    }
+}
+
+/// `full_name` -> `fullName`; a leading underscore is kept
+fn camel_case(key: &str) -> String {
+    let mut out = String::with_capacity(key.len());
+    let mut upper_next = false;
+    for c in key.chars() {
+        if c == '_' && !out.is_empty() {
+            upper_next = true;
+        } else if upper_next {
+            out.extend(c.to_uppercase());
+            upper_next = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Serialize `body` in the format negotiated from the request
+///
+/// With a registered `ResponseConfig`, JSON is pretty-printed when
+/// `pretty_json` is enabled and keys are renamed per `field_case`. The body
+/// is enveloped when `envelope` is enabled or the client asked for it.
+/// The response carries `Vary: Accept`, since both choices follow that header.
+pub fn respond<T: Serialize>(
+    req: &HttpRequest,
+    status: StatusCode,
+    body: &T,
+) -> AppResult<HttpResponse> {
+    let mut response = negotiate(req, status, body)?;
+    response
+        .headers_mut()
+        .append(header::VARY, HeaderValue::from_static("Accept"));
+    Ok(response)
+}
+
+fn negotiate<T: Serialize>(
+    req: &HttpRequest,
+    status: StatusCode,
+    body: &T,
+) -> AppResult<HttpResponse> {
+    let config = req.app_data::<web::Data<ResponseConfig>>();
+    let camel_case = config.is_some_and(|config| config.field_case == FieldCase::CamelCase);
+    let enveloped = config.is_some_and(|config| config.envelope) || envelope_requested(req);
+    if !camel_case && !enveloped {
+        return encode(req, status, body);
+    }
+
+    let mut value = serde_json::to_value(body)
+        .map_err(|e| AppError::InternalError(format!("Failed to encode response: {}", e)))?;
+    if enveloped {
+        value = envelope(value);
+    }
+    if camel_case {
+        value = camel_case_keys(value);
+    }
+    encode(req, status, &value)
+}
+
+fn encode<T: Serialize>(
+    req: &HttpRequest,
+    status: StatusCode,
+    body: &T,
+) -> AppResult<HttpResponse> {
+    match ResponseFormat::from_request(req) {
+        ResponseFormat::Json => {
+            let pretty = req
+                .app_data::<web::Data<ResponseConfig>>()
+                .is_some_and(|config| config.pretty_json);
+            Ok(HttpResponse::build(status)
+                .content_type(ContentType::json())
+                .body(to_json(body, pretty)?))
+        }
+        ResponseFormat::MsgPack => {
+            let bytes = rmp_serde::to_vec_named(body).map_err(|e| {
+                AppError::InternalError(format!("Failed to encode MessagePack response: {}", e))
+            })?;
+            Ok(HttpResponse::build(status)
+                .content_type(MSGPACK_CONTENT_TYPE)
+                .body(bytes))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{body::to_bytes, test::TestRequest};
+    use application::{UserListResponse, UserResponse};
+    use chrono::Utc;
+    use domain::UserStatus;
+    use shared::UserId;
+
+    fn sample_user() -> UserResponse {
+        let now = Utc::now();
+        UserResponse {
+            id: UserId::new(),
+            username: "testuser".to_string(),
+            email: "test@example.com".to_string(),
+            full_name: Some("Test User".to_string()),
+            avatar_url: None,
+            status: UserStatus::Active,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    async fn render<T: Serialize>(accept: Option<&str>, body: &T) -> (String, Vec<u8>) {
+        let mut req = TestRequest::default();
+        if let Some(accept) = accept {
+            req = req.insert_header((header::ACCEPT, accept));
+        }
+        let res = respond(&req.to_http_request(), StatusCode::OK, body).unwrap();
+        let content_type = res
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        let bytes = to_bytes(res.into_body()).await.unwrap();
+        (content_type, bytes.to_vec())
+    }
+
+    #[actix_web::test]
+    async fn test_json_by_default() {
+        let user = sample_user();
+        for accept in [
+            None,
+            Some("*/*"),
+            Some("application/json"),
+            Some("text/html"),
+        ] {
+            let (content_type, body) = render(accept, &user).await;
+            assert_eq!(content_type, "application/json");
+            let decoded: UserResponse = serde_json::from_slice(&body).unwrap();
+            assert_eq!(decoded.id, user.id);
+        }
+    }
+
+    #[test]
+    fn test_response_varies_by_accept() {
+        for accept in [None, Some("application/x-msgpack")] {
+            let mut req = TestRequest::default();
+            if let Some(accept) = accept {
+                req = req.insert_header((header::ACCEPT, accept));
+            }
+            let res = respond(&req.to_http_request(), StatusCode::OK, &sample_user()).unwrap();
+            assert_eq!(res.headers().get(header::VARY).unwrap(), "Accept");
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_pretty_json_follows_response_config() {
+        let user = sample_user();
+        let render_with = |pretty_json: bool| {
+            let req = TestRequest::default()
+                .app_data(web::Data::new(ResponseConfig {
+                    pretty_json,
+                    ..ResponseConfig::default()
+                }))
+                .to_http_request();
+            respond(&req, StatusCode::OK, &user).unwrap()
+        };
+
+        let dev = to_bytes(render_with(true).into_body()).await.unwrap();
+        let prod = to_bytes(render_with(false).into_body()).await.unwrap();
+        let dev = std::str::from_utf8(&dev).unwrap();
+        let prod = std::str::from_utf8(&prod).unwrap();
+
+        assert!(dev.lines().count() > 1);
+        assert!(dev.contains("\n  \"username\": \"testuser\""));
+        assert_eq!(prod.lines().count(), 1);
+        let dev: serde_json::Value = serde_json::from_str(dev).unwrap();
+        let prod: serde_json::Value = serde_json::from_str(prod).unwrap();
+        assert_eq!(dev, prod);
+    }
+
+    #[actix_web::test]
+    async fn test_field_case_follows_response_config() {
+        let user = sample_user();
+        let render_with = |field_case: FieldCase| {
+            let req = TestRequest::default()
+                .app_data(web::Data::new(ResponseConfig {
+                    field_case,
+                    ..ResponseConfig::default()
+                }))
+                .to_http_request();
+            respond(&req, StatusCode::OK, &user).unwrap()
+        };
+
+        let snake = to_bytes(render_with(FieldCase::SnakeCase).into_body())
+            .await
+            .unwrap();
+        let camel = to_bytes(render_with(FieldCase::CamelCase).into_body())
+            .await
+            .unwrap();
+        let snake: Value = serde_json::from_slice(&snake).unwrap();
+        let camel: Value = serde_json::from_slice(&camel).unwrap();
+
+        assert_eq!(snake["full_name"], "Test User");
+        assert!(snake.get("fullName").is_none());
+        assert_eq!(camel["fullName"], "Test User");
+        assert!(camel.get("full_name").is_none());
+        assert_eq!(camel["createdAt"], snake["created_at"]);
+    }
+
+    #[test]
+    fn test_camel_case_keys_renames_nested_objects() {
+        let value = serde_json::json!({
+            "users": [{ "full_name": "A", "avatar_url": null }],
+            "_meta": { "next_cursor": "x" },
+        });
+        assert_eq!(
+            camel_case_keys(value),
+            serde_json::json!({
+                "users": [{ "fullName": "A", "avatarUrl": null }],
+                "_meta": { "nextCursor": "x" },
+            })
+        );
+    }
+
+    #[test]
+    fn test_envelope_moves_pagination_into_meta() {
+        let list = serde_json::json!({
+            "users": [{ "id": 1 }],
+            "total": 1,
+            "limit": 20,
+            "offset": 0,
+            "next_cursor": null,
+        });
+        assert_eq!(
+            envelope(list),
+            serde_json::json!({
+                "data": [{ "id": 1 }],
+                "meta": { "total": 1, "limit": 20, "offset": 0, "next_cursor": null },
+            })
+        );
+
+        let report = serde_json::json!({ "total": 2, "created": 1 });
+        assert_eq!(
+            envelope(report.clone()),
+            serde_json::json!({ "data": report })
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_msgpack_when_requested() {
+        let user = sample_user();
+        let (content_type, body) = render(Some("application/x-msgpack"), &user).await;
+        assert_eq!(content_type, MSGPACK_CONTENT_TYPE);
+        let decoded: UserResponse = rmp_serde::from_slice(&body).unwrap();
+        assert_eq!(decoded.id, user.id);
+        assert_eq!(decoded.full_name.as_deref(), Some("Test User"));
+    }
+
+    #[actix_web::test]
+    async fn test_msgpack_list_response() {
+        let list = UserListResponse {
+            users: vec![sample_user(), sample_user()],
+            total: 2,
+            limit: 20,
+            offset: 0,
+            next_cursor: None,
+        };
+        let (content_type, body) =
+            render(Some("application/json;q=0.5, application/x-msgpack"), &list).await;
+        assert_eq!(content_type, MSGPACK_CONTENT_TYPE);
+        let decoded: UserListResponse = rmp_serde::from_slice(&body).unwrap();
+        assert_eq!(decoded.users.len(), 2);
+        assert_eq!(decoded.total, 2);
+    }
+}
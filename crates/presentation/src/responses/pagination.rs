@@ -0,0 +1,160 @@
+use actix_web::{
+    HttpRequest, HttpResponse,
+    http::header::{self, HeaderName, HeaderValue},
+};
+
+use application::dtos::UserListResponse;
+
+/// Total number of items across all pages
+pub const X_TOTAL_COUNT: HeaderName = HeaderName::from_static("x-total-count");
+
+/// Query parameters rewritten in the `Link` URLs; others are kept as sent
+const PAGE_PARAMS: [&str; 3] = ["limit", "offset", "cursor"];
+
+/// Where a list page sits in the full result, for the pagination headers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PagePosition {
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub next_cursor: Option<String>,
+    /// Fetched with `cursor`, so only `first` and `next` can be linked
+    pub by_cursor: bool,
+}
+
+impl PagePosition {
+    pub fn of<U>(page: &UserListResponse<U>, by_cursor: bool) -> Self {
+        Self {
+            total: page.total,
+            limit: page.limit,
+            offset: page.offset,
+            next_cursor: page.next_cursor.clone(),
+            by_cursor,
+        }
+    }
+
+    /// `(rel, query)` pairs for the neighbouring pages
+    fn links(&self) -> Vec<(&'static str, String)> {
+        let limit = self.limit.max(1);
+        let at = |offset: i64| format!("limit={}&offset={}", limit, offset);
+
+        let mut links = vec![("first", at(0))];
+        if self.by_cursor {
+            if let Some(cursor) = &self.next_cursor {
+                links.push(("next", format!("limit={}&cursor={}", limit, cursor)));
+            }
+            return links;
+        }
+        if self.offset > 0 {
+            links.push(("prev", at((self.offset - limit).max(0))));
+        }
+        if self.offset + limit < self.total {
+            links.push(("next", at(self.offset + limit)));
+        }
+        links.push(("last", at((self.total - 1).max(0) / limit * limit)));
+        links
+    }
+}
+
+/// Add `X-Total-Count` and an RFC 5988 `Link` header to a list response
+///
+/// Link URLs repeat the request's scheme, host, path and any other query
+/// parameters, changing only `limit`, `offset` and `cursor`. The body keeps
+/// its own pagination fields.
+pub fn set_pagination_headers(
+    req: &HttpRequest,
+    response: &mut HttpResponse,
+    position: &PagePosition,
+) {
+    let base = {
+        let info = req.connection_info();
+        format!("{}://{}{}", info.scheme(), info.host(), req.path())
+    };
+    let kept: Vec<&str> = req
+        .query_string()
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter(|pair| !PAGE_PARAMS.contains(&pair.split('=').next().unwrap_or_default()))
+        .collect();
+
+    let link = position
+        .links()
+        .into_iter()
+        .map(|(rel, page)| {
+            let query = kept
+                .iter()
+                .copied()
+                .chain([page.as_str()])
+                .collect::<Vec<_>>()
+                .join("&");
+            format!("<{}?{}>; rel=\"{}\"", base, query, rel)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let headers = response.headers_mut();
+    headers.insert(X_TOTAL_COUNT, HeaderValue::from(position.total));
+    if let Ok(link) = HeaderValue::from_str(&link) {
+        headers.insert(header::LINK, link);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    fn position(total: i64, limit: i64, offset: i64) -> PagePosition {
+        PagePosition {
+            total,
+            limit,
+            offset,
+            next_cursor: None,
+            by_cursor: false,
+        }
+    }
+
+    #[test]
+    fn test_links_neighbouring_pages_and_keeps_other_params() {
+        let req = TestRequest::get()
+            .uri("/users?fields=id,email&limit=10&offset=10")
+            .to_http_request();
+        let mut response = HttpResponse::Ok().finish();
+
+        set_pagination_headers(&req, &mut response, &position(25, 10, 10));
+
+        assert_eq!(response.headers().get(X_TOTAL_COUNT).unwrap(), "25");
+        let base = "http://localhost:8080/users?fields=id,email&limit=10";
+        assert_eq!(
+            response.headers().get(header::LINK).unwrap(),
+            &format!(
+                "<{base}&offset=0>; rel=\"first\", <{base}&offset=0>; rel=\"prev\", \
+                 <{base}&offset=20>; rel=\"next\", <{base}&offset=20>; rel=\"last\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_edge_pages_omit_prev_and_next() {
+        let rels = |position: PagePosition| -> Vec<&str> {
+            position.links().into_iter().map(|(rel, _)| rel).collect()
+        };
+
+        assert_eq!(rels(position(25, 10, 0)), vec!["first", "next", "last"]);
+        assert_eq!(rels(position(25, 10, 20)), vec!["first", "prev", "last"]);
+        assert_eq!(rels(position(0, 10, 0)), vec!["first", "last"]);
+
+        let cursor = PagePosition {
+            next_cursor: Some("abc".to_string()),
+            by_cursor: true,
+            ..position(25, 10, 0)
+        };
+        assert_eq!(
+            cursor.links(),
+            vec![
+                ("first", "limit=10&offset=0".to_string()),
+                ("next", "limit=10&cursor=abc".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,8 @@
+use actix_web::web;
+
+use crate::handlers::auth_handlers;
+
+/// Configure authentication routes, mounted under `/api/v1/auth`
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.route("/refresh", web::post().to(auth_handlers::refresh_token));
+}
@@ -0,0 +1,112 @@
+use actix_web::{HttpRequest, HttpResponse, web};
+use serde_json::Value;
+
+use shared::{AppConfig, AppEnv, AppError};
+
+use crate::routes::health::HealthToken;
+
+/// Redacted effective configuration served by `GET /debug/config`
+///
+/// Registered as `web::Data<EffectiveConfig>`. The endpoint is exposed in
+/// dev, and elsewhere only when a health token is configured to guard it;
+/// otherwise it answers 404 as if it did not exist.
+#[derive(Debug, Clone, Default)]
+pub struct EffectiveConfig(Option<Value>);
+
+impl EffectiveConfig {
+    pub fn new(env: AppEnv, config: &AppConfig) -> Self {
+        let guarded = config
+            .security
+            .health_token
+            .as_deref()
+            .is_some_and(|token| !token.is_empty());
+        Self((env.is_dev() || guarded).then(|| config.redacted()))
+    }
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.route("/debug/config", web::get().to(effective_config));
+}
+
+/// Dump the merged file and environment configuration, secrets masked
+///
+/// Requires `X-Health-Token` when a `HealthToken` is configured.
+async fn effective_config(
+    req: HttpRequest,
+    config: Option<web::Data<EffectiveConfig>>,
+    token: Option<web::Data<HealthToken>>,
+) -> Result<HttpResponse, AppError> {
+    let Some(config) = config.as_ref().and_then(|config| config.0.as_ref()) else {
+        return Err(AppError::NotFound("Not found".to_string()));
+    };
+    if token.is_some_and(|token| !token.permits(&req)) {
+        return Err(AppError::Unauthorized(
+            "Missing or invalid health token".to_string(),
+        ));
+    }
+    Ok(HttpResponse::Ok().json(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, http::StatusCode, test};
+
+    use crate::routes::health::HEALTH_TOKEN_HEADER;
+
+    fn config(health_token: Option<&str>) -> AppConfig {
+        let mut config = AppConfig::default();
+        config.server.port = 8123;
+        config.database.connection_string = "postgres://app:hunter2@db:5432/app".to_string();
+        config.security.health_token = health_token.map(str::to_string);
+        config
+    }
+
+    async fn get(env: AppEnv, config: AppConfig, token: Option<&str>) -> (StatusCode, Value) {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(EffectiveConfig::new(env, &config)))
+                .app_data(web::Data::new(HealthToken::new(
+                    config.security.health_token.clone(),
+                )))
+                .configure(configure),
+        )
+        .await;
+        let mut req = test::TestRequest::get().uri("/debug/config");
+        if let Some(token) = token {
+            req = req.insert_header((HEALTH_TOKEN_HEADER, token));
+        }
+        let res = test::call_service(&app, req.to_request()).await;
+        let status = res.status();
+        let body = test::read_body(res).await;
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    #[actix_web::test]
+    async fn test_dev_dump_shows_settings_and_masks_db_password() {
+        let (status, body) = get(AppEnv::Dev, config(None), None).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["server"]["port"], 8123);
+        assert_eq!(
+            body["database"]["connection_string"],
+            "postgres://app:***@db:5432/app"
+        );
+        assert!(!body.to_string().contains("hunter2"));
+        assert_eq!(body["jwt"]["secret"], "***");
+    }
+
+    #[actix_web::test]
+    async fn test_outside_dev_requires_a_health_token() {
+        let (status, _) = get(AppEnv::Prod, config(None), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = get(AppEnv::Prod, config(Some("s3cret")), None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, body) = get(AppEnv::Prod, config(Some("s3cret")), Some("s3cret")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["server"]["port"], 8123);
+        assert_eq!(body["security"]["health_token"], "***");
+    }
+}
@@ -0,0 +1,8 @@
+use actix_web::web;
+
+use crate::handlers::event_handlers;
+
+/// Configure event streaming routes
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.route("/ws/users", web::get().to(event_handlers::user_events_ws));
+}
@@ -0,0 +1,33 @@
+use actix_web::{HttpResponse, web};
+use async_graphql::http::GraphiQLSource;
+use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse};
+
+use crate::graphql::UserSchema;
+use crate::middleware::Authenticated;
+
+/// Configure the GraphQL endpoint and its GraphiQL playground
+///
+/// Expects a `web::Data<UserSchema>` registered as app data. The caller's
+/// `AuthContext`, when authenticated, is passed to resolvers as request data.
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.route("/graphql", web::post().to(graphql_handler))
+        .route("/graphiql", web::get().to(graphiql));
+}
+
+async fn graphql_handler(
+    schema: web::Data<UserSchema>,
+    caller: Option<Authenticated>,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    let mut request = request.into_inner();
+    if let Some(caller) = caller {
+        request = request.data(caller.0);
+    }
+    schema.execute(request).await.into()
+}
+
+async fn graphiql() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(GraphiQLSource::build().endpoint("/graphql").finish())
+}
@@ -1,7 +1,172 @@
-use actix_web::{HttpResponse, web};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use actix_web::{HttpRequest, HttpResponse, web};
+use deadpool_redis::redis;
+use futures_util::future::join_all;
+use serde::Serialize;
+use serde_json::json;
+
+use shared::defaults::server::DEFAULT_HEALTH_CHECK_TIMEOUT_MS;
+use shared::{AppError, AppResult};
+
+use crate::states::AppState;
+
+/// Header carrying the shared secret for the detailed readiness probe
+pub const HEALTH_TOKEN_HEADER: &str = "x-health-token";
+
+/// Shared secret guarding `/health/ready`
+///
+/// Registered as `web::Data<HealthToken>`; when it is absent or holds no
+/// token the probe stays public. Liveness is never guarded.
+#[derive(Debug, Clone, Default)]
+pub struct HealthToken(Option<String>);
+
+impl HealthToken {
+    /// An empty token is treated as unset
+    pub fn new(token: Option<String>) -> Self {
+        Self(token.filter(|token| !token.is_empty()))
+    }
+
+    pub(crate) fn permits(&self, req: &HttpRequest) -> bool {
+        let Some(expected) = &self.0 else {
+            return true;
+        };
+        req.headers()
+            .get(HEALTH_TOKEN_HEADER)
+            .is_some_and(|given| constant_time_eq(given.as_bytes(), expected.as_bytes()))
+    }
+}
+
+/// Compare without short-circuiting on the first differing byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Upper bound on each readiness dependency check
+///
+/// Registered as `web::Data<HealthCheckTimeout>`; defaults to
+/// `DEFAULT_HEALTH_CHECK_TIMEOUT_MS`. Checks run concurrently, so the probe
+/// answers within roughly one timeout however many dependencies hang.
+#[derive(Debug, Clone, Copy)]
+pub struct HealthCheckTimeout(pub Duration);
+
+impl Default for HealthCheckTimeout {
+    fn default() -> Self {
+        Self(Duration::from_millis(DEFAULT_HEALTH_CHECK_TIMEOUT_MS))
+    }
+}
+
+/// Outcome of one dependency check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Healthy,
+    Unhealthy,
+    /// The check did not finish within `HealthCheckTimeout`
+    Timeout,
+}
+
+/// Entry of the `checks` object in the readiness response
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckReport {
+    pub status: CheckStatus,
+    /// Whether a failure makes the service not ready rather than degraded
+    pub critical: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+type CheckFuture<'a> = Pin<Box<dyn Future<Output = AppResult<()>> + 'a>>;
+
+/// A named dependency check awaiting execution
+struct Check<'a> {
+    name: &'static str,
+    critical: bool,
+    run: CheckFuture<'a>,
+}
+
+/// Run every check concurrently, each bounded by `timeout`
+async fn run_checks(
+    checks: Vec<Check<'_>>,
+    timeout: Duration,
+) -> BTreeMap<&'static str, CheckReport> {
+    let runs = checks.into_iter().map(|check| async move {
+        let report = match tokio::time::timeout(timeout, check.run).await {
+            Ok(Ok(())) => CheckReport {
+                status: CheckStatus::Healthy,
+                critical: check.critical,
+                error: None,
+            },
+            Ok(Err(e)) => {
+                tracing::warn!("{} readiness check failed: {}", check.name, e);
+                CheckReport {
+                    status: CheckStatus::Unhealthy,
+                    critical: check.critical,
+                    error: Some(e.detail()),
+                }
+            }
+            Err(_) => {
+                tracing::warn!(
+                    "{} readiness check timed out after {:?}",
+                    check.name,
+                    timeout
+                );
+                CheckReport {
+                    status: CheckStatus::Timeout,
+                    critical: check.critical,
+                    error: None,
+                }
+            }
+        };
+        (check.name, report)
+    });
+    join_all(runs).await.into_iter().collect()
+}
+
+/// The dependency checks configured in the application state
+fn dependency_checks(state: &AppState) -> Vec<Check<'_>> {
+    let mut checks = Vec::new();
+    if let Some(pool) = state.db.get("default") {
+        checks.push(Check {
+            name: "database",
+            critical: true,
+            run: Box::pin(async move {
+                sqlx::query("SELECT 1").execute(pool).await?;
+                Ok(())
+            }),
+        });
+    }
+    if let Some(pool) = state.cache.get("default") {
+        checks.push(Check {
+            name: "cache",
+            critical: false,
+            run: Box::pin(async move {
+                let mut conn = pool.get().await?;
+                redis::cmd("PING").query_async::<()>(&mut conn).await?;
+                Ok(())
+            }),
+        });
+    }
+    if let Some(sender) = state.email.sender() {
+        checks.push(Check {
+            name: "email",
+            critical: state.email.is_critical(),
+            run: Box::pin(sender.verify_connection()),
+        });
+    }
+    checks
+}
 
 pub fn routes(cfg: &mut web::ServiceConfig) {
-    cfg.service(web::scope("/health").route("", web::get().to(health_check)));
+    cfg.service(
+        web::scope("/health")
+            .route("", web::get().to(health_check))
+            .route("/live", web::get().to(health_check))
+            .route("/ready", web::get().to(readiness_check)),
+    );
 }
 
 async fn health_check() -> HttpResponse {
@@ -10,3 +175,168 @@ async fn health_check() -> HttpResponse {
         "uptime": "todo"
     }))
 }
+
+/// Readiness probe: reports ready only once startup (migrations) has completed
+///
+/// Dependency checks are listed under `checks`. A failing or timed-out
+/// critical check makes the service not ready; a non-critical one only marks
+/// it degraded. Requires `X-Health-Token` when a `HealthToken` is configured.
+async fn readiness_check(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    token: Option<web::Data<HealthToken>>,
+    timeout: Option<web::Data<HealthCheckTimeout>>,
+) -> Result<HttpResponse, AppError> {
+    if token.is_some_and(|token| !token.permits(&req)) {
+        return Err(AppError::Unauthorized(
+            "Missing or invalid health token".to_string(),
+        ));
+    }
+
+    if !state.readiness.is_ready() {
+        return Ok(HttpResponse::ServiceUnavailable().json(json!({ "status": "not_ready" })));
+    }
+
+    let timeout = timeout.map(|timeout| **timeout).unwrap_or_default();
+    let checks = run_checks(dependency_checks(&state), timeout.0).await;
+    let unhealthy = |critical: bool| {
+        checks
+            .values()
+            .any(|check| check.critical == critical && check.status != CheckStatus::Healthy)
+    };
+    let failed = unhealthy(true);
+    let degraded = unhealthy(false);
+
+    Ok(if failed {
+        HttpResponse::ServiceUnavailable().json(json!({ "status": "not_ready", "checks": checks }))
+    } else if degraded {
+        HttpResponse::Ok().json(json!({ "status": "degraded", "checks": checks }))
+    } else {
+        HttpResponse::Ok().json(json!({ "status": "ready", "checks": checks }))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, http::StatusCode, test};
+    use infrastructure::email::SmtpEmailSender;
+    use shared::config::EmailConfig;
+    use std::sync::Arc;
+
+    fn state_with_unreachable_smtp(critical: bool) -> AppState {
+        let config = EmailConfig {
+            enabled: true,
+            smtp_host: "127.0.0.1".to_string(),
+            smtp_port: 1,
+            use_tls: false,
+            timeout_seconds: 1,
+            critical,
+            ..EmailConfig::default()
+        };
+        let mut state = AppState::new();
+        state.readiness.mark_ready();
+        state
+            .email
+            .set_sender(Arc::new(SmtpEmailSender::new(&config).unwrap()), critical);
+        state
+    }
+
+    async fn probe(state: AppState) -> (StatusCode, serde_json::Value) {
+        let app =
+            test::init_service(App::new().app_data(web::Data::new(state)).configure(routes)).await;
+        let req = test::TestRequest::get().uri("/health/ready").to_request();
+        let resp = test::call_service(&app, req).await;
+        let status = resp.status();
+        (status, test::read_body_json(resp).await)
+    }
+
+    #[actix_web::test]
+    async fn test_unreachable_smtp_degrades_readiness_when_non_critical() {
+        let (status, body) = probe(state_with_unreachable_smtp(false)).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "degraded");
+        assert_eq!(body["checks"]["email"]["status"], "unhealthy");
+        assert_eq!(body["checks"]["email"]["critical"], false);
+    }
+
+    #[actix_web::test]
+    async fn test_unreachable_smtp_fails_readiness_when_critical() {
+        let (status, body) = probe(state_with_unreachable_smtp(true)).await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "not_ready");
+        assert_eq!(body["checks"]["email"]["status"], "unhealthy");
+    }
+
+    #[actix_web::test]
+    async fn test_hanging_check_times_out_without_stalling_the_probe() {
+        // Accepts connections but never sends the SMTP greeting
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = EmailConfig {
+            enabled: true,
+            smtp_host: "127.0.0.1".to_string(),
+            smtp_port: listener.local_addr().unwrap().port(),
+            use_tls: false,
+            timeout_seconds: 30,
+            ..EmailConfig::default()
+        };
+        let mut state = AppState::new();
+        state.readiness.mark_ready();
+        state
+            .email
+            .set_sender(Arc::new(SmtpEmailSender::new(&config).unwrap()), false);
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(state))
+                .app_data(web::Data::new(HealthCheckTimeout(Duration::from_millis(
+                    200,
+                ))))
+                .configure(routes),
+        )
+        .await;
+
+        let started = std::time::Instant::now();
+        let req = test::TestRequest::get().uri("/health/ready").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["status"], "degraded");
+        assert_eq!(body["checks"]["email"]["status"], "timeout");
+        drop(listener);
+    }
+
+    #[actix_web::test]
+    async fn test_health_token_guards_only_the_detailed_probe() {
+        let state = AppState::new();
+        state.readiness.mark_ready();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(state))
+                .app_data(web::Data::new(HealthToken::new(Some("s3cret".to_string()))))
+                .configure(routes),
+        )
+        .await;
+        let get = |uri: &str, token: Option<&str>| {
+            let mut req = test::TestRequest::get().uri(uri);
+            if let Some(token) = token {
+                req = req.insert_header((HEALTH_TOKEN_HEADER, token));
+            }
+            req.to_request()
+        };
+
+        let resp = test::call_service(&app, get("/health/live", None)).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        for token in [None, Some("wrong")] {
+            let resp = test::call_service(&app, get("/health/ready", token)).await;
+            assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        }
+
+        let resp = test::call_service(&app, get("/health/ready", Some("s3cret"))).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+}
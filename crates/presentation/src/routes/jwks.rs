@@ -0,0 +1,61 @@
+use actix_web::{HttpResponse, http::header, web};
+
+use infrastructure::auth::JwkSet;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.route("/.well-known/jwks.json", web::get().to(jwks));
+}
+
+/// Publish the token verification keys as a JSON Web Key Set
+///
+/// Serves the `web::Data<JwkSet>` built from `JwtTokenService::jwks`, so
+/// other services can verify RS256/ES256 tokens without the private key.
+/// The set is empty with HS256, whose secret is never published.
+async fn jwks(keys: web::Data<JwkSet>) -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header((header::CACHE_CONTROL, "public, max-age=300"))
+        .json(keys.get_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, test};
+    use infrastructure::auth::JwtTokenService;
+    use shared::config::{JwtAlgorithm, JwtConfig};
+
+    fn testdata(file: &str) -> String {
+        format!(
+            "{}/../infrastructure/src/auth/testdata/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            file
+        )
+    }
+
+    #[actix_web::test]
+    async fn test_jwks_serves_parseable_rs256_key() {
+        let service = JwtTokenService::new(&JwtConfig {
+            algorithm: JwtAlgorithm::RS256,
+            private_key_path: Some(testdata("rs256_private.pem")),
+            public_key_path: Some(testdata("rs256_public.pem")),
+            key_id: "2026-10".to_string(),
+            ..JwtConfig::default()
+        })
+        .unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(service.jwks()))
+                .configure(configure),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/.well-known/jwks.json")
+            .to_request();
+        let keys: JwkSet = test::call_and_read_body_json(&app, req).await;
+
+        assert_eq!(keys.keys.len(), 1);
+        let key = keys.find("2026-10").unwrap();
+        assert!(jsonwebtoken::DecodingKey::from_jwk(key).is_ok());
+    }
+}
@@ -1,3 +1,8 @@
+pub mod auth;
+pub mod debug;
+pub mod events;
+pub mod graphql;
 pub mod health;
+pub mod jwks;
 pub mod tenant;
 pub mod user;
@@ -1,19 +1,184 @@
-use actix_web::web;
+use actix_web::{HttpResponse, Route, http::header, middleware::from_fn, web};
 
-use crate::handlers::user_handlers;
+use crate::handlers::{auth_handlers, event_handlers, import_handlers, user_handlers};
+use crate::middleware::transactional;
 
 /// Configure user routes
+///
+/// Each path is a single resource so that HEAD mirrors GET (actix drops the
+/// body) and any other method gets a 405 listing the allowed ones. Batch
+/// writes run in a request transaction so they apply all-or-nothing; the
+/// import handler opens its own once the upload has been read.
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/users")
-            .route("", web::post().to(user_handlers::create_user))
-            .route("", web::get().to(user_handlers::list_users))
-            .route("/{id}", web::get().to(user_handlers::get_user))
-            .route("/{id}", web::put().to(user_handlers::update_user))
-            .route("/{id}", web::delete().to(user_handlers::delete_user))
-            .route(
-                "/username/{username}",
-                web::get().to(user_handlers::get_user_by_username),
+            .service(
+                web::resource("")
+                    .route(web::post().to(user_handlers::create_user))
+                    .route(web::get().to(user_handlers::list_users))
+                    .route(web::head().to(user_handlers::list_users))
+                    .default_service(method_not_allowed("GET, HEAD, POST")),
+            )
+            .service(
+                web::resource("/recent")
+                    .route(web::get().to(user_handlers::recent_users))
+                    .default_service(method_not_allowed("GET")),
+            )
+            .service(
+                web::resource("/events")
+                    .route(web::get().to(event_handlers::user_events_sse))
+                    .default_service(method_not_allowed("GET")),
+            )
+            .service(
+                web::resource("/bulk")
+                    .wrap(from_fn(transactional))
+                    .route(web::post().to(user_handlers::bulk_create_users))
+                    .default_service(method_not_allowed("POST")),
+            )
+            .service(
+                web::resource("/batch-get")
+                    .route(web::post().to(user_handlers::batch_get_users))
+                    .default_service(method_not_allowed("POST")),
+            )
+            .service(
+                web::resource("/batch-delete")
+                    .wrap(from_fn(transactional))
+                    .route(web::post().to(user_handlers::batch_delete_users))
+                    .default_service(method_not_allowed("POST")),
+            )
+            .service(
+                web::resource("/import")
+                    .route(web::post().to(import_handlers::import_users))
+                    .default_service(method_not_allowed("POST")),
+            )
+            .service(
+                web::resource("/me")
+                    .route(web::get().to(user_handlers::me))
+                    .route(web::head().to(user_handlers::me))
+                    .route(web::patch().to(user_handlers::update_me))
+                    .default_service(method_not_allowed("GET, HEAD, PATCH")),
+            )
+            .service(
+                web::resource("/me/password")
+                    .route(web::post().to(auth_handlers::change_password))
+                    .default_service(method_not_allowed("POST")),
+            )
+            .service(
+                web::resource("/{id}")
+                    .route(web::get().to(user_handlers::get_user))
+                    .route(web::head().to(user_handlers::get_user))
+                    .route(web::put().to(user_handlers::update_user))
+                    .route(web::patch().to(user_handlers::patch_user))
+                    .route(web::delete().to(user_handlers::delete_user))
+                    .default_service(method_not_allowed("GET, HEAD, PUT, PATCH, DELETE")),
+            )
+            .service(
+                web::resource("/{id}/restore")
+                    .route(web::post().to(user_handlers::restore_user))
+                    .default_service(method_not_allowed("POST")),
+            )
+            .service(
+                web::resource("/{id}/impersonate")
+                    .route(web::post().to(auth_handlers::impersonate_user))
+                    .default_service(method_not_allowed("POST")),
+            )
+            .service(
+                web::resource("/domain/{domain}")
+                    .route(web::get().to(user_handlers::users_by_domain))
+                    .default_service(method_not_allowed("GET")),
+            )
+            .service(
+                web::resource("/username/{username}")
+                    .route(web::get().to(user_handlers::get_user_by_username))
+                    .route(web::head().to(user_handlers::get_user_by_username))
+                    .default_service(method_not_allowed("GET, HEAD")),
             ),
     );
 }
+
+/// Fallback for a resource: 405 with an `Allow` header naming its methods
+fn method_not_allowed(allow: &'static str) -> Route {
+    web::to(move || async move {
+        HttpResponse::MethodNotAllowed()
+            .insert_header((header::ALLOW, allow))
+            .finish()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, http::StatusCode, test};
+    use std::sync::Arc;
+
+    use application::{CreateUserRequest, UserService};
+
+    use crate::test_support::InMemoryUserRepository;
+
+    async fn service_with_user() -> (web::Data<UserService>, String) {
+        let service = web::Data::new(UserService::new(
+            Arc::new(InMemoryUserRepository::default()),
+        ));
+        let user = service
+            .create_user(CreateUserRequest {
+                username: "headuser".to_string(),
+                email: "head@example.com".to_string(),
+                full_name: None,
+            })
+            .await
+            .unwrap();
+        (service, user.id.to_string())
+    }
+
+    #[actix_web::test]
+    async fn test_head_returns_headers_without_body() {
+        let (service, id) = service_with_user().await;
+        let srv = actix_test::start(move || {
+            App::new()
+                .app_data(service.clone())
+                .service(web::scope("/api/v1").configure(configure))
+        });
+
+        let mut resp = srv
+            .head(format!("/api/v1/users/{}", id))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+        assert!(resp.body().await.unwrap().is_empty());
+    }
+
+    #[actix_web::test]
+    async fn test_unsupported_method_returns_405_with_allow() {
+        let (service, id) = service_with_user().await;
+        let app = test::init_service(
+            App::new()
+                .app_data(service)
+                .service(web::scope("/api/v1").configure(configure)),
+        )
+        .await;
+
+        let cases = [
+            (
+                test::TestRequest::default().method(actix_web::http::Method::OPTIONS),
+                format!("/api/v1/users/{}", id),
+                "GET, HEAD, PUT, PATCH, DELETE",
+            ),
+            (
+                test::TestRequest::put(),
+                "/api/v1/users".to_string(),
+                "GET, HEAD, POST",
+            ),
+        ];
+        for (req, uri, allow) in cases {
+            let resp = test::call_service(&app, req.uri(&uri).to_request()).await;
+            assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED, "{}", uri);
+            assert_eq!(resp.headers().get(header::ALLOW).unwrap(), allow);
+        }
+    }
+}
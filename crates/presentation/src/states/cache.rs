@@ -15,4 +15,12 @@ impl CacheState {
     pub fn add_cache(&mut self, name: String, config: Pool) {
         self.caches.insert(name, config);
     }
+
+    /// Close every pool, dropping idle connections and refusing new checkouts
+    pub fn close_all(&self) {
+        for (name, pool) in &self.caches {
+            pool.close();
+            tracing::info!("Closed cache pool '{}'", name);
+        }
+    }
 }
@@ -1,6 +1,8 @@
 use sqlx::PgPool;
 use std::collections::HashMap;
 
+use infrastructure::database::DbChannels;
+
 #[derive(Clone, Default)]
 pub struct DatabaseState {
     pools: HashMap<String, PgPool>,
@@ -11,7 +13,20 @@ impl DatabaseState {
         self.pools.get(name)
     }
 
+    /// Pool registered under a well-known channel name
+    pub fn channel(&self, channel: DbChannels) -> Option<&PgPool> {
+        self.get(channel.as_str())
+    }
+
     pub fn add_db_pool(&mut self, name: String, pool: PgPool) {
         self.pools.insert(name, pool);
     }
+
+    /// Close every pool, waiting for checked-out connections to be returned
+    pub async fn close_all(&self) {
+        for (name, pool) in &self.pools {
+            pool.close().await;
+            tracing::info!("Closed database pool '{}'", name);
+        }
+    }
 }
@@ -1,8 +1,13 @@
 use std::collections::HashMap;
+use std::sync::Arc;
+
+use application::ports::EmailSender;
 
 #[derive(Clone, Default)]
 pub struct EmailState {
     emails: HashMap<String, String>,
+    sender: Option<Arc<dyn EmailSender>>,
+    critical: bool,
 }
 
 impl EmailState {
@@ -13,4 +18,19 @@ impl EmailState {
     pub fn add_email(&mut self, name: String, config: String) {
         self.emails.insert(name, config);
     }
+
+    /// Register the outgoing mail sender. When `critical` is false a failed
+    /// connection check only degrades readiness.
+    pub fn set_sender(&mut self, sender: Arc<dyn EmailSender>, critical: bool) {
+        self.sender = Some(sender);
+        self.critical = critical;
+    }
+
+    pub fn sender(&self) -> Option<&Arc<dyn EmailSender>> {
+        self.sender.as_ref()
+    }
+
+    pub fn is_critical(&self) -> bool {
+        self.critical
+    }
 }
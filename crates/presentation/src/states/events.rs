@@ -0,0 +1,184 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+
+use application::EventBus;
+use domain::UserEvent;
+use infrastructure::events::InProcessEventBus;
+
+/// Buffered events per subscriber before slow clients start lagging
+const DEFAULT_EVENT_CAPACITY: usize = 256;
+
+/// Recent events kept for `Last-Event-ID` replay
+const DEFAULT_HISTORY_CAPACITY: usize = 256;
+
+/// In-process event bus carrying user lifecycle events to streaming endpoints
+///
+/// The bus is handed to `UserService`; each connected client subscribes
+/// its own receiver.
+#[derive(Clone)]
+pub struct EventsState {
+    bus: InProcessEventBus,
+    history: EventHistory,
+}
+
+impl Default for EventsState {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_EVENT_CAPACITY)
+    }
+}
+
+impl EventsState {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            bus: InProcessEventBus::new(capacity),
+            history: EventHistory::new(capacity, DEFAULT_HISTORY_CAPACITY),
+        }
+    }
+
+    pub fn bus(&self) -> Arc<dyn EventBus> {
+        Arc::new(self.bus.clone())
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<UserEvent> {
+        self.bus.subscribe()
+    }
+
+    pub fn history(&self) -> &EventHistory {
+        &self.history
+    }
+
+    /// Wait until subscribers have received every buffered event
+    pub async fn flush(&self) {
+        self.bus.flush().await;
+    }
+
+    /// Stop the bus; events published afterwards are dropped
+    pub fn close(&self) {
+        self.bus.close();
+    }
+
+    /// Spawn the task that numbers published events and records them in the
+    /// replay history. Must run for sequenced subscribers (SSE) to see events.
+    pub fn spawn_history_recorder(&self) -> JoinHandle<()> {
+        let mut receiver = self.subscribe();
+        let history = self.history.clone();
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => history.record(event),
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!("Event history lagged, skipped {} events", skipped);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+}
+
+/// An event tagged with its position in the stream
+#[derive(Debug, Clone)]
+pub struct SequencedEvent {
+    pub id: u64,
+    pub event: UserEvent,
+}
+
+/// Bounded buffer of recent sequenced events, with live fan-out
+///
+/// Recording and subscribing share one lock, so a subscriber gets every event
+/// exactly once: either in its backlog snapshot or from its receiver.
+#[derive(Clone)]
+pub struct EventHistory {
+    inner: Arc<Mutex<HistoryInner>>,
+    live: broadcast::Sender<SequencedEvent>,
+}
+
+struct HistoryInner {
+    next_id: u64,
+    capacity: usize,
+    buffer: VecDeque<SequencedEvent>,
+}
+
+impl EventHistory {
+    pub fn new(channel_capacity: usize, history_capacity: usize) -> Self {
+        let (live, _) = broadcast::channel(channel_capacity);
+        Self {
+            inner: Arc::new(Mutex::new(HistoryInner {
+                next_id: 1,
+                capacity: history_capacity,
+                buffer: VecDeque::with_capacity(history_capacity),
+            })),
+            live,
+        }
+    }
+
+    /// Assign the next ID to an event, buffer it and fan it out
+    pub fn record(&self, event: UserEvent) {
+        let mut inner = self.inner.lock().unwrap();
+        let sequenced = SequencedEvent {
+            id: inner.next_id,
+            event,
+        };
+        inner.next_id += 1;
+        if inner.buffer.len() == inner.capacity {
+            inner.buffer.pop_front();
+        }
+        inner.buffer.push_back(sequenced.clone());
+        let _ = self.live.send(sequenced);
+    }
+
+    /// Subscribe to live events, returning the buffered events after
+    /// `last_event_id` (none when `None`) to replay first
+    pub fn subscribe_after(
+        &self,
+        last_event_id: Option<u64>,
+    ) -> (Vec<SequencedEvent>, broadcast::Receiver<SequencedEvent>) {
+        let inner = self.inner.lock().unwrap();
+        let receiver = self.live.subscribe();
+        let backlog = match last_event_id {
+            Some(last) => inner
+                .buffer
+                .iter()
+                .filter(|e| e.id > last)
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        (backlog, receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::UserId;
+
+    #[test]
+    fn test_replays_only_events_after_last_id() {
+        let history = EventHistory::new(16, 16);
+        for _ in 0..3 {
+            history.record(UserEvent::deleted(UserId::new()));
+        }
+
+        let (backlog, _) = history.subscribe_after(Some(1));
+        let ids: Vec<u64> = backlog.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![2, 3]);
+
+        let (backlog, _) = history.subscribe_after(None);
+        assert!(backlog.is_empty());
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let history = EventHistory::new(16, 2);
+        for _ in 0..5 {
+            history.record(UserEvent::deleted(UserId::new()));
+        }
+
+        let (backlog, _) = history.subscribe_after(Some(0));
+        let ids: Vec<u64> = backlog.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![4, 5]);
+    }
+}
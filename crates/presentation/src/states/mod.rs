@@ -1,11 +1,23 @@
 mod cache;
 mod database;
 mod email;
+mod events;
 mod jwt;
+mod readiness;
+mod tasks;
 
 use crate::states::{cache::CacheState, database::DatabaseState, email::EmailState, jwt::JwtState};
 
-use infrastructure::{cache::redis::create_redis_pool, database::postgres::create_postgres_pool};
+pub use events::{EventHistory, EventsState, SequencedEvent};
+pub use readiness::ReadinessState;
+pub use tasks::TaskManager;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use infrastructure::cache::redis::{create_redis_pool, warm_up_redis_pool};
+use infrastructure::database::postgres::{create_lazy_postgres_pool, warm_up_postgres_pool};
+use infrastructure::email::SmtpEmailSender;
 
 /// Application state shared across all handlers
 #[derive(Clone, Default)]
@@ -14,6 +26,9 @@ pub struct AppState {
     pub cache: CacheState,
     pub jwt: JwtState,
     pub email: EmailState,
+    pub readiness: ReadinessState,
+    pub events: EventsState,
+    pub tasks: TaskManager,
 }
 
 impl AppState {
@@ -25,14 +40,147 @@ impl AppState {
         &mut self,
         conf: &shared::AppConfig,
     ) -> Result<Self, Box<dyn std::error::Error>> {
-        // Load database configurations
-        // let db_pool = create_postgres_pool(conf.database.clone()).await?;
-        // self.db.add_db_pool("default".to_string(), db_pool);
+        // Load named database pools; the default pool is created by the server
+        for (name, db_conf) in &conf.databases {
+            let pool = create_lazy_postgres_pool(db_conf)?;
+            if db_conf.warmup {
+                warm_up_postgres_pool(&pool, db_conf.min_connections).await?;
+            }
+            self.db.add_db_pool(name.clone(), pool);
+        }
 
         // Load cache configurations
         let cache = create_redis_pool(conf.cache.clone()).await?;
+        if conf.cache.warmup {
+            warm_up_redis_pool(&cache, conf.cache.pool_size).await?;
+        }
         self.cache.add_cache("default".to_string(), cache);
 
+        // Load email sender; a bad SMTP setup should not take down the rest of the state
+        if conf.email.enabled {
+            match SmtpEmailSender::new(&conf.email) {
+                Ok(sender) => self.email.set_sender(Arc::new(sender), conf.email.critical),
+                Err(e) => tracing::warn!("Email sender disabled: {}", e),
+            }
+        }
+
         Ok(self.clone())
     }
+
+    /// Tear down background tasks and connection pools for a deterministic
+    /// shutdown
+    ///
+    /// Marks the service not ready first so probes stop routing traffic here,
+    /// cancels and joins the background tasks, flushes buffered events, then
+    /// closes the cache pools, the event bus and the database pools in that
+    /// order. Joining tasks, flushing and closing the database share
+    /// `timeout`; once it is spent the rest is closed without waiting.
+    pub async fn shutdown(&self, timeout: Duration) {
+        tracing::info!("Shutting down application state");
+        let deadline = tokio::time::Instant::now() + timeout;
+        self.readiness.mark_not_ready();
+
+        // Tasks may still publish events or use the pools closed below
+        self.tasks.shutdown(timeout).await;
+
+        if tokio::time::timeout_at(deadline, self.events.flush())
+            .await
+            .is_err()
+        {
+            tracing::warn!("Timed out flushing buffered events");
+        }
+        self.cache.close_all();
+        self.events.close();
+
+        if tokio::time::timeout_at(deadline, self.db.close_all())
+            .await
+            .is_err()
+        {
+            tracing::warn!("Timed out waiting for database connections to close");
+        }
+        tracing::info!("Application state shut down");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use infrastructure::database::DbChannels;
+    use sqlx::postgres::PgPoolOptions;
+    use std::time::Duration;
+
+    #[actix_web::test]
+    async fn test_shutdown_closes_pools() {
+        let mut state = AppState::new();
+        let pool = PgPoolOptions::new()
+            .connect_lazy("postgres://postgres@127.0.0.1:1/unreachable")
+            .unwrap();
+        state.db.add_db_pool("default".to_string(), pool.clone());
+        let cache = deadpool_redis::Config::from_url("redis://127.0.0.1:1")
+            .create_pool(Some(deadpool_redis::Runtime::Tokio1))
+            .unwrap();
+        state.cache.add_cache("default".to_string(), cache.clone());
+        state.readiness.mark_ready();
+        state
+            .tasks
+            .spawn("idle", |token| async move { token.cancelled().await });
+
+        state.shutdown(Duration::from_secs(1)).await;
+
+        let query = tokio::time::timeout(
+            Duration::from_secs(1),
+            sqlx::query("SELECT 1").execute(&pool),
+        )
+        .await
+        .expect("query on a closed pool must not hang");
+        assert!(matches!(query, Err(sqlx::Error::PoolClosed)));
+        assert!(cache.is_closed());
+        assert!(!state.readiness.is_ready());
+        assert_eq!(state.tasks.running(), 0);
+    }
+
+    #[actix_web::test]
+    async fn test_shutdown_flushes_buffered_events_before_closing_the_bus() {
+        let state = AppState::new();
+        state.events.spawn_history_recorder();
+        let bus = state.events.bus();
+        for _ in 0..3 {
+            bus.publish(domain::UserEvent::deleted(shared::UserId::new()));
+        }
+
+        // The recorder has not run yet; the events are still buffered
+        state.shutdown(Duration::from_secs(1)).await;
+
+        let (recorded, _) = state.events.history().subscribe_after(Some(0));
+        assert_eq!(recorded.len(), 3);
+
+        bus.publish(domain::UserEvent::deleted(shared::UserId::new()));
+        tokio::task::yield_now().await;
+        let (recorded, _) = state.events.history().subscribe_after(Some(0));
+        assert_eq!(recorded.len(), 3);
+    }
+
+    #[actix_web::test]
+    async fn test_load_creates_a_pool_per_named_database() {
+        let named = |db: &str| shared::config::DatabaseConfig {
+            connection_string: format!("postgres://postgres@127.0.0.1:1/{}", db),
+            ..Default::default()
+        };
+        let conf = shared::AppConfig {
+            databases: [
+                ("auth_db", named("auth")),
+                ("analytics_db", named("analytics")),
+            ]
+            .into_iter()
+            .map(|(name, db)| (name.to_string(), db))
+            .collect(),
+            ..Default::default()
+        };
+
+        let state = AppState::new().load(&conf).await.unwrap();
+
+        assert!(state.db.get("analytics_db").is_some());
+        assert!(state.db.channel(DbChannels::AuthDb).is_some());
+        assert!(state.db.channel(DbChannels::LogDb).is_none());
+    }
 }
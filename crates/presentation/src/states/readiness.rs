@@ -0,0 +1,85 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Readiness flag shared between startup and the `/health/ready` probe
+#[derive(Clone, Default)]
+pub struct ReadinessState {
+    ready: Arc<AtomicBool>,
+}
+
+impl ReadinessState {
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+
+    pub fn mark_ready(&self) {
+        self.ready.store(true, Ordering::Release);
+    }
+
+    pub fn mark_not_ready(&self) {
+        self.ready.store(false, Ordering::Release);
+    }
+
+    /// Await a startup step (e.g. migrations) and only mark the service ready
+    /// once it has completed successfully. On failure readiness stays false.
+    pub async fn gate_on<F, E>(&self, step: F) -> Result<(), E>
+    where
+        F: Future<Output = Result<(), E>>,
+        E: std::fmt::Display,
+    {
+        self.mark_not_ready();
+        match step.await {
+            Ok(()) => {
+                self.mark_ready();
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Startup step failed, service stays not ready: {}", e);
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[actix_web::test]
+    async fn test_not_ready_until_step_completes() {
+        let readiness = ReadinessState::default();
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+
+        let gate = {
+            let readiness = readiness.clone();
+            actix_web::rt::spawn(async move {
+                readiness
+                    .gate_on(async move {
+                        rx.await.map_err(|e| e.to_string())?;
+                        Ok::<(), String>(())
+                    })
+                    .await
+            })
+        };
+
+        actix_web::rt::task::yield_now().await;
+        assert!(!readiness.is_ready());
+
+        tx.send(()).unwrap();
+        gate.await.unwrap().unwrap();
+        assert!(readiness.is_ready());
+    }
+
+    #[actix_web::test]
+    async fn test_failed_step_keeps_not_ready() {
+        let readiness = ReadinessState::default();
+
+        let result = readiness
+            .gate_on(async { Err::<(), _>("migration failed") })
+            .await;
+
+        assert!(result.is_err());
+        assert!(!readiness.is_ready());
+    }
+}
@@ -0,0 +1,135 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// A spawned task and the name it is logged under
+type Tracked = (&'static str, JoinHandle<()>);
+
+/// Registry of background tasks that must stop with the server
+///
+/// Each task is handed a token that `shutdown` cancels; a task should return
+/// promptly once it is cancelled. Tasks still running when the shutdown
+/// timeout is spent are aborted, so none outlive the server.
+#[derive(Clone, Default)]
+pub struct TaskManager {
+    token: CancellationToken,
+    tasks: Arc<Mutex<Vec<Tracked>>>,
+}
+
+impl TaskManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn a tracked task on the current runtime
+    ///
+    /// `task` receives the token to watch; after `shutdown` it starts out
+    /// cancelled.
+    pub fn spawn<F, Fut>(&self, name: &'static str, task: F)
+    where
+        F: FnOnce(CancellationToken) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let handle = tokio::spawn(task(self.token.child_token()));
+        let mut tasks = self.tasks.lock().unwrap();
+        tasks.retain(|(_, handle)| !handle.is_finished());
+        tasks.push((name, handle));
+    }
+
+    /// Number of tracked tasks that have not finished
+    pub fn running(&self) -> usize {
+        let tasks = self.tasks.lock().unwrap();
+        tasks
+            .iter()
+            .filter(|(_, handle)| !handle.is_finished())
+            .count()
+    }
+
+    /// Cancel every task and wait up to `timeout` for all of them to finish,
+    /// aborting the ones still running after that
+    pub async fn shutdown(&self, timeout: Duration) {
+        let deadline = tokio::time::Instant::now() + timeout;
+        self.token.cancel();
+
+        let tasks = std::mem::take(&mut *self.tasks.lock().unwrap());
+        for (name, mut handle) in tasks {
+            match tokio::time::timeout_at(deadline, &mut handle).await {
+                Ok(Err(e)) if e.is_panic() => {
+                    tracing::error!(task = name, "Background task panicked: {}", e);
+                }
+                Ok(_) => {}
+                Err(_) => {
+                    tracing::warn!(
+                        task = name,
+                        "Background task did not stop in time, aborting"
+                    );
+                    handle.abort();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_and_joins_long_running_tasks() {
+        let tasks = TaskManager::new();
+        let cleaned_up = Arc::new(AtomicBool::new(false));
+        tasks.spawn("ticker", {
+            let cleaned_up = cleaned_up.clone();
+            move |token| async move {
+                let mut ticker = tokio::time::interval(Duration::from_millis(10));
+                loop {
+                    tokio::select! {
+                        _ = token.cancelled() => break,
+                        _ = ticker.tick() => {}
+                    }
+                }
+                cleaned_up.store(true, Ordering::SeqCst);
+            }
+        });
+        assert_eq!(tasks.running(), 1);
+
+        let started = tokio::time::Instant::now();
+        tasks.shutdown(Duration::from_secs(5)).await;
+
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(cleaned_up.load(Ordering::SeqCst));
+        assert_eq!(tasks.running(), 0);
+
+        // Spawned after shutdown: already cancelled
+        tasks.spawn("late", |token| async move { token.cancelled().await });
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while tasks.running() > 0 {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("a task spawned after shutdown stops at once");
+    }
+
+    #[tokio::test]
+    async fn test_tasks_ignoring_cancellation_are_aborted_at_the_timeout() {
+        let tasks = TaskManager::new();
+        let (mut never_sent, receiver) = tokio::sync::oneshot::channel::<()>();
+        tasks.spawn("stubborn", |_token| async move {
+            let _ = receiver.await;
+        });
+
+        let started = tokio::time::Instant::now();
+        tasks.shutdown(Duration::from_millis(50)).await;
+        assert!(started.elapsed() >= Duration::from_millis(50));
+
+        // Aborting the task drops its receiver
+        tokio::time::timeout(Duration::from_secs(1), never_sent.closed())
+            .await
+            .expect("the stubborn task is aborted");
+    }
+}
@@ -0,0 +1,321 @@
+//! In-memory fakes shared by presentation tests
+//!
+//! Other crates' tests reach them through the `test-support` feature.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+#[cfg(test)]
+use std::io::Write;
+#[cfg(test)]
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use domain::{Email, User, UserFilter, UserRepository, UserSort, Username};
+use shared::config::EmailPolicy;
+use shared::{AppError, AppResult, UserId};
+
+/// In-memory `UserRepository` used to exercise handlers without a database
+#[derive(Default)]
+pub struct InMemoryUserRepository {
+    users: Mutex<HashMap<UserId, User>>,
+    deleted: Mutex<HashMap<UserId, (User, DateTime<Utc>)>>,
+    password_hashes: Mutex<HashMap<UserId, String>>,
+    session_epochs: Mutex<HashMap<UserId, i64>>,
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn create(&self, user: &User) -> AppResult<()> {
+        self.users.lock().unwrap().insert(user.id(), user.clone());
+        Ok(())
+    }
+
+    async fn create_many(&self, users: &[User], dry_run: bool) -> AppResult<()> {
+        if dry_run {
+            return Ok(());
+        }
+        let mut stored = self.users.lock().unwrap();
+        for user in users {
+            stored.insert(user.id(), user.clone());
+        }
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: UserId) -> AppResult<Option<User>> {
+        Ok(self.users.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn find_by_ids(&self, ids: &[UserId]) -> AppResult<Vec<User>> {
+        let users = self.users.lock().unwrap();
+        Ok(ids.iter().filter_map(|id| users.get(id).cloned()).collect())
+    }
+
+    async fn find_by_username(&self, username: &Username) -> AppResult<Option<User>> {
+        let users = self.users.lock().unwrap();
+        Ok(users
+            .values()
+            .find(|u| {
+                u.username()
+                    .as_str()
+                    .eq_ignore_ascii_case(username.as_str())
+            })
+            .cloned())
+    }
+
+    async fn find_by_email(&self, email: &Email) -> AppResult<Option<User>> {
+        let users = self.users.lock().unwrap();
+        Ok(users.values().find(|u| u.email() == email).cloned())
+    }
+
+    async fn find_by_canonical_email(
+        &self,
+        email: &Email,
+        policy: &EmailPolicy,
+    ) -> AppResult<Option<User>> {
+        let canonical = email.canonical(policy);
+        let users = self.users.lock().unwrap();
+        Ok(users
+            .values()
+            .find(|u| u.email().canonical(policy) == canonical)
+            .cloned())
+    }
+
+    async fn find_by_email_domain(
+        &self,
+        domain: &str,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<User>> {
+        let users = self.users.lock().unwrap();
+        let mut matching: Vec<User> = users
+            .values()
+            .filter(|u| u.email().domain() == Some(domain))
+            .cloned()
+            .collect();
+        matching.sort_by_key(|u| std::cmp::Reverse((u.created_at(), *u.id().as_uuid())));
+        Ok(matching
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn update(&self, user: &User) -> AppResult<()> {
+        self.users.lock().unwrap().insert(user.id(), user.clone());
+        Ok(())
+    }
+
+    async fn update_claiming_username(&self, user: &User) -> AppResult<()> {
+        let mut users = self.users.lock().unwrap();
+        if users.values().any(|u| {
+            u.id() != user.id()
+                && u.username()
+                    .as_str()
+                    .eq_ignore_ascii_case(user.username().as_str())
+        }) {
+            return Err(AppError::AlreadyExists(format!(
+                "Username '{}' already exists",
+                user.username()
+            )));
+        }
+        let stored = users
+            .get_mut(&user.id())
+            .ok_or_else(|| AppError::NotFound(format!("User with ID {} not found", user.id())))?;
+        *stored = user.clone();
+        Ok(())
+    }
+
+    async fn delete(&self, id: UserId) -> AppResult<()> {
+        self.delete_many(&[id]).await?;
+        Ok(())
+    }
+
+    async fn delete_many(&self, ids: &[UserId]) -> AppResult<Vec<UserId>> {
+        let mut users = self.users.lock().unwrap();
+        let mut deleted = self.deleted.lock().unwrap();
+        Ok(ids
+            .iter()
+            .filter(|id| match users.remove(id) {
+                Some(user) => deleted.insert(**id, (user, Utc::now())).is_none(),
+                None => false,
+            })
+            .copied()
+            .collect())
+    }
+
+    async fn restore(&self, id: UserId) -> AppResult<User> {
+        let mut users = self.users.lock().unwrap();
+        let mut deleted = self.deleted.lock().unwrap();
+        let (user, _) = deleted
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound(format!("No deleted user with ID {}", id)))?;
+        if users
+            .values()
+            .any(|u| u.username() == user.username() || u.email() == user.email())
+        {
+            return Err(AppError::AlreadyExists(
+                "Username or email already exists".to_string(),
+            ));
+        }
+        deleted.remove(&id);
+        users.insert(id, user.clone());
+        Ok(user)
+    }
+
+    async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> AppResult<u64> {
+        let mut deleted = self.deleted.lock().unwrap();
+        let before = deleted.len();
+        deleted.retain(|_, (_, deleted_at)| *deleted_at >= deleted_before);
+        Ok((before - deleted.len()) as u64)
+    }
+
+    async fn find_password_hash(&self, id: UserId) -> AppResult<Option<String>> {
+        Ok(self.password_hashes.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn set_password_hash(&self, id: UserId, password_hash: &str) -> AppResult<()> {
+        if !self.users.lock().unwrap().contains_key(&id) {
+            return Err(AppError::NotFound(format!("User with ID {} not found", id)));
+        }
+        self.password_hashes
+            .lock()
+            .unwrap()
+            .insert(id, password_hash.to_string());
+        Ok(())
+    }
+
+    async fn change_password_hash(&self, id: UserId, password_hash: &str) -> AppResult<i64> {
+        self.set_password_hash(id, password_hash).await?;
+        let mut epochs = self.session_epochs.lock().unwrap();
+        let epoch = epochs.entry(id).or_default();
+        *epoch += 1;
+        Ok(*epoch)
+    }
+
+    async fn find_session_epoch(&self, id: UserId) -> AppResult<Option<i64>> {
+        if !self.users.lock().unwrap().contains_key(&id) {
+            return Ok(None);
+        }
+        Ok(Some(
+            self.session_epochs
+                .lock()
+                .unwrap()
+                .get(&id)
+                .copied()
+                .unwrap_or_default(),
+        ))
+    }
+
+    async fn username_exists(&self, username: &Username) -> AppResult<bool> {
+        Ok(self.find_by_username(username).await?.is_some())
+    }
+
+    async fn email_exists(&self, email: &Email) -> AppResult<bool> {
+        Ok(self.find_by_email(email).await?.is_some())
+    }
+
+    async fn list(
+        &self,
+        filter: &UserFilter,
+        sort: &UserSort,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<User>> {
+        let users = self.users.lock().unwrap();
+        let mut matching: Vec<User> = users
+            .values()
+            .filter(|u| filter.matches(u))
+            .cloned()
+            .collect();
+        matching.sort_by(|a, b| sort.compare(a, b));
+        Ok(matching
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn list_after(
+        &self,
+        filter: &UserFilter,
+        after: (DateTime<Utc>, UserId),
+        limit: i64,
+    ) -> AppResult<Vec<User>> {
+        // Cursors carry microseconds, as Postgres stores them
+        let position = (after.0.timestamp_micros(), *after.1.as_uuid());
+        let users = self.users.lock().unwrap();
+        let mut matching: Vec<User> = users
+            .values()
+            .filter(|u| filter.matches(u))
+            .filter(|u| (u.created_at().timestamp_micros(), *u.id().as_uuid()) < position)
+            .cloned()
+            .collect();
+        matching.sort_by_key(|u| std::cmp::Reverse((u.created_at(), *u.id().as_uuid())));
+        matching.truncate(limit as usize);
+        Ok(matching)
+    }
+
+    async fn list_created_since(
+        &self,
+        filter: &UserFilter,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<User>> {
+        let users = self.users.lock().unwrap();
+        let mut matching: Vec<User> = users
+            .values()
+            .filter(|u| filter.matches(u) && u.created_at() > since)
+            .cloned()
+            .collect();
+        matching.sort_by_key(|u| std::cmp::Reverse((u.created_at(), *u.id().as_uuid())));
+        matching.truncate(limit as usize);
+        Ok(matching)
+    }
+
+    async fn count(&self, filter: &UserFilter) -> AppResult<i64> {
+        let users = self.users.lock().unwrap();
+        Ok(users.values().filter(|u| filter.matches(u)).count() as i64)
+    }
+}
+
+/// Log sink for asserting on emitted tracing events
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+#[cfg(test)]
+impl CapturedLogs {
+    /// Route this thread's events here as JSON lines until the guard drops
+    pub fn capture(&self) -> tracing::subscriber::DefaultGuard {
+        let writer = self.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .with_current_span(true)
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::set_default(subscriber)
+    }
+
+    /// Every event captured so far
+    pub fn events(&self) -> Vec<serde_json::Value> {
+        let logs = self.0.lock().unwrap();
+        String::from_utf8_lossy(&logs)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+impl Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -1,39 +1,118 @@
 use super::{
+    AppEnv,
     CacheConfig,
-    // JwtConfig, OAuthConfig, EmailConfig,
-    // SecurityConfig, LoggingConfig, FeatureFlags, EventPublisherConfig
+    // EventPublisherConfig
     DatabaseConfig,
+    EmailConfig,
+    FeatureFlags,
+    GrpcConfig,
+    JwtConfig,
+    LoggingConfig,
+    OAuthConfig,
+    ResponseConfig,
+    RetentionConfig,
+    SecurityConfig,
     ServerConfig,
+    ValidationConfig,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct AppConfig {
     pub server: ServerConfig,
+    pub grpc: GrpcConfig,
     pub database: DatabaseConfig,
+    /// Additional pools keyed by name, alongside the default `database`
+    #[serde(default)]
+    pub databases: HashMap<String, DatabaseConfig>,
     pub cache: CacheConfig,
     // pub event_publisher: EventPublisherConfig,
-    // pub jwt: JwtConfig,
-    // pub oauth: OAuthConfig,
-    // pub email: EmailConfig,
-    // pub security: SecurityConfig,
-    // pub logging: LoggingConfig,
-    // pub features: FeatureFlags,
+    pub jwt: JwtConfig,
+    pub oauth: OAuthConfig,
+    pub email: EmailConfig,
+    pub security: SecurityConfig,
+    pub response: ResponseConfig,
+    pub validation: ValidationConfig,
+    pub retention: RetentionConfig,
+    pub logging: LoggingConfig,
+    pub features: FeatureFlags,
 }
 
 impl AppConfig {
-    pub fn load(env: &str) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn load(env: AppEnv) -> Result<Self, Box<dyn std::error::Error>> {
+        let defaults = environment_defaults(&env);
+        let env = env.as_str();
         Ok(AppConfig {
             server: ServerConfig::load(env)?,
-            database: DatabaseConfig::load(env)?,
+            grpc: GrpcConfig::load(env)?,
+            database: DatabaseConfig::load(env, defaults.database)?,
+            databases: DatabaseConfig::load_named(env)?,
             cache: CacheConfig::load(env)?,
             // event_publisher: EventPublisherConfig::load(&env)?,
-            // jwt: JwtConfig::load(&env)?,
-            // oauth: OAuthConfig::default(),
-            // email: EmailConfig::load(&env)?,
-            // security: SecurityConfig::load(&env)?,
-            // logging: LoggingConfig::load(&env)?,
-            // features: FeatureFlags::load(&env)?,
+            jwt: JwtConfig::load(env)?,
+            oauth: OAuthConfig::load(env)?,
+            email: EmailConfig::load(env)?,
+            security: SecurityConfig::load(env)?,
+            response: ResponseConfig::load(env)?,
+            validation: ValidationConfig::load(env)?,
+            retention: RetentionConfig::load(env)?,
+            logging: LoggingConfig::load(env)?,
+            features: FeatureFlags::load(env)?,
         })
     }
+
+    /// The effective configuration as JSON with every secret masked
+    pub fn redacted(&self) -> serde_json::Value {
+        super::redact::redact(serde_json::to_value(self).unwrap_or_default())
+    }
+}
+
+/// Built-in defaults for `env`, layered beneath `config/{env}.toml` and
+/// `APP__` variables
+///
+/// Starts from the flat `defaults` consts and adjusts the few settings that
+/// should differ without any config file: test runs get a small pool that
+/// fails fast, and staging and prod leave migrations to the deploy job.
+pub fn environment_defaults(env: &AppEnv) -> AppConfig {
+    let mut config = AppConfig::default();
+    match env {
+        AppEnv::Dev => {}
+        AppEnv::Test => {
+            config.database.min_connections = 1;
+            config.database.max_connections = 5;
+            config.database.connection_timeout_seconds = 5;
+        }
+        AppEnv::Staging | AppEnv::Prod => {
+            config.database.run_migrations = false;
+        }
+    }
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_test_env_defaults_to_a_smaller_pool_than_prod() {
+        let test = environment_defaults(&AppEnv::Test).database;
+        let prod = environment_defaults(&AppEnv::Prod).database;
+
+        assert!(test.max_connections < prod.max_connections);
+        assert!(test.min_connections < prod.min_connections);
+        assert!(test.run_migrations);
+        assert!(!prod.run_migrations);
+        assert!(environment_defaults(&AppEnv::Dev).database.run_migrations);
+    }
+
+    #[test]
+    fn test_environment_defaults_apply_without_a_config_file() {
+        // There is no config/test.toml, so only the defaults are in play
+        let defaults = environment_defaults(&AppEnv::Test).database;
+        let loaded = DatabaseConfig::load("test", defaults.clone()).unwrap();
+
+        assert_eq!(loaded.max_connections, defaults.max_connections);
+        assert_eq!(loaded.min_connections, defaults.min_connections);
+    }
 }
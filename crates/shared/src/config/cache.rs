@@ -1,9 +1,11 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use crate::defaults::cache;
+use crate::{AppError, AppResult};
 
 /// Cache (Redis) configuration
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CacheConfig {
     pub url: String,
     pub pool_size: usize,
@@ -12,6 +14,12 @@ pub struct CacheConfig {
     pub connection_timeout_seconds: u64,
     pub idle_timeout_seconds: u64,
     pub max_lifetime_seconds: u64,
+    /// TTL for cached query results (e.g. user listings)
+    pub query_ttl_seconds: u64,
+    /// Per-endpoint TTLs overriding `query_ttl_seconds`
+    pub policy: CachePolicy,
+    /// Open and ping `pool_size` connections at startup instead of lazily
+    pub warmup: bool,
 }
 
 impl Default for CacheConfig {
@@ -24,15 +32,45 @@ impl Default for CacheConfig {
             connection_timeout_seconds: cache::DEFAULT_CACHE_CONNECTION_TIMEOUT_SECONDS,
             idle_timeout_seconds: cache::DEFAULT_CACHE_IDLE_TIMEOUT_SECONDS,
             max_lifetime_seconds: cache::DEFAULT_CACHE_MAX_LIFETIME_SECONDS,
+            query_ttl_seconds: cache::DEFAULT_CACHE_QUERY_TTL_SECONDS,
+            policy: CachePolicy::default(),
+            warmup: cache::DEFAULT_CACHE_WARMUP,
         }
     }
 }
 
+/// Query-cache TTL in seconds for each read endpoint, keyed by endpoint name
+/// (`list_users`, `recent_users`, ...)
+///
+/// Lets ops bound how stale each endpoint may be without code changes.
+/// Entries from config are merged over the built-in ones.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct CachePolicy(pub HashMap<String, u64>);
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self(
+            cache::DEFAULT_CACHE_POLICY
+                .iter()
+                .map(|(endpoint, ttl_seconds)| (endpoint.to_string(), *ttl_seconds))
+                .collect(),
+        )
+    }
+}
+
+impl CachePolicy {
+    /// TTL configured for `endpoint`, if any
+    pub fn ttl_seconds(&self, endpoint: &str) -> Option<u64> {
+        self.0.get(endpoint).copied()
+    }
+}
+
 impl CacheConfig {
     /// Load configuration from environment variables and config files
     pub fn load(env: &str) -> Result<Self, config::ConfigError> {
         let default: CacheConfig = Self::default();
-        let builder = config::Config::builder()
+        let mut builder = config::Config::builder()
             .set_default("cache.url", default.url)?
             .set_default("cache.pool_size", default.pool_size as i64)?
             .set_default("cache.min_connections", default.min_connections)?
@@ -42,17 +80,94 @@ impl CacheConfig {
                 default.connection_timeout_seconds,
             )?
             .set_default("cache.idle_timeout_seconds", default.idle_timeout_seconds)?
-            .set_default("cache.max_lifetime_seconds", default.max_lifetime_seconds)?;
+            .set_default("cache.max_lifetime_seconds", default.max_lifetime_seconds)?
+            .set_default("cache.query_ttl_seconds", default.query_ttl_seconds)?
+            .set_default("cache.warmup", default.warmup)?;
+        for (endpoint, ttl_seconds) in &default.policy.0 {
+            builder = builder.set_default(format!("cache.policy.{}", endpoint), *ttl_seconds)?;
+        }
 
         let config = builder
             .add_source(config::File::with_name(&format!("config/{}", env)).required(false))
             .add_source(
                 config::Environment::with_prefix("APP")
                     .prefix_separator("__")
-                    .separator("__")
+                    .separator("__"),
             )
             .build()?;
 
-        config.get::<CacheConfig>("cache")
+        let cache: CacheConfig = config.get("cache")?;
+        cache
+            .validate()
+            .map_err(|e| config::ConfigError::Message(e.to_string()))?;
+        Ok(cache)
+    }
+
+    /// Check that `url` is a `redis://` or `rediss://` URL naming a host
+    ///
+    /// The URL itself is left out of the error since it may carry a password.
+    pub fn validate(&self) -> AppResult<()> {
+        let url = url::Url::parse(&self.url)
+            .map_err(|e| AppError::ConfigurationError(format!("Invalid cache.url: {}", e)))?;
+        if !matches!(url.scheme(), "redis" | "rediss") {
+            return Err(AppError::ConfigurationError(format!(
+                "cache.url must use the redis:// or rediss:// scheme, not {}://",
+                url.scheme()
+            )));
+        }
+        if url.host_str().is_none_or(str::is_empty) {
+            return Err(AppError::ConfigurationError(
+                "cache.url must name a host".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether connections are made over TLS (`rediss://`)
+    pub fn uses_tls(&self) -> bool {
+        self.url.starts_with("rediss://")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_url(url: &str) -> CacheConfig {
+        CacheConfig {
+            url: url.to_string(),
+            ..CacheConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_redis_and_rediss_urls_are_accepted() {
+        let plain = with_url("redis://localhost:6379/0");
+        assert!(plain.validate().is_ok());
+        assert!(!plain.uses_tls());
+
+        let tls = with_url("rediss://:secret@cache.example.com:6380");
+        assert!(tls.validate().is_ok());
+        assert!(tls.uses_tls());
+    }
+
+    #[test]
+    fn test_non_redis_urls_are_rejected() {
+        for url in [
+            "postgres://postgres@localhost:5432/app",
+            "http://localhost:6379",
+            "redis://",
+            "localhost:6379",
+            "",
+        ] {
+            assert!(
+                matches!(
+                    with_url(url).validate(),
+                    Err(AppError::ConfigurationError(_))
+                ),
+                "{}",
+                url
+            );
+        }
     }
 }
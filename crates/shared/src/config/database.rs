@@ -1,12 +1,18 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use crate::defaults::database;
 
 /// Database (PostgreSQL) configuration
-#[derive(Debug, Clone, Deserialize)]
+///
+/// Fields missing from a named `[databases.<name>]` entry take the defaults.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct DatabaseConfig {
     pub database_system: String,
     pub connection_string: String,
+    /// Read replica used for lookups; unset sends every query to the primary
+    pub replica_connection_string: Option<String>,
     pub min_connections: u32,
     pub max_connections: u32,
     pub connection_timeout_seconds: u64,
@@ -14,6 +20,22 @@ pub struct DatabaseConfig {
     pub max_lifetime_seconds: u64,
     pub enable_logging: bool,
     pub run_migrations: bool,
+    /// Extra attempts made when acquiring a pooled connection times out
+    pub acquire_retries: u32,
+    /// Delay before the first acquire retry, doubled on each further attempt
+    pub acquire_retry_backoff_ms: u64,
+    /// Open `min_connections` connections at startup instead of lazily
+    pub warmup: bool,
+    /// Upper bound on each repository query; 0 disables it
+    pub query_timeout_ms: u64,
+    /// Upper bound on getting a connection for a lookup, retries included;
+    /// 0 leaves it to the pool's acquire timeout
+    pub read_acquire_timeout_ms: u64,
+    /// Upper bound on getting a connection for a write, retries included;
+    /// 0 leaves it to the pool's acquire timeout
+    pub write_acquire_timeout_ms: u64,
+    /// Create the database at startup when it does not exist (dev only)
+    pub create_database_if_missing: bool,
 }
 
 impl Default for DatabaseConfig {
@@ -21,6 +43,7 @@ impl Default for DatabaseConfig {
         Self {
             database_system: database::DEFAULT_DATABASE_SYSTEM.to_string(),
             connection_string: database::DEFAULT_DATABASE_CONNECTION_STRING.to_string(),
+            replica_connection_string: None,
             min_connections: database::DEFAULT_DATABASE_MIN_CONNECTIONS,
             max_connections: database::DEFAULT_DATABASE_MAX_CONNECTIONS,
             connection_timeout_seconds: database::DEFAULT_DATABASE_CONNECTION_TIMEOUT_SECONDS,
@@ -28,13 +51,22 @@ impl Default for DatabaseConfig {
             max_lifetime_seconds: database::DEFAULT_DATABASE_MAX_LIFETIME_SECONDS,
             enable_logging: database::DEFAULT_DATABASE_ENABLE_LOGGING,
             run_migrations: database::DEFAULT_DATABASE_RUN_MIGRATIONS,
+            acquire_retries: database::DEFAULT_DATABASE_ACQUIRE_RETRIES,
+            acquire_retry_backoff_ms: database::DEFAULT_DATABASE_ACQUIRE_RETRY_BACKOFF_MS,
+            warmup: database::DEFAULT_DATABASE_WARMUP,
+            query_timeout_ms: database::DEFAULT_DATABASE_QUERY_TIMEOUT_MS,
+            read_acquire_timeout_ms: database::DEFAULT_DATABASE_READ_ACQUIRE_TIMEOUT_MS,
+            write_acquire_timeout_ms: database::DEFAULT_DATABASE_WRITE_ACQUIRE_TIMEOUT_MS,
+            create_database_if_missing: database::DEFAULT_DATABASE_CREATE_DATABASE_IF_MISSING,
         }
     }
 }
 
 impl DatabaseConfig {
-    pub fn load(env: &str) -> Result<Self, config::ConfigError> {
-        let default: DatabaseConfig = Self::default();
+    /// Load the default database, with `default` beneath the file and env
+    ///
+    /// `default` normally comes from `environment_defaults`.
+    pub fn load(env: &str, default: Self) -> Result<Self, config::ConfigError> {
         let builder = config::Config::builder()
             .set_default("database.database_system", default.database_system.clone())?
             .set_default(
@@ -56,17 +88,56 @@ impl DatabaseConfig {
                 default.max_lifetime_seconds,
             )?
             .set_default("database.enable_logging", default.enable_logging)?
-            .set_default("database.run_migrations", default.run_migrations)?;
+            .set_default("database.run_migrations", default.run_migrations)?
+            .set_default("database.acquire_retries", default.acquire_retries)?
+            .set_default(
+                "database.acquire_retry_backoff_ms",
+                default.acquire_retry_backoff_ms,
+            )?
+            .set_default("database.warmup", default.warmup)?
+            .set_default("database.query_timeout_ms", default.query_timeout_ms)?
+            .set_default(
+                "database.read_acquire_timeout_ms",
+                default.read_acquire_timeout_ms,
+            )?
+            .set_default(
+                "database.write_acquire_timeout_ms",
+                default.write_acquire_timeout_ms,
+            )?
+            .set_default(
+                "database.create_database_if_missing",
+                default.create_database_if_missing,
+            )?;
 
         let config = builder
             .add_source(config::File::with_name(&format!("config/{}", env)).required(false))
             .add_source(
                 config::Environment::with_prefix("APP")
                     .prefix_separator("__")
-                    .separator("__")
+                    .separator("__"),
             )
             .build()?;
 
         config.get::<DatabaseConfig>("database")
     }
+
+    /// Load the additional named databases from `[databases.<name>]` tables
+    ///
+    /// Names follow `DbChannels` (e.g. `auth_db`, `analytics_db`); an absent
+    /// section yields no named databases.
+    pub fn load_named(env: &str) -> Result<HashMap<String, Self>, config::ConfigError> {
+        let config = config::Config::builder()
+            .add_source(config::File::with_name(&format!("config/{}", env)).required(false))
+            .add_source(
+                config::Environment::with_prefix("APP")
+                    .prefix_separator("__")
+                    .separator("__"),
+            )
+            .build()?;
+
+        match config.get::<HashMap<String, DatabaseConfig>>("databases") {
+            Err(config::ConfigError::NotFound(_)) => Ok(HashMap::new()),
+            result => result,
+        }
+    }
 }
@@ -1 +1,62 @@
+use serde::{Deserialize, Serialize};
 
+use crate::defaults::email::*;
+
+/// Outgoing email (SMTP) configuration
+///
+/// When `critical` is false an unreachable SMTP server only degrades
+/// readiness instead of failing it.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EmailConfig {
+    pub enabled: bool,
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    pub use_tls: bool,
+    pub from_address: String,
+    pub timeout_seconds: u64,
+    pub critical: bool,
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            enabled: DEFAULT_EMAIL_ENABLED,
+            smtp_host: DEFAULT_SMTP_HOST.to_string(),
+            smtp_port: DEFAULT_SMTP_PORT,
+            smtp_username: None,
+            smtp_password: None,
+            use_tls: DEFAULT_SMTP_USE_TLS,
+            from_address: DEFAULT_FROM_ADDRESS.to_string(),
+            timeout_seconds: DEFAULT_SMTP_TIMEOUT_SECONDS,
+            critical: DEFAULT_EMAIL_CRITICAL,
+        }
+    }
+}
+
+impl EmailConfig {
+    /// Load configuration from environment variables and config files
+    pub fn load(env: &str) -> Result<Self, config::ConfigError> {
+        let default: EmailConfig = Self::default();
+        let builder = config::Config::builder()
+            .set_default("email.enabled", default.enabled)?
+            .set_default("email.smtp_host", default.smtp_host.clone())?
+            .set_default("email.smtp_port", default.smtp_port)?
+            .set_default("email.use_tls", default.use_tls)?
+            .set_default("email.from_address", default.from_address.clone())?
+            .set_default("email.timeout_seconds", default.timeout_seconds)?
+            .set_default("email.critical", default.critical)?;
+
+        let config = builder
+            .add_source(config::File::with_name(&format!("config/{}", env)).required(false))
+            .add_source(
+                config::Environment::with_prefix("APP")
+                    .prefix_separator("__")
+                    .separator("__"),
+            )
+            .build()?;
+
+        config.get::<EmailConfig>("email")
+    }
+}
@@ -0,0 +1,93 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Deployment environment selected by `APP_ENV`
+///
+/// Picks the `config/{env}.toml` file and the development-only toggles.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AppEnv {
+    #[default]
+    Dev,
+    Test,
+    Staging,
+    Prod,
+}
+
+impl AppEnv {
+    /// Read `APP_ENV`, falling back to `Dev` when unset or unknown
+    ///
+    /// Runs before logging is configured, so an unknown value is reported on
+    /// stderr.
+    pub fn from_env() -> Self {
+        match std::env::var("APP_ENV") {
+            Ok(raw) => raw.parse().unwrap_or_else(|e| {
+                eprintln!("Warning: {}; falling back to {}", e, AppEnv::Dev);
+                AppEnv::Dev
+            }),
+            Err(_) => AppEnv::Dev,
+        }
+    }
+
+    /// Name of the environment, matching its config file
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AppEnv::Dev => "dev",
+            AppEnv::Test => "test",
+            AppEnv::Staging => "staging",
+            AppEnv::Prod => "prod",
+        }
+    }
+
+    pub fn is_dev(self) -> bool {
+        self == AppEnv::Dev
+    }
+}
+
+impl FromStr for AppEnv {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "dev" | "development" => Ok(AppEnv::Dev),
+            "test" => Ok(AppEnv::Test),
+            "staging" => Ok(AppEnv::Staging),
+            "prod" | "production" => Ok(AppEnv::Prod),
+            _ => Err(format!("Unknown APP_ENV '{}'", value)),
+        }
+    }
+}
+
+impl fmt::Display for AppEnv {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_each_variant() {
+        for (raw, env) in [
+            ("dev", AppEnv::Dev),
+            ("development", AppEnv::Dev),
+            ("test", AppEnv::Test),
+            ("staging", AppEnv::Staging),
+            ("Prod", AppEnv::Prod),
+            (" production ", AppEnv::Prod),
+        ] {
+            assert_eq!(raw.parse::<AppEnv>(), Ok(env));
+        }
+        for env in [AppEnv::Dev, AppEnv::Test, AppEnv::Staging, AppEnv::Prod] {
+            assert_eq!(env.as_str().parse::<AppEnv>(), Ok(env));
+        }
+    }
+
+    #[test]
+    fn test_unknown_value_is_rejected() {
+        let err = "qa".parse::<AppEnv>().unwrap_err();
+        assert!(err.contains("qa"));
+        assert_eq!(AppEnv::default(), AppEnv::Dev);
+    }
+}
@@ -1 +1,39 @@
+use serde::{Deserialize, Serialize};
 
+use crate::defaults::features::*;
+
+/// Feature flags toggling behaviour per deployment
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FeatureFlags {
+    /// When false users are never removed: single and batch deletes are
+    /// forbidden, and so is purging users soft-deleted earlier
+    pub allow_user_deletion: bool,
+}
+
+impl Default for FeatureFlags {
+    fn default() -> Self {
+        Self {
+            allow_user_deletion: DEFAULT_ALLOW_USER_DELETION,
+        }
+    }
+}
+
+impl FeatureFlags {
+    /// Load configuration from environment variables and config files
+    pub fn load(env: &str) -> Result<Self, config::ConfigError> {
+        let default: FeatureFlags = Self::default();
+        let builder = config::Config::builder()
+            .set_default("features.allow_user_deletion", default.allow_user_deletion)?;
+
+        let config = builder
+            .add_source(config::File::with_name(&format!("config/{}", env)).required(false))
+            .add_source(
+                config::Environment::with_prefix("APP")
+                    .prefix_separator("__")
+                    .separator("__"),
+            )
+            .build()?;
+
+        config.get::<FeatureFlags>("features")
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+use crate::defaults::grpc::*;
+
+/// gRPC server configuration
+///
+/// `enabled` controls whether the API service also serves gRPC alongside
+/// HTTP; the standalone `grpc` service always serves it.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GrpcConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: DEFAULT_GRPC_ENABLED,
+            host: DEFAULT_GRPC_HOST.to_string(),
+            port: DEFAULT_GRPC_PORT,
+        }
+    }
+}
+
+impl GrpcConfig {
+    pub fn load(env: &str) -> Result<Self, config::ConfigError> {
+        let default: GrpcConfig = Self::default();
+        let builder = config::Config::builder()
+            .set_default("grpc.enabled", default.enabled)?
+            .set_default("grpc.host", default.host.clone())?
+            .set_default("grpc.port", default.port)?;
+
+        let config = builder
+            .add_source(config::File::with_name(&format!("config/{}", env)).required(false))
+            .add_source(
+                config::Environment::with_prefix("APP")
+                    .prefix_separator("__")
+                    .separator("__"),
+            )
+            .build()?;
+
+        config.get::<GrpcConfig>("grpc")
+    }
+}
@@ -1 +1,179 @@
+use serde::{Deserialize, Serialize};
 
+use super::AppEnv;
+use crate::defaults::jwt::*;
+use crate::{AppError, AppResult};
+
+/// Token signing algorithm
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum JwtAlgorithm {
+    /// HMAC with the shared `secret`
+    #[default]
+    HS256,
+    /// RSA, signed with `private_key_path` and published in the JWKS
+    RS256,
+    /// ECDSA over P-256, signed with `private_key_path` and published in the JWKS
+    ES256,
+}
+
+impl JwtAlgorithm {
+    /// Whether tokens are verified with a public key others can fetch
+    pub fn is_asymmetric(self) -> bool {
+        !matches!(self, Self::HS256)
+    }
+}
+
+/// A retired signing key still accepted for verification
+///
+/// HS256 keys carry the `secret`, RS256/ES256 keys the `public_key_path`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JwtVerificationKey {
+    pub kid: String,
+    #[serde(default)]
+    pub secret: String,
+    #[serde(default)]
+    pub public_key_path: Option<String>,
+}
+
+/// JWT signing and lifetime configuration
+///
+/// To rotate, move the current `key_id` and its `secret` (or `public_key_path`)
+/// into `previous_keys` and configure a new key; tokens signed with the old
+/// key stay valid until they expire.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JwtConfig {
+    pub algorithm: JwtAlgorithm,
+    /// HMAC key used to sign and verify tokens (HS256)
+    pub secret: String,
+    /// PEM private key used to sign tokens (RS256/ES256)
+    #[serde(default)]
+    pub private_key_path: Option<String>,
+    /// PEM public key matching `private_key_path`, served at
+    /// `/.well-known/jwks.json`
+    #[serde(default)]
+    pub public_key_path: Option<String>,
+    /// `kid` header of tokens signed with the current key
+    pub key_id: String,
+    /// Keys only used to verify tokens signed before a rotation
+    #[serde(default)]
+    pub previous_keys: Vec<JwtVerificationKey>,
+    pub issuer: String,
+    pub access_token_ttl_seconds: u64,
+    /// Lifetime of tokens issued to admins impersonating a user
+    pub impersonation_ttl_seconds: u64,
+    /// Clock skew tolerated when checking `exp`, `nbf` and `iat`
+    pub leeway_seconds: u64,
+}
+
+impl Default for JwtConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: JwtAlgorithm::default(),
+            secret: DEFAULT_JWT_SECRET.to_string(),
+            private_key_path: None,
+            public_key_path: None,
+            key_id: DEFAULT_JWT_KEY_ID.to_string(),
+            previous_keys: Vec::new(),
+            issuer: DEFAULT_JWT_ISSUER.to_string(),
+            access_token_ttl_seconds: DEFAULT_ACCESS_TOKEN_TTL_SECONDS,
+            impersonation_ttl_seconds: DEFAULT_IMPERSONATION_TTL_SECONDS,
+            leeway_seconds: DEFAULT_LEEWAY_SECONDS,
+        }
+    }
+}
+
+impl JwtConfig {
+    /// Load configuration from environment variables and config files
+    pub fn load(env: &str) -> Result<Self, config::ConfigError> {
+        let default: JwtConfig = Self::default();
+        let builder = config::Config::builder()
+            .set_default("jwt.algorithm", DEFAULT_JWT_ALGORITHM)?
+            .set_default("jwt.secret", default.secret.clone())?
+            .set_default("jwt.key_id", default.key_id.clone())?
+            .set_default("jwt.issuer", default.issuer.clone())?
+            .set_default(
+                "jwt.access_token_ttl_seconds",
+                default.access_token_ttl_seconds,
+            )?
+            .set_default(
+                "jwt.impersonation_ttl_seconds",
+                default.impersonation_ttl_seconds,
+            )?
+            .set_default("jwt.leeway_seconds", default.leeway_seconds)?;
+
+        let config = builder
+            .add_source(config::File::with_name(&format!("config/{}", env)).required(false))
+            .add_source(
+                config::Environment::with_prefix("APP")
+                    .prefix_separator("__")
+                    .separator("__"),
+            )
+            .build()?;
+
+        let jwt: JwtConfig = config.get("jwt")?;
+        jwt.validate(env)
+            .map_err(|e| config::ConfigError::Message(e.to_string()))?;
+        Ok(jwt)
+    }
+
+    /// Check that environments other than dev do not sign or verify with the
+    /// built-in development secret
+    ///
+    /// Empty secrets are rejected when the token service is built.
+    pub fn validate(&self, env: &str) -> AppResult<()> {
+        if self.algorithm.is_asymmetric() || env == AppEnv::Dev.as_str() {
+            return Ok(());
+        }
+        let uses_default = std::iter::once(&self.secret)
+            .chain(self.previous_keys.iter().map(|key| &key.secret))
+            .any(|secret| secret == DEFAULT_JWT_SECRET);
+        if uses_default {
+            return Err(AppError::ConfigurationError(format!(
+                "jwt.secret must not be the built-in development secret in {}; set APP__JWT__SECRET",
+                env
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_secret(secret: &str) -> JwtConfig {
+        JwtConfig {
+            secret: secret.to_string(),
+            ..JwtConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_default_secret_is_only_accepted_in_dev() {
+        assert!(JwtConfig::default().validate("dev").is_ok());
+        for env in ["test", "staging", "prod"] {
+            assert!(
+                matches!(
+                    JwtConfig::default().validate(env),
+                    Err(AppError::ConfigurationError(_))
+                ),
+                "{}",
+                env
+            );
+        }
+        assert!(with_secret("a-real-secret").validate("prod").is_ok());
+
+        let retired_default = JwtConfig {
+            previous_keys: vec![JwtVerificationKey {
+                kid: "2025-01".to_string(),
+                secret: DEFAULT_JWT_SECRET.to_string(),
+                public_key_path: None,
+            }],
+            ..with_secret("a-real-secret")
+        };
+        assert!(matches!(
+            retired_default.validate("prod"),
+            Err(AppError::ConfigurationError(_))
+        ));
+    }
+}
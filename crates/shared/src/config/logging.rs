@@ -1 +1,41 @@
+use serde::{Deserialize, Serialize};
 
+use crate::defaults::logging::*;
+
+/// Logging configuration
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LoggingConfig {
+    /// Fraction (0.0-1.0) of successful requests written to the access log;
+    /// 4xx and 5xx responses are always logged
+    pub access_log_sampling_rate: f64,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            access_log_sampling_rate: DEFAULT_ACCESS_LOG_SAMPLING_RATE,
+        }
+    }
+}
+
+impl LoggingConfig {
+    /// Load configuration from environment variables and config files
+    pub fn load(env: &str) -> Result<Self, config::ConfigError> {
+        let default: LoggingConfig = Self::default();
+        let builder = config::Config::builder().set_default(
+            "logging.access_log_sampling_rate",
+            default.access_log_sampling_rate,
+        )?;
+
+        let config = builder
+            .add_source(config::File::with_name(&format!("config/{}", env)).required(false))
+            .add_source(
+                config::Environment::with_prefix("APP")
+                    .prefix_separator("__")
+                    .separator("__"),
+            )
+            .build()?;
+
+        config.get::<LoggingConfig>("logging")
+    }
+}
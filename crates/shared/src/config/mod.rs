@@ -2,24 +2,38 @@ pub mod app;
 pub mod cache;
 pub mod database;
 pub mod email;
+pub mod env;
 pub mod event_publisher;
 pub mod features;
+pub mod grpc;
 pub mod jwt;
 pub mod logging;
 pub mod oauth;
+pub mod redact;
+pub mod response;
+pub mod retention;
 pub mod security;
 pub mod server;
+pub mod validation;
 
-pub use app::AppConfig;
-pub use cache::CacheConfig;
+pub use app::{AppConfig, environment_defaults};
+pub use cache::{CacheConfig, CachePolicy};
 pub use database::DatabaseConfig;
+pub use email::EmailConfig;
+pub use env::AppEnv;
+pub use features::FeatureFlags;
+pub use grpc::GrpcConfig;
+pub use jwt::{JwtAlgorithm, JwtConfig, JwtVerificationKey};
+pub use logging::LoggingConfig;
+pub use oauth::{OAuthConfig, OAuthProviderConfig};
+pub use response::{ErrorDetail, FieldCase, ResponseConfig, TimestampFormat};
+pub use retention::RetentionConfig;
+pub use security::{
+    CorsConfig, CorsOverride, CorsPolicy, PasswordHashing, PasswordPolicy, SecurityConfig,
+};
 pub use server::ServerConfig;
+pub use validation::{EmailDomainRule, EmailPolicy, ValidationConfig};
 // pub use event_publisher::EventPublisherConfig;
-// pub use jwt::JwtConfig;
-// pub use oauth::{OAuthConfig, OAuthProviderConfig};
-// pub use email::EmailConfig;
 // pub use security::{
-//     SecurityConfig, PasswordPolicy, RateLimitingConfig, RateLockout, SessionConfig, MfaConfig, CorsConfig,
+//     RateLimitingConfig, RateLockout, SessionConfig, MfaConfig,
 // };
-// pub use logging::LoggingConfig;
-// pub use features::FeatureFlags;
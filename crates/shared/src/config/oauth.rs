@@ -1 +1,40 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
+/// A single OAuth identity provider
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct OAuthProviderConfig {
+    pub client_id: String,
+    /// Callback `redirect_uri`s accepted verbatim
+    pub redirect_uris: Vec<String>,
+    /// Accept any `redirect_uri` with the same scheme, host and port as one
+    /// of these whose path lies at or below the prefix's path
+    pub redirect_uri_prefixes: Vec<String>,
+}
+
+/// OAuth providers keyed by name, from `[oauth.providers.<name>]` tables
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct OAuthConfig {
+    pub providers: HashMap<String, OAuthProviderConfig>,
+}
+
+impl OAuthConfig {
+    /// Load the configured providers; an absent `[oauth]` section yields none
+    pub fn load(env: &str) -> Result<Self, config::ConfigError> {
+        let config = config::Config::builder()
+            .add_source(config::File::with_name(&format!("config/{}", env)).required(false))
+            .add_source(
+                config::Environment::with_prefix("APP")
+                    .prefix_separator("__")
+                    .separator("__"),
+            )
+            .build()?;
+
+        match config.get::<OAuthConfig>("oauth") {
+            Err(config::ConfigError::NotFound(_)) => Ok(Self::default()),
+            result => result,
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+use crate::defaults::response::*;
+
+/// Wire format of timestamp fields in API responses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampFormat {
+    /// `2024-01-01T00:00:00Z`
+    #[default]
+    Rfc3339,
+    /// Milliseconds since the Unix epoch
+    UnixMillis,
+}
+
+/// Naming convention for JSON object keys in API responses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldCase {
+    /// `full_name`, as the DTOs are declared
+    #[default]
+    SnakeCase,
+    /// `fullName`
+    CamelCase,
+}
+
+/// How much of an internal error (database, configuration, ...) reaches clients
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorDetail {
+    /// The full error message; for development only
+    Full,
+    /// A generic message; the full error is only logged under the error id
+    #[default]
+    Generic,
+}
+
+/// Response serialization configuration
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct ResponseConfig {
+    pub timestamp_format: TimestampFormat,
+    /// Indent JSON bodies for human reading; keep off outside development
+    pub pretty_json: bool,
+    /// Key naming convention of response bodies
+    pub field_case: FieldCase,
+    /// `Cache-Control: max-age` sent on cacheable read endpoints
+    pub read_cache_max_age_seconds: u64,
+    /// Detail of internal error messages in error responses
+    pub error_detail: ErrorDetail,
+    /// Wrap every success body as `{ "data": ..., "meta": ... }`; clients can
+    /// also opt in per request with `Accept: application/json; envelope=true`
+    pub envelope: bool,
+}
+
+impl ResponseConfig {
+    pub fn load(env: &str) -> Result<Self, config::ConfigError> {
+        let builder = config::Config::builder()
+            .set_default("response.timestamp_format", DEFAULT_TIMESTAMP_FORMAT)?
+            .set_default("response.pretty_json", DEFAULT_PRETTY_JSON)?
+            .set_default("response.field_case", DEFAULT_FIELD_CASE)?
+            .set_default(
+                "response.read_cache_max_age_seconds",
+                DEFAULT_READ_CACHE_MAX_AGE_SECONDS,
+            )?
+            .set_default("response.error_detail", DEFAULT_ERROR_DETAIL)?
+            .set_default("response.envelope", DEFAULT_ENVELOPE)?;
+
+        let config = builder
+            .add_source(config::File::with_name(&format!("config/{}", env)).required(false))
+            .add_source(
+                config::Environment::with_prefix("APP")
+                    .prefix_separator("__")
+                    .separator("__"),
+            )
+            .build()?;
+
+        config.get::<ResponseConfig>("response")
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::defaults::retention::*;
+
+/// Retention of soft-deleted data
+///
+/// A background job hard-deletes users once they have been soft-deleted for
+/// longer than `deleted_user_retention_days`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RetentionConfig {
+    pub purge_enabled: bool,
+    pub purge_interval_seconds: u64,
+    pub deleted_user_retention_days: u64,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            purge_enabled: DEFAULT_PURGE_ENABLED,
+            purge_interval_seconds: DEFAULT_PURGE_INTERVAL_SECONDS,
+            deleted_user_retention_days: DEFAULT_DELETED_USER_RETENTION_DAYS,
+        }
+    }
+}
+
+impl RetentionConfig {
+    /// Load configuration from environment variables and config files
+    pub fn load(env: &str) -> Result<Self, config::ConfigError> {
+        let default: RetentionConfig = Self::default();
+        let builder = config::Config::builder()
+            .set_default("retention.purge_enabled", default.purge_enabled)?
+            .set_default(
+                "retention.purge_interval_seconds",
+                default.purge_interval_seconds,
+            )?
+            .set_default(
+                "retention.deleted_user_retention_days",
+                default.deleted_user_retention_days,
+            )?;
+
+        let config = builder
+            .add_source(config::File::with_name(&format!("config/{}", env)).required(false))
+            .add_source(
+                config::Environment::with_prefix("APP")
+                    .prefix_separator("__")
+                    .separator("__"),
+            )
+            .build()?;
+
+        config.get::<RetentionConfig>("retention")
+    }
+}
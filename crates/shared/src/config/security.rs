@@ -1 +1,226 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
+use crate::defaults::security;
+
+/// Password complexity rules enforced by the `Password` value object
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub max_length: usize,
+    pub require_upper: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: security::DEFAULT_PASSWORD_MIN_LENGTH,
+            max_length: security::DEFAULT_PASSWORD_MAX_LENGTH,
+            require_upper: security::DEFAULT_PASSWORD_REQUIRE_UPPER,
+            require_digit: security::DEFAULT_PASSWORD_REQUIRE_DIGIT,
+            require_symbol: security::DEFAULT_PASSWORD_REQUIRE_SYMBOL,
+        }
+    }
+}
+
+/// Argon2id cost parameters used when hashing passwords
+///
+/// Raising them only affects new hashes; with `rehash_on_login` a stored hash
+/// using weaker parameters is upgraded the next time its password verifies.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PasswordHashing {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    pub rehash_on_login: bool,
+}
+
+impl Default for PasswordHashing {
+    fn default() -> Self {
+        Self {
+            memory_kib: security::DEFAULT_PASSWORD_HASH_MEMORY_KIB,
+            iterations: security::DEFAULT_PASSWORD_HASH_ITERATIONS,
+            parallelism: security::DEFAULT_PASSWORD_HASH_PARALLELISM,
+            rehash_on_login: security::DEFAULT_PASSWORD_REHASH_ON_LOGIN,
+        }
+    }
+}
+
+/// Effective CORS rules for a group of routes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorsPolicy {
+    pub allowed_origins: Vec<String>,
+    /// Whether a `*` entry in `allowed_origins` is honoured
+    pub allow_wildcard: bool,
+    pub max_age_seconds: usize,
+}
+
+/// Per-scope CORS settings; unset fields fall back to the global ones
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CorsOverride {
+    pub allowed_origins: Option<Vec<String>>,
+    pub allow_wildcard: Option<bool>,
+    pub max_age_seconds: Option<usize>,
+}
+
+/// CORS configuration: global rules plus overrides keyed by route scope
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CorsConfig {
+    pub allowed_origins: Vec<String>,
+    pub allow_wildcard: bool,
+    pub max_age_seconds: usize,
+    #[serde(default)]
+    pub scopes: HashMap<String, CorsOverride>,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        let auth = CorsOverride {
+            allow_wildcard: Some(security::DEFAULT_AUTH_CORS_ALLOW_WILDCARD),
+            ..CorsOverride::default()
+        };
+        Self {
+            allowed_origins: security::DEFAULT_CORS_ALLOWED_ORIGINS
+                .iter()
+                .map(|origin| origin.to_string())
+                .collect(),
+            allow_wildcard: security::DEFAULT_CORS_ALLOW_WILDCARD,
+            max_age_seconds: security::DEFAULT_CORS_MAX_AGE_SECONDS,
+            scopes: HashMap::from([("auth".to_string(), auth)]),
+        }
+    }
+}
+
+impl CorsConfig {
+    /// Rules applied to routes without a scope override
+    pub fn global(&self) -> CorsPolicy {
+        CorsPolicy {
+            allowed_origins: self.allowed_origins.clone(),
+            allow_wildcard: self.allow_wildcard,
+            max_age_seconds: self.max_age_seconds,
+        }
+    }
+
+    /// Rules for `scope`: its override layered over the global rules
+    pub fn scope(&self, scope: &str) -> CorsPolicy {
+        let global = self.global();
+        let Some(scoped) = self.scopes.get(scope) else {
+            return global;
+        };
+        CorsPolicy {
+            allowed_origins: scoped
+                .allowed_origins
+                .clone()
+                .unwrap_or(global.allowed_origins),
+            allow_wildcard: scoped.allow_wildcard.unwrap_or(global.allow_wildcard),
+            max_age_seconds: scoped.max_age_seconds.unwrap_or(global.max_age_seconds),
+        }
+    }
+}
+
+/// Security configuration
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SecurityConfig {
+    pub password_policy: PasswordPolicy,
+    pub password_hashing: PasswordHashing,
+    pub cors: CorsConfig,
+    /// Key for the HMAC that signs pagination cursors
+    pub cursor_secret: String,
+    /// Proxy addresses (IPs or CIDRs) whose forwarding headers are trusted
+    pub trusted_proxies: Vec<String>,
+    /// Shared secret required by the detailed readiness probe; unset keeps it public
+    #[serde(default)]
+    pub health_token: Option<String>,
+}
+
+impl Default for SecurityConfig {
+    fn default() -> Self {
+        Self {
+            password_policy: PasswordPolicy::default(),
+            password_hashing: PasswordHashing::default(),
+            cors: CorsConfig::default(),
+            cursor_secret: security::DEFAULT_CURSOR_SECRET.to_string(),
+            trusted_proxies: Vec::new(),
+            health_token: None,
+        }
+    }
+}
+
+impl SecurityConfig {
+    /// Load configuration from environment variables and config files
+    pub fn load(env: &str) -> Result<Self, config::ConfigError> {
+        let default: SecurityConfig = Self::default();
+        let policy = default.password_policy;
+        let hashing = default.password_hashing;
+        let builder = config::Config::builder()
+            .set_default("security.cursor_secret", default.cursor_secret)?
+            .set_default("security.trusted_proxies", default.trusted_proxies)?
+            .set_default(
+                "security.cors.allowed_origins",
+                default.cors.allowed_origins,
+            )?
+            .set_default("security.cors.allow_wildcard", default.cors.allow_wildcard)?
+            .set_default(
+                "security.cors.max_age_seconds",
+                default.cors.max_age_seconds as i64,
+            )?
+            .set_default(
+                "security.cors.scopes.auth.allow_wildcard",
+                security::DEFAULT_AUTH_CORS_ALLOW_WILDCARD,
+            )?
+            .set_default(
+                "security.password_policy.min_length",
+                policy.min_length as i64,
+            )?
+            .set_default(
+                "security.password_policy.max_length",
+                policy.max_length as i64,
+            )?
+            .set_default(
+                "security.password_policy.require_upper",
+                policy.require_upper,
+            )?
+            .set_default(
+                "security.password_policy.require_digit",
+                policy.require_digit,
+            )?
+            .set_default(
+                "security.password_policy.require_symbol",
+                policy.require_symbol,
+            )?
+            .set_default(
+                "security.password_hashing.memory_kib",
+                hashing.memory_kib as i64,
+            )?
+            .set_default(
+                "security.password_hashing.iterations",
+                hashing.iterations as i64,
+            )?
+            .set_default(
+                "security.password_hashing.parallelism",
+                hashing.parallelism as i64,
+            )?
+            .set_default(
+                "security.password_hashing.rehash_on_login",
+                hashing.rehash_on_login,
+            )?;
+
+        let config = builder
+            .add_source(config::File::with_name(&format!("config/{}", env)).required(false))
+            .add_source(
+                config::Environment::with_prefix("APP")
+                    .prefix_separator("__")
+                    .separator("__")
+                    .list_separator(",")
+                    .with_list_parse_key("security.trusted_proxies")
+                    .with_list_parse_key("security.cors.allowed_origins")
+                    .with_list_parse_key("security.cors.scopes.auth.allowed_origins")
+                    .try_parsing(true),
+            )
+            .build()?;
+
+        config.get::<SecurityConfig>("security")
+    }
+}
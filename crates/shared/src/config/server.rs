@@ -1,15 +1,31 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::defaults::server::*;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
+    /// Serve over this Unix domain socket instead of `host`/`port` (Unix only)
+    pub unix_socket_path: Option<String>,
     pub workers: usize,
     pub request_timeout_seconds: u64,
     pub keep_alive_seconds: u64,
     pub max_connections: usize,
+    /// Concurrent connections accepted from one remote IP; 0 disables the cap
+    pub max_connections_per_ip: usize,
+    /// Requests served at once across all workers; beyond it requests get 503
+    pub max_inflight_requests: usize,
+    /// `Retry-After` sent with every 503 the service returns
+    pub overload_retry_after_seconds: u64,
+    /// Upper bound on each readiness dependency check
+    pub health_check_timeout_ms: u64,
+    /// Total budget for draining events and closing pools after the server stops
+    pub shutdown_timeout_ms: u64,
+    /// Deepest nesting of objects and arrays accepted in a JSON body
+    pub max_json_depth: usize,
+    /// Most elements accepted in any single JSON array of a body
+    pub max_json_array_len: usize,
 }
 
 impl Default for ServerConfig {
@@ -17,10 +33,18 @@ impl Default for ServerConfig {
         Self {
             host: DEFAULT_HOST.to_string(),
             port: DEFAULT_PORT,
+            unix_socket_path: None,
             workers: DEFAULT_WORKERS,
             request_timeout_seconds: DEFAULT_REQUEST_TIMEOUT_SECONDS,
             keep_alive_seconds: DEFAULT_KEEP_ALIVE_SECONDS,
             max_connections: DEFAULT_MAX_CONNECTIONS,
+            max_connections_per_ip: DEFAULT_MAX_CONNECTIONS_PER_IP,
+            max_inflight_requests: DEFAULT_MAX_INFLIGHT_REQUESTS,
+            overload_retry_after_seconds: DEFAULT_OVERLOAD_RETRY_AFTER_SECONDS,
+            health_check_timeout_ms: DEFAULT_HEALTH_CHECK_TIMEOUT_MS,
+            shutdown_timeout_ms: DEFAULT_SHUTDOWN_TIMEOUT_MS,
+            max_json_depth: DEFAULT_MAX_JSON_DEPTH,
+            max_json_array_len: DEFAULT_MAX_JSON_ARRAY_LEN,
         }
     }
 }
@@ -37,14 +61,36 @@ impl ServerConfig {
                 default.request_timeout_seconds,
             )?
             .set_default("server.keep_alive_seconds", default.keep_alive_seconds)?
-            .set_default("server.max_connections", default.max_connections as i64)?;
+            .set_default("server.max_connections", default.max_connections as i64)?
+            .set_default(
+                "server.max_connections_per_ip",
+                default.max_connections_per_ip as i64,
+            )?
+            .set_default(
+                "server.max_inflight_requests",
+                default.max_inflight_requests as i64,
+            )?
+            .set_default(
+                "server.overload_retry_after_seconds",
+                default.overload_retry_after_seconds,
+            )?
+            .set_default(
+                "server.health_check_timeout_ms",
+                default.health_check_timeout_ms,
+            )?
+            .set_default("server.shutdown_timeout_ms", default.shutdown_timeout_ms)?
+            .set_default("server.max_json_depth", default.max_json_depth as i64)?
+            .set_default(
+                "server.max_json_array_len",
+                default.max_json_array_len as i64,
+            )?;
 
         let config = builder
             .add_source(config::File::with_name(&format!("config/{}", env)).required(false))
             .add_source(
                 config::Environment::with_prefix("APP")
                     .prefix_separator("__")
-                    .separator("__")
+                    .separator("__"),
             )
             .build()?;
 
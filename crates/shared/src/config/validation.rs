@@ -0,0 +1,201 @@
+use serde::{Deserialize, Serialize};
+
+use crate::defaults::validation::*;
+use crate::{AppError, AppResult};
+
+/// Username lengths allowed by the `users_username_length` constraint, and
+/// the widths of the `users.username` and `users.full_name` columns.
+/// Configured bounds outside them would accept input the database rejects.
+const USERNAME_CHECK_MIN_LENGTH: usize = 3;
+const USERNAME_COLUMN_LENGTH: usize = 30;
+const FULL_NAME_COLUMN_LENGTH: usize = 100;
+
+/// Canonicalization applied to addresses at the listed email domains
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EmailDomainRule {
+    pub domains: Vec<String>,
+    /// Drop everything from the first `+` in the local part
+    #[serde(default)]
+    pub strip_plus_tag: bool,
+    /// Remove every `.` from the local part
+    #[serde(default)]
+    pub ignore_dots: bool,
+}
+
+/// Which address variants count as the same mailbox, per email domain.
+/// Domains without a rule are compared verbatim.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EmailPolicy {
+    #[serde(default)]
+    pub rules: Vec<EmailDomainRule>,
+}
+
+impl EmailPolicy {
+    /// The rule covering `domain`, if any
+    pub fn rule_for(&self, domain: &str) -> Option<&EmailDomainRule> {
+        self.rules
+            .iter()
+            .find(|rule| rule.domains.iter().any(|d| d.eq_ignore_ascii_case(domain)))
+    }
+}
+
+/// Input bounds enforced by the user value objects and list queries
+///
+/// The length bounds may only tighten what the `users` table allows:
+/// usernames within 3..=30 characters and full names up to 100. `load`
+/// rejects anything wider.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ValidationConfig {
+    pub username_min_length: usize,
+    pub username_max_length: usize,
+    pub full_name_max_length: usize,
+    /// Largest `offset` accepted by offset pagination; deeper pages use a cursor
+    pub max_offset: i64,
+    /// Most ids accepted by one batch user lookup
+    pub max_batch_get_ids: usize,
+    /// Order of user listings that pass no `sort`, e.g. `status,username`
+    pub default_user_sort: String,
+    /// Most keys accepted in a `sort` spec
+    pub max_sort_keys: usize,
+    /// Usernames nobody may register, compared case-insensitively
+    pub reserved_usernames: Vec<String>,
+    /// Also reject usernames that look like a reserved one (e.g. Cyrillic
+    /// `а` for Latin `a`, or `1` for `l`), compared by their UTS #39 skeleton
+    pub detect_confusable_usernames: bool,
+    /// Email canonicalization used by uniqueness checks
+    #[serde(default)]
+    pub email_policy: EmailPolicy,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            username_min_length: DEFAULT_USERNAME_MIN_LENGTH,
+            username_max_length: DEFAULT_USERNAME_MAX_LENGTH,
+            full_name_max_length: DEFAULT_FULL_NAME_MAX_LENGTH,
+            max_offset: DEFAULT_MAX_OFFSET,
+            max_batch_get_ids: DEFAULT_MAX_BATCH_GET_IDS,
+            default_user_sort: DEFAULT_USER_SORT.to_string(),
+            max_sort_keys: DEFAULT_MAX_SORT_KEYS,
+            reserved_usernames: DEFAULT_RESERVED_USERNAMES
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            detect_confusable_usernames: DEFAULT_DETECT_CONFUSABLE_USERNAMES,
+            email_policy: EmailPolicy::default(),
+        }
+    }
+}
+
+impl ValidationConfig {
+    /// Load configuration from environment variables and config files
+    pub fn load(env: &str) -> Result<Self, config::ConfigError> {
+        let default: ValidationConfig = Self::default();
+        let builder = config::Config::builder()
+            .set_default(
+                "validation.username_min_length",
+                default.username_min_length as i64,
+            )?
+            .set_default(
+                "validation.username_max_length",
+                default.username_max_length as i64,
+            )?
+            .set_default(
+                "validation.full_name_max_length",
+                default.full_name_max_length as i64,
+            )?
+            .set_default("validation.max_offset", default.max_offset)?
+            .set_default(
+                "validation.max_batch_get_ids",
+                default.max_batch_get_ids as i64,
+            )?
+            .set_default("validation.default_user_sort", default.default_user_sort)?
+            .set_default("validation.max_sort_keys", default.max_sort_keys as i64)?
+            .set_default("validation.reserved_usernames", default.reserved_usernames)?
+            .set_default(
+                "validation.detect_confusable_usernames",
+                default.detect_confusable_usernames,
+            )?;
+
+        let config = builder
+            .add_source(config::File::with_name(&format!("config/{}", env)).required(false))
+            .add_source(
+                config::Environment::with_prefix("APP")
+                    .prefix_separator("__")
+                    .separator("__"),
+            )
+            .build()?;
+
+        let validation: ValidationConfig = config.get("validation")?;
+        validation
+            .validate()
+            .map_err(|e| config::ConfigError::Message(e.to_string()))?;
+        Ok(validation)
+    }
+
+    /// Check that the length bounds fit the `users` table's columns and
+    /// constraints
+    pub fn validate(&self) -> AppResult<()> {
+        if self.username_min_length < USERNAME_CHECK_MIN_LENGTH {
+            return Err(AppError::ConfigurationError(format!(
+                "validation.username_min_length must be at least {}, as users_username_length requires",
+                USERNAME_CHECK_MIN_LENGTH
+            )));
+        }
+        if self.username_min_length > self.username_max_length {
+            return Err(AppError::ConfigurationError(
+                "validation.username_min_length must not exceed username_max_length".to_string(),
+            ));
+        }
+        if self.username_max_length > USERNAME_COLUMN_LENGTH {
+            return Err(AppError::ConfigurationError(format!(
+                "validation.username_max_length must be at most {}, the width of users.username",
+                USERNAME_COLUMN_LENGTH
+            )));
+        }
+        if self.full_name_max_length > FULL_NAME_COLUMN_LENGTH {
+            return Err(AppError::ConfigurationError(format!(
+                "validation.full_name_max_length must be at most {}, the width of users.full_name",
+                FULL_NAME_COLUMN_LENGTH
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_bounds_fit_the_schema() {
+        assert!(ValidationConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_bounds_outside_the_schema_are_rejected() {
+        let long_username = ValidationConfig {
+            username_max_length: USERNAME_COLUMN_LENGTH + 1,
+            ..ValidationConfig::default()
+        };
+        let long_full_name = ValidationConfig {
+            full_name_max_length: FULL_NAME_COLUMN_LENGTH + 1,
+            ..ValidationConfig::default()
+        };
+        let short_username = ValidationConfig {
+            username_min_length: USERNAME_CHECK_MIN_LENGTH - 1,
+            ..ValidationConfig::default()
+        };
+        let empty_range = ValidationConfig {
+            username_min_length: 20,
+            username_max_length: 10,
+            ..ValidationConfig::default()
+        };
+        for config in [long_username, long_full_name, short_username, empty_range] {
+            assert!(matches!(
+                config.validate(),
+                Err(AppError::ConfigurationError(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Client-facing messages for database unique constraint violations
+//!
+//! Maps constraint names (e.g. `users_email_key`) to the message returned in
+//! `AppError::AlreadyExists`. The registry is process-wide; entities add
+//! their own constraints at startup via [`register_unique_constraint`].
+
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+/// Message used when the violated constraint is unknown
+pub const DEFAULT_UNIQUE_VIOLATION_MESSAGE: &str = "Resource already exists";
+
+const BUILTIN: &[(&str, &str)] = &[
+    ("users_username_key", "Username already exists"),
+    ("users_email_key", "Email already exists"),
+];
+
+fn registry() -> &'static RwLock<HashMap<String, String>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, String>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        RwLock::new(
+            BUILTIN
+                .iter()
+                .map(|(name, message)| (name.to_string(), message.to_string()))
+                .collect(),
+        )
+    })
+}
+
+/// Register (or replace) the message for a unique constraint
+pub fn register_unique_constraint(constraint: impl Into<String>, message: impl Into<String>) {
+    registry()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(constraint.into(), message.into());
+}
+
+/// Message for a violation of `constraint`, falling back to a generic one
+pub fn unique_violation_message(constraint: Option<&str>) -> String {
+    constraint
+        .and_then(|name| {
+            registry()
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .get(name)
+                .cloned()
+        })
+        .unwrap_or_else(|| DEFAULT_UNIQUE_VIOLATION_MESSAGE.to_string())
+}
+
+#[cfg(all(test, feature = "sqlx-integration"))]
+mod tests {
+    use super::*;
+    use crate::AppError;
+    use sqlx::error::{DatabaseError, ErrorKind};
+    use std::borrow::Cow;
+    use std::fmt;
+
+    /// Stand-in for a Postgres unique violation on `constraint`
+    #[derive(Debug)]
+    struct UniqueViolation {
+        constraint: &'static str,
+    }
+
+    impl fmt::Display for UniqueViolation {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "duplicate key value violates unique constraint")
+        }
+    }
+
+    impl std::error::Error for UniqueViolation {}
+
+    impl DatabaseError for UniqueViolation {
+        fn message(&self) -> &str {
+            "duplicate key value violates unique constraint"
+        }
+
+        fn code(&self) -> Option<Cow<'_, str>> {
+            Some(Cow::Borrowed("23505"))
+        }
+
+        fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+            self
+        }
+
+        fn constraint(&self) -> Option<&str> {
+            Some(self.constraint)
+        }
+
+        fn kind(&self) -> ErrorKind {
+            ErrorKind::UniqueViolation
+        }
+    }
+
+    fn violation(constraint: &'static str) -> AppError {
+        sqlx::Error::Database(Box::new(UniqueViolation { constraint })).into()
+    }
+
+    #[test]
+    fn test_user_constraints_map_to_distinct_messages() {
+        let email = violation("users_email_key");
+        let username = violation("users_username_key");
+
+        assert!(matches!(&email, AppError::AlreadyExists(msg) if msg == "Email already exists"));
+        assert!(
+            matches!(&username, AppError::AlreadyExists(msg) if msg == "Username already exists")
+        );
+    }
+
+    #[test]
+    fn test_registered_and_unknown_constraints() {
+        register_unique_constraint("tenants_slug_key", "Tenant slug already exists");
+
+        assert!(
+            matches!(violation("tenants_slug_key"), AppError::AlreadyExists(msg) if msg == "Tenant slug already exists")
+        );
+        assert!(
+            matches!(violation("widgets_code_key"), AppError::AlreadyExists(msg) if msg == DEFAULT_UNIQUE_VIOLATION_MESSAGE)
+        );
+    }
+}
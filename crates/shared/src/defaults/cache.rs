@@ -9,3 +9,12 @@ pub const DEFAULT_CACHE_CONNECTION_TIMEOUT_SECONDS: u64 = 5;
 pub const DEFAULT_CACHE_IDLE_TIMEOUT_SECONDS: u64 = 300;
 pub const DEFAULT_CACHE_MAX_LIFETIME_SECONDS: u64 = 1800;
 pub const DEFAULT_CACHE_ENABLE_LOGGING: bool = false;
+pub const DEFAULT_CACHE_QUERY_TTL_SECONDS: u64 = 30;
+/// Query-cache TTL in seconds per read endpoint; others use the query TTL
+pub const DEFAULT_CACHE_POLICY: &[(&str, u64)] = &[
+    ("list_users", 30),
+    ("list_users_after", 30),
+    ("recent_users", 5),
+    ("users_by_domain", 120),
+];
+pub const DEFAULT_CACHE_WARMUP: bool = false;
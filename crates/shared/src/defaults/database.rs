@@ -10,3 +10,10 @@ pub const DEFAULT_DATABASE_IDLE_TIMEOUT_SECONDS: u64 = 600;
 pub const DEFAULT_DATABASE_MAX_LIFETIME_SECONDS: u64 = 1800;
 pub const DEFAULT_DATABASE_ENABLE_LOGGING: bool = false;
 pub const DEFAULT_DATABASE_RUN_MIGRATIONS: bool = true;
+pub const DEFAULT_DATABASE_ACQUIRE_RETRIES: u32 = 2;
+pub const DEFAULT_DATABASE_ACQUIRE_RETRY_BACKOFF_MS: u64 = 50;
+pub const DEFAULT_DATABASE_WARMUP: bool = false;
+pub const DEFAULT_DATABASE_QUERY_TIMEOUT_MS: u64 = 30000;
+pub const DEFAULT_DATABASE_READ_ACQUIRE_TIMEOUT_MS: u64 = 0;
+pub const DEFAULT_DATABASE_WRITE_ACQUIRE_TIMEOUT_MS: u64 = 0;
+pub const DEFAULT_DATABASE_CREATE_DATABASE_IF_MISSING: bool = false;
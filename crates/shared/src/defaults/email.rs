@@ -1 +1,9 @@
+//! Email (SMTP) default configurations
 
+pub const DEFAULT_EMAIL_ENABLED: bool = false;
+pub const DEFAULT_SMTP_HOST: &str = "localhost";
+pub const DEFAULT_SMTP_PORT: u16 = 587;
+pub const DEFAULT_SMTP_USE_TLS: bool = true;
+pub const DEFAULT_FROM_ADDRESS: &str = "no-reply@localhost";
+pub const DEFAULT_SMTP_TIMEOUT_SECONDS: u64 = 5;
+pub const DEFAULT_EMAIL_CRITICAL: bool = false;
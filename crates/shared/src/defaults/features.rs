@@ -1 +1,3 @@
+//! Default values for feature flags.
 
+pub const DEFAULT_ALLOW_USER_DELETION: bool = true;
@@ -0,0 +1,5 @@
+//! gRPC server default configurations
+
+pub const DEFAULT_GRPC_ENABLED: bool = false;
+pub const DEFAULT_GRPC_HOST: &str = "0.0.0.0";
+pub const DEFAULT_GRPC_PORT: u16 = 50051;
@@ -1 +1,10 @@
+//! JWT default configurations
 
+pub const DEFAULT_JWT_ALGORITHM: &str = "HS256";
+/// Development-only signing key; override in every deployed environment
+pub const DEFAULT_JWT_SECRET: &str = "dev-jwt-secret-change-me";
+pub const DEFAULT_JWT_KEY_ID: &str = "default";
+pub const DEFAULT_JWT_ISSUER: &str = "rs-service-template";
+pub const DEFAULT_ACCESS_TOKEN_TTL_SECONDS: u64 = 900;
+pub const DEFAULT_IMPERSONATION_TTL_SECONDS: u64 = 300;
+pub const DEFAULT_LEEWAY_SECONDS: u64 = 30;
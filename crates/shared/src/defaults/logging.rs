@@ -1 +1,3 @@
+//! Logging default configurations
 
+pub const DEFAULT_ACCESS_LOG_SAMPLING_RATE: f64 = 1.0;
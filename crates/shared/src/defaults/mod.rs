@@ -3,8 +3,12 @@ pub mod database;
 pub mod email;
 pub mod event_publisher;
 pub mod features;
+pub mod grpc;
 pub mod jwt;
 pub mod logging;
 pub mod oauth;
+pub mod response;
+pub mod retention;
 pub mod security;
 pub mod server;
+pub mod validation;
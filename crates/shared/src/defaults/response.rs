@@ -0,0 +1,8 @@
+//! Response serialization default configurations
+
+pub const DEFAULT_TIMESTAMP_FORMAT: &str = "rfc3339";
+pub const DEFAULT_PRETTY_JSON: bool = false;
+pub const DEFAULT_FIELD_CASE: &str = "snake_case";
+pub const DEFAULT_READ_CACHE_MAX_AGE_SECONDS: u64 = 30;
+pub const DEFAULT_ERROR_DETAIL: &str = "generic";
+pub const DEFAULT_ENVELOPE: bool = false;
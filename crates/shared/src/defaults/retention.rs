@@ -0,0 +1,5 @@
+//! Default values for the data retention configuration.
+
+pub const DEFAULT_PURGE_ENABLED: bool = true;
+pub const DEFAULT_PURGE_INTERVAL_SECONDS: u64 = 3600;
+pub const DEFAULT_DELETED_USER_RETENTION_DAYS: u64 = 30;
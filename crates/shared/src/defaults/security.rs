@@ -1 +1,22 @@
+//! Default security configuration values
 
+pub const DEFAULT_PASSWORD_MIN_LENGTH: usize = 8;
+pub const DEFAULT_PASSWORD_MAX_LENGTH: usize = 128;
+pub const DEFAULT_PASSWORD_REQUIRE_UPPER: bool = true;
+pub const DEFAULT_PASSWORD_REQUIRE_DIGIT: bool = true;
+pub const DEFAULT_PASSWORD_REQUIRE_SYMBOL: bool = false;
+
+/// Argon2id parameters from the OWASP password storage recommendations
+pub const DEFAULT_PASSWORD_HASH_MEMORY_KIB: u32 = 19_456;
+pub const DEFAULT_PASSWORD_HASH_ITERATIONS: u32 = 2;
+pub const DEFAULT_PASSWORD_HASH_PARALLELISM: u32 = 1;
+pub const DEFAULT_PASSWORD_REHASH_ON_LOGIN: bool = true;
+
+/// Development-only cursor signing key; override in every deployed environment
+pub const DEFAULT_CURSOR_SECRET: &str = "dev-cursor-secret-change-me";
+
+pub const DEFAULT_CORS_ALLOWED_ORIGINS: &[&str] = &["*"];
+pub const DEFAULT_CORS_ALLOW_WILDCARD: bool = true;
+pub const DEFAULT_CORS_MAX_AGE_SECONDS: usize = 3600;
+/// Auth routes never honour a wildcard origin unless configured otherwise
+pub const DEFAULT_AUTH_CORS_ALLOW_WILDCARD: bool = false;
@@ -6,3 +6,10 @@ pub const DEFAULT_WORKERS: usize = 4;
 pub const DEFAULT_REQUEST_TIMEOUT_SECONDS: u64 = 60;
 pub const DEFAULT_KEEP_ALIVE_SECONDS: u64 = 75;
 pub const DEFAULT_MAX_CONNECTIONS: usize = 25000;
+pub const DEFAULT_MAX_CONNECTIONS_PER_IP: usize = 256;
+pub const DEFAULT_MAX_INFLIGHT_REQUESTS: usize = 1024;
+pub const DEFAULT_OVERLOAD_RETRY_AFTER_SECONDS: u64 = 1;
+pub const DEFAULT_HEALTH_CHECK_TIMEOUT_MS: u64 = 2000;
+pub const DEFAULT_SHUTDOWN_TIMEOUT_MS: u64 = 10000;
+pub const DEFAULT_MAX_JSON_DEPTH: usize = 32;
+pub const DEFAULT_MAX_JSON_ARRAY_LEN: usize = 1000;
@@ -0,0 +1,19 @@
+//! Default input validation bounds
+
+pub const DEFAULT_USERNAME_MIN_LENGTH: usize = 3;
+pub const DEFAULT_USERNAME_MAX_LENGTH: usize = 30;
+pub const DEFAULT_FULL_NAME_MAX_LENGTH: usize = 100;
+pub const DEFAULT_MAX_OFFSET: i64 = 10_000;
+pub const DEFAULT_MAX_BATCH_GET_IDS: usize = 100;
+pub const DEFAULT_USER_SORT: &str = "-created_at";
+pub const DEFAULT_MAX_SORT_KEYS: usize = 3;
+pub const DEFAULT_RESERVED_USERNAMES: &[&str] = &[
+    "admin",
+    "administrator",
+    "root",
+    "support",
+    "security",
+    "system",
+    "api",
+];
+pub const DEFAULT_DETECT_CONFUSABLE_USERNAMES: bool = true;
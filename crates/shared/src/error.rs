@@ -1,13 +1,147 @@
+use serde::Serialize;
 use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::config::ErrorDetail;
+use crate::i18n::Locale;
+
+static FULL_ERROR_DETAIL: AtomicBool = AtomicBool::new(false);
+
+/// Set how much of an internal error is shown in HTTP error responses
+///
+/// Process-wide and set once at startup; defaults to `ErrorDetail::Generic`.
+pub fn set_error_detail(detail: ErrorDetail) {
+    FULL_ERROR_DETAIL.store(detail == ErrorDetail::Full, Ordering::Relaxed);
+}
+
+/// Detail level currently used for error responses
+pub fn error_detail() -> ErrorDetail {
+    if FULL_ERROR_DETAIL.load(Ordering::Relaxed) {
+        ErrorDetail::Full
+    } else {
+        ErrorDetail::Generic
+    }
+}
+
+/// Identifier of one failure, returned to the client and logged with the
+/// full error so the two can be matched up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorId(pub uuid::Uuid);
+
+impl fmt::Display for ErrorId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
 
 /// Application result type alias
 pub type AppResult<T> = Result<T, AppError>;
 
+/// A single validation failure tied to an input field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub code: String,
+    pub message: String,
+}
+
+/// Collection of field-level validation failures
+///
+/// Used to report every invalid field of a request at once instead of
+/// failing on the first one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct ValidationErrors(Vec<FieldError>);
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a failure for a field
+    pub fn add(
+        &mut self,
+        field: impl Into<String>,
+        code: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        self.0.push(FieldError {
+            field: field.into(),
+            code: code.into(),
+            message: message.into(),
+        });
+    }
+
+    /// Record the error of a failed validation result for `field`,
+    /// returning the value when validation succeeded.
+    pub fn capture<T>(&mut self, field: &str, result: Result<T, AppError>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(AppError::Validation(nested)) => {
+                self.0.extend(nested.0);
+                None
+            }
+            Err(err) => {
+                self.add(field, err.code(), err.detail());
+                None
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn errors(&self) -> &[FieldError] {
+        &self.0
+    }
+
+    /// `Ok(())` when nothing was recorded, otherwise `AppError::Validation`
+    pub fn into_result(self) -> AppResult<()> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::Validation(self))
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self
+            .0
+            .iter()
+            .map(|e| format!("{}: {}", e.field, e.message))
+            .collect();
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
+/// A cache failure, split by where it happened so callers can choose to
+/// fail open or closed per kind
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheError {
+    /// No connection could be checked out: the pool is exhausted or the
+    /// cache server cannot be reached
+    PoolExhausted(String),
+    /// A connection was obtained but the command failed or returned bad data
+    Command(String),
+}
+
+impl fmt::Display for CacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheError::PoolExhausted(msg) | CacheError::Command(msg) => f.write_str(msg),
+        }
+    }
+}
+
 /// Application-wide error type
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum AppError {
     // Domain errors
     ValidationError(String),
+    Validation(ValidationErrors),
     InvalidEmail(String),
     InvalidUsername(String),
 
@@ -19,17 +153,74 @@ pub enum AppError {
 
     // Infrastructure errors
     DatabaseError(String),
-    CacheError(String),
+    CacheError(CacheError),
+    EmailError(String),
 
     // Internal errors
     InternalError(String),
     ConfigurationError(String),
 }
 
+/// Transport-independent classification of an `AppError`
+///
+/// The HTTP status, gRPC code and GraphQL error code are all derived from
+/// the kind, so every transport reports the same failure the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    Validation,
+    NotFound,
+    Conflict,
+    Unauthenticated,
+    Forbidden,
+    Internal,
+}
+
+impl ErrorKind {
+    /// HTTP status code for the kind
+    pub fn http_status(self) -> u16 {
+        match self {
+            ErrorKind::Validation => 400,
+            ErrorKind::NotFound => 404,
+            ErrorKind::Conflict => 409,
+            ErrorKind::Unauthenticated => 401,
+            ErrorKind::Forbidden => 403,
+            ErrorKind::Internal => 500,
+        }
+    }
+
+    /// Value of the `kind` extension on GraphQL errors
+    pub fn graphql_code(self) -> &'static str {
+        match self {
+            ErrorKind::Validation => "BAD_USER_INPUT",
+            ErrorKind::NotFound => "NOT_FOUND",
+            ErrorKind::Conflict => "CONFLICT",
+            ErrorKind::Unauthenticated => "UNAUTHENTICATED",
+            ErrorKind::Forbidden => "FORBIDDEN",
+            ErrorKind::Internal => "INTERNAL_SERVER_ERROR",
+        }
+    }
+
+    /// gRPC status code for the kind
+    #[cfg(feature = "grpc-integration")]
+    pub fn grpc_code(self) -> tonic::Code {
+        use tonic::Code;
+
+        match self {
+            ErrorKind::Validation => Code::InvalidArgument,
+            ErrorKind::NotFound => Code::NotFound,
+            ErrorKind::Conflict => Code::AlreadyExists,
+            ErrorKind::Unauthenticated => Code::Unauthenticated,
+            ErrorKind::Forbidden => Code::PermissionDenied,
+            ErrorKind::Internal => Code::Internal,
+        }
+    }
+}
+
 impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             AppError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
+            AppError::Validation(errors) => write!(f, "Validation error: {}", errors),
             AppError::InvalidEmail(msg) => write!(f, "Invalid email: {}", msg),
             AppError::InvalidUsername(msg) => write!(f, "Invalid username: {}", msg),
             AppError::NotFound(msg) => write!(f, "Not found: {}", msg),
@@ -37,27 +228,108 @@ impl fmt::Display for AppError {
             AppError::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
             AppError::Forbidden(msg) => write!(f, "Forbidden: {}", msg),
             AppError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
-            AppError::CacheError(msg) => write!(f, "Cache error: {}", msg),
+            AppError::CacheError(err) => write!(f, "Cache error: {}", err),
+            AppError::EmailError(msg) => write!(f, "Email error: {}", msg),
             AppError::InternalError(msg) => write!(f, "Internal error: {}", msg),
             AppError::ConfigurationError(msg) => write!(f, "Configuration error: {}", msg),
         }
     }
 }
 
+impl AppError {
+    /// Classification shared by every transport's error mapping
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            AppError::ValidationError(_)
+            | AppError::Validation(_)
+            | AppError::InvalidEmail(_)
+            | AppError::InvalidUsername(_) => ErrorKind::Validation,
+            AppError::NotFound(_) => ErrorKind::NotFound,
+            AppError::AlreadyExists(_) => ErrorKind::Conflict,
+            AppError::Unauthorized(_) => ErrorKind::Unauthenticated,
+            AppError::Forbidden(_) => ErrorKind::Forbidden,
+            AppError::DatabaseError(_)
+            | AppError::CacheError(_)
+            | AppError::EmailError(_)
+            | AppError::InternalError(_)
+            | AppError::ConfigurationError(_) => ErrorKind::Internal,
+        }
+    }
+
+    /// Stable machine-readable code for the error kind
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::ValidationError(_) => "validation_error",
+            AppError::Validation(_) => "validation_error",
+            AppError::InvalidEmail(_) => "invalid_email",
+            AppError::InvalidUsername(_) => "invalid_username",
+            AppError::NotFound(_) => "not_found",
+            AppError::AlreadyExists(_) => "already_exists",
+            AppError::Unauthorized(_) => "unauthorized",
+            AppError::Forbidden(_) => "forbidden",
+            AppError::DatabaseError(_) => "database_error",
+            AppError::CacheError(_) => "cache_error",
+            AppError::EmailError(_) => "email_error",
+            AppError::InternalError(_) => "internal_error",
+            AppError::ConfigurationError(_) => "configuration_error",
+        }
+    }
+
+    /// Whether the detail may expose internals (SQL, config values) that
+    /// clients should not see outside development
+    pub fn is_sensitive(&self) -> bool {
+        matches!(
+            self,
+            AppError::DatabaseError(_)
+                | AppError::InternalError(_)
+                | AppError::ConfigurationError(_)
+        )
+    }
+
+    /// The message carried by the error, without the kind prefix
+    pub fn detail(&self) -> String {
+        match self {
+            AppError::Validation(errors) => errors.to_string(),
+            AppError::CacheError(err) => err.to_string(),
+            AppError::ValidationError(msg)
+            | AppError::InvalidEmail(msg)
+            | AppError::InvalidUsername(msg)
+            | AppError::NotFound(msg)
+            | AppError::AlreadyExists(msg)
+            | AppError::Unauthorized(msg)
+            | AppError::Forbidden(msg)
+            | AppError::DatabaseError(msg)
+            | AppError::EmailError(msg)
+            | AppError::InternalError(msg)
+            | AppError::ConfigurationError(msg) => msg.clone(),
+        }
+    }
+}
+
+impl AppError {
+    /// Render the client-facing message in the given locale
+    pub fn localized_message(&self, locale: Locale) -> String {
+        locale.render(self.code(), &self.detail())
+    }
+}
+
 impl std::error::Error for AppError {}
 
 // Conversions from infrastructure errors
+#[cfg(feature = "sqlx-integration")]
 impl From<sqlx::Error> for AppError {
     fn from(err: sqlx::Error) -> Self {
         match err {
             sqlx::Error::RowNotFound => AppError::NotFound("Resource not found".to_string()),
             sqlx::Error::Database(db_err) => {
                 // Check for unique constraint violations
-                if let Some(code) = db_err.code() {
-                    if code == "23505" {
-                        // PostgreSQL unique violation
-                        return AppError::AlreadyExists("Resource already exists".to_string());
-                    }
+                if let Some(code) = db_err.code()
+                    && code == "23505"
+                {
+                    // PostgreSQL unique violation; name which constraint fired
+                    return AppError::AlreadyExists(crate::constraints::unique_violation_message(
+                        db_err.constraint(),
+                    ));
                 }
                 AppError::DatabaseError(db_err.to_string())
             }
@@ -66,50 +338,221 @@ impl From<sqlx::Error> for AppError {
     }
 }
 
+#[cfg(feature = "redis-integration")]
 impl From<deadpool_redis::PoolError> for AppError {
     fn from(err: deadpool_redis::PoolError) -> Self {
-        AppError::CacheError(err.to_string())
+        AppError::CacheError(CacheError::PoolExhausted(err.to_string()))
+    }
+}
+
+#[cfg(feature = "redis-integration")]
+impl From<deadpool_redis::redis::RedisError> for AppError {
+    fn from(err: deadpool_redis::redis::RedisError) -> Self {
+        AppError::CacheError(CacheError::Command(err.to_string()))
     }
 }
 
-impl From<redis::RedisError> for AppError {
-    fn from(err: redis::RedisError) -> Self {
-        AppError::CacheError(err.to_string())
+#[cfg(feature = "grpc-integration")]
+impl From<AppError> for tonic::Status {
+    fn from(err: AppError) -> Self {
+        tonic::Status::new(
+            err.kind().grpc_code(),
+            err.localized_message(Locale::default()),
+        )
     }
 }
 
 #[cfg(feature = "actix-integration")]
 impl actix_web::ResponseError for AppError {
     fn status_code(&self) -> actix_web::http::StatusCode {
-        use actix_web::http::StatusCode;
+        actix_web::http::StatusCode::from_u16(self.kind().http_status())
+            .unwrap_or(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR)
+    }
 
-        match self {
-            AppError::ValidationError(_) => StatusCode::BAD_REQUEST,
-            AppError::InvalidEmail(_) => StatusCode::BAD_REQUEST,
-            AppError::InvalidUsername(_) => StatusCode::BAD_REQUEST,
-            AppError::NotFound(_) => StatusCode::NOT_FOUND,
-            AppError::AlreadyExists(_) => StatusCode::CONFLICT,
-            AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
-            AppError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            AppError::CacheError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            AppError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            AppError::ConfigurationError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    /// Logs the full error under a fresh `error_id` that is also returned
+    /// to the client; inside a request span the log line carries its id too.
+    /// Only internal errors log at ERROR; client errors log at INFO so that
+    /// failed validation or scanner traffic does not set off alerting.
+    fn error_response(&self) -> actix_web::HttpResponse {
+        let error_id = ErrorId(uuid::Uuid::new_v4());
+        if self.kind() == ErrorKind::Internal {
+            tracing::error!(%error_id, error = %self, "Request failed");
+        } else {
+            tracing::info!(%error_id, error = %self, "Request failed");
         }
+        self.localized_error_response(Locale::default(), Some(error_id))
     }
+}
 
-    fn error_response(&self) -> actix_web::HttpResponse {
+#[cfg(feature = "actix-integration")]
+impl AppError {
+    /// Build the JSON error response with the message rendered in `locale`
+    ///
+    /// The `error_id` is included in the body and kept in the response
+    /// extensions, so re-rendering can carry the same id.
+    pub fn localized_error_response(
+        &self,
+        locale: Locale,
+        error_id: Option<ErrorId>,
+    ) -> actix_web::HttpResponse {
+        self.render_error_response(locale, error_detail(), error_id)
+    }
+
+    fn render_error_response(
+        &self,
+        locale: Locale,
+        detail: ErrorDetail,
+        error_id: Option<ErrorId>,
+    ) -> actix_web::HttpResponse {
         use actix_web::HttpResponse;
+        use actix_web::ResponseError;
         use serde_json::json;
 
         let status = self.status_code();
-        let error_message = self.to_string();
+        let error_message = if self.is_sensitive() && detail == ErrorDetail::Generic {
+            locale.render("internal_error_generic", "")
+        } else {
+            self.localized_message(locale)
+        };
 
-        HttpResponse::build(status).json(json!({
-            "error": {
-                "message": error_message,
-                "code": status.as_u16(),
-            }
-        }))
+        let mut error = json!({
+            "message": error_message,
+            "code": status.as_u16(),
+        });
+        if let Some(error_id) = error_id {
+            error["error_id"] = json!(error_id.to_string());
+        }
+
+        let mut response = if let AppError::Validation(errors) = self {
+            HttpResponse::build(status).json(json!({ "error": error, "errors": errors }))
+        } else {
+            HttpResponse::build(status).json(json!({ "error": error }))
+        };
+        if let Some(error_id) = error_id {
+            response.extensions_mut().insert(error_id);
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_variant_maps_to_expected_http_status() {
+        let cases = [
+            (AppError::ValidationError("x".into()), 400),
+            (AppError::Validation(ValidationErrors::new()), 400),
+            (AppError::InvalidEmail("x".into()), 400),
+            (AppError::InvalidUsername("x".into()), 400),
+            (AppError::NotFound("x".into()), 404),
+            (AppError::AlreadyExists("x".into()), 409),
+            (AppError::Unauthorized("x".into()), 401),
+            (AppError::Forbidden("x".into()), 403),
+            (AppError::DatabaseError("x".into()), 500),
+            (AppError::CacheError(CacheError::Command("x".into())), 500),
+            (AppError::EmailError("x".into()), 500),
+            (AppError::InternalError("x".into()), 500),
+            (AppError::ConfigurationError("x".into()), 500),
+        ];
+
+        for (err, status) in cases {
+            assert_eq!(err.kind().http_status(), status, "{}", err.code());
+        }
+    }
+
+    #[test]
+    fn test_kind_drives_graphql_code() {
+        assert_eq!(
+            AppError::AlreadyExists("x".into()).kind().graphql_code(),
+            "CONFLICT"
+        );
+        assert_eq!(
+            AppError::InvalidEmail("x".into()).kind().graphql_code(),
+            "BAD_USER_INPUT"
+        );
+    }
+
+    #[cfg(feature = "actix-integration")]
+    #[test]
+    fn test_response_error_uses_kind() {
+        use actix_web::ResponseError;
+
+        let err = AppError::Forbidden("x".into());
+        assert_eq!(err.status_code().as_u16(), err.kind().http_status());
+    }
+
+    #[cfg(feature = "actix-integration")]
+    fn error_body(err: &AppError, detail: ErrorDetail, error_id: ErrorId) -> serde_json::Value {
+        use actix_web::body::MessageBody;
+
+        let body = err
+            .render_error_response(Locale::En, detail, Some(error_id))
+            .into_body()
+            .try_into_bytes()
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[cfg(feature = "actix-integration")]
+    #[test]
+    fn test_sensitive_detail_is_generic_unless_full_detail_is_configured() {
+        let err = AppError::DatabaseError("relation \"users\" does not exist".into());
+        let error_id = ErrorId(uuid::Uuid::new_v4());
+
+        let generic = error_body(&err, ErrorDetail::Generic, error_id);
+        assert_eq!(generic["error"]["message"], "An internal error occurred");
+        assert_eq!(generic["error"]["error_id"], error_id.to_string());
+        assert_eq!(generic["error"]["code"], 500);
+
+        let full = error_body(&err, ErrorDetail::Full, error_id);
+        assert_eq!(
+            full["error"]["message"],
+            "Database error: relation \"users\" does not exist"
+        );
+        assert_eq!(full["error"]["error_id"], error_id.to_string());
+
+        // Client errors are never redacted
+        let not_found = AppError::NotFound("User 42".into());
+        let body = error_body(&not_found, ErrorDetail::Generic, error_id);
+        assert_eq!(body["error"]["message"], "Not found: User 42");
+    }
+
+    #[cfg(feature = "redis-integration")]
+    #[test]
+    fn test_redis_errors_map_to_cache_sub_kinds() {
+        use deadpool_redis::redis::{ErrorKind as RedisErrorKind, RedisError};
+        use deadpool_redis::{PoolError, TimeoutType};
+
+        let exhausted: AppError = PoolError::Timeout(TimeoutType::Wait).into();
+        assert!(matches!(
+            exhausted,
+            AppError::CacheError(CacheError::PoolExhausted(_))
+        ));
+
+        let unreachable: AppError = PoolError::Backend(RedisError::from((
+            RedisErrorKind::IoError,
+            "connection refused",
+        )))
+        .into();
+        assert!(matches!(
+            unreachable,
+            AppError::CacheError(CacheError::PoolExhausted(_))
+        ));
+
+        let command: AppError = RedisError::from((RedisErrorKind::TypeError, "WRONGTYPE")).into();
+        assert!(matches!(
+            command,
+            AppError::CacheError(CacheError::Command(_))
+        ));
+        assert_eq!(command.code(), "cache_error");
+    }
+
+    #[cfg(feature = "grpc-integration")]
+    #[test]
+    fn test_grpc_status_uses_kind() {
+        let status: tonic::Status = AppError::NotFound("x".into()).into();
+        assert_eq!(status.code(), tonic::Code::NotFound);
     }
 }
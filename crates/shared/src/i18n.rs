@@ -0,0 +1,132 @@
+//! Localized error message catalogs
+//!
+//! Templates are keyed by the stable `AppError::code()` and may reference the
+//! error detail through the `{detail}` placeholder.
+
+/// Supported response locales
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    En,
+    Tr,
+}
+
+impl Locale {
+    /// Match a single language tag (e.g. `tr-TR`) against the supported locales
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        match primary.as_str() {
+            "en" => Some(Locale::En),
+            "tr" => Some(Locale::Tr),
+            _ => None,
+        }
+    }
+
+    /// Negotiate a locale from an `Accept-Language` header value, honoring
+    /// quality weights and defaulting to English.
+    pub fn from_accept_language(header: &str) -> Self {
+        let mut candidates: Vec<(f32, Locale)> = header
+            .split(',')
+            .filter_map(|part| {
+                let mut pieces = part.split(';');
+                let locale = Self::from_tag(pieces.next()?)?;
+                let quality = pieces
+                    .find_map(|p| p.trim().strip_prefix("q="))
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (quality > 0.0).then_some((quality, locale))
+            })
+            .collect();
+
+        // Stable sort keeps header order for equal weights
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+        candidates.first().map(|(_, l)| *l).unwrap_or_default()
+    }
+
+    fn catalog(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN,
+            Locale::Tr => TR,
+        }
+    }
+
+    /// Render the message template for an error code
+    pub fn render(&self, code: &str, detail: &str) -> String {
+        let template = self
+            .catalog()
+            .iter()
+            .chain(EN.iter())
+            .find(|(c, _)| *c == code)
+            .map(|(_, t)| *t)
+            .unwrap_or("{detail}");
+        template.replace("{detail}", detail)
+    }
+}
+
+const EN: &[(&str, &str)] = &[
+    ("validation_error", "Validation error: {detail}"),
+    ("invalid_email", "Invalid email: {detail}"),
+    ("invalid_username", "Invalid username: {detail}"),
+    ("not_found", "Not found: {detail}"),
+    ("already_exists", "Already exists: {detail}"),
+    ("unauthorized", "Unauthorized: {detail}"),
+    ("forbidden", "Forbidden: {detail}"),
+    ("database_error", "Database error: {detail}"),
+    ("cache_error", "Cache error: {detail}"),
+    ("email_error", "Email error: {detail}"),
+    ("internal_error", "Internal error: {detail}"),
+    ("configuration_error", "Configuration error: {detail}"),
+    ("internal_error_generic", "An internal error occurred"),
+];
+
+const TR: &[(&str, &str)] = &[
+    ("validation_error", "Doğrulama hatası: {detail}"),
+    ("invalid_email", "Geçersiz e-posta: {detail}"),
+    ("invalid_username", "Geçersiz kullanıcı adı: {detail}"),
+    ("not_found", "Bulunamadı: {detail}"),
+    ("already_exists", "Zaten mevcut: {detail}"),
+    ("unauthorized", "Yetkisiz: {detail}"),
+    ("forbidden", "Erişim engellendi: {detail}"),
+    ("database_error", "Veritabanı hatası: {detail}"),
+    ("cache_error", "Önbellek hatası: {detail}"),
+    ("email_error", "E-posta hatası: {detail}"),
+    ("internal_error", "Dahili hata: {detail}"),
+    ("configuration_error", "Yapılandırma hatası: {detail}"),
+    ("internal_error_generic", "Dahili bir hata oluştu"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AppError;
+
+    #[test]
+    fn test_accept_language_negotiation() {
+        assert_eq!(Locale::from_accept_language("tr-TR,tr;q=0.9"), Locale::Tr);
+        assert_eq!(Locale::from_accept_language("de-DE, tr;q=0.5"), Locale::Tr);
+        assert_eq!(
+            Locale::from_accept_language("en;q=0.4, tr;q=0.8"),
+            Locale::Tr
+        );
+        assert_eq!(Locale::from_accept_language("fr"), Locale::En);
+        assert_eq!(Locale::from_accept_language(""), Locale::En);
+    }
+
+    #[test]
+    fn test_not_found_localized() {
+        let err = AppError::NotFound("user 42".to_string());
+
+        let en = err.localized_message(Locale::En);
+        let tr = err.localized_message(Locale::Tr);
+
+        assert_eq!(en, "Not found: user 42");
+        assert_eq!(tr, "Bulunamadı: user 42");
+        assert_ne!(en, tr);
+    }
+
+    #[test]
+    fn test_english_matches_display() {
+        let err = AppError::AlreadyExists("Username 'bob' already exists".to_string());
+        assert_eq!(err.localized_message(Locale::En), err.to_string());
+    }
+}
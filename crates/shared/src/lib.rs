@@ -1,5 +1,5 @@
 pub mod config;
-pub use config::AppConfig;
+pub use config::{AppConfig, AppEnv};
 
 pub mod defaults;
 pub use defaults::{
@@ -15,8 +15,15 @@ pub use defaults::{
     server,
 };
 
+pub mod constraints;
+
 pub mod error;
-pub use error::{AppError, AppResult};
+pub use error::{
+    AppError, AppResult, CacheError, ErrorId, ErrorKind, FieldError, ValidationErrors,
+};
+
+pub mod i18n;
+pub use i18n::Locale;
 
 pub mod types;
 pub use types::UserId;
@@ -0,0 +1,56 @@
+use actix_cors::Cors;
+use actix_web::http::{Method, header};
+
+use shared::config::{CorsConfig, CorsPolicy};
+
+/// Methods the API's routes answer to, all allowed cross-origin
+pub fn allowed_methods() -> Vec<Method> {
+    vec![
+        Method::GET,
+        Method::HEAD,
+        Method::POST,
+        Method::PUT,
+        Method::PATCH,
+        Method::DELETE,
+        Method::OPTIONS,
+    ]
+}
+
+/// Everything needed to build CORS middleware for any route scope
+#[derive(Debug, Clone)]
+pub struct CorsSettings {
+    pub config: CorsConfig,
+    pub headers: Vec<header::HeaderName>,
+    pub methods: Vec<Method>,
+}
+
+impl CorsSettings {
+    /// CORS middleware for routes without a scope override
+    pub fn global(&self) -> Cors {
+        self.build(&self.config.global())
+    }
+
+    /// CORS middleware for `scope`, layered over the global rules
+    pub fn scope(&self, scope: &str) -> Cors {
+        self.build(&self.config.scope(scope))
+    }
+
+    fn build(&self, policy: &CorsPolicy) -> Cors {
+        let mut cors = Cors::default()
+            .allowed_headers(self.headers.clone())
+            .allowed_methods(self.methods.clone())
+            .max_age(policy.max_age_seconds);
+
+        for origin in &policy.allowed_origins {
+            if origin != "*" {
+                cors = cors.allowed_origin(origin);
+            } else if policy.allow_wildcard {
+                cors = cors.allow_any_origin();
+            } else {
+                tracing::warn!("Ignoring wildcard CORS origin for a scope that disallows it");
+            }
+        }
+
+        cors
+    }
+}
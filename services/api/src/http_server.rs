@@ -1,32 +1,76 @@
-use actix_cors::Cors;
 use actix_web::{
     App, HttpServer,
     http::{Method, header},
-    middleware::{Compress, Logger},
+    middleware::{Compress, from_fn},
     web,
 };
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
-use application::UserService;
+use application::{AuthService, PurgeDeletedUsersJob, QueryCache, UserService};
 use infrastructure::PostgresUserRepository;
+use infrastructure::auth::{Argon2PasswordHasher, JwkSet, JwtTokenService};
+use infrastructure::cache::RedisCacheStore;
+use infrastructure::database::retry::AcquireRetry;
 
+use crate::cors::{self, CorsSettings};
 use crate::route_configuration::configure_routes;
+use presentation::extractors::json_error;
+use presentation::graphql::{UserSchema, build_schema};
+use presentation::middleware::{
+    AccessLog, ConcurrencyLimit, ConnectionLimit, JsonLimits, RetryAfter, TrustedProxies,
+    access_log, authenticate, limit_concurrency, limit_connections, limit_json, localize_errors,
+    request_id, retry_after,
+};
+use presentation::routes::debug::EffectiveConfig;
+use presentation::routes::health::{HealthCheckTimeout, HealthToken};
 use presentation::states::AppState;
+use shared::config::ResponseConfig;
+
+/// Permissions for the Unix socket: owner and group (the proxy) may connect
+#[cfg(unix)]
+const UNIX_SOCKET_MODE: u32 = 0o660;
 
 pub struct Server {
     host: String,
     port: u16,
+    unix_socket_path: Option<PathBuf>,
     state: web::Data<AppState>,
     user_service: web::Data<UserService>,
-    origins: Vec<String>,
-    headers: Vec<header::HeaderName>,
-    methods: Vec<Method>,
+    auth_service: web::Data<AuthService>,
+    schema: web::Data<UserSchema>,
+    trusted_proxies: web::Data<TrustedProxies>,
+    response: web::Data<ResponseConfig>,
+    health_token: web::Data<HealthToken>,
+    health_timeout: web::Data<HealthCheckTimeout>,
+    effective_config: web::Data<EffectiveConfig>,
+    jwks: web::Data<JwkSet>,
+    concurrency: web::Data<ConcurrencyLimit>,
+    connection_limit: ConnectionLimit,
+    retry_after: web::Data<RetryAfter>,
+    access_log: web::Data<AccessLog>,
+    json_limits: web::Data<JsonLimits>,
+    grpc_addr: Option<SocketAddr>,
+    shutdown_timeout: Duration,
+    cors: CorsSettings,
 }
 
 impl Server {
-    pub async fn new(config: &shared::AppConfig) -> Result<Self, Box<dyn std::error::Error>> {
+    pub async fn new(
+        env: shared::AppEnv,
+        config: &shared::AppConfig,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        #[cfg(not(unix))]
+        if config.server.unix_socket_path.is_some() {
+            return Err("server.unix_socket_path is only supported on Unix".into());
+        }
+        application::dtos::timestamp::set_format(config.response.timestamp_format);
+        shared::error::set_error_detail(config.response.error_detail);
+
         let mut app_state: AppState = AppState::new();
-        let app_state: AppState = match app_state.load(config).await {
+        let mut app_state: AppState = match app_state.load(config).await {
             Ok(state) => state,
             Err(e) => {
                 tracing::error!(
@@ -36,97 +80,331 @@ impl Server {
                 AppState::new()
             }
         };
-        let state: web::Data<AppState> = web::Data::new(app_state);
-
         // Create database pool for services
+        infrastructure::database::postgres::create_database_if_missing(&config.database, env)
+            .await?;
         let db_pool =
             infrastructure::database::postgres::create_postgres_pool(config.database.clone())
                 .await?;
+        if config.database.warmup {
+            infrastructure::database::postgres::warm_up_postgres_pool(
+                &db_pool,
+                config.database.min_connections,
+            )
+            .await?;
+        }
+        app_state
+            .db
+            .add_db_pool("default".to_string(), db_pool.clone());
+        let replica_pool =
+            infrastructure::database::postgres::create_replica_pool(&config.database).await?;
+        if let Some(replica) = &replica_pool {
+            app_state
+                .db
+                .add_db_pool("replica".to_string(), replica.clone());
+        }
+
+        // Readiness only flips once migrations have fully completed
+        if config.database.run_migrations {
+            app_state
+                .readiness
+                .gate_on(infrastructure::database::postgres::run_migrations(&db_pool))
+                .await?;
+        } else {
+            infrastructure::database::postgres::ensure_migrations_applied(&db_pool).await?;
+            app_state.readiness.mark_ready();
+        }
 
         // Create repository implementations
-        let user_repository = Arc::new(PostgresUserRepository::new(db_pool.clone()));
+        let user_repository = match replica_pool {
+            Some(replica) => PostgresUserRepository::new_with_replica(db_pool.clone(), replica),
+            None => PostgresUserRepository::new(db_pool.clone()),
+        };
+        let user_repository = Arc::new(
+            user_repository
+                .with_acquire_retry(AcquireRetry::from_config(&config.database))
+                .with_query_timeout(Duration::from_millis(config.database.query_timeout_ms))
+                .with_acquire_timeouts(
+                    Duration::from_millis(config.database.read_acquire_timeout_ms),
+                    Duration::from_millis(config.database.write_acquire_timeout_ms),
+                ),
+        );
 
         // Create application services
-        let user_service = web::Data::new(UserService::new(user_repository));
+        let password_hashing = &config.security.password_hashing;
+        let token_service = JwtTokenService::new(&config.jwt)?;
+        let jwks = web::Data::new(token_service.jwks());
+        let auth_service = web::Data::new(
+            AuthService::new(
+                user_repository.clone(),
+                Arc::new(token_service),
+                config.jwt.clone(),
+            )
+            .with_password_hasher(
+                Arc::new(Argon2PasswordHasher::new(password_hashing)?),
+                password_hashing,
+            )
+            .with_password_policy(config.security.password_policy.clone()),
+        );
+        let mut user_service = UserService::new(user_repository)
+            .with_event_bus(app_state.events.bus())
+            .with_validation(config.validation.clone())
+            .with_features(config.features.clone())
+            .with_cursor_secret(config.security.cursor_secret.as_bytes());
+        if let Some(pool) = app_state.cache.get("default") {
+            let query_cache = QueryCache::new(
+                Arc::new(RedisCacheStore::new(pool.clone())),
+                "users",
+                Duration::from_secs(config.cache.query_ttl_seconds),
+            )
+            .with_policy(config.cache.policy.clone());
+            user_service = user_service.with_query_cache(query_cache);
+        }
+        let user_service = Arc::new(user_service);
+        if config.retention.purge_enabled && config.features.allow_user_deletion {
+            let job = PurgeDeletedUsersJob::from_config(user_service.clone(), &config.retention);
+            let interval = Duration::from_secs(config.retention.purge_interval_seconds.max(1));
+            app_state
+                .tasks
+                .spawn("purge_deleted_users", move |token| async move {
+                    token.run_until_cancelled(job.run_every(interval)).await;
+                });
+        }
+        let schema = web::Data::new(build_schema(user_service.clone()));
+        let user_service = web::Data::from(user_service);
+
+        // Number and buffer published events for SSE replay
+        app_state.events.spawn_history_recorder();
+
+        let state: web::Data<AppState> = web::Data::new(app_state);
+        let trusted_proxies =
+            web::Data::new(TrustedProxies::parse(&config.security.trusted_proxies)?);
+        let connection_limit = ConnectionLimit::new(
+            config.server.max_connections_per_ip,
+            trusted_proxies.get_ref().clone(),
+        );
+        let response = web::Data::new(config.response.clone());
+        let health_token = web::Data::new(HealthToken::new(config.security.health_token.clone()));
+        let health_timeout = web::Data::new(HealthCheckTimeout(Duration::from_millis(
+            config.server.health_check_timeout_ms,
+        )));
+        let effective_config = web::Data::new(EffectiveConfig::new(env, config));
+        let concurrency =
+            web::Data::new(ConcurrencyLimit::new(config.server.max_inflight_requests));
+        let retry_after =
+            web::Data::new(RetryAfter::new(config.server.overload_retry_after_seconds));
+        let access_log = web::Data::new(AccessLog::new(config.logging.access_log_sampling_rate));
+        let json_limits = web::Data::new(JsonLimits::new(
+            config.server.max_json_depth,
+            config.server.max_json_array_len,
+        ));
+
+        // Optionally serve gRPC alongside HTTP from the same process
+        let grpc_addr: Option<SocketAddr> = if config.grpc.enabled {
+            Some(format!("{}:{}", config.grpc.host, config.grpc.port).parse()?)
+        } else {
+            None
+        };
 
-        let origins: Vec<String> = vec!["*".to_string()];
         let headers: Vec<header::HeaderName> = vec![
             header::AUTHORIZATION,
             header::CONTENT_TYPE,
             header::ACCEPT,
             header::ORIGIN,
         ];
-        let methods: Vec<Method> = vec![
-            Method::GET,
-            Method::POST,
-            Method::PUT,
-            Method::DELETE,
-            Method::OPTIONS,
-        ];
-
         Ok(Self {
             host: config.server.host.clone(),
             port: config.server.port,
+            unix_socket_path: config.server.unix_socket_path.clone().map(PathBuf::from),
             state,
             user_service,
-            origins,
-            headers,
-            methods,
+            auth_service,
+            schema,
+            trusted_proxies,
+            response,
+            health_token,
+            health_timeout,
+            effective_config,
+            jwks,
+            concurrency,
+            connection_limit,
+            retry_after,
+            access_log,
+            json_limits,
+            grpc_addr,
+            shutdown_timeout: Duration::from_millis(config.server.shutdown_timeout_ms),
+            cors: CorsSettings {
+                config: config.security.cors.clone(),
+                headers,
+                methods: cors::allowed_methods(),
+            },
         })
     }
 
     pub async fn run(self) -> Result<(), actix_web::Error> {
         let bind_address = format!("{}:{}", self.host, self.port);
 
-        let headers = self.headers.clone();
-        let methods = self.methods.clone();
-        let origins = self.origins.clone();
+        let cors = self.cors.clone();
         let shared_state = self.state.clone();
         let user_service = self.user_service.clone();
+        let auth_service = self.auth_service.clone();
+        let schema = self.schema.clone();
+        let trusted_proxies = self.trusted_proxies.clone();
+        let response = self.response.clone();
+        let health_token = self.health_token.clone();
+        let health_timeout = self.health_timeout.clone();
+        let effective_config = self.effective_config.clone();
+        let jwks = self.jwks.clone();
+        let concurrency = self.concurrency.clone();
+        let retry_after_config = self.retry_after.clone();
+        let access_log_config = self.access_log.clone();
+        let json_limits = self.json_limits.clone();
 
-        tracing::info!("Starting HTTP server on {}", bind_address);
-
-        HttpServer::new(move || {
-            let mut cors = Cors::default()
-                .allowed_headers(headers.clone())
-                .allowed_methods(methods.clone())
-                .max_age(3600);
-
-            if origins.len() == 1 && origins[0] == "*" {
-                cors = cors.allow_any_origin();
-            } else {
-                for origin in &origins {
-                    cors = cors.allowed_origin(origin);
+        if let Some(addr) = self.grpc_addr {
+            let service = self.user_service.clone().into_inner();
+            self.state.tasks.spawn("grpc", move |token| async move {
+                if let Some(Err(e)) = token.run_until_cancelled(grpc::serve(addr, service)).await {
+                    tracing::error!("gRPC server error: {}", e);
                 }
-            }
+            });
+        }
 
+        let server = HttpServer::new(move || {
             App::new()
                 .app_data(shared_state.clone())
                 .app_data(user_service.clone())
+                .app_data(auth_service.clone())
+                .app_data(schema.clone())
+                .app_data(trusted_proxies.clone())
+                .app_data(response.clone())
+                .app_data(health_token.clone())
+                .app_data(health_timeout.clone())
+                .app_data(effective_config.clone())
+                .app_data(jwks.clone())
+                .app_data(concurrency.clone())
+                .app_data(retry_after_config.clone())
+                .app_data(access_log_config.clone())
+                .app_data(json_limits.clone())
+                .app_data(web::JsonConfig::default().error_handler(json_error))
                 // .wrap(TrackingLogger::default)
-                .wrap(Logger::default())
+                .wrap(from_fn(limit_json))
+                .wrap(from_fn(authenticate))
+                .wrap(from_fn(localize_errors))
+                // Access log with the real client address rather than the proxy's
+                .wrap(from_fn(access_log))
+                // Tags log lines, including logged errors, with the request id
+                .wrap(from_fn(request_id))
                 .wrap(Compress::default())
-                .wrap(cors)
-                .configure(configure_routes)
-        })
-        .bind(bind_address)?
-        .run()
-        .await
-        .map_err(actix_web::Error::from)
+                // Outside everything but the connection cap and Retry-After, so
+                // rejected requests skip logging, auth and the handler
+                .wrap(from_fn(limit_concurrency))
+                // Hint a backoff on every 503, including the limiter's
+                .wrap(from_fn(retry_after))
+                // Connections over the per-IP cap are turned away before anything else
+                .wrap(from_fn(limit_connections))
+                .configure(|cfg| configure_routes(cfg, &cors))
+        });
+        let connection_limit = self.connection_limit.clone();
+        let server = server
+            .on_connect(move |connection, data| connection_limit.on_connect(connection, data));
+
+        let server = match &self.unix_socket_path {
+            #[cfg(unix)]
+            Some(path) => {
+                tracing::info!("Starting HTTP server on unix:{}", path.display());
+                server.listen_uds(unix_listener(path)?)?
+            }
+            _ => {
+                tracing::info!("Starting HTTP server on {}", bind_address);
+                server.bind(bind_address)?
+            }
+        };
+        server.run().await?;
+
+        // Graceful stop has drained in-flight requests; flush events and release pools
+        self.state.shutdown(self.shutdown_timeout).await;
+        Ok(())
     }
 
     #[allow(dead_code)]
     pub fn set_origins(&mut self, origins: Vec<&str>) {
-        self.origins = origins.into_iter().map(|s| s.to_string()).collect();
+        self.cors.config.allowed_origins = origins.into_iter().map(|s| s.to_string()).collect();
     }
 
     #[allow(dead_code)]
     pub fn set_headers(&mut self, headers: Vec<header::HeaderName>) {
-        self.headers = headers;
+        self.cors.headers = headers;
     }
 
     #[allow(dead_code)]
     pub fn set_methods(&mut self, methods: Vec<Method>) {
-        self.methods = methods;
+        self.cors.methods = methods;
+    }
+}
+
+/// Bind a Unix socket at `path`, replacing a stale socket left by a previous run
+#[cfg(unix)]
+fn unix_listener(path: &std::path::Path) -> std::io::Result<std::os::unix::net::UnixListener> {
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+    // Only ever remove a socket; any other file at the path is a misconfiguration
+    if let Ok(metadata) = std::fs::symlink_metadata(path)
+        && metadata.file_type().is_socket()
+    {
+        std::fs::remove_file(path)?;
+    }
+    let listener = std::os::unix::net::UnixListener::bind(path)?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(UNIX_SOCKET_MODE))?;
+    Ok(listener)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    fn get_over_socket(path: PathBuf, uri: &'static str) -> String {
+        let mut stream = UnixStream::connect(path).unwrap();
+        write!(
+            stream,
+            "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+            uri
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[actix_web::test]
+    async fn test_serves_health_over_a_unix_socket() {
+        let path = std::env::temp_dir().join(format!("api-test-{}.sock", std::process::id()));
+        // A socket left behind by a previous run must not block the bind
+        drop(UnixListener::bind(&path).unwrap());
+
+        let listener = unix_listener(&path).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, UNIX_SOCKET_MODE);
+
+        let server = HttpServer::new(|| App::new().configure(presentation::routes::health::routes))
+            .workers(1)
+            .listen_uds(listener)
+            .unwrap()
+            .run();
+        let handle = server.handle();
+        actix_web::rt::spawn(server);
+
+        let response = web::block({
+            let path = path.clone();
+            move || get_over_socket(path, "/health")
+        })
+        .await
+        .unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+
+        handle.stop(true).await;
+        let _ = std::fs::remove_file(&path);
     }
 }
@@ -1,9 +1,14 @@
+mod cors;
+mod diagnostics;
 mod http_server;
 pub mod route_configuration;
+mod self_test;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    let env = std::env::var("APP_ENV").unwrap_or_else(|_| "dev".to_string());
+    let env = shared::AppEnv::from_env();
+    // `--check` verifies config and dependencies, then exits without serving
+    let check_only = std::env::args().skip(1).any(|arg| arg == "--check");
 
     // Initialize tracing subscriber for logging
     // This reads the RUST_LOG environment variable to configure log levels
@@ -11,18 +16,17 @@ async fn main() -> std::io::Result<()> {
     tracing_subscriber::fmt()
         .with_env_filter(
             tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"))
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
         )
-        .with_target(true)  // Show the module path (e.g., api::http_server)
-        .with_level(true)   // Show log level (INFO, ERROR, etc.)
-        .with_thread_ids(env == "dev")    // Show thread IDs in dev mode
-        .with_thread_names(env == "dev")  // Show thread names in dev mode
-        .with_file(env == "dev")   // Don't show file name (can enable for debugging)
-        .with_line_number(env == "dev")  // Don't show line numbers (can enable for debugging)
+        .with_target(true) // Show the module path (e.g., api::http_server)
+        .with_level(true) // Show log level (INFO, ERROR, etc.)
+        .with_thread_ids(env.is_dev()) // Show thread IDs in dev mode
+        .with_thread_names(env.is_dev()) // Show thread names in dev mode
+        .with_file(env.is_dev()) // Don't show file name (can enable for debugging)
+        .with_line_number(env.is_dev()) // Don't show line numbers (can enable for debugging)
         .init();
 
-
-    let config: shared::AppConfig = match shared::AppConfig::load(&env) {
+    let config: shared::AppConfig = match shared::AppConfig::load(env) {
         Ok(cfg) => cfg,
         Err(e) => {
             eprintln!("Failed to load configuration: {}", e);
@@ -35,29 +39,24 @@ async fn main() -> std::io::Result<()> {
         env!("CARGO_PKG_NAME"),
         env!("CARGO_PKG_VERSION")
     );
-    tracing::info!("Environment: {}", env);
-    tracing::info!(
-        "HTTP server will listen on {}:{}",
-        config.server.host,
-        config.server.port
-    );
+    diagnostics::log_startup(env, &config);
 
-    let http_server: http_server::Server =
-        http_server::Server::new(&config).await.map_err(|e| {
-            std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Failed to create HTTP server: {}", e),
-            )
-        })?;
+    if check_only {
+        let report = self_test::run(&config).await;
+        println!("{}", report);
+        std::process::exit(if report.passed() { 0 } else { 1 });
+    }
+
+    let http_server: http_server::Server = http_server::Server::new(env, &config)
+        .await
+        .map_err(|e| std::io::Error::other(format!("Failed to create HTTP server: {}", e)))?;
 
     tracing::info!("Server initialization complete. Starting HTTP server...");
 
-    let http_result: std::io::Result<()> = http_server.run().await.map_err(|e| {
-        std::io::Error::new(
-            std::io::ErrorKind::Other,
-            format!("HTTP server error: {}", e),
-        )
-    });
+    let http_result: std::io::Result<()> = http_server
+        .run()
+        .await
+        .map_err(|e| std::io::Error::other(format!("HTTP server error: {}", e)));
 
     tracing::info!("Server shutdown complete");
     http_result
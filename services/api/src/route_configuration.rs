@@ -1,11 +1,195 @@
 use actix_web::web;
 use presentation::routes::*;
 
-pub fn configure_routes(cfg: &mut actix_web::web::ServiceConfig) {
-    cfg.configure(health::routes);
+use crate::cors::CorsSettings;
 
-    // API v1 routes
+/// Register all routes, each scope wrapped in its own CORS layer
+///
+/// Scopes with a CORS override must be registered before the catch-all
+/// scope so their stricter rules apply instead of the global ones.
+pub fn configure_routes(cfg: &mut web::ServiceConfig, cors: &CorsSettings) {
     cfg.service(
-        web::scope("/api/v1").configure(user::configure), // .configure(tenant::configure)
+        web::scope("/api/v1/auth")
+            .wrap(cors.scope("auth"))
+            .configure(auth::configure),
     );
+
+    cfg.service(
+        web::scope("")
+            .wrap(cors.global())
+            .configure(health::routes)
+            .configure(debug::configure)
+            .configure(jwks::configure)
+            .configure(graphql::configure)
+            .configure(events::configure)
+            // API v1 routes
+            .service(
+                web::scope("/api/v1").configure(user::configure), // .configure(tenant::configure)
+            ),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{
+        App,
+        http::{Method, StatusCode, header},
+        test,
+    };
+    use shared::config::{CorsConfig, CorsOverride};
+    use std::collections::HashMap;
+
+    use crate::cors::allowed_methods;
+
+    fn settings() -> CorsSettings {
+        let auth = CorsOverride {
+            allowed_origins: Some(vec!["*".to_string(), "https://app.example.com".to_string()]),
+            allow_wildcard: Some(false),
+            ..CorsOverride::default()
+        };
+        CorsSettings {
+            config: CorsConfig {
+                allowed_origins: vec!["*".to_string()],
+                allow_wildcard: true,
+                max_age_seconds: 60,
+                scopes: HashMap::from([("auth".to_string(), auth)]),
+            },
+            headers: vec![header::CONTENT_TYPE],
+            methods: allowed_methods(),
+        }
+    }
+
+    fn preflight(uri: &str, origin: &str) -> test::TestRequest {
+        preflight_for(Method::POST, uri, origin)
+    }
+
+    fn preflight_for(method: Method, uri: &str, origin: &str) -> test::TestRequest {
+        test::TestRequest::default()
+            .method(Method::OPTIONS)
+            .uri(uri)
+            .insert_header((header::ORIGIN, origin))
+            .insert_header((header::ACCESS_CONTROL_REQUEST_METHOD, method.as_str()))
+    }
+
+    #[actix_web::test]
+    async fn test_auth_scope_rejects_origin_public_routes_accept() {
+        let cors = settings();
+        let app =
+            test::init_service(App::new().configure(|cfg| configure_routes(cfg, &cors))).await;
+        let origin = "https://anywhere.example.org";
+
+        let public =
+            test::call_service(&app, preflight("/api/v1/users", origin).to_request()).await;
+        assert_eq!(public.status(), StatusCode::OK);
+        assert_eq!(
+            public
+                .headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .unwrap(),
+            origin
+        );
+
+        let auth =
+            test::call_service(&app, preflight("/api/v1/auth/login", origin).to_request()).await;
+        assert_eq!(auth.status(), StatusCode::BAD_REQUEST);
+        assert!(
+            auth.headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .is_none()
+        );
+
+        let allowed = "https://app.example.com";
+        let auth =
+            test::call_service(&app, preflight("/api/v1/auth/login", allowed).to_request()).await;
+        assert_eq!(auth.status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn test_preflight_allows_patch_on_me() {
+        let cors = settings();
+        let app =
+            test::init_service(App::new().configure(|cfg| configure_routes(cfg, &cors))).await;
+
+        let req = preflight_for(Method::PATCH, "/api/v1/users/me", "https://app.example.com")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let allowed = resp
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_METHODS)
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert!(allowed.split(", ").any(|m| m == "PATCH"), "{}", allowed);
+    }
+
+    #[actix_web::test]
+    async fn test_preflight_allows_json_patch_on_user() {
+        let cors = settings();
+        let app =
+            test::init_service(App::new().configure(|cfg| configure_routes(cfg, &cors))).await;
+
+        let req = preflight_for(
+            Method::PATCH,
+            "/api/v1/users/4f1c2d3e-5a6b-4c7d-8e9f-0a1b2c3d4e5f",
+            "https://app.example.com",
+        )
+        .insert_header((header::ACCESS_CONTROL_REQUEST_HEADERS, "content-type"))
+        .to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let allowed = resp
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_METHODS)
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert!(allowed.split(", ").any(|m| m == "PATCH"), "{}", allowed);
+        assert!(
+            resp.headers()
+                .contains_key(header::ACCESS_CONTROL_ALLOW_HEADERS)
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_localized_cross_origin_error_keeps_cors_headers() {
+        use actix_web::{middleware::from_fn, web};
+        use application::UserService;
+        use presentation::middleware::localize_errors;
+        use presentation::test_support::InMemoryUserRepository;
+        use std::sync::Arc;
+
+        let cors = settings();
+        let service = web::Data::new(UserService::new(
+            Arc::new(InMemoryUserRepository::default()),
+        ));
+        let app = test::init_service(
+            App::new()
+                .app_data(service)
+                .wrap(from_fn(localize_errors))
+                .configure(|cfg| configure_routes(cfg, &cors)),
+        )
+        .await;
+        let origin = "https://app.example.com";
+
+        let req = test::TestRequest::get()
+            .uri("/api/v1/users/me")
+            .insert_header((header::ORIGIN, origin))
+            .insert_header((header::ACCEPT_LANGUAGE, "tr"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            resp.headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .unwrap(),
+            origin
+        );
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["error"]["message"], "Yetkisiz: Missing bearer token");
+    }
 }
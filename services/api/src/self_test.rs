@@ -0,0 +1,219 @@
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+use actix_web::rt::time::timeout;
+use deadpool_redis::redis;
+
+use application::ports::EmailSender;
+use infrastructure::auth::{Argon2PasswordHasher, JwtTokenService};
+use infrastructure::cache::redis::create_redis_pool;
+use infrastructure::database::postgres::{
+    create_postgres_pool, create_replica_pool, ensure_migrations_applied,
+};
+use infrastructure::email::SmtpEmailSender;
+use presentation::middleware::TrustedProxies;
+use shared::AppConfig;
+
+/// Upper bound on each dependency check
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Outcome of one startup check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckOutcome {
+    Passed,
+    Failed(String),
+    /// Not configured, so there is nothing to check
+    Skipped(&'static str),
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub outcome: CheckOutcome,
+}
+
+/// Result of `--check`: one entry per dependency, in the order checked
+#[derive(Debug, Clone, Default)]
+pub struct SelfTestReport {
+    pub checks: Vec<CheckResult>,
+}
+
+impl SelfTestReport {
+    pub fn passed(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks
+            .iter()
+            .filter(|check| matches!(check.outcome, CheckOutcome::Failed(_)))
+    }
+}
+
+impl fmt::Display for SelfTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            match &check.outcome {
+                CheckOutcome::Passed => writeln!(f, "  ok       {}", check.name)?,
+                CheckOutcome::Failed(e) => writeln!(f, "  FAILED   {}: {}", check.name, e)?,
+                CheckOutcome::Skipped(why) => writeln!(f, "  skipped  {}: {}", check.name, why)?,
+            }
+        }
+        match self.failures().count() {
+            0 => write!(f, "Self-test passed"),
+            failed => write!(
+                f,
+                "Self-test failed: {} of {} checks failed",
+                failed,
+                self.checks.len()
+            ),
+        }
+    }
+}
+
+/// Check everything the server needs before it could start serving
+///
+/// Builds the same config-derived pieces as `Server::new`, connects to each
+/// configured dependency and verifies the schema is up to date without
+/// running migrations. Nothing is bound and every connection is dropped
+/// afterwards.
+pub async fn run(config: &AppConfig) -> SelfTestReport {
+    let mut report = SelfTestReport::default();
+
+    report.checks.push(
+        check("config", async {
+            JwtTokenService::new(&config.jwt)?;
+            Argon2PasswordHasher::new(&config.security.password_hashing)?;
+            TrustedProxies::parse(&config.security.trusted_proxies)?;
+            Ok::<_, Box<dyn std::error::Error>>(())
+        })
+        .await,
+    );
+
+    report.checks.push(
+        check("database", async {
+            let pool = create_postgres_pool(config.database.clone()).await?;
+            ensure_migrations_applied(&pool).await?;
+            pool.close().await;
+            Ok::<_, Box<dyn std::error::Error>>(())
+        })
+        .await,
+    );
+
+    report
+        .checks
+        .push(match &config.database.replica_connection_string {
+            None => skipped("database replica", "no replica_connection_string"),
+            Some(_) => {
+                check("database replica", async {
+                    if let Some(pool) = create_replica_pool(&config.database).await? {
+                        pool.close().await;
+                    }
+                    Ok::<_, sqlx::Error>(())
+                })
+                .await
+            }
+        });
+
+    report.checks.push(
+        check("cache", async {
+            let pool = create_redis_pool(config.cache.clone()).await?;
+            let mut conn = pool.get().await?;
+            redis::cmd("PING").query_async::<()>(&mut conn).await?;
+            Ok::<_, Box<dyn std::error::Error>>(())
+        })
+        .await,
+    );
+
+    report
+        .checks
+        .push(skipped("event bus", "in-process, nothing to connect to"));
+
+    report.checks.push(if !config.email.enabled {
+        skipped("email", "email.enabled is off")
+    } else {
+        check("email", async {
+            SmtpEmailSender::new(&config.email)?
+                .verify_connection()
+                .await
+        })
+        .await
+    });
+
+    report
+}
+
+async fn check<E: fmt::Display>(
+    name: &'static str,
+    probe: impl Future<Output = Result<(), E>>,
+) -> CheckResult {
+    let outcome = match timeout(CHECK_TIMEOUT, probe).await {
+        Ok(Ok(())) => CheckOutcome::Passed,
+        Ok(Err(e)) => CheckOutcome::Failed(e.to_string()),
+        Err(_) => CheckOutcome::Failed(format!("no answer within {}s", CHECK_TIMEOUT.as_secs())),
+    };
+    CheckResult { name, outcome }
+}
+
+fn skipped(name: &'static str, reason: &'static str) -> CheckResult {
+    CheckResult {
+        name,
+        outcome: CheckOutcome::Skipped(reason),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(database_url: &str, cache_url: &str) -> AppConfig {
+        let mut config = AppConfig::default();
+        config.database.connection_string = database_url.to_string();
+        config.cache.url = cache_url.to_string();
+        config
+    }
+
+    fn outcome<'a>(report: &'a SelfTestReport, name: &str) -> &'a CheckOutcome {
+        &report
+            .checks
+            .iter()
+            .find(|check| check.name == name)
+            .unwrap()
+            .outcome
+    }
+
+    #[actix_web::test]
+    async fn test_unreachable_database_fails_the_check() {
+        let report = run(&config(
+            "postgres://postgres@127.0.0.1:1/app",
+            "redis://127.0.0.1:1",
+        ))
+        .await;
+
+        assert!(!report.passed());
+        assert_eq!(outcome(&report, "config"), &CheckOutcome::Passed);
+        assert!(matches!(
+            outcome(&report, "database"),
+            CheckOutcome::Failed(_)
+        ));
+        let rendered = report.to_string();
+        assert!(rendered.contains("FAILED   database: "), "{}", rendered);
+        assert!(rendered.ends_with("Self-test failed: 2 of 6 checks failed"));
+    }
+
+    #[actix_web::test]
+    #[ignore = "requires PostgreSQL and Redis (DATABASE_URL, REDIS_URL)"]
+    async fn test_reachable_dependencies_pass_the_check() {
+        let database = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let cache = std::env::var("REDIS_URL").expect("REDIS_URL must be set");
+        let report = run(&config(&database, &cache)).await;
+
+        assert!(report.passed(), "{}", report);
+        assert_eq!(outcome(&report, "cache"), &CheckOutcome::Passed);
+        assert_eq!(
+            outcome(&report, "email"),
+            &CheckOutcome::Skipped("email.enabled is off")
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use std::process::Command;
+
+fn check(database_url: &str, cache_url: &str) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_api"))
+        .arg("--check")
+        .env("APP_ENV", "test")
+        .env("RUST_LOG", "off")
+        .env("APP__DATABASE__CONNECTION_STRING", database_url)
+        .env("APP__CACHE__URL", cache_url)
+        .env("APP__EMAIL__ENABLED", "false")
+        .env("APP__JWT__SECRET", "self-test-secret")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_check_exits_non_zero_when_the_database_is_unreachable() {
+    let output = check("postgres://postgres@127.0.0.1:1/app", "redis://127.0.0.1:1");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(stdout.contains("FAILED   database: "), "{}", stdout);
+    assert!(stdout.contains("Self-test failed"), "{}", stdout);
+}
+
+#[test]
+#[ignore = "requires PostgreSQL and Redis (DATABASE_URL, REDIS_URL)"]
+fn test_check_exits_zero_when_dependencies_are_reachable() {
+    let database = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let cache = std::env::var("REDIS_URL").expect("REDIS_URL must be set");
+    let output = check(&database, &cache);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert!(stdout.ends_with("Self-test passed\n"), "{}", stdout);
+}
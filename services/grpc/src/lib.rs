@@ -0,0 +1,9 @@
+pub mod proto {
+    tonic::include_proto!("user.v1");
+}
+
+pub mod server;
+pub mod user_service;
+
+pub use server::{serve, serve_with_listener};
+pub use user_service::UserGrpcService;
@@ -1,3 +1,73 @@
-fn main() {
-    println!("Hello, world!");
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use application::UserService;
+use infrastructure::PostgresUserRepository;
+use infrastructure::database::retry::AcquireRetry;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let env = shared::AppEnv::from_env();
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .with_target(true)
+        .with_level(true)
+        .init();
+
+    let config: shared::AppConfig = shared::AppConfig::load(env)?;
+
+    tracing::info!(
+        "Starting {} v{}",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION")
+    );
+    tracing::info!("Environment: {}", env);
+
+    infrastructure::database::postgres::create_database_if_missing(&config.database, env).await?;
+    let db_pool =
+        infrastructure::database::postgres::create_postgres_pool(config.database.clone()).await?;
+    if config.database.warmup {
+        infrastructure::database::postgres::warm_up_postgres_pool(
+            &db_pool,
+            config.database.min_connections,
+        )
+        .await?;
+    }
+    if config.database.run_migrations {
+        infrastructure::database::postgres::run_migrations(&db_pool).await?;
+    } else {
+        infrastructure::database::postgres::ensure_migrations_applied(&db_pool).await?;
+    }
+
+    let user_repository =
+        match infrastructure::database::postgres::create_replica_pool(&config.database).await? {
+            Some(replica) => PostgresUserRepository::new_with_replica(db_pool, replica),
+            None => PostgresUserRepository::new(db_pool),
+        };
+    let user_repository = Arc::new(
+        user_repository
+            .with_acquire_retry(AcquireRetry::from_config(&config.database))
+            .with_query_timeout(Duration::from_millis(config.database.query_timeout_ms))
+            .with_acquire_timeouts(
+                Duration::from_millis(config.database.read_acquire_timeout_ms),
+                Duration::from_millis(config.database.write_acquire_timeout_ms),
+            ),
+    );
+    let user_service = Arc::new(
+        UserService::new(user_repository)
+            .with_validation(config.validation.clone())
+            .with_features(config.features.clone())
+            .with_cursor_secret(config.security.cursor_secret.as_bytes()),
+    );
+
+    let addr: SocketAddr = format!("{}:{}", config.grpc.host, config.grpc.port).parse()?;
+    grpc::serve(addr, user_service).await?;
+
+    tracing::info!("Server shutdown complete");
+    Ok(())
 }
@@ -0,0 +1,33 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tonic::transport::Server;
+use tonic::transport::server::TcpIncoming;
+
+use application::UserService;
+
+use crate::UserGrpcService;
+use crate::proto::user_service_server::UserServiceServer;
+
+/// Serve the gRPC user service on `addr` until the process stops
+pub async fn serve(
+    addr: SocketAddr,
+    service: Arc<UserService>,
+) -> Result<(), tonic::transport::Error> {
+    tracing::info!("Starting gRPC server on {}", addr);
+    Server::builder()
+        .add_service(UserServiceServer::new(UserGrpcService::new(service)))
+        .serve(addr)
+        .await
+}
+
+/// Serve the gRPC user service on an already-bound listener
+pub async fn serve_with_listener(
+    listener: TcpListener,
+    service: Arc<UserService>,
+) -> Result<(), tonic::transport::Error> {
+    Server::builder()
+        .add_service(UserServiceServer::new(UserGrpcService::new(service)))
+        .serve_with_incoming(TcpIncoming::from(listener))
+        .await
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+
+use application::{PublicUserResponse, UserListResponse, UserResponse, UserService};
+use shared::{AppError, UserId};
+
+use crate::proto;
+use crate::proto::user_service_server::UserService as UserServiceRpc;
+
+const DEFAULT_LIMIT: i64 = 20;
+
+/// gRPC adapter over the application `UserService`
+pub struct UserGrpcService {
+    service: Arc<UserService>,
+}
+
+impl UserGrpcService {
+    pub fn new(service: Arc<UserService>) -> Self {
+        Self { service }
+    }
+}
+
+impl From<PublicUserResponse> for proto::User {
+    fn from(user: PublicUserResponse) -> Self {
+        Self {
+            id: user.id.to_string(),
+            username: user.username,
+            email: user.email,
+            full_name: user.full_name,
+            status: None,
+            created_at: user.created_at.to_rfc3339(),
+            updated_at: None,
+        }
+    }
+}
+
+impl From<UserResponse> for proto::User {
+    fn from(user: UserResponse) -> Self {
+        PublicUserResponse::from(user).into()
+    }
+}
+
+impl From<UserListResponse> for proto::ListUsersResponse {
+    fn from(list: UserListResponse) -> Self {
+        Self {
+            users: list.users.into_iter().map(proto::User::from).collect(),
+            total: list.total,
+            limit: list.limit,
+            offset: list.offset,
+        }
+    }
+}
+
+fn parse_user_id(id: &str) -> Result<UserId, Status> {
+    uuid::Uuid::parse_str(id)
+        .map(UserId::from_uuid)
+        .map_err(|_| AppError::ValidationError("Invalid user ID format".to_string()).into())
+}
+
+#[tonic::async_trait]
+impl UserServiceRpc for UserGrpcService {
+    async fn get_user(
+        &self,
+        request: Request<proto::GetUserRequest>,
+    ) -> Result<Response<proto::User>, Status> {
+        let user_id = parse_user_id(&request.into_inner().id)?;
+        let user = self.service.get_user(user_id).await?;
+        Ok(Response::new(user.into()))
+    }
+
+    async fn create_user(
+        &self,
+        request: Request<proto::CreateUserRequest>,
+    ) -> Result<Response<proto::User>, Status> {
+        let request = request.into_inner();
+        let user = self
+            .service
+            .create_user(application::CreateUserRequest {
+                username: request.username,
+                email: request.email,
+                full_name: request.full_name,
+            })
+            .await?;
+        Ok(Response::new(user.into()))
+    }
+
+    async fn list_users(
+        &self,
+        request: Request<proto::ListUsersRequest>,
+    ) -> Result<Response<proto::ListUsersResponse>, Status> {
+        let request = request.into_inner();
+        let limit = if request.limit == 0 {
+            DEFAULT_LIMIT
+        } else {
+            request.limit
+        };
+        // gRPC callers are not authenticated, so they get the public view
+        let list = self
+            .service
+            .list_users(None, None, limit, request.offset)
+            .await?;
+        Ok(Response::new(list.into()))
+    }
+}
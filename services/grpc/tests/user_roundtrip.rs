@@ -0,0 +1,80 @@
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+use application::UserService;
+use grpc::proto::user_service_client::UserServiceClient;
+use grpc::proto::{CreateUserRequest, GetUserRequest, ListUsersRequest};
+use presentation::test_support::InMemoryUserRepository;
+use shared::UserId;
+
+async fn start_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let service = Arc::new(UserService::new(
+        Arc::new(InMemoryUserRepository::default()),
+    ));
+    tokio::spawn(grpc::serve_with_listener(listener, service));
+    format!("http://{}", addr)
+}
+
+#[tokio::test]
+async fn test_create_then_get_round_trip() {
+    let endpoint = start_server().await;
+    let mut client = UserServiceClient::connect(endpoint).await.unwrap();
+
+    let created = client
+        .create_user(CreateUserRequest {
+            username: "grpcuser".to_string(),
+            email: "grpc@example.com".to_string(),
+            full_name: Some("Grpc User".to_string()),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(created.username, "grpcuser");
+    // Admin-only fields are not sent to unauthenticated callers
+    assert_eq!(created.status, None);
+    assert_eq!(created.updated_at, None);
+
+    let fetched = client
+        .get_user(GetUserRequest {
+            id: created.id.clone(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(fetched, created);
+
+    let listed = client
+        .list_users(ListUsersRequest {
+            limit: 0,
+            offset: 0,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(listed.total, 1);
+    assert_eq!(listed.limit, 20);
+}
+
+#[tokio::test]
+async fn test_app_errors_map_to_status_codes() {
+    let endpoint = start_server().await;
+    let mut client = UserServiceClient::connect(endpoint).await.unwrap();
+
+    let missing = client
+        .get_user(GetUserRequest {
+            id: UserId::new().to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(missing.code(), tonic::Code::NotFound);
+
+    let invalid = client
+        .get_user(GetUserRequest {
+            id: "not-a-uuid".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(invalid.code(), tonic::Code::InvalidArgument);
+}